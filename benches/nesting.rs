@@ -0,0 +1,64 @@
+//! Deserializing replies through nested wrapper types, like
+//! `Option<Result<T, E>>`, each of which dispatches on the same header.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use serde::Deserialize;
+use seredies::{de::from_bytes_with_config, Config};
+
+/// An array of `count` integer replies, alternating between 0 and 1.
+fn integers(count: usize) -> Vec<u8> {
+    let mut input = format!("*{count}\r\n").into_bytes();
+
+    for i in 0..count {
+        input.extend_from_slice(if i % 2 == 0 { b":0\r\n" } else { b":1\r\n" });
+    }
+
+    input
+}
+
+fn deserialize<'a, T: Deserialize<'a>>(input: &'a [u8], config: Config) -> Vec<T> {
+    from_bytes_with_config(input, config).unwrap()
+}
+
+fn nesting(c: &mut Criterion) {
+    let input = integers(1000);
+    let mut group = c.benchmark_group("nesting");
+
+    // The limits are only checked when they're set, so the nested types are
+    // benchmarked with and without them.
+    for (name, config) in [
+        ("default", Config::new()),
+        (
+            "limited",
+            Config::new()
+                .with_max_array_len(10_000)
+                .with_max_total_len(1 << 20),
+        ),
+    ] {
+        group.bench_function(format!("i64/{name}"), |b| {
+            b.iter(|| deserialize::<i64>(black_box(&input), config))
+        });
+
+        group.bench_function(format!("bool/{name}"), |b| {
+            b.iter(|| deserialize::<bool>(black_box(&input), config))
+        });
+
+        group.bench_function(format!("option_result_bool/{name}"), |b| {
+            b.iter(|| deserialize::<Option<Result<bool, String>>>(black_box(&input), config))
+        });
+
+        group.bench_function(format!("option_option_result_bool/{name}"), |b| {
+            b.iter(|| {
+                deserialize::<Option<Option<Result<Option<bool>, String>>>>(
+                    black_box(&input),
+                    config,
+                )
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, nesting);
+criterion_main!(benches);
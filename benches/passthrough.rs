@@ -0,0 +1,48 @@
+//! Forwarding a reply unchanged, either by decoding it into `Value`s and
+//! encoding it again, or by passing the original bytes through as
+//! `RawValue`s.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use seredies::{
+    de::from_bytes,
+    ser::to_vec,
+    value::{RawValue, Value},
+};
+
+/// An array of `count` elements, each a small array of a bulk string, an
+/// integer, and a null, like a typical reply to a pipelined `HMGET`.
+fn reply(count: usize) -> Vec<u8> {
+    let mut input = format!("*{count}\r\n").into_bytes();
+
+    for i in 0..count {
+        input.extend_from_slice(
+            format!("*3\r\n$9\r\nfield:{i:03}\r\n:{i}\r\n$-1\r\n", i = i % 1000).as_bytes(),
+        );
+    }
+
+    input
+}
+
+fn passthrough(c: &mut Criterion) {
+    let input = reply(1000);
+    let mut group = c.benchmark_group("passthrough");
+
+    group.bench_function("value", |b| {
+        b.iter(|| {
+            let elements: Vec<Value> = from_bytes(black_box(&input)).unwrap();
+            to_vec(&elements).unwrap()
+        })
+    });
+
+    group.bench_function("raw_value", |b| {
+        b.iter(|| {
+            let elements: Vec<RawValue> = from_bytes(black_box(&input)).unwrap();
+            to_vec(&elements).unwrap()
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, passthrough);
+criterion_main!(benches);
@@ -0,0 +1,208 @@
+/*!
+A minimal, blocking RESP REPL, for manually poking at a Redis server.
+
+Commands are typed in the same format as `redis-cli` (which is the Redis
+[inline command] format), sent to the server, and the replies are printed as
+a tree. Run it with:
+
+```text
+cargo run --example resp-repl --features repl -- [--inline] [ADDRESS]
+```
+
+`ADDRESS` defaults to `127.0.0.1:6379`. By default, commands are sent as
+RESP arrays of bulk strings; with `--inline`, they're re-serialized and sent
+in the inline format instead, which is handy for checking how a server
+handles it.
+
+This is also a worked example of wiring together several parts of seredies:
+
+- [`read_inline_command`] splits each typed line into arguments, handling
+  quotes and escapes.
+- [`to_vec`] or [`to_inline_string`] serialize the arguments as a command.
+- [`Parser`] buffers the bytes read from the socket until a complete reply
+  has arrived, without re-parsing from the beginning on each read.
+- [`from_bytes`] deserializes the reply as a [`Value`], which can hold
+  anything the server sends, including errors.
+
+[inline command]: https://redis.io/docs/reference/protocol-spec/#inline-commands
+*/
+
+use std::{
+    borrow::Cow,
+    env,
+    error::Error,
+    io::{self, BufRead, Read, Write},
+    net::TcpStream,
+    process::ExitCode,
+    task::Poll,
+};
+
+use serde_bytes::Bytes;
+use seredies::{
+    de::{
+        from_bytes,
+        parse::{read_inline_command, Parser},
+    },
+    ser::{to_inline_string, to_vec},
+    value::Value,
+};
+
+/// How commands are sent to the server.
+#[derive(Debug, Clone, Copy)]
+enum Mode {
+    /// As a RESP array of bulk strings, the way every client library does it
+    Array,
+
+    /// As an inline command, the way a human typing into `telnet` would
+    Inline,
+}
+
+/// Serialize a command, in the given mode.
+fn encode(args: &[Cow<'_, [u8]>], mode: Mode) -> Result<Vec<u8>, seredies::ser::Error> {
+    let args: Vec<&Bytes> = args.iter().map(|arg| Bytes::new(arg)).collect();
+
+    match mode {
+        Mode::Array => to_vec(&args),
+        Mode::Inline => to_inline_string(&args).map(String::into_bytes),
+    }
+}
+
+/// Read from the connection until a complete reply has arrived, and
+/// deserialize it.
+fn read_reply(
+    connection: &mut TcpStream,
+    parser: &mut Parser,
+    buffer: &mut [u8],
+) -> Result<Value, Box<dyn Error>> {
+    let mut chunk: &[u8] = &[];
+
+    loop {
+        if let Poll::Ready(frame) = parser.feed(chunk) {
+            return Ok(from_bytes(frame?)?);
+        }
+
+        let len = connection.read(buffer)?;
+        if len == 0 {
+            return Err("the server closed the connection".into());
+        }
+
+        chunk = &buffer[..len];
+    }
+}
+
+/// Write a string, quoted and escaped the way `redis-cli` does it.
+fn write_quoted(out: &mut impl Write, bytes: &[u8]) -> io::Result<()> {
+    write!(out, "\"{}\"", bytes.escape_ascii())
+}
+
+/// Write a value as a tree, in the style of `redis-cli`. `indent` is the
+/// column where the value starts, so that nested array elements line up
+/// beneath their parents.
+fn render(out: &mut impl Write, value: &Value, indent: usize) -> io::Result<()> {
+    match *value {
+        Value::Null => writeln!(out, "(nil)"),
+        Value::Integer(i) => writeln!(out, "(integer) {i}"),
+        Value::SimpleString(ref s) => writeln!(out, "{s}"),
+        Value::Error(ref message) => writeln!(out, "(error) {message}"),
+        Value::BulkString(ref bytes) => {
+            write_quoted(out, bytes)?;
+            writeln!(out)
+        }
+        Value::Array(ref items) if items.is_empty() => writeln!(out, "(empty array)"),
+        Value::Array(ref items) => {
+            let width = items.len().to_string().len();
+
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    write!(out, "{:indent$}", "")?;
+                }
+
+                write!(out, "{:>width$}) ", i + 1)?;
+                render(out, item, indent + width + 2)?;
+            }
+
+            Ok(())
+        }
+        _ => writeln!(out, "{value:?}"),
+    }
+}
+
+fn run(address: &str, mode: Mode) -> Result<(), Box<dyn Error>> {
+    let mut connection = TcpStream::connect(address)?;
+    let mut parser = Parser::new();
+    let mut buffer = vec![0; 4096];
+
+    let stdin = io::stdin();
+    let mut stdout = io::stdout().lock();
+    let mut line = String::new();
+
+    loop {
+        write!(stdout, "{address}> ")?;
+        stdout.flush()?;
+
+        line.clear();
+        if stdin.lock().read_line(&mut line)? == 0 {
+            writeln!(stdout)?;
+            return Ok(());
+        }
+
+        // `read_inline_command` expects a complete line ending in `\r\n`,
+        // but `read_line` only includes a `\n`, or nothing at the end of
+        // the input
+        line.truncate(line.trim_end_matches(['\r', '\n']).len());
+        line.push_str("\r\n");
+
+        let args = match read_inline_command(line.as_bytes()) {
+            Ok((args, _)) => args,
+            Err(err) => {
+                writeln!(stdout, "(invalid command) {err}")?;
+                continue;
+            }
+        };
+
+        match args.first().map(|name| &**name) {
+            None => continue,
+            Some(name)
+                if name.eq_ignore_ascii_case(b"quit") || name.eq_ignore_ascii_case(b"exit") =>
+            {
+                return Ok(())
+            }
+            Some(_) => {}
+        }
+
+        connection.write_all(&encode(&args, mode)?)?;
+
+        let reply = read_reply(&mut connection, &mut parser, &mut buffer)?;
+        render(&mut stdout, &reply, 0)?;
+    }
+}
+
+fn main() -> ExitCode {
+    let mut mode = Mode::Array;
+    let mut address = None;
+
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "--inline" => mode = Mode::Inline,
+            "--help" | "-h" => {
+                println!("usage: resp-repl [--inline] [ADDRESS]");
+                return ExitCode::SUCCESS;
+            }
+            _ if address.is_none() => address = Some(arg),
+            _ => {
+                eprintln!("unexpected argument: {arg}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let address = address.as_deref().unwrap_or("127.0.0.1:6379");
+
+    match run(address, mode) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
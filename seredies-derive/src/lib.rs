@@ -0,0 +1,280 @@
+/*!
+Derive macros for [seredies](https://docs.rs/seredies). Don't depend on this
+crate directly; enable the `derive` feature of `seredies` instead, and use
+the macros from there.
+*/
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote, quote_spanned};
+use syn::{parse_macro_input, spanned::Spanned, Data, DeriveInput, Fields, LitStr, Member, Type};
+
+/// Derive `Serialize` for a struct that is a Redis command. See the
+/// `seredies::components::RedisCommand` docs for details.
+#[proc_macro_derive(RedisCommand, attributes(redis))]
+pub fn derive_redis_command(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// How a single field of the command is sent.
+enum Kind {
+    /// A `bool` field, which is sent as the token if it's true.
+    Flag(String),
+
+    /// Any other field, which is sent as its arguments, preceded by the
+    /// token (if any) when there is at least one argument.
+    Parameter(Option<String>),
+}
+
+struct Field {
+    member: Member,
+    ty: Type,
+    kind: Kind,
+}
+
+/// The default token for a command or field name: uppercase, without
+/// underscores, so that `keep_ttl` is `KEEPTTL`.
+fn default_token(ident: &syn::Ident) -> String {
+    let name = ident.to_string();
+
+    name.strip_prefix("r#")
+        .unwrap_or(&name)
+        .chars()
+        .filter(|&c| c != '_')
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+fn is_bool(ty: &Type) -> bool {
+    matches!(*ty, Type::Path(ref path) if path.qself.is_none() && path.path.is_ident("bool"))
+}
+
+/// Parse the `#[redis(name = "...")]` attribute of the struct itself.
+fn command_name(input: &DeriveInput) -> syn::Result<String> {
+    let mut name = None;
+
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("redis"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                let value: LitStr = meta.value()?.parse()?;
+                name = Some(value.value());
+                Ok(())
+            } else {
+                Err(meta.error("unknown command attribute; expected `name`"))
+            }
+        })?;
+    }
+
+    Ok(name.unwrap_or_else(|| default_token(&input.ident)))
+}
+
+/// Parse a field and its `#[redis(...)]` attributes. Returns `None` if the
+/// field is skipped.
+fn parse_field(index: usize, field: &syn::Field) -> syn::Result<Option<Field>> {
+    let mut flag: Option<Option<String>> = None;
+    let mut named: Option<Option<String>> = None;
+    let mut skip = false;
+
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("redis"))
+    {
+        attr.parse_nested_meta(|meta| {
+            let slot = if meta.path.is_ident("flag") {
+                &mut flag
+            } else if meta.path.is_ident("named") {
+                &mut named
+            } else if meta.path.is_ident("skip") {
+                skip = true;
+                return Ok(());
+            } else {
+                return Err(
+                    meta.error("unknown field attribute; expected `flag`, `named`, or `skip`")
+                );
+            };
+
+            *slot = Some(match meta.input.peek(syn::Token![=]) {
+                true => Some(meta.value()?.parse::<LitStr>()?.value()),
+                false => None,
+            });
+
+            Ok(())
+        })?;
+    }
+
+    if skip {
+        return Ok(None);
+    }
+
+    let (member, default) = match field.ident {
+        Some(ref ident) => (Member::Named(ident.clone()), Some(default_token(ident))),
+        None => (Member::Unnamed(index.into()), None),
+    };
+
+    let token = |token: Option<String>, attr: &str| {
+        token.or_else(|| default.clone()).ok_or_else(|| {
+            syn::Error::new(
+                field.span(),
+                format!(
+                    "fields of tuple structs need an explicit token, \
+                     like `#[redis({attr} = \"TOKEN\")]`"
+                ),
+            )
+        })
+    };
+
+    let kind = match (flag, named) {
+        (Some(_), Some(_)) => {
+            return Err(syn::Error::new(
+                field.span(),
+                "a field can't be both a `flag` and `named`",
+            ))
+        }
+        (Some(flag), None) => Kind::Flag(token(flag, "flag")?),
+        (None, Some(named)) => Kind::Parameter(Some(token(named, "named")?)),
+        (None, None) if is_bool(&field.ty) => {
+            return Err(syn::Error::new(
+                field.ty.span(),
+                "bool fields are sent as flags, and must be marked with `#[redis(flag)]`",
+            ))
+        }
+        (None, None) => Kind::Parameter(None),
+    };
+
+    Ok(Some(Field {
+        member,
+        ty: field.ty.clone(),
+        kind,
+    }))
+}
+
+fn expand(mut input: DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => fields.named.iter().collect(),
+            Fields::Unnamed(ref fields) => fields.unnamed.iter().collect(),
+            Fields::Unit => Vec::new(),
+        },
+        _ => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "RedisCommand can only be derived for structs",
+            ))
+        }
+    };
+
+    let name = command_name(&input)?;
+    let fields = fields
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, field)| parse_field(index, field).transpose())
+        .collect::<syn::Result<Vec<Field>>>()?;
+
+    let private = quote! { ::seredies::__private };
+    let name_parts: Vec<&str> = name.split_whitespace().collect();
+    let name_len = name_parts.len();
+
+    // Every parameter type needs to be a `CommandParameter`; bounding them
+    // here means that generic fields work too.
+    let where_clause = input.generics.make_where_clause();
+    for field in &fields {
+        if let Kind::Parameter(_) = field.kind {
+            let ty = &field.ty;
+            where_clause
+                .predicates
+                .push(syn::parse_quote!(#ty: ::seredies::components::CommandParameter));
+        }
+    }
+
+    let counts = fields.iter().enumerate().map(|(index, field)| {
+        let count = format_ident!("__count{}", index);
+        let member = &field.member;
+
+        match field.kind {
+            Kind::Flag(_) => quote_spanned! {field.ty.span()=>
+                let #count = #private::flag_count(&self.#member);
+                __len += #count;
+            },
+            Kind::Parameter(ref token) => {
+                let token_count = token.as_ref().map(|_| {
+                    quote! { + usize::from(#count > 0) }
+                });
+
+                quote! {
+                    let #count = ::seredies::components::CommandParameter::argument_count(
+                        &self.#member,
+                    );
+                    __len += #count #token_count;
+                }
+            }
+        }
+    });
+
+    let serialize_fields = fields.iter().enumerate().map(|(index, field)| {
+        let count = format_ident!("__count{}", index);
+        let member = &field.member;
+
+        match field.kind {
+            Kind::Flag(ref token) => quote! {
+                if #count > 0 {
+                    #private::SerializeSeq::serialize_element(&mut __seq, #token)?;
+                }
+            },
+            Kind::Parameter(ref token) => {
+                let token = token.as_ref().map(|token| {
+                    quote! {
+                        if #count > 0 {
+                            #private::SerializeSeq::serialize_element(&mut __seq, #token)?;
+                        }
+                    }
+                });
+
+                quote! {
+                    #token
+                    ::seredies::components::CommandParameter::serialize_arguments(
+                        &self.#member,
+                        &mut __seq,
+                    )?;
+                }
+            }
+        }
+    });
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics #private::Serialize for #ident #ty_generics #where_clause {
+            fn serialize<__S>(
+                &self,
+                __serializer: __S,
+            ) -> ::core::result::Result<__S::Ok, __S::Error>
+            where
+                __S: #private::Serializer,
+            {
+                let mut __len: usize = #name_len;
+                #(#counts)*
+
+                let mut __seq = #private::Serializer::serialize_seq(
+                    __serializer,
+                    ::core::option::Option::Some(__len),
+                )?;
+
+                #(#private::SerializeSeq::serialize_element(&mut __seq, #name_parts)?;)*
+                #(#serialize_fields)*
+
+                #private::SerializeSeq::end(__seq)
+            }
+        }
+    })
+}
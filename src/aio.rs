@@ -0,0 +1,207 @@
+/*!
+Writing commands and reading replies with tokio's [`AsyncRead`] and
+[`AsyncWrite`] traits.
+
+For runtimes that use the `futures-io` traits instead, see
+[`ser::to_async_writer`] and
+[`de::from_async_reader`][crate::de::from_async_reader] (with the
+`futures-io` feature).
+
+# Example
+
+```
+use seredies::aio::{read_reply, write_command};
+use seredies::components::Command;
+
+# futures_executor::block_on(async {
+let mut request = Vec::new();
+write_command(&Command(("INCRBY", "counter", 5)), &mut request).await.unwrap();
+assert_eq!(request, b"*3\r\n$6\r\nINCRBY\r\n$7\r\ncounter\r\n$1\r\n5\r\n");
+
+let mut replies: &[u8] = b":15\r\n+OK\r\n";
+let count: i64 = read_reply(&mut replies).await.unwrap();
+assert_eq!(count, 15);
+
+// The next reply is still in the reader
+assert_eq!(replies, b"+OK\r\n");
+# })
+```
+*/
+
+use alloc::vec::Vec;
+
+use serde::de::DeserializeOwned;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::{
+    components::CommandLike,
+    de::{from_bytes, parse, Error, ReadError},
+    ser,
+};
+
+/// The largest number of bytes that [`read_reply`] reads at once, so that
+/// a bulk string with a huge (claimed) length doesn't cause a huge
+/// allocation before any of its data has arrived.
+const MAX_READ_SIZE: usize = 64 * 1024;
+
+/**
+Write a command to a tokio [`AsyncWrite`] destination.
+
+The command is first serialized into an in-memory buffer, which is then
+written in its entirety, so a serialization error never results in a
+partial write. The destination is flushed after the write. Use a
+[`Command`][crate::components::Command] (or a
+[`&dyn CommandLike`][CommandLike]) for the command.
+*/
+pub async fn write_command<C, W>(command: &C, dest: &mut W) -> Result<(), ser::Error>
+where
+    C: CommandLike + ?Sized,
+    W: AsyncWrite + Unpin + ?Sized,
+{
+    let mut buffer = Vec::new();
+    command.write_command(&mut buffer)?;
+
+    dest.write_all(&buffer).await?;
+    dest.flush().await?;
+
+    Ok(())
+}
+
+/**
+Read a single reply from a tokio [`AsyncRead`] source, and deserialize it
+as a `T`.
+
+This reads exactly the bytes of the reply, and nothing more, so any data
+after it (such as the reply to the next pipelined command) is left in the
+reader, and the reader doesn't need to be buffered to preserve it. To do
+this, it uses the [`UnexpectedEof`][parse::Error::UnexpectedEof] hints from
+the parser to decide how much to read next: a bulk string is read in one
+go (up to 64KB at a time), but the headers are read a few bytes at a time.
+When the source is a socket, wrapping it in a
+[`BufReader`][tokio::io::BufReader] avoids a system call for each of
+those small reads.
+
+Because the reply is read into an internal buffer, `T` must own its data.
+If the source reaches EOF before the reply is complete, this returns an
+[`UnexpectedEof`][std::io::ErrorKind::UnexpectedEof] i/o error.
+*/
+pub async fn read_reply<T, R>(source: &mut R) -> Result<T, ReadError>
+where
+    T: DeserializeOwned,
+    R: AsyncRead + Unpin + ?Sized,
+{
+    let mut buffer = Vec::new();
+    let mut frame = parse::FrameScanner::new();
+
+    loop {
+        match frame.scan(&buffer) {
+            Ok(_) => break,
+            Err(parse::Error::UnexpectedEof(needed)) => {
+                let start = buffer.len();
+                buffer.resize(start + needed.min(MAX_READ_SIZE), 0);
+                source.read_exact(&mut buffer[start..]).await?;
+            }
+            Err(err) => return Err(Error::Parse(err).into()),
+        }
+    }
+
+    from_bytes(&buffer).map_err(ReadError::Deserialize)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io,
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    use cool_asserts::assert_matches;
+    use futures_executor::block_on;
+    use tokio::io::ReadBuf;
+
+    use super::*;
+    use crate::components::Command;
+
+    /// A reader that counts how many times it was read from.
+    struct Counting<'a> {
+        data: &'a [u8],
+        reads: usize,
+    }
+
+    impl AsyncRead for Counting<'_> {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<io::Result<()>> {
+            self.reads += 1;
+            Pin::new(&mut self.data).poll_read(cx, buf)
+        }
+    }
+
+    #[test]
+    fn write_dyn_command() {
+        let command: &dyn CommandLike = &Command::raw("PING");
+        let mut buffer = Vec::new();
+
+        block_on(write_command(command, &mut buffer)).unwrap();
+        assert_eq!(buffer, b"*1\r\n$4\r\nPING\r\n");
+    }
+
+    #[test]
+    fn nested() {
+        let mut input: &[u8] = b"*2\r\n*2\r\n:1\r\n$3\r\nabc\r\n*-1\r\n:2\r\n";
+
+        let reply: Vec<Option<(i64, String)>> = block_on(read_reply(&mut input)).unwrap();
+        assert_eq!(reply, [Some((1, "abc".to_owned())), None]);
+        assert_eq!(input, b":2\r\n");
+    }
+
+    #[test]
+    fn bulk_string_in_one_read() {
+        let data = [b"$1000\r\n".as_slice(), &[b'x'; 1000], b"\r\n"].concat();
+        let mut reader = Counting {
+            data: &data,
+            reads: 0,
+        };
+
+        let reply: String = block_on(read_reply(&mut reader)).unwrap();
+        assert_eq!(reply.len(), 1000);
+
+        // The header, then the rest of the header and the whole body
+        assert!(reader.reads <= 4, "{} reads", reader.reads);
+    }
+
+    #[test]
+    fn incomplete() {
+        let mut input: &[u8] = b"*2\r\n:1\r\n";
+
+        assert_matches!(
+            block_on(read_reply::<Vec<i64>, _>(&mut input)),
+            Err(ReadError::Io(err)) => assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof)
+        );
+    }
+
+    #[test]
+    fn malformed() {
+        let mut input: &[u8] = b"?1\r\n";
+
+        assert_matches!(
+            block_on(read_reply::<i64, _>(&mut input)),
+            Err(ReadError::Deserialize(Error::Parse(parse::Error::BadTag(
+                b'?'
+            ))))
+        );
+    }
+
+    #[test]
+    fn redis_error() {
+        let mut input: &[u8] = b"-ERR bad\r\n";
+
+        assert_matches!(
+            block_on(read_reply::<i64, _>(&mut input)),
+            Err(ReadError::Deserialize(Error::Redis(_)))
+        );
+    }
+}
@@ -0,0 +1,348 @@
+/*!
+Integrity checks for RESP frames stored at rest.
+
+Applications that cache serialized RESP frames (in a file, a blob store, and
+so on) often want to detect corruption before handing the data back to the
+deserializer. This module provides [`ChecksumOutput`], an [`Output`] adapter
+that computes a checksum of everything written through it and appends it
+after the RESP payload, and [`strip_checksum`], which verifies and removes
+that trailer so that the payload can be passed to any of the usual
+deserialization entry points.
+
+The checksum is stored as a fixed-size big-endian trailer, *outside* the RESP
+payload; data with a checksum trailer is not valid RESP until the trailer is
+removed.
+
+Checksum algorithms implement the [`Checksum`] trait. CRC32 is available with
+the `crc32` feature, and XXH3 is available with the `xxhash` feature.
+
+# Example
+
+```
+use serde::Serialize;
+use seredies::checksum::{strip_checksum, Checksum, ChecksumOutput};
+use seredies::ser::Serializer;
+
+/// A (very weak) checksum, for demonstration purposes
+#[derive(Default)]
+struct Sum(u8);
+
+impl Checksum for Sum {
+    const LEN: usize = 1;
+
+    fn update(&mut self, data: &[u8]) {
+        self.0 = data.iter().fold(self.0, |sum, &b| sum.wrapping_add(b));
+    }
+
+    fn finish(&self) -> u64 {
+        self.0.into()
+    }
+}
+
+let mut output = ChecksumOutput::<_, Sum>::new(Vec::new());
+["a", "b"].serialize(Serializer::new(&mut output)).unwrap();
+let frame = output.finish().unwrap();
+
+assert_eq!(&frame[..frame.len() - 1], b"*2\r\n$1\r\na\r\n$1\r\nb\r\n");
+
+let payload = strip_checksum::<Sum>(&frame).unwrap();
+let data: Vec<String> = seredies::de::from_bytes(payload).unwrap();
+assert_eq!(data, ["a", "b"]);
+
+// Any corruption is detected
+let mut corrupt = frame.clone();
+corrupt[9] = b'x';
+assert!(strip_checksum::<Sum>(&corrupt).is_err());
+```
+*/
+
+use core::fmt;
+
+use crate::ser::{self, Output};
+
+/// A checksum algorithm that can be used with [`ChecksumOutput`] and
+/// [`strip_checksum`].
+pub trait Checksum: Default {
+    /// The number of bytes in the checksum trailer. Must be at most 8.
+    const LEN: usize;
+
+    /// Add data to the checksum.
+    fn update(&mut self, data: &[u8]);
+
+    /// Get the checksum of all the data seen so far. Only the low
+    /// [`LEN`][Checksum::LEN] bytes are written to the trailer.
+    fn finish(&self) -> u64;
+}
+
+/// Get the checksum as big-endian bytes, along with the offset at which the
+/// `C::LEN` byte trailer starts.
+#[inline]
+fn trailer<C: Checksum>(checksum: &C) -> ([u8; 8], usize) {
+    let start = 8 - C::LEN.min(8);
+    (checksum.finish().to_be_bytes(), start)
+}
+
+/**
+An [`Output`] adapter that computes a checksum of all the data written to an
+underlying [`Output`]. Call [`finish`][ChecksumOutput::finish] after
+serializing to append the checksum trailer and retrieve the underlying
+output.
+*/
+pub struct ChecksumOutput<O, C> {
+    output: O,
+    checksum: C,
+}
+
+impl<O: Output, C: Checksum> ChecksumOutput<O, C> {
+    /// Create a new `ChecksumOutput` wrapping `output`.
+    #[inline]
+    #[must_use]
+    pub fn new(output: O) -> Self {
+        Self {
+            output,
+            checksum: C::default(),
+        }
+    }
+
+    /// Get the checksum of all the data written so far, without appending
+    /// it to the output.
+    #[inline]
+    #[must_use]
+    pub fn checksum(&self) -> u64 {
+        self.checksum.finish()
+    }
+
+    /// Append the checksum trailer to the output, and return the output.
+    pub fn finish(mut self) -> Result<O, ser::Error> {
+        let (bytes, start) = trailer(&self.checksum);
+        self.output.write_bytes(&bytes[start..])?;
+        Ok(self.output)
+    }
+}
+
+impl<O: Output, C: Checksum> Output for ChecksumOutput<O, C> {
+    #[inline]
+    fn reserve(&mut self, count: usize) {
+        self.output.reserve(count)
+    }
+
+    #[inline]
+    fn write_str(&mut self, s: &str) -> Result<(), ser::Error> {
+        self.output.write_str(s)?;
+        self.checksum.update(s.as_bytes());
+        Ok(())
+    }
+
+    #[inline]
+    fn write_bytes(&mut self, b: &[u8]) -> Result<(), ser::Error> {
+        self.output.write_bytes(b)?;
+        self.checksum.update(b);
+        Ok(())
+    }
+}
+
+impl<O: fmt::Debug, C> fmt::Debug for ChecksumOutput<O, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChecksumOutput")
+            .field("output", &self.output)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Errors that can occur while verifying a checksum trailer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The input was too short to contain a checksum trailer
+    TooShort,
+
+    /// The checksum trailer didn't match the payload
+    Mismatch {
+        /// The checksum found in the trailer
+        expected: u64,
+
+        /// The checksum computed from the payload
+        computed: u64,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Error::TooShort => f.write_str("input is too short to contain a checksum"),
+            Error::Mismatch { expected, computed } => write!(
+                f,
+                "checksum mismatch: expected {expected:#x}, computed {computed:#x}"
+            ),
+        }
+    }
+}
+
+impl serde::de::StdError for Error {}
+
+/// Verify the checksum trailer at the end of `input`, and return the payload
+/// that precedes it. The payload can then be passed to
+/// [`from_bytes`][crate::de::from_bytes] or any other deserialization entry
+/// point.
+pub fn strip_checksum<C: Checksum>(input: &[u8]) -> Result<&[u8], Error> {
+    let len = C::LEN.min(8);
+    let split = input.len().checked_sub(len).ok_or(Error::TooShort)?;
+    let (payload, stored) = input.split_at(split);
+
+    let mut checksum = C::default();
+    checksum.update(payload);
+    let (computed, start) = trailer(&checksum);
+
+    match stored == &computed[start..] {
+        true => Ok(payload),
+        false => {
+            let mut expected = [0; 8];
+            expected[start..].copy_from_slice(stored);
+
+            Err(Error::Mismatch {
+                expected: u64::from_be_bytes(expected),
+                computed: u64::from_be_bytes(computed),
+            })
+        }
+    }
+}
+
+/// The CRC32 (IEEE) checksum, with a 4 byte trailer.
+#[cfg(feature = "crc32")]
+#[derive(Debug, Clone, Default)]
+pub struct Crc32(crc32fast::Hasher);
+
+#[cfg(feature = "crc32")]
+impl Checksum for Crc32 {
+    const LEN: usize = 4;
+
+    #[inline]
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data)
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.0.clone().finalize().into()
+    }
+}
+
+/// The 64 bit XXH3 hash, with an 8 byte trailer.
+#[cfg(feature = "xxhash")]
+#[derive(Clone, Default)]
+pub struct Xxh3(xxhash_rust::xxh3::Xxh3);
+
+#[cfg(feature = "xxhash")]
+impl Checksum for Xxh3 {
+    const LEN: usize = 8;
+
+    #[inline]
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data)
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.0.digest()
+    }
+}
+
+#[cfg(feature = "xxhash")]
+impl fmt::Debug for Xxh3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Xxh3").field(&self.0.digest()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cool_asserts::assert_matches;
+    use serde::Serialize;
+
+    use super::*;
+    use crate::ser::Serializer;
+
+    /// FNV-1a, truncated to 2 bytes to exercise short trailers
+    struct Fnv(u64);
+
+    impl Default for Fnv {
+        fn default() -> Self {
+            Self(0xcbf29ce484222325)
+        }
+    }
+
+    impl Checksum for Fnv {
+        const LEN: usize = 2;
+
+        fn update(&mut self, data: &[u8]) {
+            self.0 = data.iter().fold(self.0, |hash, &b| {
+                (hash ^ u64::from(b)).wrapping_mul(0x100000001b3)
+            });
+        }
+
+        fn finish(&self) -> u64 {
+            self.0
+        }
+    }
+
+    fn encode<C: Checksum>(data: &impl Serialize) -> Vec<u8> {
+        let mut output = ChecksumOutput::<_, C>::new(Vec::new());
+        data.serialize(Serializer::new(&mut output)).unwrap();
+        output.finish().unwrap()
+    }
+
+    #[test]
+    fn round_trip() {
+        let frame = encode::<Fnv>(&("hello", 10, [Some("a"), None]));
+        let payload = strip_checksum::<Fnv>(&frame).unwrap();
+
+        assert_eq!(frame.len(), payload.len() + 2);
+        assert_eq!(
+            payload,
+            b"*3\r\n$5\r\nhello\r\n:10\r\n*2\r\n$1\r\na\r\n$-1\r\n"
+        );
+    }
+
+    #[test]
+    fn every_corruption_detected() {
+        let frame = encode::<Fnv>(&["key", "value"]);
+
+        for i in 0..frame.len() {
+            let mut corrupt = frame.clone();
+            corrupt[i] ^= 0x01;
+            assert_matches!(strip_checksum::<Fnv>(&corrupt), Err(Error::Mismatch { .. }));
+        }
+    }
+
+    #[test]
+    fn too_short() {
+        assert_eq!(strip_checksum::<Fnv>(b"a"), Err(Error::TooShort));
+        assert_eq!(strip_checksum::<Fnv>(b""), Err(Error::TooShort));
+    }
+
+    #[cfg(feature = "crc32")]
+    #[test]
+    fn crc32() {
+        let frame = encode::<Crc32>(&"123456789");
+        let payload = b"$9\r\n123456789\r\n";
+
+        assert_eq!(
+            &frame[frame.len() - 4..],
+            crc32fast::hash(payload).to_be_bytes()
+        );
+        assert_eq!(strip_checksum::<Crc32>(&frame).unwrap(), payload);
+    }
+
+    #[cfg(feature = "xxhash")]
+    #[test]
+    fn xxh3() {
+        let frame = encode::<Xxh3>(&"123456789");
+        let payload = b"$9\r\n123456789\r\n";
+
+        assert_eq!(
+            &frame[frame.len() - 8..],
+            xxhash_rust::xxh3::xxh3_64(payload).to_be_bytes()
+        );
+        assert_eq!(strip_checksum::<Xxh3>(&frame).unwrap(), payload);
+    }
+}
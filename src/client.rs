@@ -0,0 +1,533 @@
+/*!
+A minimal blocking Redis client, built on the seredies serializer and
+deserializer.
+
+[`RedisConnection`] sends commands and reads their replies over a
+[`TcpStream`] (or any other stream), with support for pipelining. It's
+deliberately small: there's no connection pooling, no reconnection, no
+pub/sub, and no cluster support. It's useful for scripts, tests, and tools,
+and as an example of how to put the pieces of seredies together; a
+production client will probably want to do more.
+
+# Example
+
+```no_run
+use serde::Serialize;
+use seredies::client::RedisConnection;
+use seredies::components::{Command, CommandLike, RedisCommandSpec};
+
+#[derive(Serialize)]
+#[serde(rename = "INCRBY")]
+struct IncrBy<'a> {
+    key: &'a str,
+    increment: i64,
+}
+
+impl RedisCommandSpec for IncrBy<'_> {
+    type Response = i64;
+}
+
+let mut connection = RedisConnection::connect("localhost:6379")?;
+
+// A typed command knows the type of its reply
+let count = connection.call(&Command(IncrBy { key: "counter", increment: 5 }))?;
+
+// Any other command can be sent with an explicit reply type
+let name: Option<String> = connection.query(&Command(("GET", "name")))?;
+
+// Pipelined commands are written all at once, and their replies are
+// deserialized as a tuple
+let pipeline: [&dyn CommandLike; 2] = [
+    &Command(IncrBy { key: "counter", increment: 1 }),
+    &Command::raw("PING"),
+];
+let (count, pong): (i64, String) = connection.pipeline(pipeline)?;
+# Ok::<(), seredies::client::ClientError>(())
+```
+*/
+
+use std::{
+    fmt,
+    io::{self, Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+    task::Poll,
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    components::{CommandLike, RedisCommandSpec},
+    de::{
+        self, from_bytes_pipeline_with_config, from_bytes_with_config, parse::Parser, RedisMessage,
+    },
+    ser::{self, to_vec_with_config},
+    Config,
+};
+
+/// The size of the buffer used for each read from the stream.
+const READ_CHUNK_SIZE: usize = 4096;
+
+/**
+A blocking connection to a Redis server.
+
+Each command is serialized with the connection's [`Config`] and written
+to the stream in a single write; replies are read with a
+[`Parser`], so any data after a reply (like the reply to the next pipelined
+command) stays buffered for the next read. Replies that are Redis errors
+become [`ClientError::Redis`], unless the reply type is a [`Result`].
+
+After an i/o error, or a reply that couldn't be parsed, the connection is in
+an unknown state, and should be discarded. Replies that were parsed but
+failed to deserialize are consumed, so the connection can still be used.
+*/
+#[derive(Debug)]
+pub struct RedisConnection<S = TcpStream> {
+    stream: S,
+    parser: Parser,
+    config: Config,
+}
+
+impl RedisConnection {
+    /// Connect to a Redis server over TCP.
+    pub fn connect(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        let stream = TcpStream::connect(addr)?;
+
+        // Commands are always written all at once, so there's nothing for
+        // Nagle's algorithm to coalesce
+        stream.set_nodelay(true)?;
+
+        Ok(Self::new(stream))
+    }
+}
+
+impl<S: Read + Write> RedisConnection<S> {
+    /// Create a connection over an existing stream, which should already be
+    /// connected to a Redis server. The stream is flushed after each write,
+    /// so it can be buffered.
+    #[inline]
+    #[must_use]
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream,
+            parser: Parser::new(),
+            config: Config::new(),
+        }
+    }
+
+    /// Set the [`Config`] used to serialize commands and deserialize their
+    /// replies, including [pipelined][Self::pipeline] ones.
+    #[inline]
+    #[must_use]
+    pub fn with_config(self, config: Config) -> Self {
+        Self { config, ..self }
+    }
+
+    /// Get a reference to the underlying stream.
+    #[inline]
+    #[must_use]
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    /// Get a mutable reference to the underlying stream. Reading from or
+    /// writing to it directly will probably desynchronize the connection.
+    #[inline]
+    #[must_use]
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+
+    /// Send a command, without waiting for its reply. Each call to `send`
+    /// should be followed (eventually) by a call to
+    /// [`receive`][Self::receive].
+    pub fn send<C: Serialize + ?Sized>(&mut self, command: &C) -> Result<(), ClientError> {
+        let request = to_vec_with_config(command, self.config).map_err(ClientError::Serialize)?;
+        self.stream.write_all(&request)?;
+        self.stream.flush()?;
+
+        Ok(())
+    }
+
+    /// Read the next reply, and deserialize it as a `T`.
+    pub fn receive<T: DeserializeOwned>(&mut self) -> Result<T, ClientError> {
+        let config = self.config;
+        self.with_next_reply(|reply| from_bytes_with_config(reply, config))?
+            .map_err(ClientError::from)
+    }
+
+    /// Send a command, and then read its reply as a `T`.
+    pub fn query<T, C>(&mut self, command: &C) -> Result<T, ClientError>
+    where
+        T: DeserializeOwned,
+        C: Serialize + ?Sized,
+    {
+        self.send(command)?;
+        self.receive()
+    }
+
+    /// Send a [typed command][RedisCommandSpec], and then read its reply.
+    #[inline]
+    pub fn call<C>(&mut self, command: &C) -> Result<C::Response, ClientError>
+    where
+        C: RedisCommandSpec + Serialize + ?Sized,
+    {
+        self.query(command)
+    }
+
+    /**
+    Send several commands in a single write, and then read all of their
+    replies. The replies are deserialized together as a `T`, with the
+    [`from_bytes_pipeline`][de::from_bytes_pipeline] rules, so `T` is
+    usually a tuple with one element per command. If `T` has fewer elements
+    than there are commands, this returns [`de::Error::TrailingData`], but
+    all of the replies are still consumed.
+
+    If any reply is a Redis error, this returns [`ClientError::Redis`],
+    unless the corresponding element of `T` is a `Result`.
+    */
+    pub fn pipeline<T, I>(&mut self, commands: I) -> Result<T, ClientError>
+    where
+        T: DeserializeOwned,
+        I: IntoIterator,
+        I::Item: CommandLike,
+    {
+        let mut request = Vec::new();
+        let mut count: usize = 0;
+        let config = self.config;
+
+        commands.into_iter().try_for_each(|command| {
+            count += 1;
+            command.write_command_with_config(&mut request, config)
+        })?;
+
+        self.stream.write_all(&request)?;
+        self.stream.flush()?;
+
+        let mut replies = Vec::new();
+        for _ in 0..count {
+            self.with_next_reply(|reply| replies.extend_from_slice(reply))?;
+        }
+
+        match from_bytes_pipeline_with_config(&replies, self.config)? {
+            (value, []) => Ok(value),
+            (_, _) => Err(de::Error::TrailingData.into()),
+        }
+    }
+
+    /// Read from the stream until a complete reply is available, and pass
+    /// it to `handle`.
+    fn with_next_reply<R>(&mut self, handle: impl FnOnce(&[u8]) -> R) -> Result<R, ClientError> {
+        let mut chunk = [0; READ_CHUNK_SIZE];
+        let mut len = 0;
+
+        loop {
+            match self.parser.feed(&chunk[..len]) {
+                Poll::Ready(Ok(reply)) => return Ok(handle(reply)),
+                Poll::Ready(Err(err)) => return Err(de::Error::Parse(err).into()),
+                Poll::Pending => {}
+            }
+
+            len = match self.stream.read(&mut chunk) {
+                Ok(0) => return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into()),
+                Ok(len) => len,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => 0,
+                Err(err) => return Err(err.into()),
+            };
+        }
+    }
+}
+
+/// Errors from a [`RedisConnection`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ClientError {
+    /// There was an i/o error while sending a command or reading a reply.
+    /// If the server closed the connection before a reply was complete,
+    /// this is an error with [`io::ErrorKind::UnexpectedEof`].
+    Io(io::Error),
+
+    /// A command couldn't be serialized.
+    Serialize(ser::Error),
+
+    /// A reply couldn't be parsed, or didn't deserialize as the expected
+    /// type.
+    Deserialize(de::Error),
+
+    /// The server replied with an error.
+    Redis(RedisMessage),
+}
+
+impl From<io::Error> for ClientError {
+    #[inline]
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<ser::Error> for ClientError {
+    #[inline]
+    fn from(err: ser::Error) -> Self {
+        Self::Serialize(err)
+    }
+}
+
+/// Redis errors are converted to [`ClientError::Redis`], rather than being
+/// treated as deserialize errors.
+impl From<de::Error> for ClientError {
+    #[inline]
+    fn from(err: de::Error) -> Self {
+        match err {
+            de::Error::Redis(message) => Self::Redis(message),
+            err => Self::Deserialize(err),
+        }
+    }
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            ClientError::Io(_) => f.write_str("i/o error on the redis connection"),
+            ClientError::Serialize(_) => f.write_str("failed to serialize the command"),
+            ClientError::Deserialize(_) => f.write_str("failed to deserialize the reply"),
+            ClientError::Redis(ref message) => write!(f, "redis replied with an error: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match *self {
+            ClientError::Io(ref err) => Some(err),
+            ClientError::Serialize(ref err) => Some(err),
+            ClientError::Deserialize(ref err) => Some(err),
+            ClientError::Redis(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::BufRead, net::TcpListener, thread};
+
+    use cool_asserts::assert_matches;
+
+    use super::*;
+    use crate::components::Command;
+
+    /// A stream that replies with canned data, a few bytes at a time, and
+    /// records everything written to it.
+    struct MockStream {
+        replies: &'static [u8],
+        written: Vec<u8>,
+        flushed: usize,
+    }
+
+    impl MockStream {
+        fn new(replies: &'static [u8]) -> Self {
+            Self {
+                replies,
+                written: Vec::new(),
+                flushed: 0,
+            }
+        }
+    }
+
+    impl Read for MockStream {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let len = buf.len().min(self.replies.len()).min(3);
+            let (head, tail) = self.replies.split_at(len);
+
+            buf[..len].copy_from_slice(head);
+            self.replies = tail;
+
+            Ok(len)
+        }
+    }
+
+    impl Write for MockStream {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.written.write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            self.flushed = self.written.len();
+            Ok(())
+        }
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename = "INCR")]
+    struct Incr<'a> {
+        key: &'a str,
+    }
+
+    impl RedisCommandSpec for Incr<'_> {
+        type Response = i64;
+    }
+
+    #[test]
+    fn call() {
+        let mut connection = RedisConnection::new(MockStream::new(b":10\r\n"));
+
+        assert_eq!(
+            connection.call(&Command(Incr { key: "counter" })).unwrap(),
+            10
+        );
+        assert_eq!(
+            connection.get_ref().written,
+            b"*2\r\n$4\r\nINCR\r\n$7\r\ncounter\r\n"
+        );
+    }
+
+    #[test]
+    fn send_receive() {
+        let mut connection = RedisConnection::new(MockStream::new(b"+OK\r\n$3\r\nabc\r\n"));
+
+        connection.send(&Command(("SET", "key", "abc"))).unwrap();
+        connection.send(&Command(("GET", "key"))).unwrap();
+
+        let () = connection.receive::<Result<(), String>>().unwrap().unwrap();
+        let value: Option<String> = connection.receive().unwrap();
+        assert_eq!(value.as_deref(), Some("abc"));
+    }
+
+    #[test]
+    fn pipeline() {
+        let mut connection = RedisConnection::new(MockStream::new(b":1\r\n-ERR bad\r\n+PONG\r\n"));
+
+        let pipeline: [&dyn CommandLike; 3] = [
+            &Command(Incr { key: "counter" }),
+            &Command::raw("BAD"),
+            &Command::raw("PING"),
+        ];
+
+        let (count, error, pong): (i64, Result<(), String>, String) =
+            connection.pipeline(pipeline).unwrap();
+
+        assert_eq!(count, 1);
+        assert_eq!(error, Err(String::from("ERR bad")));
+        assert_eq!(pong, "PONG");
+        assert_eq!(
+            connection.get_ref().written,
+            b"*2\r\n$4\r\nINCR\r\n$7\r\ncounter\r\n*1\r\n$3\r\nBAD\r\n*1\r\n$4\r\nPING\r\n"
+        );
+    }
+
+    #[test]
+    fn flushes_writes() {
+        let mut connection = RedisConnection::new(MockStream::new(b"+PONG\r\n:1\r\n"));
+
+        let pong: String = connection.query(&Command::raw("PING")).unwrap();
+        assert_eq!(pong, "PONG");
+        assert_eq!(
+            connection.get_ref().flushed,
+            connection.get_ref().written.len()
+        );
+
+        let (count,): (i64,) = connection.pipeline([Command(("INCR", "key"))]).unwrap();
+        assert_eq!(count, 1);
+        assert_eq!(
+            connection.get_ref().flushed,
+            connection.get_ref().written.len()
+        );
+    }
+
+    #[test]
+    fn pipeline_config() {
+        let config = Config::new().with_string_floats(true);
+        let mut connection =
+            RedisConnection::new(MockStream::new(b":1\r\n$3\r\n2.5\r\n")).with_config(config);
+
+        let pipeline = [
+            Command(("ZADD", "key", 1.5, "member")),
+            Command(("ZINCRBY", "key", 1.0, "member")),
+        ];
+
+        let (added, score): (i64, f64) = connection.pipeline(&pipeline).unwrap();
+        assert_eq!(added, 1);
+        assert_eq!(score, 2.5);
+        assert_eq!(
+            connection.get_ref().written,
+            b"*4\r\n$4\r\nZADD\r\n$3\r\nkey\r\n$3\r\n1.5\r\n$6\r\nmember\r\n\
+              *4\r\n$7\r\nZINCRBY\r\n$3\r\nkey\r\n$1\r\n1\r\n$6\r\nmember\r\n"
+        );
+    }
+
+    #[test]
+    fn pipeline_too_many_replies() {
+        let mut connection = RedisConnection::new(MockStream::new(b":1\r\n:2\r\n:3\r\n"));
+
+        assert_matches!(
+            connection.pipeline::<(i64,), _>([Command::raw("A"), Command::raw("B")]),
+            Err(ClientError::Deserialize(de::Error::TrailingData))
+        );
+
+        // Both replies were consumed
+        assert_eq!(connection.receive::<i64>().unwrap(), 3);
+    }
+
+    #[test]
+    fn redis_error() {
+        let mut connection = RedisConnection::new(MockStream::new(b"-WRONGTYPE oops\r\n:1\r\n"));
+
+        assert_matches!(
+            connection.call(&Command(Incr { key: "list" })),
+            Err(ClientError::Redis(message)) => assert_eq!(message, b"WRONGTYPE oops")
+        );
+
+        // The connection is still usable
+        assert_eq!(
+            connection.call(&Command(Incr { key: "counter" })).unwrap(),
+            1
+        );
+    }
+
+    #[test]
+    fn deserialize_error() {
+        let mut connection = RedisConnection::new(MockStream::new(b"$3\r\nabc\r\n:1\r\n"));
+
+        assert_matches!(
+            connection.call(&Command(Incr { key: "string" })),
+            Err(ClientError::Deserialize(_))
+        );
+        assert_eq!(connection.receive::<i64>().unwrap(), 1);
+    }
+
+    #[test]
+    fn connection_closed() {
+        let mut connection = RedisConnection::new(MockStream::new(b":12"));
+
+        assert_matches!(
+            connection.call(&Command(Incr { key: "counter" })),
+            Err(ClientError::Io(err)) => assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof)
+        );
+    }
+
+    #[test]
+    fn tcp() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        // A tiny server that replies to each command with the number of
+        // arguments it had
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = io::BufReader::new(&stream);
+            let mut line = String::new();
+
+            while reader.read_line(&mut line).unwrap() > 0 {
+                if let Some(count) = line.strip_prefix('*') {
+                    write!(&stream, ":{count}").unwrap();
+                }
+                line.clear();
+            }
+        });
+
+        let mut connection = RedisConnection::connect(addr).unwrap();
+        let count: i64 = connection.query(&Command(("ECHO", "hello"))).unwrap();
+        assert_eq!(count, 2);
+
+        drop(connection);
+        server.join().unwrap();
+    }
+}
@@ -0,0 +1,176 @@
+/*!
+Utilities for working with [Redis Cluster](https://redis.io/docs/reference/cluster-spec/).
+
+Redis Cluster shards the keyspace into 16384 hash slots. Every key is mapped
+to a slot by taking the CRC16 of the key (or of its *hash tag*, if present)
+modulo 16384. Routing layers need to compute this slot in order to send a
+command to the correct node.
+
+# Example
+
+```
+use seredies::cluster::{hash_slot, hash_tag};
+
+assert_eq!(hash_slot(b"foo"), 12182);
+
+// Keys that share a hash tag always map to the same slot
+assert_eq!(hash_tag(b"{user1000}.following"), b"user1000");
+assert_eq!(
+    hash_slot(b"{user1000}.following"),
+    hash_slot(b"{user1000}.followers"),
+);
+```
+*/
+
+/// The number of hash slots in a Redis Cluster.
+pub const HASH_SLOTS: u16 = 16384;
+
+/// Lookup table for the CRC16 XMODEM polynomial (0x1021), computed at compile
+/// time.
+const CRC16_TABLE: [u16; 256] = {
+    let mut table = [0u16; 256];
+    let mut i = 0;
+
+    while i < 256 {
+        let mut crc = (i as u16) << 8;
+        let mut bit = 0;
+
+        while bit < 8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+            bit += 1;
+        }
+
+        table[i] = crc;
+        i += 1;
+    }
+
+    table
+};
+
+/**
+Compute the CRC16 checksum of `data`, using the XMODEM variant used by Redis
+Cluster (polynomial `0x1021`, initial value `0`, no reflection, no final xor).
+
+# Example
+
+```
+use seredies::cluster::crc16;
+
+assert_eq!(crc16(b"123456789"), 0x31C3);
+```
+*/
+#[must_use]
+pub const fn crc16(data: &[u8]) -> u16 {
+    let mut crc: u16 = 0;
+    let mut i = 0;
+
+    while i < data.len() {
+        let idx = ((crc >> 8) as u8 ^ data[i]) as usize;
+        crc = (crc << 8) ^ CRC16_TABLE[idx];
+        i += 1;
+    }
+
+    crc
+}
+
+/**
+Find the part of a key that is used for hashing.
+
+If the key contains a `{`, followed later by a `}`, with at least one byte in
+between, then only the bytes between the first `{` and the first `}` after it
+are hashed. Otherwise, the whole key is hashed. This allows users to force
+related keys into the same hash slot.
+
+# Example
+
+```
+use seredies::cluster::hash_tag;
+
+assert_eq!(hash_tag(b"{user}.name"), b"user");
+assert_eq!(hash_tag(b"plain-key"), b"plain-key");
+
+// Empty tags are ignored, and the whole key is hashed
+assert_eq!(hash_tag(b"{}.name"), b"{}.name");
+```
+*/
+#[must_use]
+pub fn hash_tag(key: &[u8]) -> &[u8] {
+    let Some(open) = memchr::memchr(b'{', key) else {
+        return key;
+    };
+
+    let tail = &key[open + 1..];
+
+    match memchr::memchr(b'}', tail) {
+        Some(0) | None => key,
+        Some(close) => &tail[..close],
+    }
+}
+
+/**
+Compute the Redis Cluster hash slot for a key. This accounts for hash tags
+(see [`hash_tag`]); the result is always less than [`HASH_SLOTS`].
+
+# Example
+
+```
+use seredies::cluster::hash_slot;
+
+assert_eq!(hash_slot(b"foo"), 12182);
+assert_eq!(hash_slot(b"{foo}.bar"), 12182);
+```
+*/
+#[inline]
+#[must_use]
+pub fn hash_slot(key: &[u8]) -> u16 {
+    crc16(hash_tag(key)) % HASH_SLOTS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crc16_reference() {
+        // The reference vector from the Redis Cluster specification
+        assert_eq!(crc16(b"123456789"), 0x31C3);
+        assert_eq!(crc16(b""), 0);
+    }
+
+    macro_rules! slot_tests {
+        ($($name:ident: $key:literal => $slot:literal,)*) => {$(
+            #[test]
+            fn $name() {
+                assert_eq!(hash_slot($key), $slot);
+            }
+        )*};
+    }
+
+    // Expected slots, including the hash tag edge cases described in the
+    // cluster specification
+    slot_tests! {
+        foo: b"foo" => 12182,
+        bar: b"bar" => 5061,
+        hello: b"hello" => 866,
+        empty: b"" => 0,
+        tagged: b"{foo}bar" => 12182,
+        tag_at_end: b"bar{foo}" => 12182,
+        empty_tag: b"{}foo" => 9500,
+        first_tag_wins: b"{foo}{bar}" => 12182,
+        unclosed_tag: b"{foo" => 13308,
+        nested_open: b"{{foo}}" => 13308,
+    }
+
+    #[test]
+    fn hash_tags() {
+        assert_eq!(hash_tag(b"{a}b"), b"a");
+        assert_eq!(hash_tag(b"a{b}c{d}"), b"b");
+        assert_eq!(hash_tag(b"}a{"), b"}a{");
+        assert_eq!(hash_tag(b"{}{a}"), b"{}{a}");
+        assert_eq!(hash_tag(b"{{a}}"), b"{a");
+    }
+}
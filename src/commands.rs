@@ -0,0 +1,128 @@
+/*!
+Typed models of Redis commands.
+
+Each command in this module is a plain struct that should be serialized with
+the [`Command`][crate::components::Command] adapter. Commands are organized by
+the Redis version that introduced them, and each version's commands are gated
+behind a cargo feature (`redis6`, `redis7`), so that users who only target
+older servers can trim the API surface. Every command also implements
+[`CommandInfo`], which exposes its name and minimum server version at runtime
+for capability negotiation.
+
+# Example
+
+```
+# #[cfg(feature = "redis6")] {
+use seredies::commands::{CommandInfo, Version, redis6::GetDel};
+use seredies::components::Command;
+use seredies::ser::to_vec;
+
+let command = Command(GetDel { key: "my-key" });
+
+assert_eq!(
+    to_vec(&command).unwrap(),
+    b"*2\r\n$6\r\nGETDEL\r\n$6\r\nmy-key\r\n",
+);
+
+assert_eq!(GetDel::<&str>::NAME, "GETDEL");
+assert!(GetDel::<&str>::is_supported_by(Version::new(7, 0, 0)));
+assert!(!GetDel::<&str>::is_supported_by(Version::new(6, 0, 9)));
+# }
+```
+
+# Replies that depend on the request
+
+Some commands reply with different shapes depending on their options. For
+these, the reply type is an `untagged` enum with a variant for each shape,
+so that the command can still have a single
+[`Response`][crate::components::RedisCommandSpec::Response] type. For
+instance, `LPOS` replies with a single index without `COUNT`, and an array
+of indexes with it, so its reply is
+[`LPosReply`](redis6::LPosReply):
+
+```
+# #[cfg(feature = "redis6")] {
+use seredies::commands::redis6::{LPos, LPosReply};
+use seredies::components::{Command, RedisCommandSpec};
+
+type Lpos<'a> = Command<LPos<&'a str, &'a str>>;
+
+let reply = Lpos::parse_response(b":2\r\n").unwrap();
+assert_eq!(reply, LPosReply::Single(Some(2)));
+
+let reply = Lpos::parse_response(b"*2\r\n:2\r\n:5\r\n").unwrap();
+assert_eq!(reply, LPosReply::Multiple(vec![2, 5]));
+# }
+```
+
+The variants should have shapes that can't be confused with each other,
+since an untagged enum uses the first variant that matches.
+*/
+
+use core::fmt::{self, Display};
+
+/// Implement `CommandInfo` for a command type. It's defined before the
+/// version modules, so that they can use it.
+#[cfg(feature = "redis6")]
+macro_rules! command_info {
+    ($($Type:ident $(<$($Generic:ident),*>)? = $name:literal since ($major:literal, $minor:literal, $patch:literal);)*) => {$(
+        impl $(<$($Generic),*>)? $crate::commands::CommandInfo for $Type $(<$($Generic),*>)? {
+            const NAME: &'static str = $name;
+            const SINCE: $crate::commands::Version =
+                $crate::commands::Version::new($major, $minor, $patch);
+        }
+    )*};
+}
+
+#[cfg(feature = "redis6")]
+pub mod redis6;
+#[cfg(feature = "redis7")]
+pub mod redis7;
+
+/// A Redis server version, such as `6.2.0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Version {
+    /// The major version
+    pub major: u16,
+
+    /// The minor version
+    pub minor: u16,
+
+    /// The patch version
+    pub patch: u16,
+}
+
+impl Version {
+    /// Create a new version
+    #[inline]
+    #[must_use]
+    pub const fn new(major: u16, minor: u16, patch: u16) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Metadata about a typed Redis command.
+pub trait CommandInfo {
+    /// The name of the command, as sent to Redis.
+    const NAME: &'static str;
+
+    /// The first Redis version that supports this command.
+    const SINCE: Version;
+
+    /// Check if a server running `version` supports this command.
+    #[inline]
+    #[must_use]
+    fn is_supported_by(version: Version) -> bool {
+        version >= Self::SINCE
+    }
+}
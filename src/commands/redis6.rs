@@ -0,0 +1,205 @@
+//! Commands introduced in Redis 6.
+
+use alloc::{string::String, vec::Vec};
+
+use serde::{Deserialize, Serialize};
+
+use crate::components::RedisCommandSpec;
+
+/// [`GETDEL`](https://redis.io/commands/getdel/): get the value of a key and
+/// delete it. Replies with the value, or null if the key didn't exist.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename = "GETDEL")]
+pub struct GetDel<K> {
+    /// The key to get and delete
+    pub key: K,
+}
+
+/// [`COPY`](https://redis.io/commands/copy/): copy the value stored at
+/// `source` to `destination`. Replies with 1 if the value was copied, or 0 if
+/// it wasn't.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename = "COPY")]
+pub struct CopyKey<S, D> {
+    /// The key to copy from
+    pub source: S,
+
+    /// The key to copy to
+    pub destination: D,
+
+    /// Copy the value into this logical database, rather than the current
+    /// one
+    #[serde(rename = "DB")]
+    pub db: Option<u32>,
+
+    /// Remove the destination key before copying, if it exists
+    #[serde(rename = "REPLACE")]
+    pub replace: bool,
+}
+
+/// [`LPOS`](https://redis.io/commands/lpos/): find the index of matching
+/// elements in a list. Replies with an integer (or null) if `count` is
+/// absent, or an array of integers if it's present; [`LPosReply`] accepts
+/// either.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename = "LPOS")]
+pub struct LPos<K, E> {
+    /// The list to search
+    pub key: K,
+
+    /// The element to search for
+    pub element: E,
+
+    /// Return the nth match, rather than the first. Negative values search
+    /// from the end of the list.
+    #[serde(rename = "RANK")]
+    pub rank: Option<i64>,
+
+    /// Return up to this many matches (0 means all of them).
+    #[serde(rename = "COUNT")]
+    pub count: Option<u64>,
+
+    /// Only compare this many elements of the list (0 means all of them).
+    #[serde(rename = "MAXLEN")]
+    pub max_len: Option<u64>,
+}
+
+/// The reply to [`LPos`], whose shape depends on whether `count` was set.
+/// Without `count`, Redis replies with the index of the match, or null if
+/// there wasn't one; with `count`, it replies with an array of the indexes of
+/// every match.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum LPosReply {
+    /// The reply when `count` wasn't set
+    Single(Option<u64>),
+
+    /// The reply when `count` was set
+    Multiple(Vec<u64>),
+}
+
+impl LPosReply {
+    /// Get the index of the first match, if there was one.
+    #[inline]
+    #[must_use]
+    pub fn first(&self) -> Option<u64> {
+        match *self {
+            LPosReply::Single(index) => index,
+            LPosReply::Multiple(ref indexes) => indexes.first().copied(),
+        }
+    }
+
+    /// Get the indexes of all of the matches, regardless of the shape of
+    /// the reply.
+    #[inline]
+    #[must_use]
+    pub fn into_vec(self) -> Vec<u64> {
+        match self {
+            LPosReply::Single(index) => index.into_iter().collect(),
+            LPosReply::Multiple(indexes) => indexes,
+        }
+    }
+}
+
+impl<K> RedisCommandSpec for GetDel<K> {
+    type Response = Option<String>;
+}
+
+impl<S, D> RedisCommandSpec for CopyKey<S, D> {
+    type Response = bool;
+}
+
+impl<K, E> RedisCommandSpec for LPos<K, E> {
+    type Response = LPosReply;
+}
+
+command_info! {
+    GetDel<K> = "GETDEL" since (6, 2, 0);
+    CopyKey<S, D> = "COPY" since (6, 2, 0);
+    LPos<K, E> = "LPOS" since (6, 0, 6);
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_test::{assert_ser_tokens, Token};
+
+    use super::*;
+    use crate::components::Command;
+
+    #[test]
+    fn copy() {
+        assert_ser_tokens(
+            &Command(CopyKey {
+                source: "src",
+                destination: "dest",
+                db: Some(2),
+                replace: true,
+            }),
+            &[
+                Token::Seq { len: Some(6) },
+                Token::Str("COPY"),
+                Token::Str("src"),
+                Token::Str("dest"),
+                Token::Str("DB"),
+                Token::Str("2"),
+                Token::Str("REPLACE"),
+                Token::SeqEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn lpos() {
+        assert_ser_tokens(
+            &Command(LPos {
+                key: "list",
+                element: "x",
+                rank: Some(-1),
+                count: None,
+                max_len: Some(100),
+            }),
+            &[
+                Token::Seq { len: Some(7) },
+                Token::Str("LPOS"),
+                Token::Str("list"),
+                Token::Str("x"),
+                Token::Str("RANK"),
+                Token::Str("-1"),
+                Token::Str("MAXLEN"),
+                Token::Str("100"),
+                Token::SeqEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn responses() {
+        assert_eq!(
+            Command::<GetDel<&str>>::parse_response(b"$5\r\nvalue\r\n").unwrap(),
+            Some(String::from("value"))
+        );
+        assert_eq!(
+            Command::<GetDel<&str>>::parse_response(b"$-1\r\n").unwrap(),
+            None
+        );
+        assert!(Command::<CopyKey<&str, &str>>::parse_response(b":1\r\n").unwrap());
+    }
+
+    #[test]
+    fn lpos_responses() {
+        let parse = Command::<LPos<&str, &str>>::parse_response;
+
+        assert_eq!(parse(b":3\r\n").unwrap(), LPosReply::Single(Some(3)));
+        assert_eq!(parse(b"$-1\r\n").unwrap(), LPosReply::Single(None));
+        assert_eq!(parse(b"*-1\r\n").unwrap().first(), None);
+
+        let reply = parse(b"*2\r\n:1\r\n:4\r\n").unwrap();
+        assert_eq!(reply.first(), Some(1));
+        assert_eq!(reply.into_vec(), [1, 4]);
+
+        assert_eq!(parse(b"*0\r\n").unwrap(), LPosReply::Multiple(Vec::new()));
+        assert_eq!(LPosReply::Single(Some(2)).into_vec(), [2]);
+
+        assert!(parse(b":-1\r\n").is_err());
+    }
+}
@@ -0,0 +1,37 @@
+//! Commands introduced in Redis 7.
+
+use serde::Serialize;
+
+use crate::components::RedisCommandSpec;
+
+/// [`EXPIRETIME`](https://redis.io/commands/expiretime/): get the absolute
+/// unix timestamp, in seconds, at which a key will expire. Replies with -1 if
+/// the key has no expiration, or -2 if it doesn't exist.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename = "EXPIRETIME")]
+pub struct ExpireTime<K> {
+    /// The key to inspect
+    pub key: K,
+}
+
+/// [`PEXPIRETIME`](https://redis.io/commands/pexpiretime/): like
+/// [`ExpireTime`], but in milliseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename = "PEXPIRETIME")]
+pub struct PExpireTime<K> {
+    /// The key to inspect
+    pub key: K,
+}
+
+impl<K> RedisCommandSpec for ExpireTime<K> {
+    type Response = i64;
+}
+
+impl<K> RedisCommandSpec for PExpireTime<K> {
+    type Response = i64;
+}
+
+command_info! {
+    ExpireTime<K> = "EXPIRETIME" since (7, 0, 0);
+    PExpireTime<K> = "PEXPIRETIME" since (7, 0, 0);
+}
@@ -2,10 +2,156 @@
 Helper components implementing common Redis and Rust patterns.
  */
 
+mod case;
 mod command;
+mod discriminant;
+mod error;
+mod exec;
+mod expect;
+mod expiration;
+mod geo;
+mod joined;
 mod key_value;
+mod mapped;
+mod redirect;
+mod scan;
+mod set;
+mod simple_string;
+mod stream;
 mod string;
+mod verbatim;
+mod with_scores;
+mod wrong_type;
+mod zadd;
 
-pub use command::Command;
-pub use key_value::KeyValuePairs;
+pub use case::{Case, Cased, KebabCase, Lowercase, SnakeCase, Uppercase};
+#[cfg(feature = "std")]
+pub use command::CallError;
+pub use command::{
+    Command, CommandArgument, CommandLike, CommandParameter, RawCommand, RedisCommandSpec,
+    UppercaseNames,
+};
+pub use discriminant::{Discriminant, Discriminated};
+pub use error::RedisError;
+pub use exec::ExecResponse;
+pub use expect::{ExpectArray, ExpectBulk, ExpectInteger};
+pub use expiration::Expiration;
+pub use geo::GeoPosition;
+pub use joined::Joined;
+pub use key_value::{FirstWins, KeyValuePairs, LastWins, LooseMap, WithExtra};
+pub(crate) use key_value::{
+    KeyValuePairsDeserializer, FIRST_WINS_TOKEN, LAST_WINS_TOKEN, WITH_EXTRA_TOKEN,
+};
+pub use mapped::{Mapped, Mapping};
+pub use redirect::{Redirect, RedirectKind};
+pub use scan::ScanResponse;
+pub use set::RedisSet;
+pub use simple_string::SimpleString;
+pub use stream::{StreamEntry, StreamId, XReadResponse};
 pub use string::RedisString;
+pub use verbatim::Verbatim;
+pub(crate) use verbatim::VERBATIM_TOKEN;
+pub use with_scores::WithScores;
+pub use wrong_type::OrWrongType;
+pub(crate) use wrong_type::{is_wrong_type, OR_WRONG_TYPE_TOKEN};
+pub use zadd::{Score, ZaddCondition, ZaddIncrOptions, ZaddMode, ZaddOptions};
+
+/**
+Derive `Serialize` for a struct that is a Redis command, checking at compile
+time that each of its fields can be sent as command arguments.
+
+This is an alternative to the [`Command`] adapter. `Command` decides how to
+send each field at runtime, based on how it serializes, so mistakes like a
+list of lists, or a `bool` in a tuple struct (which has no field name to
+send as a flag) are only reported when the command is serialized. The
+derived implementation instead requires each field to be a
+[`CommandParameter`], and each element of a list parameter to be a
+[`CommandArgument`], so these mistakes are compile errors. It's available
+with the `derive` feature.
+
+The command is sent as an array of strings, starting with the command name,
+followed by each field in order:
+
+- The command name is the struct name in uppercase, or can be set with
+  `#[redis(name = "...")]`. Names with spaces, like `"CLIENT KILL"`, are
+  sent as several arguments.
+- Fields are sent as their arguments. Primitive values are sent as one
+  argument, options as one argument or nothing, and lists as one argument
+  for each element.
+- `#[redis(named)]` sends the field's token before its arguments, if there
+  are any, like `EX 10`.
+- `#[redis(flag)]` sends the field's token if the field is `true`, and
+  nothing otherwise. Flags must be `bool`, and `bool` fields must be flags.
+- `#[redis(skip)]` skips the field.
+
+A field's token is its name in uppercase, without underscores, so that
+`keep_ttl` is `KEEPTTL`. It can be set with `#[redis(named = "...")]` or
+`#[redis(flag = "...")]`, which is required for the fields of tuple structs.
+
+The derive only implements `Serialize`; use [`Command`] for commands that
+also need to be deserialized.
+
+# Example
+
+```
+use seredies::components::RedisCommand;
+use seredies::ser::to_vec;
+
+#[derive(RedisCommand)]
+struct Set<'a> {
+    key: &'a str,
+    value: &'a str,
+    #[redis(flag = "NX")]
+    only_if_new: bool,
+    #[redis(named = "EX")]
+    expire_seconds: Option<u64>,
+    #[redis(flag)]
+    keep_ttl: bool,
+}
+
+let command = Set {
+    key: "key",
+    value: "value",
+    only_if_new: true,
+    expire_seconds: Some(60),
+    keep_ttl: false,
+};
+
+assert_eq!(
+    to_vec(&command).unwrap(),
+    b"*6\r\n$3\r\nSET\r\n$3\r\nkey\r\n$5\r\nvalue\r\n$2\r\nNX\r\n$2\r\nEX\r\n$2\r\n60\r\n",
+);
+```
+
+```compile_fail
+use seredies::components::RedisCommand;
+
+// Lists of lists can't be flattened into arguments
+#[derive(RedisCommand)]
+struct MultiGet {
+    keys: Vec<Vec<String>>,
+}
+```
+
+```compile_fail
+use seredies::components::RedisCommand;
+
+// A `bool` is sent as a flag, so it has to be marked as one
+#[derive(RedisCommand)]
+struct Set<'a> {
+    key: &'a str,
+    value: &'a str,
+    nx: bool,
+}
+```
+
+```compile_fail
+use seredies::components::RedisCommand;
+
+// Tuple struct fields have no name to use as a token, so it has to be given
+#[derive(RedisCommand)]
+struct Set<'a>(&'a str, &'a str, #[redis(flag)] bool);
+```
+*/
+#[cfg(feature = "derive")]
+pub use seredies_derive::RedisCommand;
@@ -0,0 +1,610 @@
+use core::{
+    fmt::{self, Write as _},
+    marker::PhantomData,
+};
+
+use arrayvec::ArrayString;
+use paste::paste;
+use serde::{de, ser};
+
+/**
+A case convention for the names of unit enum variants. This is used by
+[`Cased`] to convert variant names to and from their representation on the
+wire.
+
+Implement this trait for your own type to use a convention other than the
+ones provided by seredies.
+*/
+pub trait Case {
+    /// Write the variant `name`, converted to this case, to `dest`.
+    fn write_name<W: fmt::Write>(name: &str, dest: &mut W) -> fmt::Result;
+}
+
+/// Variant names are all lowercase: `NotExists` becomes `notexists`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Lowercase;
+
+impl Case for Lowercase {
+    fn write_name<W: fmt::Write>(name: &str, dest: &mut W) -> fmt::Result {
+        name.chars()
+            .flat_map(char::to_lowercase)
+            .try_for_each(|c| dest.write_char(c))
+    }
+}
+
+/// Variant names are all uppercase: `NotExists` becomes `NOTEXISTS`. This is
+/// the convention used by most Redis command options.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Uppercase;
+
+impl Case for Uppercase {
+    fn write_name<W: fmt::Write>(name: &str, dest: &mut W) -> fmt::Result {
+        name.chars()
+            .flat_map(char::to_uppercase)
+            .try_for_each(|c| dest.write_char(c))
+    }
+}
+
+/// Variant names are lowercase words separated by `-`: `NotExists` becomes
+/// `not-exists`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct KebabCase;
+
+impl Case for KebabCase {
+    #[inline]
+    fn write_name<W: fmt::Write>(name: &str, dest: &mut W) -> fmt::Result {
+        write_words(name, '-', dest)
+    }
+}
+
+/// Variant names are lowercase words separated by `_`: `NotExists` becomes
+/// `not_exists`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct SnakeCase;
+
+impl Case for SnakeCase {
+    #[inline]
+    fn write_name<W: fmt::Write>(name: &str, dest: &mut W) -> fmt::Result {
+        write_words(name, '_', dest)
+    }
+}
+
+/// Write a PascalCase `name` as lowercase words joined by `separator`. A
+/// word starts at each uppercase letter that follows a lowercase letter or
+/// digit, or that begins a new word after an acronym (like the `S` in
+/// `HTTPServer`).
+fn write_words<W: fmt::Write>(name: &str, separator: char, dest: &mut W) -> fmt::Result {
+    let mut chars = name.chars().peekable();
+    let mut previous: Option<char> = None;
+
+    while let Some(c) = chars.next() {
+        if c.is_uppercase() {
+            let boundary = match previous {
+                None => false,
+                Some(p) if p.is_lowercase() || p.is_numeric() => true,
+                Some(p) if p.is_uppercase() => chars.peek().is_some_and(|n| n.is_lowercase()),
+                Some(_) => false,
+            };
+
+            if boundary {
+                dest.write_char(separator)?;
+            }
+        }
+
+        c.to_lowercase().try_for_each(|c| dest.write_char(c))?;
+        previous = Some(c);
+    }
+
+    Ok(())
+}
+
+/// Display adapter for a variant name converted with a [`Case`].
+struct CasedName<'a, C> {
+    name: &'a str,
+    case: PhantomData<C>,
+}
+
+impl<C: Case> fmt::Display for CasedName<'_, C> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        C::write_name(self.name, f)
+    }
+}
+
+/// `fmt::Write` implementation that checks if the written data is exactly
+/// equal to some expected bytes, without allocating.
+struct Matcher<'a> {
+    remaining: &'a [u8],
+}
+
+impl fmt::Write for Matcher<'_> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        match self.remaining.strip_prefix(s.as_bytes()) {
+            Some(tail) => {
+                self.remaining = tail;
+                Ok(())
+            }
+            None => Err(fmt::Error),
+        }
+    }
+}
+
+/// Check if `name`, converted with the case `C`, is equal to `wire`.
+#[inline]
+#[must_use]
+fn name_matches<C: Case>(name: &str, wire: &[u8]) -> bool {
+    let mut matcher = Matcher { remaining: wire };
+    C::write_name(name, &mut matcher).is_ok() && matcher.remaining.is_empty()
+}
+
+/**
+Adapter type that converts the names of unit enum variants to a different
+case when they're serialized or deserialized.
+
+Unit variants are serialized as their name, which in Rust is usually
+PascalCase, but Redis and many module APIs expect names in some other
+case, like `NX` or `yes`. A `#[serde(rename_all)]` attribute works for
+types you control; `Cased` applies a [`Case`] to any enum, including one
+whose variant names are used in more than one convention. The conversion
+applies only to the wrapped value itself, so a collection of enums should
+contain `Cased` values, like `Vec<Cased<T, C>>`. Any other value is
+(de)serialized unchanged.
+
+The [`Lowercase`], [`Uppercase`], [`KebabCase`], and [`SnakeCase`] cases are
+provided.
+
+# Example
+
+```
+use serde::{Deserialize, Serialize};
+use seredies::components::{Cased, KebabCase, Lowercase};
+use seredies::de::from_bytes;
+use seredies::ser::to_vec;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+enum Answer {
+    Yes,
+    No,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+enum Policy {
+    AllKeysLru,
+    NoEviction,
+}
+
+let answer: Cased<Answer, Lowercase> = Cased::new(Answer::Yes);
+assert_eq!(to_vec(&answer).unwrap(), b"$3\r\nyes\r\n");
+
+let policy: Cased<Policy, KebabCase> = from_bytes(b"$12\r\nall-keys-lru\r\n").unwrap();
+assert_eq!(policy.value, Policy::AllKeysLru);
+```
+*/
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Cased<T, C> {
+    /// The wrapped value.
+    pub value: T,
+    case: PhantomData<C>,
+}
+
+impl<T, C> Cased<T, C> {
+    /// Wrap a value, so that its variant name is converted to the case `C`.
+    #[inline]
+    #[must_use]
+    pub const fn new(value: T) -> Self {
+        Self {
+            value,
+            case: PhantomData,
+        }
+    }
+
+    /// Get the wrapped value.
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T, C> ser::Serialize for Cased<T, C>
+where
+    T: ser::Serialize,
+    C: Case,
+{
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        self.value.serialize(CasedSerializer {
+            inner: serializer,
+            case: PhantomData::<C>,
+        })
+    }
+}
+
+impl<'de, T, C> de::Deserialize<'de> for Cased<T, C>
+where
+    T: de::Deserialize<'de>,
+    C: Case,
+{
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        T::deserialize(CasedDeserializer {
+            inner: deserializer,
+            case: PhantomData::<C>,
+        })
+        .map(Self::new)
+    }
+}
+
+/// Serializer that converts unit variant names with a [`Case`], and forwards
+/// everything else to the underlying serializer.
+struct CasedSerializer<S, C> {
+    inner: S,
+    case: PhantomData<C>,
+}
+
+macro_rules! forward_serialize {
+    ($($method:ident($($arg:ident: $type:ty),*) $(-> $Compound:ident)?;)*) => {$(
+        paste! {
+            #[inline]
+            fn [<serialize_ $method>](
+                self,
+                $($arg: $type),*
+            ) -> Result<forward_serialize!(@ret $($Compound)?), Self::Error> {
+                self.inner.[<serialize_ $method>]($($arg),*)
+            }
+        }
+    )*};
+
+    (@ret) => { Self::Ok };
+    (@ret $Compound:ident) => { Self::$Compound };
+}
+
+impl<S, C> ser::Serializer for CasedSerializer<S, C>
+where
+    S: ser::Serializer,
+    C: Case,
+{
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    type SerializeSeq = S::SerializeSeq;
+    type SerializeTuple = S::SerializeTuple;
+    type SerializeTupleStruct = S::SerializeTupleStruct;
+    type SerializeTupleVariant = S::SerializeTupleVariant;
+    type SerializeMap = S::SerializeMap;
+    type SerializeStruct = S::SerializeStruct;
+    type SerializeStructVariant = S::SerializeStructVariant;
+
+    forward_serialize! {
+        bool(v: bool);
+        i8(v: i8);
+        i16(v: i16);
+        i32(v: i32);
+        i64(v: i64);
+        i128(v: i128);
+        u8(v: u8);
+        u16(v: u16);
+        u32(v: u32);
+        u64(v: u64);
+        u128(v: u128);
+        f32(v: f32);
+        f64(v: f64);
+        char(v: char);
+        str(v: &str);
+        bytes(v: &[u8]);
+        none();
+        unit();
+        unit_struct(name: &'static str);
+        seq(len: Option<usize>) -> SerializeSeq;
+        tuple(len: usize) -> SerializeTuple;
+        tuple_struct(name: &'static str, len: usize) -> SerializeTupleStruct;
+        tuple_variant(
+            name: &'static str,
+            variant_index: u32,
+            variant: &'static str,
+            len: usize
+        ) -> SerializeTupleVariant;
+        map(len: Option<usize>) -> SerializeMap;
+        struct(name: &'static str, len: usize) -> SerializeStruct;
+        struct_variant(
+            name: &'static str,
+            variant_index: u32,
+            variant: &'static str,
+            len: usize
+        ) -> SerializeStructVariant;
+    }
+
+    #[inline]
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.inner.collect_str(&CasedName::<C> {
+            name: variant,
+            case: PhantomData,
+        })
+    }
+
+    #[inline]
+    fn serialize_some<T: ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: ser::Serialize,
+    {
+        self.inner.serialize_some(value)
+    }
+
+    #[inline]
+    fn serialize_newtype_struct<T: ?Sized>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ser::Serialize,
+    {
+        self.inner.serialize_newtype_struct(name, value)
+    }
+
+    #[inline]
+    fn serialize_newtype_variant<T: ?Sized>(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ser::Serialize,
+    {
+        self.inner
+            .serialize_newtype_variant(name, variant_index, variant, value)
+    }
+
+    #[inline]
+    fn collect_str<T: ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: fmt::Display,
+    {
+        self.inner.collect_str(value)
+    }
+
+    #[inline]
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+}
+
+/// Deserializer that matches unit variant names converted with a [`Case`],
+/// and forwards everything else to the underlying deserializer.
+struct CasedDeserializer<D, C> {
+    inner: D,
+    case: PhantomData<C>,
+}
+
+macro_rules! forward_deserialize {
+    ($($method:ident $(($($arg:ident : $type:ty),*))?)*) => {$(
+        paste! {
+            #[inline]
+            fn [<deserialize_ $method>]<V>(
+                self,
+                $($($arg : $type,)*)?
+                visitor: V,
+            ) -> Result<V::Value, Self::Error>
+            where
+                V: de::Visitor<'de>
+            {
+                self.inner.[<deserialize_ $method>]($($($arg,)*)? visitor)
+            }
+        }
+    )*}
+}
+
+pub(super) use forward_deserialize;
+
+impl<'de, D, C> de::Deserializer<'de> for CasedDeserializer<D, C>
+where
+    D: de::Deserializer<'de>,
+    C: Case,
+{
+    type Error = D::Error;
+
+    forward_deserialize! {
+        any ignored_any bool
+        i8 i16 i32 i64 i128
+        u8 u16 u32 u64 u128
+        f32 f64
+        char str string bytes byte_buf identifier
+        option unit
+        seq map
+
+        unit_struct(name: &'static str)
+        newtype_struct(name: &'static str)
+        tuple(len: usize)
+        tuple_struct(name: &'static str, len: usize)
+        struct(name: &'static str, fields: &'static[&'static str])
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        let variant = self.inner.deserialize_str(VariantVisitor {
+            variants,
+            case: PhantomData::<C>,
+        })?;
+
+        visitor.visit_enum(de::IntoDeserializer::into_deserializer(variant))
+    }
+
+    #[inline]
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+}
+
+/// Visitor that finds the variant whose converted name matches a string.
+struct VariantVisitor<C> {
+    variants: &'static [&'static str],
+    case: PhantomData<C>,
+}
+
+impl<C: Case> de::Visitor<'_> for VariantVisitor<C> {
+    type Value = &'static str;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("one of ")?;
+
+        self.variants
+            .iter()
+            .enumerate()
+            .try_for_each(|(idx, &variant)| {
+                let separator = if idx == 0 { "" } else { ", " };
+                let variant = CasedName::<C> {
+                    name: variant,
+                    case: PhantomData,
+                };
+
+                write!(formatter, "{separator}`{variant}`")
+            })
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        self.find(v)
+            .ok_or_else(|| E::invalid_value(de::Unexpected::Bytes(v), &self))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        self.find(v.as_bytes())
+            .ok_or_else(|| E::invalid_value(de::Unexpected::Str(v), &self))
+    }
+
+    // Integer replies are matched by their decimal representation, for
+    // variants with tokens like `1` and `0`
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+        let mut buffer: ArrayString<20> = ArrayString::new();
+        let _ = write!(buffer, "{v}");
+
+        self.find(buffer.as_bytes())
+            .ok_or_else(|| E::invalid_value(de::Unexpected::Signed(v), &self))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        let mut buffer: ArrayString<20> = ArrayString::new();
+        let _ = write!(buffer, "{v}");
+
+        self.find(buffer.as_bytes())
+            .ok_or_else(|| E::invalid_value(de::Unexpected::Unsigned(v), &self))
+    }
+}
+
+impl<C: Case> VariantVisitor<C> {
+    #[inline]
+    #[must_use]
+    fn find(&self, wire: &[u8]) -> Option<&'static str> {
+        self.variants
+            .iter()
+            .copied()
+            .find(|variant| name_matches::<C>(variant, wire))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::{de::from_bytes, ser::to_vec};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+    enum Mode {
+        NotExists,
+        HTTPServer,
+        Version2Only,
+    }
+
+    fn convert<C: Case>(name: &str) -> String {
+        CasedName::<C> {
+            name,
+            case: PhantomData,
+        }
+        .to_string()
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(convert::<Lowercase>("NotExists"), "notexists");
+        assert_eq!(convert::<Uppercase>("NotExists"), "NOTEXISTS");
+        assert_eq!(convert::<KebabCase>("NotExists"), "not-exists");
+        assert_eq!(convert::<SnakeCase>("NotExists"), "not_exists");
+        assert_eq!(convert::<KebabCase>("HTTPServer"), "http-server");
+        assert_eq!(convert::<SnakeCase>("Version2Only"), "version2_only");
+        assert_eq!(convert::<KebabCase>("A"), "a");
+    }
+
+    #[test]
+    fn round_trip() {
+        for mode in [Mode::NotExists, Mode::HTTPServer, Mode::Version2Only] {
+            let cased: Cased<Mode, KebabCase> = Cased::new(mode);
+            let data = to_vec(&cased).unwrap();
+            let parsed: Cased<Mode, KebabCase> = from_bytes(&data).unwrap();
+
+            assert_eq!(parsed, cased);
+        }
+    }
+
+    #[test]
+    fn serialize() {
+        assert_eq!(
+            to_vec(&Cased::<_, Uppercase>::new(Mode::NotExists)).unwrap(),
+            b"$9\r\nNOTEXISTS\r\n"
+        );
+    }
+
+    #[test]
+    fn deserialize_case_sensitive() {
+        let mode: Cased<Mode, SnakeCase> = from_bytes(b"$10\r\nnot_exists\r\n").unwrap();
+        assert_eq!(mode.value, Mode::NotExists);
+
+        assert!(from_bytes::<Cased<Mode, SnakeCase>>(b"$10\r\nNOT_EXISTS\r\n").is_err());
+        assert!(from_bytes::<Cased<Mode, SnakeCase>>(b"$9\r\nNotExists\r\n").is_err());
+        assert!(from_bytes::<Cased<Mode, SnakeCase>>(b"$3\r\nnot\r\n").is_err());
+    }
+
+    #[test]
+    fn collection_of_cased() {
+        let modes: Vec<Cased<Mode, Lowercase>> =
+            Vec::from([Cased::new(Mode::HTTPServer), Cased::new(Mode::NotExists)]);
+        let data = to_vec(&modes).unwrap();
+
+        assert_eq!(data, b"*2\r\n$10\r\nhttpserver\r\n$9\r\nnotexists\r\n");
+        assert_eq!(
+            from_bytes::<Vec<Cased<Mode, Lowercase>>>(&data).unwrap(),
+            modes
+        );
+    }
+
+    #[test]
+    fn other_values_unchanged() {
+        assert_eq!(
+            to_vec(&Cased::<_, Uppercase>::new("NotAVariant")).unwrap(),
+            b"$11\r\nNotAVariant\r\n"
+        );
+
+        let value: Cased<i64, Uppercase> = from_bytes(b":12\r\n").unwrap();
+        assert_eq!(value.value, 12);
+    }
+}
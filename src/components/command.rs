@@ -1,4 +1,10 @@
+mod arguments;
+mod batch;
+mod deserialize;
 mod length;
+mod raw;
+mod spec;
+mod uppercase;
 
 use lazy_format::lazy_format;
 use serde::ser;
@@ -8,6 +14,15 @@ use crate::ser::util::TupleSeqAdapter;
 
 use super::RedisString;
 
+pub use arguments::{CommandArgument, CommandParameter};
+pub use batch::CommandLike;
+pub use raw::RawCommand;
+#[cfg(feature = "std")]
+pub use spec::CallError;
+pub use spec::RedisCommandSpec;
+pub use uppercase::UppercaseNames;
+use uppercase::{TypeName, UPPERCASE_NAMES_TOKEN};
+
 /**
 Adapter type for serializing redis commands.
 
@@ -17,12 +32,28 @@ implement commands by serializing the underlying type as though it was a redis
 command. It uses the following rules & conventions:
 
 - The type should be a struct or enum. The name of the struct or name of
-  the enum will be used as the command name. Tuples, lists, and maps cannot
-  be commands.
+  the enum will be used as the command name. Lists and maps cannot be
+  commands.
+- A name with spaces in it, like `#[serde(rename = "CONFIG SET")]`, is
+  sent as a separate argument for each word.
+- If the type is a newtype struct or variant whose value is a struct or
+  tuple variant, like `Client::Kill { .. }` in
+  `Command::Client(Client::Kill { .. })`, that variant is a subcommand: its
+  name is sent after the command name, followed by its fields.
+- The type may also be a tuple whose first element is a string, like
+  `("GET", key)`. The first element is used as the command name, and the
+  rest of the elements are serialized as arguments, using the same rules as
+  the fields of a tuple struct.
 - All of the command arguments will be serialized as either strings or bytes
   objects (preferring strings).
 - The fields in the type will be serialized as arguments, using these rules:
   - Fields will be serialized in order, and the field names will be ignored.
+    Redis arguments are positional, so this order is guaranteed: arguments
+    are always written in the order that the fields are serialized, which
+    for `#[derive(Serialize)]` is the order they're declared in. Fields that
+    are skipped (with `#[serde(skip)]` or `skip_serializing_if`) contribute
+    no arguments and don't affect the order of the others. Struct commands
+    can't use `#[serde(flatten)]`, since it serializes the struct as a map.
   - Primitive types like strings and numbers will be serialized as strings.
   - Booleans are treated like flags, and will serialize the name of the
     field if true.
@@ -32,8 +63,23 @@ command. It uses the following rules & conventions:
     variant name will be used as the argument (see examples).
   - Enums will serialize the name of the enum, followed by its value (if
     present)
-  - Lists will be flattened one level. Nested lists are an error.
+  - Lists will be flattened one level. Nested lists are an error. An empty
+    list contributes no arguments at all.
+  - Tuples and structs inside of lists are flattened one more level, into a
+    group of arguments, so that a `Vec<(f64, String)>` is sent as
+    alternating scores and members. Their fields are sent in order, and
+    struct field names are ignored, like the fields of the command itself.
+    Groups can only contain primitive values.
   - Maps will be flattened to key-value sequences. Nested maps are an error.
+    Values that are `None` are skipped, so that their key is sent on its own,
+    like a flag.
+  - Empty strings are sent as empty arguments. Nulls can't be sent as
+    arguments, so options inside of lists are an error.
+
+Names are sent exactly as serde reports them, so a struct named `Set` is
+sent as `Set`, but Redis expects `SET`. Either rename each one with
+`#[serde(rename)]`, or create the command with [`Command::uppercase`] to send
+every name from the type in uppercase (see [`UppercaseNames`]).
 
 # Examples
 
@@ -201,6 +247,131 @@ assert_ser_tokens(&command, &[
     Token::SeqEnd
 ]);
 ```
+
+## Multi-word commands
+
+Commands like `CONFIG SET` and `CLIENT KILL` have names made of two words.
+They can be written as a struct with a multi-word name, or as an enum of
+commands with a variant for each subcommand.
+
+```
+use serde::Serialize;
+use serde_test::{assert_ser_tokens, Token};
+use seredies::components::Command;
+
+#[derive(Serialize)]
+#[serde(rename = "CONFIG SET")]
+struct ConfigSet<'a> {
+    parameter: &'a str,
+    value: &'a str,
+}
+
+assert_ser_tokens(&Command(ConfigSet { parameter: "port", value: "6380" }), &[
+    Token::Seq { len: Some(4) },
+    Token::Str("CONFIG"),
+    Token::Str("SET"),
+    Token::Str("port"),
+    Token::Str("6380"),
+    Token::SeqEnd,
+]);
+
+#[derive(Serialize)]
+enum Client<'a> {
+    Kill {
+        user: Option<&'a str>,
+    },
+    List,
+}
+
+#[derive(Serialize)]
+enum Admin<'a> {
+    Client(#[serde(borrow)] Client<'a>),
+}
+
+assert_ser_tokens(&Command::uppercase(Admin::Client(Client::Kill { user: Some("bob") })), &[
+    Token::Seq { len: Some(4) },
+    Token::Str("CLIENT"),
+    Token::Str("KILL"),
+    Token::Str("USER"),
+    Token::Str("bob"),
+    Token::SeqEnd,
+]);
+```
+
+## Borrowed commands
+
+A `Command` can wrap a reference to a command, as well as the command
+itself, and both serialize identically. This is convenient when the command
+is built from (or stored in) borrowed data, since the command type doesn't
+have to be `Clone`. The inner value is serialized twice (once to compute
+the length of the array, and once to write it), but it's never cloned.
+
+```
+use serde::Serialize;
+use seredies::components::Command;
+use seredies::ser::to_vec;
+
+#[derive(Serialize)]
+#[serde(rename = "DEL")]
+struct Del {
+    keys: Vec<String>,
+}
+
+let del = Del { keys: vec!["a".to_owned(), "b".to_owned()] };
+let bytes = to_vec(&Command::borrowed(&del)).unwrap();
+
+assert_eq!(bytes, to_vec(&Command(del)).unwrap());
+```
+
+# Deserializing
+
+`Command` can also be deserialized from an array of strings, as received by
+a Redis server or proxy, by reversing the rules above. The first argument
+must be the struct or variant name, and the command name, flags, and
+keywords are all matched case-insensitively. A struct name with spaces in it
+is matched one argument per word, but enum variant names can only be one
+word, including the variants of subcommands. A bool is true if its field name
+is the next argument. An option is `None` unless the next argument is its
+field name (for primitive values) or one of its variant names (for enums),
+so optional parameters must appear in the same order as the fields. Lists
+and maps consume all of the remaining arguments, and each tuple or struct in
+a list consumes one argument per field. A key without a value can't be
+told apart from a key followed by a value, so every map key is expected to
+have one, and optional map values are always `Some`.
+
+```
+use serde::Deserialize;
+use seredies::components::Command;
+use seredies::de::from_bytes;
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(rename = "SET")]
+struct Set<'a> {
+    key: &'a str,
+    value: &'a str,
+
+    #[serde(rename = "GET")]
+    get: bool,
+
+    expiry: Option<Expiry>,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+enum Expiry {
+    EX(u64),
+    PX(u64),
+}
+
+let input = b"*5\r\n$3\r\nset\r\n$3\r\nkey\r\n$5\r\nvalue\r\n$2\r\nex\r\n$2\r\n60\r\n";
+let Command(command): Command<Set> = from_bytes(input).unwrap();
+
+assert_eq!(command, Set {
+    key: "key",
+    value: "value",
+    get: false,
+    expiry: Some(Expiry::EX(60)),
+});
+```
 */
 #[derive(Debug, Copy, Clone, Default)]
 pub struct Command<T>(pub T);
@@ -214,6 +385,26 @@ where
     }
 }
 
+impl<T> Command<T> {
+    /// Borrow the inner command, as a `Command<&T>`, which serializes the
+    /// same way as this one.
+    #[inline]
+    #[must_use]
+    pub const fn by_ref(&self) -> Command<&T> {
+        Command(&self.0)
+    }
+}
+
+impl<'a, T: ?Sized> Command<&'a T> {
+    /// Wrap a reference to a command. This is the same as `Command(command)`,
+    /// but it allows `T` to be unsized, and it can help type inference.
+    #[inline]
+    #[must_use]
+    pub const fn borrowed(command: &'a T) -> Self {
+        Self(command)
+    }
+}
+
 impl<T> ser::Serialize for Command<T>
 where
     T: ser::Serialize,
@@ -227,16 +418,38 @@ where
             .serialize(CommandSerializer {
                 serializer: length::Serializer,
                 length: (),
+                uppercase: false,
             })
             .map_err(|err| match err {
                 length::Error::Custom(msg) => ser::Error::custom(msg),
                 err => ser::Error::custom(err),
             })?;
 
-        self.0.serialize(CommandSerializer { serializer, length })
+        self.0.serialize(CommandSerializer {
+            serializer,
+            length,
+            uppercase: false,
+        })
     }
 }
 
+/// Split a command name from the command type into words. Some commands, like
+/// `CONFIG SET`, are made of two words, which Redis expects as separate
+/// arguments.
+#[inline]
+fn command_words(name: &str) -> impl Iterator<Item = &str> {
+    name.split(' ').filter(|word| !word.is_empty())
+}
+
+#[inline]
+fn serialize_command_name<S>(sequence: &mut S, name: &str, uppercase: bool) -> Result<(), S::Error>
+where
+    S: ser::SerializeSeq,
+{
+    command_words(name)
+        .try_for_each(|word| sequence.serialize_element(&TypeName::new(word, uppercase)))
+}
+
 fn invalid_command_type<T, E: ser::Error>(kind: &str) -> Result<T, E> {
     Err(ser::Error::custom(lazy_format!(
         "cannot serialize {kind} as a Redis command"
@@ -250,7 +463,6 @@ trait MaybeLength: Copy {
 
 impl MaybeLength for () {
     #[inline(always)]
-    #[must_use]
     fn get(self) -> Option<usize> {
         None
     }
@@ -258,7 +470,6 @@ impl MaybeLength for () {
 
 impl MaybeLength for usize {
     #[inline(always)]
-    #[must_use]
     fn get(self) -> Option<usize> {
         Some(self)
     }
@@ -267,6 +478,10 @@ impl MaybeLength for usize {
 struct CommandSerializer<S, L> {
     serializer: S,
     length: L,
+
+    /// If true, names that come from the command type, rather than from its
+    /// data, are sent in uppercase. See [`UppercaseNames`].
+    uppercase: bool,
 }
 
 impl<S, L> ser::Serializer for CommandSerializer<S, L>
@@ -278,7 +493,7 @@ where
     type Error = S::Error;
 
     type SerializeSeq = ser::Impossible<S::Ok, Self::Error>;
-    type SerializeTuple = ser::Impossible<S::Ok, Self::Error>;
+    type SerializeTuple = TupleCommandSequencer<S::SerializeSeq>;
     type SerializeMap = ser::Impossible<S::Ok, Self::Error>;
 
     type SerializeStruct = CommandSequencer<S::SerializeSeq>;
@@ -410,11 +625,24 @@ where
     where
         T: serde::Serialize,
     {
-        use ser::SerializeTupleStruct as _;
+        use ser::SerializeSeq as _;
+
+        if name == UPPERCASE_NAMES_TOKEN {
+            return value.serialize(CommandSerializer {
+                uppercase: true,
+                ..self
+            });
+        }
 
-        let mut receiver = self.serialize_tuple_struct(name, 1)?;
-        receiver.serialize_field(value)?;
-        receiver.end()
+        // The value of a newtype command may be a subcommand, like
+        // `Config::Set { .. }` in `Command::Config(Config::Set { .. })`
+        let mut sequence = self.serializer.serialize_seq(self.length.get())?;
+        serialize_command_name(&mut sequence, name, self.uppercase)?;
+        value.serialize(AnonymousParameterSerializer::subcommand(
+            &mut sequence,
+            self.uppercase,
+        ))?;
+        sequence.end()
     }
 
     #[inline]
@@ -438,7 +666,16 @@ where
 
     #[inline]
     fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
-        self.serialize_seq(Some(len))
+        if len == 0 {
+            return invalid_command_type("an empty tuple");
+        }
+
+        let sequence = self.serializer.serialize_seq(self.length.get())?;
+        Ok(TupleCommandSequencer {
+            sequence,
+            named: false,
+            uppercase: self.uppercase,
+        })
     }
 
     #[inline]
@@ -447,11 +684,12 @@ where
         name: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeTupleStruct, Self::Error> {
-        use ser::SerializeSeq as _;
-
         let mut sequence = self.serializer.serialize_seq(self.length.get())?;
-        sequence.serialize_element(RedisString::new_ref(name))?;
-        Ok(TupleSeqAdapter::new(CommandSequencer { sequence }))
+        serialize_command_name(&mut sequence, name, self.uppercase)?;
+        Ok(TupleSeqAdapter::new(CommandSequencer {
+            sequence,
+            uppercase: self.uppercase,
+        }))
     }
 
     #[inline]
@@ -476,11 +714,12 @@ where
         name: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStruct, Self::Error> {
-        use ser::SerializeSeq as _;
-
         let mut sequence = self.serializer.serialize_seq(self.length.get())?;
-        sequence.serialize_element(RedisString::new_ref(name))?;
-        Ok(CommandSequencer { sequence })
+        serialize_command_name(&mut sequence, name, self.uppercase)?;
+        Ok(CommandSequencer {
+            sequence,
+            uppercase: self.uppercase,
+        })
     }
 
     #[inline]
@@ -501,6 +740,7 @@ where
 /// *after* the command name itself is serialized.
 struct CommandSequencer<S: ser::SerializeSeq> {
     sequence: S,
+    uppercase: bool,
 }
 
 impl<S> ser::SerializeSeq for CommandSequencer<S>
@@ -515,7 +755,10 @@ where
     where
         T: serde::Serialize,
     {
-        value.serialize(AnonymousParameterSerializer::new(&mut self.sequence))
+        value.serialize(AnonymousParameterSerializer::new(
+            &mut self.sequence,
+            self.uppercase,
+        ))
     }
 
     #[inline]
@@ -540,7 +783,11 @@ where
     where
         T: serde::Serialize,
     {
-        value.serialize(NamedParameterSerializer::new(key, &mut self.sequence))
+        value.serialize(NamedParameterSerializer::new(
+            key,
+            &mut self.sequence,
+            self.uppercase,
+        ))
     }
 
     #[inline]
@@ -549,157 +796,137 @@ where
     }
 }
 
-trait ParameterName: Copy {
-    #[must_use]
-    fn get(self) -> Option<&'static str>;
+/// Sequencer for commands that are plain tuples, like `("GET", key)`. The
+/// first element is the command name, and the rest are anonymous parameters.
+struct TupleCommandSequencer<S: ser::SerializeSeq> {
+    sequence: S,
+    named: bool,
+    uppercase: bool,
 }
 
-impl ParameterName for () {
-    #[inline(always)]
-    #[must_use]
-    fn get(self) -> Option<&'static str> {
-        None
-    }
-}
+impl<S> ser::SerializeTuple for TupleCommandSequencer<S>
+where
+    S: ser::SerializeSeq,
+{
+    type Ok = S::Ok;
+    type Error = S::Error;
 
-impl ParameterName for &'static str {
-    #[inline(always)]
-    #[must_use]
-    fn get(self) -> Option<&'static str> {
-        Some(self)
+    #[inline]
+    fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: serde::Serialize,
+    {
+        match self.named {
+            true => value.serialize(AnonymousParameterSerializer::new(
+                &mut self.sequence,
+                self.uppercase,
+            )),
+            false => {
+                value.serialize(CommandNameSerializer {
+                    sequence: &mut self.sequence,
+                })?;
+                self.named = true;
+                Ok(())
+            }
+        }
     }
-}
-
-/// This serializer handles a single parameter. It especially handles all the
-/// logic for variadic parameters (as in a list of keys for MGET), optional
-/// parameters, etc.
-struct CommandParameterSerializer<'a, S, N: ParameterName> {
-    sequence: &'a mut S,
-    name: N,
-}
 
-type AnonymousParameterSerializer<'a, S> = CommandParameterSerializer<'a, S, ()>;
-type NamedParameterSerializer<'a, S> = CommandParameterSerializer<'a, S, &'static str>;
-
-impl<'a, S: ser::SerializeSeq> AnonymousParameterSerializer<'a, S> {
     #[inline]
-    #[must_use]
-    pub fn new(sequence: &'a mut S) -> Self {
-        Self { sequence, name: () }
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.sequence.end()
     }
 }
 
-impl<'a, S: ser::SerializeSeq> NamedParameterSerializer<'a, S> {
-    #[inline]
-    #[must_use]
-    pub fn new(name: &'static str, sequence: &'a mut S) -> Self {
-        Self { sequence, name }
-    }
+fn invalid_command_name<T, E: ser::Error>() -> Result<T, E> {
+    Err(ser::Error::custom(
+        "the first element of a tuple command must be a string command name",
+    ))
 }
 
-impl<'a, S, N> CommandParameterSerializer<'a, S, N>
-where
-    N: ParameterName,
-{
-    #[inline]
-    fn name<E: ser::Error>(&self) -> Result<&'static str, E> {
-        self.name.get().ok_or_else(|| {
-            ser::Error::custom(
-                "can't serialize a bool, optional parameter, \
-                or unit from a tuple struct",
-            )
-        })
-    }
+/// This serializer handles the first element of a tuple command, which must
+/// be the command name.
+struct CommandNameSerializer<'a, S> {
+    sequence: &'a mut S,
 }
 
-impl<'a, S, N> ser::Serializer for CommandParameterSerializer<'a, S, N>
+impl<'a, S> ser::Serializer for CommandNameSerializer<'a, S>
 where
     S: ser::SerializeSeq,
-    N: ParameterName,
 {
     type Ok = ();
     type Error = S::Error;
 
-    type SerializeSeq = VariadicParameter<'a, S>;
-    type SerializeTuple = TupleSeqAdapter<VariadicParameter<'a, S>>;
-    type SerializeTupleStruct = TupleSeqAdapter<VariadicParameter<'a, S>>;
-
-    type SerializeMap = VariadicParameter<'a, S>;
-    type SerializeStruct = VariadicParameter<'a, S>;
-
-    type SerializeTupleVariant = ser::Impossible<(), Self::Error>;
-    type SerializeStructVariant = ser::Impossible<(), Self::Error>;
+    type SerializeSeq = ser::Impossible<(), S::Error>;
+    type SerializeTuple = ser::Impossible<(), S::Error>;
+    type SerializeTupleStruct = ser::Impossible<(), S::Error>;
+    type SerializeTupleVariant = ser::Impossible<(), S::Error>;
+    type SerializeMap = ser::Impossible<(), S::Error>;
+    type SerializeStruct = ser::Impossible<(), S::Error>;
+    type SerializeStructVariant = ser::Impossible<(), S::Error>;
 
     #[inline]
-    fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
-        // Even if `v` is false, we want to disallow serializing a bool parameter
-        // without a name, so compute it eagerly
-        let name = self.name()?;
-
-        match v {
-            true => self.sequence.serialize_element(RedisString::new_ref(name)),
-            false => Ok(()),
-        }
+    fn serialize_bool(self, _v: bool) -> Result<Self::Ok, Self::Error> {
+        invalid_command_name()
     }
 
     #[inline]
-    fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
-        self.sequence.serialize_element(&RedisString(v))
+    fn serialize_i8(self, _v: i8) -> Result<Self::Ok, Self::Error> {
+        invalid_command_name()
     }
 
     #[inline]
-    fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
-        self.sequence.serialize_element(&RedisString(v))
+    fn serialize_i16(self, _v: i16) -> Result<Self::Ok, Self::Error> {
+        invalid_command_name()
     }
 
     #[inline]
-    fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
-        self.sequence.serialize_element(&RedisString(v))
+    fn serialize_i32(self, _v: i32) -> Result<Self::Ok, Self::Error> {
+        invalid_command_name()
     }
 
     #[inline]
-    fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
-        self.sequence.serialize_element(&RedisString(v))
+    fn serialize_i64(self, _v: i64) -> Result<Self::Ok, Self::Error> {
+        invalid_command_name()
     }
 
     #[inline]
-    fn serialize_i128(self, v: i128) -> Result<Self::Ok, Self::Error> {
-        self.sequence.serialize_element(&RedisString(v))
+    fn serialize_i128(self, _v: i128) -> Result<Self::Ok, Self::Error> {
+        invalid_command_name()
     }
 
     #[inline]
-    fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
-        self.sequence.serialize_element(&RedisString(v))
+    fn serialize_u8(self, _v: u8) -> Result<Self::Ok, Self::Error> {
+        invalid_command_name()
     }
 
     #[inline]
-    fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
-        self.sequence.serialize_element(&RedisString(v))
+    fn serialize_u16(self, _v: u16) -> Result<Self::Ok, Self::Error> {
+        invalid_command_name()
     }
 
     #[inline]
-    fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
-        self.sequence.serialize_element(&RedisString(v))
+    fn serialize_u32(self, _v: u32) -> Result<Self::Ok, Self::Error> {
+        invalid_command_name()
     }
 
     #[inline]
-    fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
-        self.sequence.serialize_element(&RedisString(v))
+    fn serialize_u64(self, _v: u64) -> Result<Self::Ok, Self::Error> {
+        invalid_command_name()
     }
 
     #[inline]
-    fn serialize_u128(self, v: u128) -> Result<Self::Ok, Self::Error> {
-        self.sequence.serialize_element(&RedisString(v))
+    fn serialize_u128(self, _v: u128) -> Result<Self::Ok, Self::Error> {
+        invalid_command_name()
     }
 
     #[inline]
-    fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
-        self.sequence.serialize_element(&RedisString(v))
+    fn serialize_f32(self, _v: f32) -> Result<Self::Ok, Self::Error> {
+        invalid_command_name()
     }
 
     #[inline]
-    fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> {
-        self.sequence.serialize_element(&RedisString(v))
+    fn serialize_f64(self, _v: f64) -> Result<Self::Ok, Self::Error> {
+        invalid_command_name()
     }
 
     #[inline]
@@ -720,29 +947,25 @@ where
 
     #[inline]
     fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
-        Ok(())
+        invalid_command_name()
     }
 
     #[inline]
-    fn serialize_some<T: ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error>
+    fn serialize_some<T: ?Sized>(self, _value: &T) -> Result<Self::Ok, Self::Error>
     where
         T: serde::Serialize,
     {
-        value.serialize(OptionalParameterSerializer {
-            name: self.name,
-            sequence: self.sequence,
-        })
+        invalid_command_name()
     }
 
     #[inline]
     fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
-        let name = self.name()?;
-        self.serialize_unit_struct(name)
+        invalid_command_name()
     }
 
     #[inline]
-    fn serialize_unit_struct(self, name: &'static str) -> Result<Self::Ok, Self::Error> {
-        self.serialize_str(name)
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        invalid_command_name()
     }
 
     #[inline]
@@ -752,7 +975,7 @@ where
         _variant_index: u32,
         variant: &'static str,
     ) -> Result<Self::Ok, Self::Error> {
-        self.serialize_unit_struct(variant)
+        self.serialize_str(variant)
     }
 
     #[inline]
@@ -772,36 +995,32 @@ where
         self,
         _name: &'static str,
         _variant_index: u32,
-        variant: &'static str,
-        value: &T,
+        _variant: &'static str,
+        _value: &T,
     ) -> Result<Self::Ok, Self::Error>
     where
         T: serde::Serialize,
     {
-        self.sequence
-            .serialize_element(RedisString::new_ref(variant))?;
-        self.sequence.serialize_element(RedisString::new_ref(value))
+        invalid_command_name()
     }
 
     #[inline]
     fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
-        Ok(VariadicParameter {
-            sequence: self.sequence,
-        })
+        invalid_command_name()
     }
 
     #[inline]
-    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
-        self.serialize_seq(Some(len)).map(TupleSeqAdapter::new)
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        invalid_command_name()
     }
 
     #[inline]
     fn serialize_tuple_struct(
         self,
         _name: &'static str,
-        len: usize,
+        _len: usize,
     ) -> Result<Self::SerializeTupleStruct, Self::Error> {
-        self.serialize_tuple(len)
+        invalid_command_name()
     }
 
     #[inline]
@@ -812,16 +1031,12 @@ where
         _variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeTupleVariant, Self::Error> {
-        Err(ser::Error::custom(
-            "can't serialize complex enums as Redis command parameters",
-        ))
+        invalid_command_name()
     }
 
     #[inline]
     fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
-        Ok(VariadicParameter {
-            sequence: self.sequence,
-        })
+        invalid_command_name()
     }
 
     #[inline]
@@ -830,9 +1045,7 @@ where
         _name: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStruct, Self::Error> {
-        Ok(VariadicParameter {
-            sequence: self.sequence,
-        })
+        invalid_command_name()
     }
 
     #[inline]
@@ -843,14 +1056,417 @@ where
         _variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStructVariant, Self::Error> {
-        Err(ser::Error::custom(
-            "can't serialize complex enums as Redis command parameters",
-        ))
+        invalid_command_name()
     }
 }
 
-struct VariadicParameter<'a, S> {
-    sequence: &'a mut S,
+trait ParameterName: Copy {
+    #[must_use]
+    fn get(self) -> Option<&'static str>;
+}
+
+impl ParameterName for () {
+    #[inline(always)]
+    fn get(self) -> Option<&'static str> {
+        None
+    }
+}
+
+impl ParameterName for &'static str {
+    #[inline(always)]
+    fn get(self) -> Option<&'static str> {
+        Some(self)
+    }
+}
+
+/// This serializer handles a single parameter. It especially handles all the
+/// logic for variadic parameters (as in a list of keys for MGET), optional
+/// parameters, etc.
+struct CommandParameterSerializer<'a, S, N: ParameterName> {
+    sequence: &'a mut S,
+    name: N,
+    uppercase: bool,
+
+    /// If true, this is the only parameter of a newtype command, so it can
+    /// be a struct or tuple variant, which is sent as a subcommand: the
+    /// variant name followed by its fields.
+    subcommand: bool,
+}
+
+type AnonymousParameterSerializer<'a, S> = CommandParameterSerializer<'a, S, ()>;
+type NamedParameterSerializer<'a, S> = CommandParameterSerializer<'a, S, &'static str>;
+
+impl<'a, S: ser::SerializeSeq> AnonymousParameterSerializer<'a, S> {
+    #[inline]
+    #[must_use]
+    pub fn new(sequence: &'a mut S, uppercase: bool) -> Self {
+        Self {
+            sequence,
+            name: (),
+            uppercase,
+            subcommand: false,
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn subcommand(sequence: &'a mut S, uppercase: bool) -> Self {
+        Self {
+            subcommand: true,
+            ..Self::new(sequence, uppercase)
+        }
+    }
+}
+
+impl<'a, S: ser::SerializeSeq> NamedParameterSerializer<'a, S> {
+    #[inline]
+    #[must_use]
+    pub fn new(name: &'static str, sequence: &'a mut S, uppercase: bool) -> Self {
+        Self {
+            sequence,
+            name,
+            uppercase,
+            subcommand: false,
+        }
+    }
+}
+
+impl<'a, S, N> CommandParameterSerializer<'a, S, N>
+where
+    N: ParameterName,
+{
+    fn serialize_subcommand(self, variant: &'static str) -> Result<Subcommand<'a, S>, S::Error>
+    where
+        S: ser::SerializeSeq,
+    {
+        if !self.subcommand {
+            return Err(ser::Error::custom(
+                "can't serialize complex enums as Redis command parameters",
+            ));
+        }
+
+        serialize_command_name(self.sequence, variant, self.uppercase)?;
+        Ok(Subcommand {
+            sequence: self.sequence,
+            uppercase: self.uppercase,
+        })
+    }
+
+    #[inline]
+    fn name<E: ser::Error>(&self) -> Result<&'static str, E> {
+        self.name.get().ok_or_else(|| {
+            ser::Error::custom(
+                "can't serialize a bool, optional parameter, \
+                or unit from a tuple struct",
+            )
+        })
+    }
+}
+
+impl<'a, S, N> ser::Serializer for CommandParameterSerializer<'a, S, N>
+where
+    S: ser::SerializeSeq,
+    N: ParameterName,
+{
+    type Ok = ();
+    type Error = S::Error;
+
+    type SerializeSeq = VariadicParameter<'a, S>;
+    type SerializeTuple = TupleSeqAdapter<VariadicParameter<'a, S>>;
+    type SerializeTupleStruct = TupleSeqAdapter<VariadicParameter<'a, S>>;
+
+    type SerializeMap = VariadicParameter<'a, S>;
+    type SerializeStruct = VariadicParameter<'a, S>;
+
+    type SerializeTupleVariant = Subcommand<'a, S>;
+    type SerializeStructVariant = Subcommand<'a, S>;
+
+    #[inline]
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
+        // Even if `v` is false, we want to disallow serializing a bool parameter
+        // without a name, so compute it eagerly
+        let name = self.name()?;
+
+        match v {
+            true => self
+                .sequence
+                .serialize_element(&TypeName::new(name, self.uppercase)),
+            false => Ok(()),
+        }
+    }
+
+    #[inline]
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
+        self.sequence.serialize_element(&RedisString(v))
+    }
+
+    #[inline]
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
+        self.sequence.serialize_element(&RedisString(v))
+    }
+
+    #[inline]
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
+        self.sequence.serialize_element(&RedisString(v))
+    }
+
+    #[inline]
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
+        self.sequence.serialize_element(&RedisString(v))
+    }
+
+    #[inline]
+    fn serialize_i128(self, v: i128) -> Result<Self::Ok, Self::Error> {
+        self.sequence.serialize_element(&RedisString(v))
+    }
+
+    #[inline]
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
+        self.sequence.serialize_element(&RedisString(v))
+    }
+
+    #[inline]
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
+        self.sequence.serialize_element(&RedisString(v))
+    }
+
+    #[inline]
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
+        self.sequence.serialize_element(&RedisString(v))
+    }
+
+    #[inline]
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
+        self.sequence.serialize_element(&RedisString(v))
+    }
+
+    #[inline]
+    fn serialize_u128(self, v: u128) -> Result<Self::Ok, Self::Error> {
+        self.sequence.serialize_element(&RedisString(v))
+    }
+
+    #[inline]
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
+        self.sequence.serialize_element(&RedisString(v))
+    }
+
+    #[inline]
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> {
+        self.sequence.serialize_element(&RedisString(v))
+    }
+
+    #[inline]
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
+        let mut buf = [0; 4];
+        self.serialize_str(v.encode_utf8(&mut buf))
+    }
+
+    #[inline]
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        self.sequence.serialize_element(RedisString::new_ref(v))
+    }
+
+    #[inline]
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        self.sequence.serialize_element(Bytes::new(v))
+    }
+
+    #[inline]
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_some<T: ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: serde::Serialize,
+    {
+        value.serialize(OptionalParameterSerializer {
+            name: self.name,
+            sequence: self.sequence,
+            uppercase: self.uppercase,
+        })
+    }
+
+    #[inline]
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        let name = self.name()?;
+        self.serialize_unit_struct(name)
+    }
+
+    #[inline]
+    fn serialize_unit_struct(self, name: &'static str) -> Result<Self::Ok, Self::Error> {
+        self.sequence
+            .serialize_element(&TypeName::new(name, self.uppercase))
+    }
+
+    #[inline]
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.serialize_unit_struct(variant)
+    }
+
+    #[inline]
+    fn serialize_newtype_struct<T: ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: serde::Serialize,
+    {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_newtype_variant<T: ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: serde::Serialize,
+    {
+        self.sequence
+            .serialize_element(&TypeName::new(variant, self.uppercase))?;
+        self.sequence.serialize_element(RedisString::new_ref(value))
+    }
+
+    #[inline]
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Ok(VariadicParameter {
+            sequence: self.sequence,
+            uppercase: self.uppercase,
+        })
+    }
+
+    #[inline]
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        self.serialize_seq(Some(len)).map(TupleSeqAdapter::new)
+    }
+
+    #[inline]
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        self.serialize_tuple(len)
+    }
+
+    #[inline]
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        self.serialize_subcommand(variant)
+    }
+
+    #[inline]
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Ok(VariadicParameter {
+            sequence: self.sequence,
+            uppercase: self.uppercase,
+        })
+    }
+
+    #[inline]
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Ok(VariadicParameter {
+            sequence: self.sequence,
+            uppercase: self.uppercase,
+        })
+    }
+
+    #[inline]
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        self.serialize_subcommand(variant)
+    }
+}
+
+/// The fields of a subcommand, like `CONFIG SET`, which is the struct or
+/// tuple variant in a newtype command. They're sent the same way as the
+/// fields of the command itself.
+struct Subcommand<'a, S> {
+    sequence: &'a mut S,
+    uppercase: bool,
+}
+
+impl<'a, S> ser::SerializeTupleVariant for Subcommand<'a, S>
+where
+    S: ser::SerializeSeq,
+{
+    type Ok = ();
+    type Error = S::Error;
+
+    #[inline]
+    fn serialize_field<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: serde::Serialize,
+    {
+        value.serialize(AnonymousParameterSerializer::new(
+            self.sequence,
+            self.uppercase,
+        ))
+    }
+
+    #[inline]
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+}
+
+impl<'a, S> ser::SerializeStructVariant for Subcommand<'a, S>
+where
+    S: ser::SerializeSeq,
+{
+    type Ok = ();
+    type Error = S::Error;
+
+    #[inline]
+    fn serialize_field<T: ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error>
+    where
+        T: serde::Serialize,
+    {
+        value.serialize(NamedParameterSerializer::new(
+            key,
+            self.sequence,
+            self.uppercase,
+        ))
+    }
+
+    #[inline]
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+}
+
+struct VariadicParameter<'a, S> {
+    sequence: &'a mut S,
+    uppercase: bool,
 }
 
 impl<'a, S> ser::SerializeSeq for VariadicParameter<'a, S>
@@ -861,10 +1477,70 @@ where
     type Error = S::Error;
 
     #[inline]
-    fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+    fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: serde::Serialize,
+    {
+        value.serialize(ElementSerializer {
+            sequence: self.sequence,
+            kind: ElementKind::ListElement,
+            uppercase: self.uppercase,
+        })
+    }
+
+    #[inline]
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+}
+
+impl<'a, S: ser::SerializeSeq> ser::SerializeMap for VariadicParameter<'a, S> {
+    type Ok = ();
+    type Error = S::Error;
+
+    #[inline]
+    fn serialize_key<T: ?Sized>(&mut self, key: &T) -> Result<(), Self::Error>
+    where
+        T: serde::Serialize,
+    {
+        self.sequence.serialize_element(RedisString::new_ref(key))
+    }
+
+    #[inline]
+    fn serialize_value<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: serde::Serialize,
+    {
+        value.serialize(ElementSerializer {
+            sequence: self.sequence,
+            kind: ElementKind::MapValue,
+            uppercase: self.uppercase,
+        })
+    }
+
+    #[inline]
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+}
+
+impl<'a, S> ser::SerializeStruct for VariadicParameter<'a, S>
+where
+    S: ser::SerializeSeq,
+{
+    type Ok = ();
+    type Error = S::Error;
+
+    #[inline]
+    fn serialize_field<T: ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error>
     where
         T: serde::Serialize,
     {
+        self.sequence.serialize_element(RedisString::new_ref(key))?;
         self.sequence.serialize_element(RedisString::new_ref(value))
     }
 
@@ -874,24 +1550,330 @@ where
     }
 }
 
-impl<'a, S: ser::SerializeSeq> ser::SerializeMap for VariadicParameter<'a, S> {
+/// Where an [`ElementSerializer`] value appears in a parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ElementKind {
+    /// An element of a list. Tuples and structs are flattened into a group
+    /// of arguments.
+    ListElement,
+
+    /// A field of a tuple or struct in a list, which can't be flattened any
+    /// further.
+    GroupField,
+
+    /// A value in a map. `None` values contribute no arguments, so that
+    /// their key is sent on its own as a flag.
+    MapValue,
+}
+
+/// Serializer for a single element of a list parameter, a field of a group
+/// in a list, or a value in a map parameter. Most elements are serialized as
+/// a single argument, exactly like `RedisString`, except that the names of
+/// unit variants and unit structs are sent like other names from the command
+/// type (see [`UppercaseNames`]).
+struct ElementSerializer<'a, S> {
+    sequence: &'a mut S,
+    kind: ElementKind,
+    uppercase: bool,
+}
+
+impl<'a, S> ElementSerializer<'a, S>
+where
+    S: ser::SerializeSeq,
+{
+    #[inline]
+    fn serialize_argument<T>(self, value: &T) -> Result<(), S::Error>
+    where
+        T: ser::Serialize + ?Sized,
+    {
+        self.sequence.serialize_element(RedisString::new_ref(value))
+    }
+}
+
+impl<'a, S> ser::Serializer for ElementSerializer<'a, S>
+where
+    S: ser::SerializeSeq,
+{
     type Ok = ();
     type Error = S::Error;
 
+    type SerializeSeq = ser::Impossible<(), S::Error>;
+    type SerializeTuple = TupleSeqAdapter<ArgumentGroup<'a, S>>;
+    type SerializeTupleStruct = TupleSeqAdapter<ArgumentGroup<'a, S>>;
+    type SerializeTupleVariant = ser::Impossible<(), S::Error>;
+    type SerializeMap = ser::Impossible<(), S::Error>;
+    type SerializeStruct = ArgumentGroup<'a, S>;
+    type SerializeStructVariant = ser::Impossible<(), S::Error>;
+
+    #[inline]
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
+        self.serialize_argument(&v)
+    }
+
+    #[inline]
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
+        self.serialize_argument(&v)
+    }
+
+    #[inline]
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
+        self.serialize_argument(&v)
+    }
+
+    #[inline]
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
+        self.serialize_argument(&v)
+    }
+
+    #[inline]
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
+        self.serialize_argument(&v)
+    }
+
+    #[inline]
+    fn serialize_i128(self, v: i128) -> Result<Self::Ok, Self::Error> {
+        self.serialize_argument(&v)
+    }
+
+    #[inline]
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
+        self.serialize_argument(&v)
+    }
+
+    #[inline]
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
+        self.serialize_argument(&v)
+    }
+
+    #[inline]
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
+        self.serialize_argument(&v)
+    }
+
+    #[inline]
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
+        self.serialize_argument(&v)
+    }
+
+    #[inline]
+    fn serialize_u128(self, v: u128) -> Result<Self::Ok, Self::Error> {
+        self.serialize_argument(&v)
+    }
+
+    #[inline]
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
+        self.serialize_argument(&v)
+    }
+
+    #[inline]
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> {
+        self.serialize_argument(&v)
+    }
+
+    #[inline]
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
+        self.serialize_argument(&v)
+    }
+
+    #[inline]
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        self.serialize_argument(v)
+    }
+
+    #[inline]
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        self.serialize_argument(Bytes::new(v))
+    }
+
+    #[inline]
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        match self.kind {
+            ElementKind::MapValue => Ok(()),
+            _ => self.serialize_argument(&None::<()>),
+        }
+    }
+
+    #[inline]
+    fn serialize_some<T: ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: serde::Serialize,
+    {
+        match self.kind {
+            ElementKind::MapValue => self.serialize_argument(value),
+            _ => self.serialize_argument(&Some(value)),
+        }
+    }
+
+    #[inline]
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        self.serialize_argument(&())
+    }
+
+    #[inline]
+    fn serialize_unit_struct(self, name: &'static str) -> Result<Self::Ok, Self::Error> {
+        self.sequence
+            .serialize_element(&TypeName::new(name, self.uppercase))
+    }
+
+    #[inline]
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.serialize_unit_struct(variant)
+    }
+
+    #[inline]
+    fn serialize_newtype_struct<T: ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: serde::Serialize,
+    {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_newtype_variant<T: ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: serde::Serialize,
+    {
+        Err(ser::Error::custom(
+            "can't serialize data enums as redis strings",
+        ))
+    }
+
+    #[inline]
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Err(ser::Error::custom("can't serialize lists as redis strings"))
+    }
+
+    #[inline]
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        if self.kind != ElementKind::ListElement {
+            return Err(ser::Error::custom(
+                "can't serialize tuples as redis strings",
+            ));
+        }
+
+        Ok(TupleSeqAdapter::new(ArgumentGroup {
+            sequence: self.sequence,
+            uppercase: self.uppercase,
+        }))
+    }
+
+    #[inline]
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        if self.kind != ElementKind::ListElement {
+            return Err(ser::Error::custom(
+                "can't serialize structs as redis strings",
+            ));
+        }
+
+        self.serialize_tuple(len)
+    }
+
+    #[inline]
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(ser::Error::custom(
+            "can't serialize data enums as redis strings",
+        ))
+    }
+
+    #[inline]
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Err(ser::Error::custom("can't serialize maps as redis strings"))
+    }
+
+    #[inline]
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        if self.kind != ElementKind::ListElement {
+            return Err(ser::Error::custom(
+                "can't serialize structs as redis strings",
+            ));
+        }
+
+        Ok(ArgumentGroup {
+            sequence: self.sequence,
+            uppercase: self.uppercase,
+        })
+    }
+
+    #[inline]
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(ser::Error::custom(
+            "can't serialize data enums as redis strings",
+        ))
+    }
+}
+
+/// A tuple or struct inside of a list parameter, whose fields are flattened
+/// into arguments. The fields themselves can't be flattened any further.
+struct ArgumentGroup<'a, S> {
+    sequence: &'a mut S,
+    uppercase: bool,
+}
+
+impl<'a, S> ArgumentGroup<'a, S>
+where
+    S: ser::SerializeSeq,
+{
     #[inline]
-    fn serialize_key<T: ?Sized>(&mut self, key: &T) -> Result<(), Self::Error>
+    fn serialize_member<T>(&mut self, value: &T) -> Result<(), S::Error>
     where
-        T: serde::Serialize,
+        T: serde::Serialize + ?Sized,
     {
-        self.sequence.serialize_element(RedisString::new_ref(key))
+        value.serialize(ElementSerializer {
+            sequence: self.sequence,
+            kind: ElementKind::GroupField,
+            uppercase: self.uppercase,
+        })
     }
+}
+
+impl<'a, S> ser::SerializeSeq for ArgumentGroup<'a, S>
+where
+    S: ser::SerializeSeq,
+{
+    type Ok = ();
+    type Error = S::Error;
 
     #[inline]
-    fn serialize_value<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+    fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
     where
         T: serde::Serialize,
     {
-        self.sequence.serialize_element(RedisString::new_ref(value))
+        self.serialize_member(value)
     }
 
     #[inline]
@@ -900,7 +1882,7 @@ impl<'a, S: ser::SerializeSeq> ser::SerializeMap for VariadicParameter<'a, S> {
     }
 }
 
-impl<'a, S> ser::SerializeStruct for VariadicParameter<'a, S>
+impl<'a, S> ser::SerializeStruct for ArgumentGroup<'a, S>
 where
     S: ser::SerializeSeq,
 {
@@ -910,14 +1892,13 @@ where
     #[inline]
     fn serialize_field<T: ?Sized>(
         &mut self,
-        key: &'static str,
+        _key: &'static str,
         value: &T,
     ) -> Result<(), Self::Error>
     where
         T: serde::Serialize,
     {
-        self.sequence.serialize_element(RedisString::new_ref(key))?;
-        self.sequence.serialize_element(RedisString::new_ref(value))
+        self.serialize_member(value)
     }
 
     #[inline]
@@ -934,6 +1915,7 @@ where
 struct OptionalParameterSerializer<'a, S, N: ParameterName> {
     sequence: &'a mut S,
     name: N,
+    uppercase: bool,
 }
 
 impl<'a, N: ParameterName, S> OptionalParameterSerializer<'a, S, N> {
@@ -965,7 +1947,7 @@ impl<'a, S: ser::SerializeSeq, N: ParameterName> OptionalParameterSerializer<'a,
         value: &T,
     ) -> Result<(), S::Error> {
         self.sequence
-            .serialize_element(RedisString::new_ref(name))?;
+            .serialize_element(&TypeName::new(name, self.uppercase))?;
         self.sequence.serialize_element(RedisString::new_ref(value))
     }
 
@@ -977,7 +1959,8 @@ impl<'a, S: ser::SerializeSeq, N: ParameterName> OptionalParameterSerializer<'a,
 
     #[inline]
     fn serialize_just_name(self, name: &str) -> Result<(), S::Error> {
-        self.sequence.serialize_element(RedisString::new_ref(name))
+        self.sequence
+            .serialize_element(&TypeName::new(name, self.uppercase))
     }
 }
 
@@ -1347,6 +2330,144 @@ mod tests {
         )
     }
 
+    #[derive(Serialize)]
+    #[serde(rename = "PING")]
+    struct Ping;
+
+    #[test]
+    fn unit_command() {
+        assert_ser_tokens(
+            &Command(Ping),
+            &[
+                Token::Seq { len: Some(1) },
+                Token::Str("PING"),
+                Token::SeqEnd,
+            ],
+        );
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename = "MGET")]
+    struct MultiGet<'a> {
+        keys: Vec<&'a str>,
+    }
+
+    #[test]
+    fn empty_variadic() {
+        let command = Command(MultiGet { keys: Vec::new() });
+
+        assert_ser_tokens(
+            &command,
+            &[
+                Token::Seq { len: Some(1) },
+                Token::Str("MGET"),
+                Token::SeqEnd,
+            ],
+        );
+
+        assert_eq!(
+            crate::ser::to_vec(&command).unwrap(),
+            b"*1\r\n$4\r\nMGET\r\n"
+        );
+    }
+
+    #[test]
+    fn empty_variadic_map() {
+        let command = Command(HashMultiSet {
+            key: "hash-key",
+            values: BTreeMap::new(),
+        });
+
+        assert_ser_tokens(
+            &command,
+            &[
+                Token::Seq { len: Some(2) },
+                Token::Str("HMSET"),
+                Token::Str("hash-key"),
+                Token::SeqEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn optional_map_values() {
+        #[derive(Serialize)]
+        #[serde(rename = "TRACKING")]
+        struct Tracking<'a> {
+            options: BTreeMap<&'a str, Option<&'a str>>,
+        }
+
+        let command = Command(Tracking {
+            options: BTreeMap::from([
+                ("BCAST", None),
+                ("NOLOOP", None),
+                ("PREFIX", Some("user:")),
+                ("REDIRECT", Some("7")),
+            ]),
+        });
+
+        assert_ser_tokens(
+            &command,
+            &[
+                Token::Seq { len: Some(7) },
+                Token::Str("TRACKING"),
+                Token::Str("BCAST"),
+                Token::Str("NOLOOP"),
+                Token::Str("PREFIX"),
+                Token::Str("user:"),
+                Token::Str("REDIRECT"),
+                Token::Str("7"),
+                Token::SeqEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn disallow_nested_map_values() {
+        #[derive(Serialize)]
+        #[serde(rename = "CMD")]
+        struct Cmd {
+            options: BTreeMap<&'static str, Option<Option<i32>>>,
+        }
+
+        assert_ser_tokens_error(
+            &Command(Cmd {
+                options: BTreeMap::from([("KEY", Some(Some(1)))]),
+            }),
+            &[],
+            "can't serialize options as redis strings",
+        );
+    }
+
+    #[test]
+    fn empty_string_args() {
+        let command = Command(MultiGet {
+            keys: Vec::from(["", "a", ""]),
+        });
+
+        assert_eq!(
+            crate::ser::to_vec(&command).unwrap(),
+            b"*4\r\n$4\r\nMGET\r\n$0\r\n\r\n$1\r\na\r\n$0\r\n\r\n"
+        );
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename = "MGET")]
+    struct NullableMultiGet<'a> {
+        keys: Vec<Option<&'a str>>,
+    }
+
+    #[test]
+    fn disallow_null_variadic_args() {
+        // Redis commands are always made of strings; there's no way to send
+        // a null as an argument
+        let command = Command(NullableMultiGet {
+            keys: Vec::from([None, None]),
+        });
+
+        assert_ser_tokens_error(&command, &[], "can't serialize options as redis strings");
+    }
+
     #[derive(Serialize)]
     struct Fake {
         data: Vec<Vec<u8>>,
@@ -1360,4 +2481,423 @@ mod tests {
 
         assert_ser_tokens_error(&command, &[], "can't serialize lists as redis strings");
     }
+
+    #[test]
+    fn tuple_groups() {
+        #[derive(Serialize)]
+        #[serde(rename = "ZADD")]
+        struct ZAdd<'a> {
+            key: &'a str,
+            members: Vec<(f64, &'a str)>,
+        }
+
+        assert_ser_tokens(
+            &Command(ZAdd {
+                key: "z",
+                members: Vec::from([(1.5, "a"), (2.0, "b")]),
+            }),
+            &[
+                Token::Seq { len: Some(6) },
+                Token::Str("ZADD"),
+                Token::Str("z"),
+                Token::Str("1.5"),
+                Token::Str("a"),
+                Token::Str("2"),
+                Token::Str("b"),
+                Token::SeqEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn struct_groups() {
+        #[derive(Serialize)]
+        struct Location {
+            longitude: f64,
+            latitude: f64,
+            member: &'static str,
+        }
+
+        #[derive(Serialize)]
+        #[serde(rename = "GEOADD")]
+        struct GeoAdd {
+            key: &'static str,
+            #[serde(rename = "CH")]
+            changed: bool,
+            locations: [Location; 1],
+        }
+
+        assert_eq!(
+            crate::ser::to_vec(&Command(GeoAdd {
+                key: "places",
+                changed: true,
+                locations: [Location {
+                    longitude: 13.5,
+                    latitude: 38.25,
+                    member: "Palermo",
+                }],
+            }))
+            .unwrap(),
+            b"*6\r\n\
+              $6\r\nGEOADD\r\n\
+              $6\r\nplaces\r\n\
+              $2\r\nCH\r\n\
+              $4\r\n13.5\r\n\
+              $5\r\n38.25\r\n\
+              $7\r\nPalermo\r\n"
+        );
+    }
+
+    #[test]
+    fn disallow_nested_groups() {
+        #[derive(Serialize)]
+        #[serde(rename = "CMD")]
+        struct Cmd {
+            groups: Vec<(i32, (i32, i32))>,
+        }
+
+        assert_ser_tokens_error(
+            &Command(Cmd {
+                groups: Vec::from([(1, (2, 3))]),
+            }),
+            &[],
+            "can't serialize tuples as redis strings",
+        );
+
+        #[derive(Serialize)]
+        #[serde(rename = "CMD")]
+        struct ListGroup {
+            groups: Vec<(i32, Vec<i32>)>,
+        }
+
+        assert_ser_tokens_error(
+            &Command(ListGroup {
+                groups: Vec::from([(1, Vec::from([2]))]),
+            }),
+            &[],
+            "can't serialize lists as redis strings",
+        );
+    }
+
+    #[test]
+    fn tuple_command() {
+        let command = Command(("SET", "key", RedisString(10), ["EX", "60"]));
+
+        assert_ser_tokens(
+            &command,
+            &[
+                Token::Seq { len: Some(5) },
+                Token::Str("SET"),
+                Token::Str("key"),
+                Token::Str("10"),
+                Token::Str("EX"),
+                Token::Str("60"),
+                Token::SeqEnd,
+            ],
+        );
+
+        assert_eq!(
+            crate::ser::to_vec(&Command(("GET", "key"))).unwrap(),
+            b"*2\r\n$3\r\nGET\r\n$3\r\nkey\r\n"
+        );
+    }
+
+    #[test]
+    fn multi_word_name() {
+        #[derive(Serialize)]
+        #[serde(rename = "CONFIG SET")]
+        struct ConfigSet {
+            parameter: &'static str,
+            value: &'static str,
+        }
+
+        assert_ser_tokens(
+            &Command(ConfigSet {
+                parameter: "maxmemory",
+                value: "10mb",
+            }),
+            &[
+                Token::Seq { len: Some(4) },
+                Token::Str("CONFIG"),
+                Token::Str("SET"),
+                Token::Str("maxmemory"),
+                Token::Str("10mb"),
+                Token::SeqEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn subcommands() {
+        #[derive(Serialize)]
+        enum Client {
+            #[serde(rename = "KILL")]
+            Kill {
+                #[serde(rename = "ID")]
+                id: Option<u64>,
+            },
+
+            #[serde(rename = "LIST")]
+            List,
+        }
+
+        #[derive(Serialize)]
+        enum Xgroup {
+            Create(&'static str, &'static str, &'static str),
+        }
+
+        #[derive(Serialize)]
+        enum Admin {
+            #[serde(rename = "CLIENT")]
+            Client(Client),
+            Xgroup(Xgroup),
+        }
+
+        assert_eq!(
+            crate::ser::to_vec(&Command(Admin::Client(Client::Kill { id: Some(5) }))).unwrap(),
+            b"*4\r\n$6\r\nCLIENT\r\n$4\r\nKILL\r\n$2\r\nID\r\n$1\r\n5\r\n"
+        );
+
+        assert_eq!(
+            crate::ser::to_vec(&Command(Admin::Client(Client::List))).unwrap(),
+            b"*2\r\n$6\r\nCLIENT\r\n$4\r\nLIST\r\n"
+        );
+
+        assert_ser_tokens(
+            &Command::uppercase(Admin::Xgroup(Xgroup::Create("stream", "group", "$"))),
+            &[
+                Token::Seq { len: Some(5) },
+                Token::Str("XGROUP"),
+                Token::Str("CREATE"),
+                Token::Str("stream"),
+                Token::Str("group"),
+                Token::Str("$"),
+                Token::SeqEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn disallow_complex_enum_parameters() {
+        #[derive(Serialize)]
+        enum Filter {
+            Id { id: u64 },
+        }
+
+        #[derive(Serialize)]
+        struct Kill {
+            filter: Filter,
+        }
+
+        assert_ser_tokens_error(
+            &Command(Kill {
+                filter: Filter::Id { id: 1 },
+            }),
+            &[],
+            "can't serialize complex enums as Redis command parameters",
+        );
+    }
+
+    #[test]
+    fn tuple_command_name_only() {
+        assert_ser_tokens(
+            &Command(("PING",)),
+            &[
+                Token::Seq { len: Some(1) },
+                Token::Str("PING"),
+                Token::SeqEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn tuple_command_requires_name() {
+        assert_ser_tokens_error(
+            &Command((1, "key")),
+            &[],
+            "the first element of a tuple command must be a string command name",
+        );
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename = "ZADD")]
+    struct ZAdd<'a> {
+        key: &'a str,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        condition: Option<Skip>,
+
+        #[serde(rename = "CH")]
+        changed: bool,
+
+        #[serde(skip)]
+        #[allow(dead_code)]
+        internal: u32,
+
+        #[serde(rename = "INCR")]
+        increment: bool,
+
+        members: &'a [&'a str],
+    }
+
+    #[test]
+    fn field_order_matches_declaration() {
+        #[derive(Serialize)]
+        #[serde(rename = "CMD")]
+        struct Cmd {
+            zeta: u32,
+            alpha: u32,
+            mu: u32,
+        }
+
+        assert_ser_tokens(
+            &Command(Cmd {
+                zeta: 1,
+                alpha: 2,
+                mu: 3,
+            }),
+            &[
+                Token::Seq { len: Some(4) },
+                Token::Str("CMD"),
+                Token::Str("1"),
+                Token::Str("2"),
+                Token::Str("3"),
+                Token::SeqEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn skipped_fields_preserve_order() {
+        let command = Command(ZAdd {
+            key: "scores",
+            condition: None,
+            changed: true,
+            internal: 10,
+            increment: false,
+            members: &["1", "a", "2", "b"],
+        });
+
+        assert_ser_tokens(
+            &command,
+            &[
+                Token::Seq { len: Some(7) },
+                Token::Str("ZADD"),
+                Token::Str("scores"),
+                Token::Str("CH"),
+                Token::Str("1"),
+                Token::Str("a"),
+                Token::Str("2"),
+                Token::Str("b"),
+                Token::SeqEnd,
+            ],
+        );
+
+        let command = Command(ZAdd {
+            key: "scores",
+            condition: Some(Skip::NX),
+            changed: false,
+            internal: 10,
+            increment: true,
+            members: &["1", "a"],
+        });
+
+        assert_eq!(
+            crate::ser::to_vec(&command).unwrap(),
+            b"*6\r\n\
+              $4\r\nZADD\r\n\
+              $6\r\nscores\r\n\
+              $2\r\nNX\r\n\
+              $4\r\nINCR\r\n\
+              $1\r\n1\r\n\
+              $1\r\na\r\n"
+        );
+    }
+
+    #[test]
+    fn struct_variant_field_order() {
+        #[derive(Serialize)]
+        enum Cmd {
+            #[serde(rename = "GETEX")]
+            GetEx {
+                key: &'static str,
+
+                #[serde(skip_serializing_if = "Option::is_none")]
+                #[serde(rename = "PERSIST")]
+                persist: Option<()>,
+                expiry: Option<Expiry>,
+            },
+        }
+
+        assert_ser_tokens(
+            &Command(Cmd::GetEx {
+                key: "key",
+                persist: None,
+                expiry: Some(Expiry::Millis(100)),
+            }),
+            &[
+                Token::Seq { len: Some(4) },
+                Token::Str("GETEX"),
+                Token::Str("key"),
+                Token::Str("PX"),
+                Token::Str("100"),
+                Token::SeqEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn disallow_flattened_commands() {
+        #[derive(Serialize)]
+        struct Options {
+            #[serde(rename = "GET")]
+            get: bool,
+        }
+
+        #[derive(Serialize)]
+        #[serde(rename = "SET")]
+        struct FlatSet {
+            key: &'static str,
+
+            #[serde(flatten)]
+            options: Options,
+        }
+
+        assert_ser_tokens_error(
+            &Command(FlatSet {
+                key: "key",
+                options: Options { get: true },
+            }),
+            &[],
+            "cannot serialize a map as a Redis command",
+        );
+    }
+
+    #[test]
+    fn disallow_sequence_commands() {
+        assert_ser_tokens_error(
+            &Command(Vec::from(["GET", "key"])),
+            &[],
+            "cannot serialize a sequence as a Redis command",
+        );
+    }
+
+    #[test]
+    fn borrowed() {
+        // Deliberately not `Clone`
+        #[derive(Serialize)]
+        #[serde(rename = "MGET")]
+        struct MGet<'a> {
+            keys: &'a [&'a str],
+        }
+
+        let command = Command(MGet { keys: &["a", "b"] });
+        let expected = crate::ser::to_vec(&command).unwrap();
+
+        assert_eq!(crate::ser::to_vec(&command.by_ref()).unwrap(), expected);
+        assert_eq!(
+            crate::ser::to_vec(&Command::borrowed(&command.0)).unwrap(),
+            expected
+        );
+    }
 }
@@ -0,0 +1,278 @@
+use alloc::{borrow::Cow, boxed::Box, string::String, vec::Vec};
+
+use serde::ser::SerializeSeq;
+use serde_bytes::Bytes;
+
+use crate::components::RedisString;
+
+/**
+A value that is sent as exactly one argument of a Redis command, such as a
+string, a number, or a byte string.
+
+This is used by `#[derive(RedisCommand)]` to check, at compile time, that
+the elements of a list parameter are single arguments. It's implemented for
+the usual primitive types, for [`RedisString`], and for [`Bytes`]; implement
+it for your own types to allow them as command arguments.
+*/
+pub trait CommandArgument {
+    /// Serialize this value as a single argument.
+    fn serialize_argument<S: SerializeSeq>(&self, seq: &mut S) -> Result<(), S::Error>;
+}
+
+/**
+A field of a Redis command, which is sent as zero or more arguments.
+
+Every [`CommandArgument`] is a parameter that is sent as exactly one
+argument. An [`Option`] of an argument is sent as one argument if it's
+`Some`, and as nothing if it's `None`. A list of arguments, like a [`Vec`] or
+a slice, is sent as one argument per element. Lists and options of
+parameters (rather than arguments) aren't parameters, so nesting them is a
+compile error.
+*/
+pub trait CommandParameter {
+    /// The number of arguments that this parameter is sent as.
+    fn argument_count(&self) -> usize;
+
+    /// Serialize each of this parameter's arguments.
+    fn serialize_arguments<S: SerializeSeq>(&self, seq: &mut S) -> Result<(), S::Error>;
+}
+
+impl<T: CommandArgument + ?Sized> CommandArgument for &T {
+    #[inline]
+    fn serialize_argument<S: SerializeSeq>(&self, seq: &mut S) -> Result<(), S::Error> {
+        T::serialize_argument(self, seq)
+    }
+}
+
+impl<T: CommandArgument + ?Sized> CommandArgument for Box<T> {
+    #[inline]
+    fn serialize_argument<S: SerializeSeq>(&self, seq: &mut S) -> Result<(), S::Error> {
+        T::serialize_argument(self, seq)
+    }
+}
+
+impl<T: CommandParameter + ?Sized> CommandParameter for &T {
+    #[inline]
+    fn argument_count(&self) -> usize {
+        T::argument_count(self)
+    }
+
+    #[inline]
+    fn serialize_arguments<S: SerializeSeq>(&self, seq: &mut S) -> Result<(), S::Error> {
+        T::serialize_arguments(self, seq)
+    }
+}
+
+/// Implement `CommandArgument` and `CommandParameter` for types that are
+/// serialized as a single argument with `RedisString`.
+macro_rules! impl_argument {
+    ($($type:ty)*) => {$(
+        impl CommandArgument for $type {
+            #[inline]
+            fn serialize_argument<S: SerializeSeq>(&self, seq: &mut S) -> Result<(), S::Error> {
+                seq.serialize_element(RedisString::new_ref(self))
+            }
+        }
+
+        impl CommandParameter for $type {
+            #[inline]
+            fn argument_count(&self) -> usize {
+                1
+            }
+
+            #[inline]
+            fn serialize_arguments<S: SerializeSeq>(&self, seq: &mut S) -> Result<(), S::Error> {
+                self.serialize_argument(seq)
+            }
+        }
+    )*};
+}
+
+impl_argument! {
+    i8 i16 i32 i64 i128 isize
+    u8 u16 u32 u64 u128 usize
+    f32 f64 char str String Cow<'_, str>
+}
+
+impl CommandArgument for Bytes {
+    #[inline]
+    fn serialize_argument<S: SerializeSeq>(&self, seq: &mut S) -> Result<(), S::Error> {
+        seq.serialize_element(self)
+    }
+}
+
+impl CommandParameter for Bytes {
+    #[inline]
+    fn argument_count(&self) -> usize {
+        1
+    }
+
+    #[inline]
+    fn serialize_arguments<S: SerializeSeq>(&self, seq: &mut S) -> Result<(), S::Error> {
+        self.serialize_argument(seq)
+    }
+}
+
+impl<T: serde::Serialize + ?Sized> CommandArgument for RedisString<T> {
+    #[inline]
+    fn serialize_argument<S: SerializeSeq>(&self, seq: &mut S) -> Result<(), S::Error> {
+        seq.serialize_element(self)
+    }
+}
+
+impl<T: serde::Serialize + ?Sized> CommandParameter for RedisString<T> {
+    #[inline]
+    fn argument_count(&self) -> usize {
+        1
+    }
+
+    #[inline]
+    fn serialize_arguments<S: SerializeSeq>(&self, seq: &mut S) -> Result<(), S::Error> {
+        self.serialize_argument(seq)
+    }
+}
+
+impl<T: CommandArgument> CommandParameter for Option<T> {
+    #[inline]
+    fn argument_count(&self) -> usize {
+        usize::from(self.is_some())
+    }
+
+    #[inline]
+    fn serialize_arguments<S: SerializeSeq>(&self, seq: &mut S) -> Result<(), S::Error> {
+        match *self {
+            Some(ref value) => value.serialize_argument(seq),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T: CommandArgument> CommandParameter for [T] {
+    #[inline]
+    fn argument_count(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn serialize_arguments<S: SerializeSeq>(&self, seq: &mut S) -> Result<(), S::Error> {
+        self.iter()
+            .try_for_each(|value| value.serialize_argument(seq))
+    }
+}
+
+impl<T: CommandArgument, const N: usize> CommandParameter for [T; N] {
+    #[inline]
+    fn argument_count(&self) -> usize {
+        N
+    }
+
+    #[inline]
+    fn serialize_arguments<S: SerializeSeq>(&self, seq: &mut S) -> Result<(), S::Error> {
+        self.as_slice().serialize_arguments(seq)
+    }
+}
+
+impl<T: CommandArgument> CommandParameter for Vec<T> {
+    #[inline]
+    fn argument_count(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn serialize_arguments<S: SerializeSeq>(&self, seq: &mut S) -> Result<(), S::Error> {
+        self.as_slice().serialize_arguments(seq)
+    }
+}
+
+#[cfg(all(test, feature = "derive"))]
+mod tests {
+    use serde_test::{assert_ser_tokens, Token};
+
+    use crate::components::RedisCommand;
+
+    #[derive(RedisCommand)]
+    #[redis(name = "CLIENT KILL")]
+    struct ClientKill<'a> {
+        #[redis(named = "ID")]
+        ids: Vec<u64>,
+        #[redis(named)]
+        user: Option<&'a str>,
+        #[redis(skip)]
+        _note: &'a str,
+        #[redis(flag = "SKIPME")]
+        skip_me: bool,
+    }
+
+    #[test]
+    fn multi_word_name() {
+        assert_ser_tokens(
+            &ClientKill {
+                ids: Vec::from([1, 2]),
+                user: Some("default"),
+                _note: "ignored",
+                skip_me: true,
+            },
+            &[
+                Token::Seq { len: Some(8) },
+                Token::Str("CLIENT"),
+                Token::Str("KILL"),
+                Token::Str("ID"),
+                Token::Str("1"),
+                Token::Str("2"),
+                Token::Str("USER"),
+                Token::Str("default"),
+                Token::Str("SKIPME"),
+                Token::SeqEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn empty_parameters() {
+        assert_ser_tokens(
+            &ClientKill {
+                ids: Vec::new(),
+                user: None,
+                _note: "ignored",
+                skip_me: false,
+            },
+            &[
+                Token::Seq { len: Some(2) },
+                Token::Str("CLIENT"),
+                Token::Str("KILL"),
+                Token::SeqEnd,
+            ],
+        );
+    }
+
+    #[derive(RedisCommand)]
+    struct Incr<K>(
+        K,
+        #[redis(named = "BY")] Option<i64>,
+        #[redis(flag = "SLOW")] bool,
+    );
+
+    #[test]
+    fn generic_tuple_struct() {
+        assert_ser_tokens(
+            &Incr("counter", Some(-3), true),
+            &[
+                Token::Seq { len: Some(5) },
+                Token::Str("INCR"),
+                Token::Str("counter"),
+                Token::Str("BY"),
+                Token::Str("-3"),
+                Token::Str("SLOW"),
+                Token::SeqEnd,
+            ],
+        );
+    }
+
+    #[derive(RedisCommand)]
+    struct Ping;
+
+    #[test]
+    fn unit_struct() {
+        assert_eq!(crate::ser::to_vec(&Ping).unwrap(), b"*1\r\n$4\r\nPING\r\n");
+    }
+}
@@ -0,0 +1,183 @@
+use alloc::boxed::Box;
+
+use serde::Serialize;
+
+use super::Command;
+use crate::{
+    ser::{Error, Output, Serializer},
+    Config,
+};
+
+/**
+An object-safe trait for anything that can be written as a Redis command.
+
+[`Command`] is generic over its command type, and serde's `Serialize` can't
+be used as a trait object, so queues of different commands (such as a
+pipeline) would otherwise need an enum listing every command type. Instead,
+`CommandLike` is implemented for every serializable [`Command`], so commands
+can be stored as `Box<dyn CommandLike>` and written to any [`Output`] one at
+a time, or all at once with [`batch_to_vec`][crate::ser::batch_to_vec].
+
+# Example
+
+```
+use serde::Serialize;
+use seredies::components::{Command, CommandLike};
+use seredies::ser::batch_to_vec;
+
+#[derive(Serialize)]
+#[serde(rename = "GET")]
+struct Get<'a> {
+    key: &'a str,
+}
+
+#[derive(Serialize)]
+#[serde(rename = "INCRBY")]
+struct IncrBy<'a> {
+    key: &'a str,
+    increment: i64,
+}
+
+let pipeline: Vec<Box<dyn CommandLike>> = vec![
+    Box::new(Command(IncrBy { key: "counter", increment: 5 })),
+    Box::new(Command(Get { key: "counter" })),
+    Box::new(Command::raw("PING")),
+];
+
+assert_eq!(
+    batch_to_vec(&pipeline).unwrap(),
+    b"\
+        *3\r\n$6\r\nINCRBY\r\n$7\r\ncounter\r\n$1\r\n5\r\n\
+        *2\r\n$3\r\nGET\r\n$7\r\ncounter\r\n\
+        *1\r\n$4\r\nPING\r\n\
+    ",
+);
+```
+*/
+pub trait CommandLike {
+    /// Serialize this command as a RESP array to the `output`.
+    fn write_command(&self, output: &mut dyn Output) -> Result<(), Error>;
+
+    /// Serialize this command as a RESP array to the `output`, using the
+    /// given [`Config`]. The default implementation ignores the `config`
+    /// and calls [`write_command`][Self::write_command].
+    #[inline]
+    fn write_command_with_config(
+        &self,
+        output: &mut dyn Output,
+        config: Config,
+    ) -> Result<(), Error> {
+        let _ = config;
+        self.write_command(output)
+    }
+}
+
+impl<T: Serialize> CommandLike for Command<T> {
+    #[inline]
+    fn write_command(&self, output: &mut dyn Output) -> Result<(), Error> {
+        self.write_command_with_config(output, Config::new())
+    }
+
+    #[inline]
+    fn write_command_with_config(
+        &self,
+        mut output: &mut dyn Output,
+        config: Config,
+    ) -> Result<(), Error> {
+        self.serialize(Serializer::with_config(&mut output, config))
+    }
+}
+
+impl<T: CommandLike + ?Sized> CommandLike for &T {
+    #[inline]
+    fn write_command(&self, output: &mut dyn Output) -> Result<(), Error> {
+        T::write_command(*self, output)
+    }
+
+    #[inline]
+    fn write_command_with_config(
+        &self,
+        output: &mut dyn Output,
+        config: Config,
+    ) -> Result<(), Error> {
+        T::write_command_with_config(*self, output, config)
+    }
+}
+
+impl<T: CommandLike + ?Sized> CommandLike for Box<T> {
+    #[inline]
+    fn write_command(&self, output: &mut dyn Output) -> Result<(), Error> {
+        T::write_command(self, output)
+    }
+
+    #[inline]
+    fn write_command_with_config(
+        &self,
+        output: &mut dyn Output,
+        config: Config,
+    ) -> Result<(), Error> {
+        T::write_command_with_config(self, output, config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use cool_asserts::assert_matches;
+    use serde::Serialize;
+
+    use super::*;
+    use crate::{components::RedisString, ser::to_vec};
+
+    #[derive(Serialize)]
+    #[serde(rename = "SET")]
+    struct Set<T> {
+        key: &'static str,
+        value: RedisString<T>,
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename = "DEL")]
+    struct Delete {
+        keys: Vec<&'static str>,
+    }
+
+    #[test]
+    fn matches_individual_commands() {
+        let set = Command(Set {
+            key: "key",
+            value: RedisString(10),
+        });
+        let delete = Command(Delete {
+            keys: vec!["a", "b"],
+        });
+
+        let expected = [to_vec(&set).unwrap(), to_vec(&delete).unwrap()].concat();
+
+        let queue: VecDeque<Box<dyn CommandLike>> =
+            VecDeque::from([Box::new(set) as Box<dyn CommandLike>, Box::new(delete)]);
+
+        assert_eq!(crate::ser::batch_to_vec(&queue).unwrap(), expected);
+    }
+
+    #[test]
+    fn write_to_output() {
+        let mut output = String::new();
+
+        Command(Set {
+            key: "key",
+            value: RedisString("value"),
+        })
+        .write_command(&mut output)
+        .unwrap();
+
+        assert_eq!(output, "*3\r\n$3\r\nSET\r\n$3\r\nkey\r\n$5\r\nvalue\r\n");
+    }
+
+    #[test]
+    fn error_in_batch() {
+        let batch: [&dyn CommandLike; 2] = [&Command::raw("PING"), &Command(10)];
+        assert_matches!(crate::ser::batch_to_vec(batch), Err(Error::Custom(_)));
+    }
+}
@@ -0,0 +1,1712 @@
+use alloc::{
+    borrow::Cow,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{cell::Cell, fmt};
+
+use lazy_format::lazy_format;
+use serde::{de, forward_to_deserialize_any};
+
+use super::{command_words, Command};
+use crate::components::string::RedisStringAdapter;
+
+#[derive(Debug, Clone)]
+enum Error {
+    /// The optional parameter at this index turned out not to be present.
+    /// Optional parameters are parsed greedily, and this error causes the
+    /// command to be parsed again, with that parameter set to `None`.
+    Absent(usize),
+
+    Custom(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Error::Absent(index) => write!(f, "optional parameter {index} was absent"),
+            Error::Custom(ref msg) => f.write_str(msg),
+        }
+    }
+}
+
+impl de::StdError for Error {}
+
+impl de::Error for Error {
+    fn custom<T>(msg: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        Self::Custom(msg.to_string())
+    }
+}
+
+/// Deserialize a command, as received by a Redis server: an array of bulk
+/// strings, where the first string is the command name. The arguments are
+/// collected up front, because optional parameters require backtracking.
+impl<'de, T> de::Deserialize<'de> for Command<T>
+where
+    T: de::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let args = deserializer.deserialize_seq(ArgumentsVisitor)?;
+        let mut absent = Vec::new();
+
+        loop {
+            let context = Context {
+                args: &args,
+                position: Cell::new(0),
+                absent: &absent,
+            };
+
+            match T::deserialize(CommandDeserializer { context: &context }) {
+                Ok(command) => {
+                    return match context.peek() {
+                        None => Ok(Command(command)),
+                        Some(arg) => Err(de::Error::custom(lazy_format!(
+                            "unexpected command argument {:?}",
+                            String::from_utf8_lossy(arg)
+                        ))),
+                    }
+                }
+                Err(Error::Absent(index)) if !absent.contains(&index) => absent.push(index),
+                Err(err) => return Err(de::Error::custom(err)),
+            }
+        }
+    }
+}
+
+struct ArgumentsVisitor;
+
+impl<'de> de::Visitor<'de> for ArgumentsVisitor {
+    type Value = Vec<Cow<'de, [u8]>>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a Redis command (an array of strings)")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let mut args = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+
+        while let Some(Argument(arg)) = seq.next_element()? {
+            args.push(arg);
+        }
+
+        Ok(args)
+    }
+}
+
+/// A single command argument, which may be borrowed from the input
+struct Argument<'de>(Cow<'de, [u8]>);
+
+impl<'de> de::Deserialize<'de> for Argument<'de> {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer
+            .deserialize_bytes(ArgumentVisitor)
+            .map(Argument)
+    }
+}
+
+struct ArgumentVisitor;
+
+impl<'de> de::Visitor<'de> for ArgumentVisitor {
+    type Value = Cow<'de, [u8]>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a command argument string")
+    }
+
+    #[inline]
+    fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Cow::Borrowed(v))
+    }
+
+    #[inline]
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Cow::Owned(v.to_vec()))
+    }
+
+    #[inline]
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Cow::Owned(v))
+    }
+
+    #[inline]
+    fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Cow::Borrowed(v.as_bytes()))
+    }
+
+    #[inline]
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Cow::Owned(v.as_bytes().to_vec()))
+    }
+
+    #[inline]
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Cow::Owned(v.into_bytes()))
+    }
+}
+
+/// Redis command names and keywords are case insensitive
+#[inline]
+#[must_use]
+fn matches(arg: &[u8], name: &str) -> bool {
+    arg.eq_ignore_ascii_case(name.as_bytes())
+}
+
+/// The shared state of a single attempt at deserializing a command: the
+/// arguments, the position of the next unconsumed argument, and the indexes
+/// of optional parameters that are known to be absent.
+struct Context<'a, 'de> {
+    args: &'a [Cow<'de, [u8]>],
+    position: Cell<usize>,
+    absent: &'a [usize],
+}
+
+impl<'a, 'de> Context<'a, 'de> {
+    #[inline]
+    #[must_use]
+    fn peek(&self) -> Option<&'a Cow<'de, [u8]>> {
+        self.args.get(self.position.get())
+    }
+
+    #[inline]
+    fn next(&self) -> Option<&'a Cow<'de, [u8]>> {
+        let arg = self.peek()?;
+        self.position.set(self.position.get() + 1);
+        Some(arg)
+    }
+
+    /// If the next argument is `name`, consume it and return true.
+    #[inline]
+    fn take(&self, name: &str) -> bool {
+        self.peek()
+            .filter(|arg| matches(arg, name))
+            .and_then(|_| self.next())
+            .is_some()
+    }
+
+    /// Consume the next argument, which must be `name`.
+    fn expect(&self, name: &str) -> Result<(), Error> {
+        match self.next() {
+            Some(arg) if matches(arg, name) => Ok(()),
+            Some(arg) => Err(de::Error::custom(lazy_format!(
+                "expected {name:?}, got {:?}",
+                String::from_utf8_lossy(arg)
+            ))),
+            None => Err(de::Error::custom(lazy_format!(
+                "expected {name:?}, but there were no more arguments"
+            ))),
+        }
+    }
+
+    /// Consume the command name, which must be `name`. Names of more than
+    /// one word, like `CONFIG SET`, are sent as one argument per word.
+    fn expect_command(&self, name: &str) -> Result<(), Error> {
+        command_words(name).try_for_each(|word| self.expect(word))
+    }
+
+    /// Find the variant (if any) named by the next argument, without
+    /// consuming it.
+    #[inline]
+    #[must_use]
+    fn peek_variant(&self, variants: &'static [&'static str]) -> Option<&'static str> {
+        let arg = self.peek()?;
+        variants
+            .iter()
+            .copied()
+            .find(|variant| matches(arg, variant))
+    }
+
+    /// Consume the next argument, which must be one of `variants`.
+    fn variant(&self, variants: &'static [&'static str]) -> Result<&'static str, Error> {
+        match self.peek_variant(variants) {
+            Some(variant) => {
+                self.next();
+                Ok(variant)
+            }
+            None => match self.peek() {
+                Some(arg) => Err(de::Error::unknown_variant(
+                    &String::from_utf8_lossy(arg),
+                    variants,
+                )),
+                None => Err(de::Error::custom("missing command argument")),
+            },
+        }
+    }
+
+    /// Consume the next argument, and return a deserializer that treats it
+    /// as a primitive value, parsing numbers from strings.
+    fn scalar(&self) -> Result<RedisStringAdapter<ArgumentDeserializer<'a, 'de>>, Error> {
+        self.next()
+            .map(|arg| RedisStringAdapter(ArgumentDeserializer { arg }))
+            .ok_or_else(|| de::Error::custom("missing command argument"))
+    }
+}
+
+/// Generate deserialize methods that consume the next argument as a scalar;
+/// the type must have a `scalar` method returning the scalar deserializer.
+macro_rules! forward_to_scalar {
+    ($($method:ident)*) => {$(
+        #[inline]
+        fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+        where
+            V: de::Visitor<'de>,
+        {
+            self.scalar()?.$method(visitor)
+        }
+    )*};
+}
+
+/// Deserializer for a single argument, as bytes.
+struct ArgumentDeserializer<'a, 'de> {
+    arg: &'a Cow<'de, [u8]>,
+}
+
+impl<'a, 'de> de::Deserializer<'de> for ArgumentDeserializer<'a, 'de> {
+    type Error = Error;
+
+    #[inline]
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match *self.arg {
+            Cow::Borrowed(arg) => visitor.visit_borrowed_bytes(arg),
+            Cow::Owned(ref arg) => visitor.visit_bytes(arg),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+/// Deserializer for the command itself. Mirrors `CommandSerializer`: the
+/// first argument is the name of the struct or enum variant.
+struct CommandDeserializer<'a, 'de> {
+    context: &'a Context<'a, 'de>,
+}
+
+impl<'a, 'de> de::Deserializer<'de> for CommandDeserializer<'a, 'de> {
+    type Error = Error;
+
+    #[inline]
+    fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        Err(de::Error::custom(
+            "Redis commands can only be deserialized as structs or enums",
+        ))
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit seq tuple map identifier ignored_any
+    }
+
+    #[inline]
+    fn deserialize_unit_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.context.expect_command(name)?;
+        visitor.visit_unit()
+    }
+
+    #[inline]
+    fn deserialize_newtype_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.context.expect_command(name)?;
+        visitor.visit_newtype_struct(ParameterDeserializer {
+            context: self.context,
+            name: None,
+            index: 0,
+            subcommand: true,
+        })
+    }
+
+    #[inline]
+    fn deserialize_tuple_struct<V>(
+        self,
+        name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.context.expect_command(name)?;
+        visitor.visit_seq(Parameters::anonymous(self.context, len))
+    }
+
+    #[inline]
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.context.expect_command(name)?;
+        visitor.visit_seq(Parameters::named(self.context, fields))
+    }
+
+    #[inline]
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_enum(CommandVariant {
+            context: self.context,
+            variants,
+        })
+    }
+}
+
+/// Enum access for a command enum, where the variant is the command name.
+struct CommandVariant<'a, 'de> {
+    context: &'a Context<'a, 'de>,
+    variants: &'static [&'static str],
+}
+
+impl<'a, 'de> de::EnumAccess<'de> for CommandVariant<'a, 'de> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<S>(self, seed: S) -> Result<(S::Value, Self::Variant), Self::Error>
+    where
+        S: de::DeserializeSeed<'de>,
+    {
+        let variant = self.context.variant(self.variants)?;
+        let value = seed.deserialize(de::IntoDeserializer::<Error>::into_deserializer(variant))?;
+
+        Ok((value, self))
+    }
+}
+
+impl<'a, 'de> de::VariantAccess<'de> for CommandVariant<'a, 'de> {
+    type Error = Error;
+
+    #[inline]
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    #[inline]
+    fn newtype_variant_seed<S>(self, seed: S) -> Result<S::Value, Self::Error>
+    where
+        S: de::DeserializeSeed<'de>,
+    {
+        seed.deserialize(ParameterDeserializer {
+            context: self.context,
+            name: None,
+            index: 0,
+            subcommand: true,
+        })
+    }
+
+    #[inline]
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_seq(Parameters::anonymous(self.context, len))
+    }
+
+    #[inline]
+    fn struct_variant<V>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_seq(Parameters::named(self.context, fields))
+    }
+}
+
+/// Sequence access for the parameters of a command, in order. Mirrors
+/// `CommandSequencer`.
+struct Parameters<'a, 'de> {
+    context: &'a Context<'a, 'de>,
+    names: Option<&'static [&'static str]>,
+    len: usize,
+    index: usize,
+}
+
+impl<'a, 'de> Parameters<'a, 'de> {
+    #[inline]
+    #[must_use]
+    fn anonymous(context: &'a Context<'a, 'de>, len: usize) -> Self {
+        Self {
+            context,
+            names: None,
+            len,
+            index: 0,
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    fn named(context: &'a Context<'a, 'de>, names: &'static [&'static str]) -> Self {
+        Self {
+            context,
+            names: Some(names),
+            len: names.len(),
+            index: 0,
+        }
+    }
+}
+
+impl<'a, 'de> de::SeqAccess<'de> for Parameters<'a, 'de> {
+    type Error = Error;
+
+    fn next_element_seed<S>(&mut self, seed: S) -> Result<Option<S::Value>, Self::Error>
+    where
+        S: de::DeserializeSeed<'de>,
+    {
+        if self.index >= self.len {
+            return Ok(None);
+        }
+
+        let index = self.index;
+        self.index += 1;
+
+        seed.deserialize(ParameterDeserializer {
+            context: self.context,
+            name: self.names.map(|names| names[index]),
+            index,
+            subcommand: false,
+        })
+        .map(Some)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len - self.index)
+    }
+}
+
+/// Deserializer for a single command parameter. Mirrors
+/// `CommandParameterSerializer`.
+struct ParameterDeserializer<'a, 'de> {
+    context: &'a Context<'a, 'de>,
+    name: Option<&'static str>,
+    index: usize,
+
+    /// If true, this is the only parameter of a newtype command, so it can
+    /// be a struct or tuple variant, which is a subcommand.
+    subcommand: bool,
+}
+
+impl<'a, 'de> ParameterDeserializer<'a, 'de> {
+    #[inline]
+    fn name(&self) -> Result<&'static str, Error> {
+        self.name.ok_or_else(|| {
+            de::Error::custom(
+                "can't deserialize a bool, optional parameter, \
+                or unit from a tuple struct",
+            )
+        })
+    }
+
+    #[inline]
+    fn scalar(self) -> Result<RedisStringAdapter<ArgumentDeserializer<'a, 'de>>, Error> {
+        self.context.scalar()
+    }
+}
+
+impl<'a, 'de> de::Deserializer<'de> for ParameterDeserializer<'a, 'de> {
+    type Error = Error;
+
+    forward_to_scalar! {
+        deserialize_any deserialize_i8 deserialize_i16 deserialize_i32
+        deserialize_i64 deserialize_i128 deserialize_u8 deserialize_u16
+        deserialize_u32 deserialize_u64 deserialize_u128 deserialize_f32
+        deserialize_f64 deserialize_char deserialize_str deserialize_string
+        deserialize_bytes deserialize_byte_buf deserialize_identifier
+        deserialize_ignored_any
+    }
+
+    #[inline]
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        let name = self.name()?;
+        visitor.visit_bool(self.context.take(name))
+    }
+
+    #[inline]
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        if self.context.absent.contains(&self.index) || self.context.peek().is_none() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(OptionalParameterDeserializer {
+                context: self.context,
+                name: self.name,
+                index: self.index,
+            })
+        }
+    }
+
+    #[inline]
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        let name = self.name()?;
+        self.context.expect(name)?;
+        visitor.visit_unit()
+    }
+
+    #[inline]
+    fn deserialize_unit_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.context.expect(name)?;
+        visitor.visit_unit()
+    }
+
+    #[inline]
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    #[inline]
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_seq(VariadicParameter {
+            context: self.context,
+            remaining: None,
+            group: false,
+        })
+    }
+
+    #[inline]
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_seq(VariadicParameter {
+            context: self.context,
+            remaining: Some(len),
+            group: false,
+        })
+    }
+
+    #[inline]
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    #[inline]
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_map(VariadicParameter {
+            context: self.context,
+            remaining: None,
+            group: false,
+        })
+    }
+
+    #[inline]
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_map(VariadicParameter {
+            context: self.context,
+            remaining: Some(fields.len()),
+            group: false,
+        })
+    }
+
+    #[inline]
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        let variant = self.context.variant(variants)?;
+
+        visitor.visit_enum(ParameterVariant {
+            context: self.context,
+            variant,
+            subcommand: self.subcommand,
+        })
+    }
+}
+
+/// Access for a flattened list or map parameter. If there's no fixed
+/// length, it consumes all of the remaining arguments.
+struct VariadicParameter<'a, 'de> {
+    context: &'a Context<'a, 'de>,
+    remaining: Option<usize>,
+
+    /// True for the fields of a tuple or struct inside of a list, which
+    /// can't be flattened any further.
+    group: bool,
+}
+
+impl<'a, 'de> VariadicParameter<'a, 'de> {
+    /// Check if there's another element, and count it if there is.
+    #[inline]
+    fn advance(&mut self) -> bool {
+        match self.remaining {
+            Some(0) => false,
+            Some(ref mut remaining) => {
+                *remaining -= 1;
+                true
+            }
+            None => self.context.peek().is_some(),
+        }
+    }
+
+    fn next_scalar(
+        &mut self,
+    ) -> Result<Option<RedisStringAdapter<ArgumentDeserializer<'a, 'de>>>, Error> {
+        match self.advance() {
+            true => self.context.scalar().map(Some),
+            false => Ok(None),
+        }
+    }
+}
+
+impl<'a, 'de> de::SeqAccess<'de> for VariadicParameter<'a, 'de> {
+    type Error = Error;
+
+    #[inline]
+    fn next_element_seed<S>(&mut self, seed: S) -> Result<Option<S::Value>, Self::Error>
+    where
+        S: de::DeserializeSeed<'de>,
+    {
+        if self.group {
+            return self
+                .next_scalar()?
+                .map(|arg| seed.deserialize(arg))
+                .transpose();
+        }
+
+        match self.advance() {
+            true => seed
+                .deserialize(ElementDeserializer {
+                    context: self.context,
+                })
+                .map(Some),
+            false => Ok(None),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        Some(
+            self.remaining
+                .unwrap_or(self.context.args.len() - self.context.position.get()),
+        )
+    }
+}
+
+impl<'a, 'de> de::MapAccess<'de> for VariadicParameter<'a, 'de> {
+    type Error = Error;
+
+    #[inline]
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        self.next_scalar()?
+            .map(|arg| seed.deserialize(arg))
+            .transpose()
+    }
+
+    #[inline]
+    fn next_value_seed<S>(&mut self, seed: S) -> Result<S::Value, Self::Error>
+    where
+        S: de::DeserializeSeed<'de>,
+    {
+        seed.deserialize(MapValueDeserializer {
+            context: self.context,
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        Some(
+            self.remaining
+                .unwrap_or((self.context.args.len() - self.context.position.get()) / 2),
+        )
+    }
+}
+
+/// Deserializer for a single element of a list parameter. Mirrors
+/// `ElementSerializer`: tuples and structs are deserialized from a group of
+/// arguments, and everything else from a single argument.
+struct ElementDeserializer<'a, 'de> {
+    context: &'a Context<'a, 'de>,
+}
+
+impl<'a, 'de> ElementDeserializer<'a, 'de> {
+    #[inline]
+    fn scalar(self) -> Result<RedisStringAdapter<ArgumentDeserializer<'a, 'de>>, Error> {
+        self.context.scalar()
+    }
+
+    #[inline]
+    fn group<V>(self, len: usize, visitor: V) -> Result<V::Value, Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_seq(VariadicParameter {
+            context: self.context,
+            remaining: Some(len),
+            group: true,
+        })
+    }
+}
+
+impl<'a, 'de> de::Deserializer<'de> for ElementDeserializer<'a, 'de> {
+    type Error = Error;
+
+    forward_to_scalar! {
+        deserialize_any deserialize_bool deserialize_i8 deserialize_i16
+        deserialize_i32 deserialize_i64 deserialize_i128 deserialize_u8
+        deserialize_u16 deserialize_u32 deserialize_u64 deserialize_u128
+        deserialize_f32 deserialize_f64 deserialize_char deserialize_str
+        deserialize_string deserialize_bytes deserialize_byte_buf
+        deserialize_option deserialize_unit deserialize_seq deserialize_map
+        deserialize_identifier deserialize_ignored_any
+    }
+
+    #[inline]
+    fn deserialize_unit_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.scalar()?.deserialize_unit_struct(name, visitor)
+    }
+
+    #[inline]
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    #[inline]
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.group(len, visitor)
+    }
+
+    #[inline]
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.group(len, visitor)
+    }
+
+    #[inline]
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.group(fields.len(), visitor)
+    }
+
+    #[inline]
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.scalar()?.deserialize_enum(name, variants, visitor)
+    }
+}
+
+/// Deserializer for a value in a map parameter. Mirrors `MapValueSerializer`:
+/// the value is always a single argument, so an option is always `Some`.
+struct MapValueDeserializer<'a, 'de> {
+    context: &'a Context<'a, 'de>,
+}
+
+impl<'a, 'de> MapValueDeserializer<'a, 'de> {
+    #[inline]
+    fn scalar(self) -> Result<RedisStringAdapter<ArgumentDeserializer<'a, 'de>>, Error> {
+        self.context.scalar()
+    }
+}
+
+impl<'a, 'de> de::Deserializer<'de> for MapValueDeserializer<'a, 'de> {
+    type Error = Error;
+
+    forward_to_scalar! {
+        deserialize_any deserialize_bool deserialize_i8 deserialize_i16
+        deserialize_i32 deserialize_i64 deserialize_i128 deserialize_u8
+        deserialize_u16 deserialize_u32 deserialize_u64 deserialize_u128
+        deserialize_f32 deserialize_f64 deserialize_char deserialize_str
+        deserialize_string deserialize_bytes deserialize_byte_buf
+        deserialize_unit deserialize_seq deserialize_map
+        deserialize_identifier deserialize_ignored_any
+    }
+
+    #[inline]
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_some(self.scalar()?)
+    }
+
+    #[inline]
+    fn deserialize_unit_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.scalar()?.deserialize_unit_struct(name, visitor)
+    }
+
+    #[inline]
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    #[inline]
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.scalar()?.deserialize_tuple(len, visitor)
+    }
+
+    #[inline]
+    fn deserialize_tuple_struct<V>(
+        self,
+        name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.scalar()?.deserialize_tuple_struct(name, len, visitor)
+    }
+
+    #[inline]
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.scalar()?.deserialize_struct(name, fields, visitor)
+    }
+
+    #[inline]
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.scalar()?.deserialize_enum(name, variants, visitor)
+    }
+}
+
+/// Enum access for an enum parameter, where the variant name has already
+/// been consumed. The only value an enum parameter can have is a single
+/// primitive argument, unless it's a subcommand.
+struct ParameterVariant<'a, 'de> {
+    context: &'a Context<'a, 'de>,
+    variant: &'static str,
+    subcommand: bool,
+}
+
+impl ParameterVariant<'_, '_> {
+    fn check_subcommand(&self) -> Result<(), Error> {
+        match self.subcommand {
+            true => Ok(()),
+            false => Err(de::Error::custom(
+                "can't deserialize complex enums as Redis command parameters",
+            )),
+        }
+    }
+}
+
+impl<'a, 'de> de::EnumAccess<'de> for ParameterVariant<'a, 'de> {
+    type Error = Error;
+    type Variant = Self;
+
+    #[inline]
+    fn variant_seed<S>(self, seed: S) -> Result<(S::Value, Self::Variant), Self::Error>
+    where
+        S: de::DeserializeSeed<'de>,
+    {
+        let value = seed.deserialize(de::IntoDeserializer::<Error>::into_deserializer(
+            self.variant,
+        ))?;
+
+        Ok((value, self))
+    }
+}
+
+impl<'a, 'de> de::VariantAccess<'de> for ParameterVariant<'a, 'de> {
+    type Error = Error;
+
+    #[inline]
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    #[inline]
+    fn newtype_variant_seed<S>(self, seed: S) -> Result<S::Value, Self::Error>
+    where
+        S: de::DeserializeSeed<'de>,
+    {
+        seed.deserialize(self.context.scalar()?)
+    }
+
+    #[inline]
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.check_subcommand()?;
+        visitor.visit_seq(Parameters::anonymous(self.context, len))
+    }
+
+    #[inline]
+    fn struct_variant<V>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.check_subcommand()?;
+        visitor.visit_seq(Parameters::named(self.context, fields))
+    }
+}
+
+/// Deserializer for an optional parameter that is tentatively `Some`. Mirrors
+/// `OptionalParameterSerializer`: the parameter is identified by its field
+/// name, or by its own struct or variant name. If that name isn't the next
+/// argument, this returns `Error::Absent`, and the command is retried with
+/// this parameter set to `None`.
+struct OptionalParameterDeserializer<'a, 'de> {
+    context: &'a Context<'a, 'de>,
+    name: Option<&'static str>,
+    index: usize,
+}
+
+impl<'a, 'de> OptionalParameterDeserializer<'a, 'de> {
+    /// Consume `name`, if it's the next argument, or else signal that this
+    /// parameter is absent.
+    #[inline]
+    fn take(&self, name: &str) -> Result<(), Error> {
+        match self.context.take(name) {
+            true => Ok(()),
+            false => Err(Error::Absent(self.index)),
+        }
+    }
+
+    #[inline]
+    fn name(&self) -> Result<&'static str, Error> {
+        self.name.ok_or_else(|| {
+            de::Error::custom(
+                "can't deserialize an optional primitive value \
+                in a tuple struct command; it needs a name",
+            )
+        })
+    }
+
+    #[inline]
+    fn scalar(self) -> Result<RedisStringAdapter<ArgumentDeserializer<'a, 'de>>, Error> {
+        self.take(self.name()?)?;
+        self.context.scalar()
+    }
+}
+
+impl<'a, 'de> de::Deserializer<'de> for OptionalParameterDeserializer<'a, 'de> {
+    type Error = Error;
+
+    forward_to_scalar! {
+        deserialize_any deserialize_i8 deserialize_i16 deserialize_i32
+        deserialize_i64 deserialize_i128 deserialize_u8 deserialize_u16
+        deserialize_u32 deserialize_u64 deserialize_u128 deserialize_f32
+        deserialize_f64 deserialize_char deserialize_str deserialize_string
+        deserialize_bytes deserialize_byte_buf deserialize_identifier
+        deserialize_ignored_any
+    }
+
+    #[inline]
+    fn deserialize_bool<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        Err(de::Error::custom("can't deserialize an Option<bool>"))
+    }
+
+    #[inline]
+    fn deserialize_option<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        Err(de::Error::custom(
+            "can't deserialize nested optional parameters",
+        ))
+    }
+
+    #[inline]
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.take(self.name()?)?;
+        visitor.visit_unit()
+    }
+
+    #[inline]
+    fn deserialize_unit_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.take(name)?;
+        visitor.visit_unit()
+    }
+
+    #[inline]
+    fn deserialize_newtype_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.take(name)?;
+        visitor.visit_newtype_struct(self.context.scalar()?)
+    }
+
+    #[inline]
+    fn deserialize_seq<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        Err(de::Error::custom("can't deserialize optional sequences"))
+    }
+
+    #[inline]
+    fn deserialize_tuple<V>(self, _len: usize, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        Err(de::Error::custom("can't deserialize optional tuples"))
+    }
+
+    #[inline]
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        Err(de::Error::custom(
+            "can't deserialize optional complex structs",
+        ))
+    }
+
+    #[inline]
+    fn deserialize_map<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        Err(de::Error::custom("can't deserialize optional maps"))
+    }
+
+    #[inline]
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        Err(de::Error::custom("can't deserialize optional structs"))
+    }
+
+    #[inline]
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        let variant = self
+            .context
+            .peek_variant(variants)
+            .ok_or(Error::Absent(self.index))?;
+
+        self.context.next();
+
+        visitor.visit_enum(ParameterVariant {
+            context: self.context,
+            variant,
+            subcommand: false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use cool_asserts::assert_matches;
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::{
+        components::RedisString,
+        de::{from_bytes, Error as DeError},
+        ser::to_vec,
+    };
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+    enum Skip {
+        NX,
+        XX,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+    enum Expiry {
+        #[serde(rename = "EX")]
+        Seconds(u64),
+
+        #[serde(rename = "PX")]
+        Millis(u64),
+
+        #[serde(rename = "KEEPTTL")]
+        Keep,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    #[serde(rename = "SET")]
+    struct Set<'a> {
+        key: &'a str,
+        value: RedisString<i64>,
+        skip: Option<Skip>,
+        #[serde(rename = "GET")]
+        get: bool,
+        expiry: Option<Expiry>,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
+    #[serde(rename = "SCAN")]
+    struct Scan {
+        cursor: u64,
+
+        #[serde(rename = "MATCH")]
+        pattern: Option<String>,
+
+        #[serde(rename = "COUNT")]
+        count: Option<u32>,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    #[serde(rename = "MGET")]
+    struct MultiGet {
+        keys: Vec<String>,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    #[serde(rename = "PING")]
+    struct Ping;
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    enum Request {
+        #[serde(rename = "GET")]
+        Get(String),
+
+        #[serde(rename = "INCRBY")]
+        IncrBy(String, i64),
+
+        #[serde(rename = "DEL")]
+        Delete { keys: Vec<String> },
+
+        #[serde(rename = "QUIT")]
+        Quit,
+    }
+
+    fn parse<'a, T: Deserialize<'a>>(input: &'a [u8]) -> Result<T, DeError> {
+        from_bytes::<Command<T>>(input).map(|Command(command)| command)
+    }
+
+    fn round_trip<'a, T>(command: T, buffer: &'a mut Vec<u8>) -> T
+    where
+        T: Serialize + Deserialize<'a>,
+    {
+        *buffer = to_vec(&Command(command)).unwrap();
+        parse(buffer).unwrap()
+    }
+
+    #[test]
+    fn set_round_trip() {
+        let mut buffer = Vec::new();
+
+        for skip in [None, Some(Skip::NX), Some(Skip::XX)] {
+            for get in [false, true] {
+                for expiry in [None, Some(Expiry::Seconds(10)), Some(Expiry::Keep)] {
+                    let command = Set {
+                        key: "key",
+                        value: RedisString(-5),
+                        skip,
+                        get,
+                        expiry,
+                    };
+
+                    let debug = format!("{command:?}");
+                    let parsed = round_trip(command, &mut buffer);
+                    assert_eq!(format!("{parsed:?}"), debug);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn case_insensitive() {
+        let parsed: Set = parse(
+            b"*6\r\n$3\r\nset\r\n$3\r\nkey\r\n$2\r\n10\r\n$2\r\nxx\r\n$2\r\npx\r\n$3\r\n100\r\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            parsed,
+            Set {
+                key: "key",
+                value: RedisString(10),
+                skip: Some(Skip::XX),
+                get: false,
+                expiry: Some(Expiry::Millis(100)),
+            }
+        );
+    }
+
+    #[test]
+    fn named_options() {
+        let mut buffer = Vec::new();
+
+        let command = Scan {
+            cursor: 10,
+            pattern: None,
+            count: Some(100),
+        };
+
+        assert_eq!(round_trip(command, &mut buffer).count, Some(100));
+
+        let parsed: Scan =
+            parse(b"*4\r\n$4\r\nSCAN\r\n$1\r\n0\r\n$5\r\nmatch\r\n$3\r\nkey\r\n").unwrap();
+
+        assert_eq!(
+            parsed,
+            Scan {
+                cursor: 0,
+                pattern: Some("key".to_owned()),
+                count: None,
+            }
+        );
+    }
+
+    #[test]
+    fn variadic() {
+        let mut buffer = Vec::new();
+
+        let command = MultiGet {
+            keys: vec!["a".to_owned(), "b".to_owned(), "c".to_owned()],
+        };
+        assert_eq!(round_trip(command, &mut buffer).keys, ["a", "b", "c"]);
+
+        let command = MultiGet { keys: Vec::new() };
+        assert_eq!(round_trip(command, &mut buffer).keys, [] as [&str; 0]);
+    }
+
+    #[test]
+    fn unit_struct() {
+        let mut buffer = Vec::new();
+        assert_eq!(round_trip(Ping, &mut buffer), Ping);
+    }
+
+    #[test]
+    fn enum_commands() {
+        let mut buffer = Vec::new();
+
+        for command in [
+            Request::Get("key".to_owned()),
+            Request::IncrBy("key".to_owned(), -3),
+            Request::Delete {
+                keys: vec!["a".to_owned(), "b".to_owned()],
+            },
+            Request::Quit,
+        ] {
+            let debug = format!("{command:?}");
+            let parsed = round_trip(command, &mut buffer);
+            assert_eq!(format!("{parsed:?}"), debug);
+        }
+
+        let parsed: Request = parse(b"*2\r\n$3\r\nget\r\n$3\r\nkey\r\n").unwrap();
+        assert_eq!(parsed, Request::Get("key".to_owned()));
+    }
+
+    #[test]
+    fn wrong_command() {
+        assert_matches!(
+            parse::<Ping>(b"*1\r\n$4\r\nPONG\r\n"),
+            Err(DeError::Custom(_))
+        );
+
+        assert_matches!(
+            parse::<Request>(b"*2\r\n$3\r\nSET\r\n$3\r\nkey\r\n"),
+            Err(DeError::Custom(_))
+        );
+
+        assert_matches!(parse::<Ping>(b"*0\r\n"), Err(DeError::Custom(_)));
+    }
+
+    #[test]
+    fn unexpected_argument() {
+        assert_matches!(
+            parse::<Ping>(b"*2\r\n$4\r\nPING\r\n$5\r\nhello\r\n"),
+            Err(DeError::Custom(msg)) => assert!(msg.contains("hello"))
+        );
+
+        assert_matches!(
+            parse::<Scan>(b"*3\r\n$4\r\nSCAN\r\n$1\r\n0\r\n$5\r\nLIMIT\r\n"),
+            Err(DeError::Custom(_))
+        );
+    }
+
+    #[test]
+    fn missing_argument() {
+        assert_matches!(
+            parse::<Scan>(b"*1\r\n$4\r\nSCAN\r\n"),
+            Err(DeError::Custom(_))
+        );
+
+        assert_matches!(
+            parse::<Scan>(b"*3\r\n$4\r\nSCAN\r\n$1\r\n0\r\n$5\r\nCOUNT\r\n"),
+            Err(DeError::Custom(_))
+        );
+    }
+
+    #[test]
+    fn bad_number() {
+        assert_matches!(
+            parse::<Scan>(b"*2\r\n$4\r\nSCAN\r\n$3\r\nabc\r\n"),
+            Err(DeError::Custom(_))
+        );
+    }
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Location<'a> {
+        longitude: f64,
+        latitude: f64,
+        member: &'a str,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename = "GEOADD")]
+    struct GeoAdd<'a> {
+        key: &'a str,
+        #[serde(borrow)]
+        locations: Vec<Location<'a>>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename = "ZADD")]
+    struct ZAdd<'a> {
+        key: &'a str,
+        members: Vec<(f64, &'a str)>,
+    }
+
+    #[test]
+    fn tuple_groups() {
+        let command: ZAdd =
+            parse(b"*6\r\n$4\r\nZADD\r\n$1\r\nz\r\n$3\r\n1.5\r\n$1\r\na\r\n$1\r\n2\r\n$1\r\nb\r\n")
+                .unwrap();
+
+        assert_eq!(
+            command,
+            ZAdd {
+                key: "z",
+                members: Vec::from([(1.5, "a"), (2.0, "b")]),
+            }
+        );
+    }
+
+    #[test]
+    fn struct_groups_round_trip() {
+        let mut buffer = Vec::new();
+        let command = GeoAdd {
+            key: "places",
+            locations: Vec::from([
+                Location {
+                    longitude: 13.361389,
+                    latitude: 38.115556,
+                    member: "Palermo",
+                },
+                Location {
+                    longitude: 15.087269,
+                    latitude: 37.502669,
+                    member: "Catania",
+                },
+            ]),
+        };
+
+        assert_eq!(
+            round_trip(command, &mut buffer).locations[1].member,
+            "Catania"
+        );
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename = "CONFIG SET")]
+    struct ConfigSet<'a> {
+        parameter: &'a str,
+        value: &'a str,
+    }
+
+    #[test]
+    fn multi_word_name() {
+        let command: ConfigSet =
+            parse(b"*4\r\n$6\r\nconfig\r\n$3\r\nset\r\n$4\r\nport\r\n$4\r\n6380\r\n").unwrap();
+
+        assert_eq!(
+            command,
+            ConfigSet {
+                parameter: "port",
+                value: "6380"
+            }
+        );
+
+        assert_matches!(
+            parse::<ConfigSet>(b"*3\r\n$6\r\nCONFIG\r\n$4\r\nport\r\n$4\r\n6380\r\n"),
+            Err(DeError::Custom(_))
+        );
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    enum Client<'a> {
+        #[serde(rename = "KILL")]
+        Kill {
+            #[serde(rename = "ID")]
+            id: Option<u64>,
+            #[serde(rename = "USER")]
+            user: Option<&'a str>,
+        },
+
+        #[serde(rename = "SETNAME")]
+        SetName(&'a str),
+
+        #[serde(rename = "LIST")]
+        List,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Admin<'a> {
+        #[serde(rename = "CLIENT", borrow)]
+        Client(Client<'a>),
+    }
+
+    #[test]
+    fn subcommand_round_trip() {
+        let mut buffer = Vec::new();
+
+        for client in [
+            Client::Kill {
+                id: None,
+                user: Some("alice"),
+            },
+            Client::Kill {
+                id: Some(3),
+                user: None,
+            },
+            Client::SetName("worker"),
+            Client::List,
+        ] {
+            assert_eq!(
+                round_trip(Admin::Client(client), &mut buffer),
+                Admin::Client(client)
+            );
+        }
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(rename = "TRACKING")]
+    struct Tracking<'a> {
+        #[serde(borrow)]
+        options: BTreeMap<&'a str, Option<&'a str>>,
+    }
+
+    #[test]
+    fn optional_map_values() {
+        // A key without a value can't be told apart from a key whose value
+        // is the next key, so every key is followed by a value
+        let command: Tracking = parse(
+            b"*5\r\n$8\r\nTRACKING\r\n$6\r\nPREFIX\r\n$5\r\nuser:\r\n$5\r\nBCAST\r\n$1\r\n1\r\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            command.options,
+            BTreeMap::from([("BCAST", Some("1")), ("PREFIX", Some("user:"))])
+        );
+    }
+
+    #[test]
+    fn incomplete_group() {
+        assert_matches!(
+            parse::<ZAdd>(b"*5\r\n$4\r\nZADD\r\n$1\r\nz\r\n$1\r\n1\r\n$1\r\na\r\n$1\r\n2\r\n"),
+            Err(DeError::Custom(_))
+        );
+    }
+}
@@ -1,24 +1,39 @@
+use alloc::string::{String, ToString};
+use core::fmt;
+
 use serde::{ser, Serialize};
-use thiserror::Error;
 
 use crate::ser::util::TupleSeqAdapter;
 
-#[derive(Debug, Clone, Error)]
+#[derive(Debug, Clone)]
 pub enum Error {
-    #[error("overflowed a usize")]
     Overflow,
 
-    #[error("tried to serialize {0} into a redis command; only sequences and bytes are allowed")]
     InvalidType(&'static str),
 
-    #[error("error from serialized type: {0}")]
     Custom(String),
 }
 
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Error::Overflow => f.write_str("overflowed a usize"),
+            Error::InvalidType(kind) => write!(
+                f,
+                "tried to serialize {kind} into a redis command; \
+                only sequences and bytes are allowed"
+            ),
+            Error::Custom(ref msg) => write!(f, "error from serialized type: {msg}"),
+        }
+    }
+}
+
+impl ser::StdError for Error {}
+
 impl ser::Error for Error {
     fn custom<T>(msg: T) -> Self
     where
-        T: std::fmt::Display,
+        T: fmt::Display,
     {
         Self::Custom(msg.to_string())
     }
@@ -127,7 +142,7 @@ impl ser::Serializer for Serializer {
     #[inline]
     fn collect_str<T: ?Sized>(self, _value: &T) -> Result<Self::Ok, Self::Error>
     where
-        T: std::fmt::Display,
+        T: fmt::Display,
     {
         Ok(1)
     }
@@ -0,0 +1,201 @@
+use alloc::{borrow::Cow, string::ToString, vec::Vec};
+use core::fmt::Display;
+
+use serde::ser::{self, SerializeTupleStruct as _};
+use serde_bytes::Bytes;
+
+use super::Command;
+use crate::components::RedisString;
+
+/**
+A Redis command built from a plain list of arguments.
+
+This is an escape hatch for sending commands that you don't want to model
+with a struct. It's created with [`Command::raw`], and arguments are added
+with the builder methods on [`Command<RawCommand>`][Command]. It is
+serialized through the same machinery as any other [`Command`], so it
+produces exactly the same output as the equivalent struct would.
+
+# Example
+
+```
+use seredies::components::Command;
+use seredies::ser::to_vec;
+
+let key = "my-key";
+let command = Command::raw("OBJECT").arg("ENCODING").arg(key);
+
+assert_eq!(
+    to_vec(&command).unwrap(),
+    b"*3\r\n$6\r\nOBJECT\r\n$8\r\nENCODING\r\n$6\r\nmy-key\r\n",
+);
+
+let command = Command::raw("SETRANGE")
+    .arg("key")
+    .arg_display(10)
+    .arg_bytes(&b"\xFF\x00"[..]);
+
+assert_eq!(
+    to_vec(&command).unwrap(),
+    b"*4\r\n$8\r\nSETRANGE\r\n$3\r\nkey\r\n$2\r\n10\r\n$2\r\n\xFF\x00\r\n",
+);
+```
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawCommand<'a> {
+    name: &'static str,
+    args: Vec<RawArgument<'a>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum RawArgument<'a> {
+    Str(Cow<'a, str>),
+    Bytes(Cow<'a, [u8]>),
+}
+
+impl<'a> RawCommand<'a> {
+    /// The name of the command
+    #[inline]
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The number of arguments in the command, not including the name
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.args.len()
+    }
+
+    /// True if this command has no arguments
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.args.is_empty()
+    }
+}
+
+impl<'a> Command<RawCommand<'a>> {
+    /// Create a new raw command with the given name and no arguments. See
+    /// [`RawCommand`] for details.
+    #[inline]
+    #[must_use]
+    pub fn raw(name: &'static str) -> Self {
+        Self(RawCommand {
+            name,
+            args: Vec::new(),
+        })
+    }
+
+    /// Add a string argument to this command.
+    #[inline]
+    #[must_use]
+    pub fn arg(mut self, arg: impl Into<Cow<'a, str>>) -> Self {
+        self.0.args.push(RawArgument::Str(arg.into()));
+        self
+    }
+
+    /// Add a binary argument to this command.
+    #[inline]
+    #[must_use]
+    pub fn arg_bytes(mut self, arg: impl Into<Cow<'a, [u8]>>) -> Self {
+        self.0.args.push(RawArgument::Bytes(arg.into()));
+        self
+    }
+
+    /// Add an argument to this command, formatted with its [`Display`]
+    /// implementation. This is useful for numeric arguments.
+    #[inline]
+    #[must_use]
+    pub fn arg_display(self, arg: impl Display) -> Self {
+        self.arg(arg.to_string())
+    }
+}
+
+impl ser::Serialize for RawCommand<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut command = serializer.serialize_tuple_struct(self.name, self.args.len())?;
+
+        self.args.iter().try_for_each(|arg| match arg {
+            RawArgument::Str(arg) => command.serialize_field(RedisString::new_ref(&**arg)),
+            RawArgument::Bytes(arg) => command.serialize_field(Bytes::new(arg)),
+        })?;
+
+        command.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+    use serde_test::{assert_ser_tokens, Token};
+
+    use super::*;
+
+    #[derive(Serialize)]
+    #[serde(rename = "OBJECT")]
+    struct Object<'a> {
+        subcommand: &'a str,
+        key: &'a str,
+    }
+
+    #[test]
+    fn matches_struct_command() {
+        let expected = [
+            Token::Seq { len: Some(3) },
+            Token::Str("OBJECT"),
+            Token::Str("ENCODING"),
+            Token::Str("my-key"),
+            Token::SeqEnd,
+        ];
+
+        assert_ser_tokens(
+            &Command(Object {
+                subcommand: "ENCODING",
+                key: "my-key",
+            }),
+            &expected,
+        );
+
+        assert_ser_tokens(
+            &Command::raw("OBJECT").arg("ENCODING").arg("my-key"),
+            &expected,
+        );
+    }
+
+    #[test]
+    fn no_args() {
+        assert_ser_tokens(
+            &Command::raw("PING"),
+            &[
+                Token::Seq { len: Some(1) },
+                Token::Str("PING"),
+                Token::SeqEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn mixed_args() {
+        assert_ser_tokens(
+            &Command::raw("SET")
+                .arg(String::from("key"))
+                .arg_bytes(Vec::from(*b"\0\xFF"))
+                .arg("EX")
+                .arg_display(60u32),
+            &[
+                Token::Seq { len: Some(5) },
+                Token::Str("SET"),
+                Token::Str("key"),
+                Token::Bytes(b"\0\xFF"),
+                Token::Str("EX"),
+                Token::Str("60"),
+                Token::SeqEnd,
+            ],
+        );
+    }
+}
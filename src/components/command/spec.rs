@@ -0,0 +1,242 @@
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io;
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::Command;
+use crate::{de, ser};
+
+/**
+A Redis command with a known response type, for a lightweight typed RPC
+layer on top of the serializer and deserializer.
+
+Implement this for a command type to associate it with the type of its
+reply; the provided methods then serialize the request and deserialize the
+reply, so the two can't get out of sync. A command type that is serialized
+with the [`Command`] adapter should implement this for itself; `Command<T>`
+then implements it with the same response, so it's the `Command` that gets
+sent.
+
+This doesn't manage connections, and it doesn't pipeline; it's meant to be
+a building block for clients, rather than a client.
+
+# Example
+
+```
+use serde::Serialize;
+use seredies::components::{Command, RedisCommandSpec};
+
+#[derive(Serialize)]
+#[serde(rename = "INCRBY")]
+struct IncrBy<'a> {
+    key: &'a str,
+    increment: i64,
+}
+
+impl RedisCommandSpec for IncrBy<'_> {
+    type Response = i64;
+}
+
+let command = Command(IncrBy { key: "counter", increment: 5 });
+
+assert_eq!(
+    command.to_request().unwrap(),
+    b"*3\r\n$6\r\nINCRBY\r\n$7\r\ncounter\r\n$1\r\n5\r\n",
+);
+
+// Over a real connection, `call` does both at once
+let mut request = Vec::new();
+let mut reply: &[u8] = b":15\r\n";
+assert_eq!(command.call(&mut request, &mut reply).unwrap(), 15);
+```
+*/
+pub trait RedisCommandSpec {
+    /// The type of the reply to this command.
+    type Response: DeserializeOwned;
+
+    /// Serialize this command as a request.
+    #[inline]
+    fn to_request(&self) -> Result<Vec<u8>, ser::Error>
+    where
+        Self: Serialize,
+    {
+        ser::to_vec(self)
+    }
+
+    /// Deserialize the reply to this command from a byte slice, which must
+    /// contain exactly one RESP value.
+    #[inline]
+    fn parse_response(input: &[u8]) -> Result<Self::Response, de::Error> {
+        de::from_bytes(input)
+    }
+
+    /// Send this command to the `writer`, and then read and deserialize the
+    /// reply from the `reader`. The writer is flushed after the command is
+    /// written. Exactly the bytes of the reply are consumed from the reader,
+    /// so any data after it is left in the reader.
+    #[cfg(feature = "std")]
+    fn call<W, R>(&self, mut writer: W, mut reader: R) -> Result<Self::Response, CallError>
+    where
+        Self: Serialize,
+        W: io::Write,
+        R: io::BufRead,
+    {
+        writer
+            .write_all(&self.to_request().map_err(CallError::Serialize)?)
+            .and_then(|()| writer.flush())
+            .map_err(CallError::Io)?;
+
+        let mut buffer = Vec::new();
+        let mut frame = de::parse::FrameScanner::new();
+
+        loop {
+            match frame.scan(&buffer) {
+                Ok(_) => break,
+
+                // Only take as much of the reader's buffer as the reply is
+                // known to need, so that any pipelined data stays in the
+                // reader.
+                Err(de::parse::Error::UnexpectedEof(needed)) => {
+                    let chunk = reader.fill_buf().map_err(CallError::Io)?;
+                    let chunk = &chunk[..chunk.len().min(needed)];
+
+                    if chunk.is_empty() {
+                        return Err(CallError::Io(io::ErrorKind::UnexpectedEof.into()));
+                    }
+
+                    buffer.extend_from_slice(chunk);
+                    let read = chunk.len();
+                    reader.consume(read);
+                }
+                Err(err) => return Err(CallError::Deserialize(de::Error::Parse(err))),
+            }
+        }
+
+        de::from_bytes(&buffer).map_err(CallError::Deserialize)
+    }
+}
+
+impl<T: RedisCommandSpec> RedisCommandSpec for Command<T> {
+    type Response = T::Response;
+}
+
+impl<T: RedisCommandSpec + ?Sized> RedisCommandSpec for &T {
+    type Response = T::Response;
+}
+
+/// Errors from [`RedisCommandSpec::call`].
+#[cfg(feature = "std")]
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum CallError {
+    /// The command couldn't be serialized.
+    Serialize(ser::Error),
+
+    /// There was an i/o error while sending the command or reading the
+    /// reply. If the reader reached EOF before the reply was complete, this
+    /// is an error with [`io::ErrorKind::UnexpectedEof`].
+    Io(io::Error),
+
+    /// The reply was read, but failed to deserialize. This includes replies
+    /// that are Redis errors, unless the response type is a [`Result`].
+    Deserialize(de::Error),
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for CallError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            CallError::Serialize(_) => f.write_str("failed to serialize the command"),
+            CallError::Io(_) => f.write_str("i/o error during command"),
+            CallError::Deserialize(_) => f.write_str("failed to deserialize the reply"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CallError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match *self {
+            CallError::Serialize(ref err) => Some(err),
+            CallError::Io(ref err) => Some(err),
+            CallError::Deserialize(ref err) => Some(err),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use cool_asserts::assert_matches;
+
+    use super::*;
+
+    #[derive(Serialize)]
+    #[serde(rename = "GET")]
+    struct Get<'a> {
+        key: &'a str,
+    }
+
+    impl RedisCommandSpec for Get<'_> {
+        type Response = Option<String>;
+    }
+
+    #[test]
+    fn call() {
+        let mut request = Vec::new();
+        let mut reply: &[u8] = b"$5\r\nvalue\r\n:1\r\n";
+
+        let value = Command(Get { key: "key" })
+            .call(&mut request, &mut reply)
+            .unwrap();
+
+        assert_eq!(value.as_deref(), Some("value"));
+        assert_eq!(request, b"*2\r\n$3\r\nGET\r\n$3\r\nkey\r\n");
+        assert_eq!(reply, b":1\r\n");
+    }
+
+    #[test]
+    fn flushes_writer() {
+        let mut writer = io::BufWriter::new(Vec::new());
+        let mut reply: &[u8] = b"$-1\r\n";
+
+        let value = Command(Get { key: "key" })
+            .call(&mut writer, &mut reply)
+            .unwrap();
+
+        assert_eq!(value, None);
+        assert_eq!(writer.get_ref(), b"*2\r\n$3\r\nGET\r\n$3\r\nkey\r\n");
+    }
+
+    #[test]
+    fn split_reply() {
+        let reply: &[u8] = b"$5\r\nvalue\r\n";
+        let reader = io::BufReader::with_capacity(2, reply);
+
+        let value = Command(Get { key: "key" })
+            .call(io::sink(), reader)
+            .unwrap();
+
+        assert_eq!(value.as_deref(), Some("value"));
+    }
+
+    #[test]
+    fn incomplete_reply() {
+        let mut reply: &[u8] = b"$5\r\nval";
+
+        assert_matches!(
+            Command(Get { key: "key" }).call(io::sink(), &mut reply),
+            Err(CallError::Io(err)) => assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof)
+        );
+    }
+
+    #[test]
+    fn error_reply() {
+        assert_matches!(
+            Command::<Get>::parse_response(b"-ERR wrong\r\n"),
+            Err(de::Error::Redis(_))
+        );
+    }
+}
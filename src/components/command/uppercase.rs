@@ -0,0 +1,279 @@
+use core::fmt;
+
+use serde::ser;
+
+use super::{Command, RedisCommandSpec};
+use crate::components::{Case as _, Uppercase};
+
+/// The newtype struct name used by [`UppercaseNames`] to tell the
+/// [`Command`] serializer to send names in uppercase.
+pub(super) const UPPERCASE_NAMES_TOKEN: &str = "$seredies::UppercaseNames";
+
+/**
+Adapter type that makes [`Command`] send the names from a command type in
+uppercase, so that they don't each need a `#[serde(rename)]`. It's usually
+created with [`Command::uppercase`].
+
+The names that are converted are the ones that come from the command type
+itself: the command name (the name of the struct or enum variant), the
+names of flags and optional parameters (the field names), and the names of
+unit variants and unit structs used as parameters, including the ones in
+lists, tuples, and map values. Multi-word names are just uppercased, not
+split, so `KeepTtl` becomes `KEEPTTL`. Names given with `#[serde(rename)]`
+are converted too, which has no effect if they're already uppercase.
+Anything that comes from the command's data, like
+string arguments, the name in a tuple command like `("get", key)`, and the
+keys of a map or struct used as a list of field-value pairs, is sent
+unchanged.
+
+When it's serialized outside of a `Command`, an `UppercaseNames` is
+transparent, and serializes the same way as the command inside it.
+
+# Example
+
+```
+use serde::Serialize;
+use seredies::components::Command;
+use seredies::ser::to_vec;
+
+#[derive(Serialize)]
+enum Expiry {
+    Ex(u64),
+    KeepTtl,
+}
+
+#[derive(Serialize)]
+struct Set<'a> {
+    key: &'a str,
+    value: &'a str,
+    nx: bool,
+    expiry: Option<Expiry>,
+}
+
+let command = Command::uppercase(Set {
+    key: "key",
+    value: "value",
+    nx: true,
+    expiry: Some(Expiry::Ex(60)),
+});
+
+assert_eq!(
+    to_vec(&command).unwrap(),
+    b"*6\r\n\
+    $3\r\nSET\r\n\
+    $3\r\nkey\r\n\
+    $5\r\nvalue\r\n\
+    $2\r\nNX\r\n\
+    $2\r\nEX\r\n\
+    $2\r\n60\r\n",
+);
+```
+*/
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct UppercaseNames<T>(pub T);
+
+impl<T> Command<UppercaseNames<T>> {
+    /// Wrap a command so that its names are sent in uppercase. See
+    /// [`UppercaseNames`] for details.
+    #[inline]
+    #[must_use]
+    pub const fn uppercase(command: T) -> Self {
+        Self(UppercaseNames(command))
+    }
+}
+
+impl<T> ser::Serialize for UppercaseNames<T>
+where
+    T: ser::Serialize,
+{
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.serialize_newtype_struct(UPPERCASE_NAMES_TOKEN, &self.0)
+    }
+}
+
+impl<T: RedisCommandSpec> RedisCommandSpec for UppercaseNames<T> {
+    type Response = T::Response;
+}
+
+/// A name that comes from the command type, like the command name or a
+/// field name, which is sent in uppercase if the command is wrapped in
+/// [`UppercaseNames`].
+#[derive(Debug, Clone, Copy)]
+pub(super) struct TypeName<'a> {
+    name: &'a str,
+    uppercase: bool,
+}
+
+impl<'a> TypeName<'a> {
+    #[inline]
+    #[must_use]
+    pub const fn new(name: &'a str, uppercase: bool) -> Self {
+        Self { name, uppercase }
+    }
+}
+
+impl fmt::Display for TypeName<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.uppercase {
+            false => f.write_str(self.name),
+            true => Uppercase::write_name(self.name, f),
+        }
+    }
+}
+
+impl ser::Serialize for TypeName<'_> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        match self.uppercase {
+            false => serializer.serialize_str(self.name),
+            true => serializer.collect_str(self),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+    use serde_test::{assert_ser_tokens, Token};
+
+    use super::*;
+    use crate::ser::to_vec;
+
+    #[derive(Serialize)]
+    enum Condition {
+        Nx,
+        #[serde(rename = "XX")]
+        OnlyExisting,
+    }
+
+    #[derive(Serialize)]
+    struct Zadd<'a> {
+        key: &'a str,
+        condition: Option<Condition>,
+        ch: bool,
+        incr: bool,
+        members: &'a [(f64, &'a str)],
+    }
+
+    #[test]
+    fn names() {
+        let command = Command::uppercase(Zadd {
+            key: "zset",
+            condition: Some(Condition::Nx),
+            ch: true,
+            incr: false,
+            members: &[(1.5, "a")],
+        });
+
+        assert_ser_tokens(
+            &command,
+            &[
+                Token::Seq { len: Some(6) },
+                Token::Str("ZADD"),
+                Token::Str("zset"),
+                Token::Str("NX"),
+                Token::Str("CH"),
+                Token::Str("1.5"),
+                Token::Str("a"),
+                Token::SeqEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn unit_variants_in_lists_and_tuples() {
+        #[derive(Serialize)]
+        struct Zadd {
+            flags: Vec<Condition>,
+            mode: Option<Condition>,
+            tagged: (Condition, &'static str),
+            groups: Vec<(Condition, i32)>,
+        }
+
+        assert_ser_tokens(
+            &Command::uppercase(Zadd {
+                flags: Vec::from([Condition::Nx, Condition::OnlyExisting]),
+                mode: Some(Condition::Nx),
+                tagged: (Condition::Nx, "x"),
+                groups: Vec::from([(Condition::Nx, 1)]),
+            }),
+            &[
+                Token::Seq { len: Some(8) },
+                Token::Str("ZADD"),
+                Token::Str("NX"),
+                Token::Str("XX"),
+                Token::Str("NX"),
+                Token::Str("NX"),
+                Token::Str("x"),
+                Token::Str("NX"),
+                Token::Str("1"),
+                Token::SeqEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn renamed() {
+        let command = Command::uppercase(Zadd {
+            key: "zset",
+            condition: Some(Condition::OnlyExisting),
+            ch: false,
+            incr: true,
+            members: &[],
+        });
+
+        assert_eq!(
+            to_vec(&command).unwrap(),
+            b"*4\r\n$4\r\nZADD\r\n$4\r\nzset\r\n$2\r\nXX\r\n$4\r\nINCR\r\n"
+        );
+    }
+
+    #[test]
+    fn data_unchanged() {
+        #[derive(Serialize)]
+        struct Fields {
+            name: &'static str,
+        }
+
+        #[derive(Serialize)]
+        struct Hset {
+            key: &'static str,
+            fields: Fields,
+        }
+
+        assert_eq!(
+            to_vec(&Command::uppercase(Hset {
+                key: "key",
+                fields: Fields { name: "value" },
+            }))
+            .unwrap(),
+            b"*4\r\n$4\r\nHSET\r\n$3\r\nkey\r\n$4\r\nname\r\n$5\r\nvalue\r\n"
+        );
+
+        assert_eq!(
+            to_vec(&Command::uppercase(("get", "key"))).unwrap(),
+            b"*2\r\n$3\r\nget\r\n$3\r\nkey\r\n"
+        );
+    }
+
+    #[test]
+    fn without_uppercase() {
+        #[derive(Serialize)]
+        struct Get {
+            key: &'static str,
+        }
+
+        assert_eq!(
+            to_vec(&Command(Get { key: "key" })).unwrap(),
+            b"*2\r\n$3\r\nGet\r\n$3\r\nkey\r\n"
+        );
+    }
+}
@@ -0,0 +1,274 @@
+use core::{fmt, marker::PhantomData};
+
+use serde::{de, ser};
+
+use crate::de::parse::parse_integer;
+
+/**
+A unit-only enum whose variants have integer discriminants. This is used by
+[`Discriminant`].
+
+[`discriminant`][Discriminated::discriminant] and
+[`VARIANTS`][Discriminated::VARIANTS] have to agree: deserialization looks up
+a discriminant in `VARIANTS`, so a variant that's missing from it can be
+serialized but not deserialized. Prefer implementing this trait with
+[`discriminated!`][crate::discriminated], which generates both from a single
+list of variants.
+*/
+pub trait Discriminated: Copy + 'static {
+    /// Every variant of the enum.
+    const VARIANTS: &'static [Self];
+
+    /// The discriminant of this variant.
+    fn discriminant(self) -> i64;
+
+    /// The variant with the given discriminant, if there is one.
+    #[inline]
+    #[must_use]
+    fn from_discriminant(discriminant: i64) -> Option<Self> {
+        Self::VARIANTS
+            .iter()
+            .copied()
+            .find(|variant| variant.discriminant() == discriminant)
+    }
+}
+
+/**
+Define a unit-only enum and implement [`Discriminated`] for it, from a single
+list of variants and their discriminants.
+
+The enum is emitted as written, along with an exhaustive `match` for
+[`discriminant`][Discriminated::discriminant] and the matching
+[`VARIANTS`][Discriminated::VARIANTS], so the two can't disagree. The enum
+must derive (or otherwise implement) `Clone` and `Copy`. Discriminants can be
+any `i64` expression, including negative ones.
+
+# Example
+
+```
+use seredies::components::Discriminated;
+
+seredies::discriminated! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Priority {
+        Low = -1,
+        High = 10,
+    }
+}
+
+assert_eq!(Priority::High.discriminant(), 10);
+assert_eq!(Priority::VARIANTS, [Priority::Low, Priority::High]);
+assert_eq!(Priority::from_discriminant(-1), Some(Priority::Low));
+```
+*/
+#[macro_export]
+macro_rules! discriminated {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $Name:ident {
+            $(
+                $(#[$variant_meta:meta])*
+                $Variant:ident = $discriminant:expr
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $Name {
+            $(
+                $(#[$variant_meta])*
+                $Variant,
+            )*
+        }
+
+        impl $crate::components::Discriminated for $Name {
+            const VARIANTS: &'static [Self] = &[$(Self::$Variant),*];
+
+            #[inline]
+            fn discriminant(self) -> i64 {
+                match self {
+                    $(Self::$Variant => $discriminant,)*
+                }
+            }
+        }
+    };
+}
+
+/**
+Adapter type that (de)serializes a unit-only enum as the integer
+discriminant of its variant, rather than its name.
+
+Some Redis options are small integers that are really a choice from a fixed
+set, like a database index or a set of flags. A `Discriminant` serializes
+the wrapped variant as an integer with its
+[`discriminant`][Discriminated::discriminant], which is sent as a RESP
+integer, or as a string when it's a command argument. It deserializes an
+integer, or a string containing one, back into the variant with that
+discriminant, and fails for any other integer.
+
+# Example
+
+```
+use seredies::components::{Command, Discriminant};
+use seredies::de::from_bytes;
+use seredies::ser::to_vec;
+
+seredies::discriminated! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Database {
+        Sessions = 0,
+        Cache = 3,
+        Queue = 7,
+    }
+}
+
+assert_eq!(
+    to_vec(&Command(("SELECT", Discriminant(Database::Cache)))).unwrap(),
+    b"*2\r\n$6\r\nSELECT\r\n$1\r\n3\r\n",
+);
+
+let Discriminant(database): Discriminant<Database> = from_bytes(b":7\r\n").unwrap();
+assert_eq!(database, Database::Queue);
+
+assert!(from_bytes::<Discriminant<Database>>(b":1\r\n").is_err());
+```
+*/
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Discriminant<T>(pub T);
+
+impl<T: Discriminated> ser::Serialize for Discriminant<T> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.serialize_i64(self.0.discriminant())
+    }
+}
+
+/// Visitor for a discriminant, which is an integer or a string containing
+/// one.
+struct DiscriminantVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Discriminated> de::Visitor<'de> for DiscriminantVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("one of the discriminants")?;
+
+        T::VARIANTS.iter().enumerate().try_for_each(|(i, variant)| {
+            let sep = if i == 0 { " " } else { ", " };
+            write!(formatter, "{sep}{}", variant.discriminant())
+        })
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        T::from_discriminant(v)
+            .ok_or_else(|| de::Error::invalid_value(de::Unexpected::Signed(v), &self))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match i64::try_from(v) {
+            Ok(v) => self.visit_i64(v),
+            Err(_) => Err(de::Error::invalid_value(de::Unexpected::Unsigned(v), &self)),
+        }
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match parse_integer(v) {
+            Some(v) => self.visit_i64(v),
+            None => Err(de::Error::invalid_value(de::Unexpected::Bytes(v), &self)),
+        }
+    }
+
+    #[inline]
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.visit_bytes(v.as_bytes())
+    }
+}
+
+impl<'de, T: Discriminated> de::Deserialize<'de> for Discriminant<T> {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer
+            .deserialize_i64(DiscriminantVisitor(PhantomData))
+            .map(Discriminant)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cool_asserts::assert_matches;
+
+    use super::*;
+    use crate::{
+        de::{from_bytes, Error},
+        ser::to_vec,
+    };
+
+    crate::discriminated! {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        enum Priority {
+            Low = -1,
+            High = 10,
+            Urgent = 100,
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        for &priority in Priority::VARIANTS {
+            let data = to_vec(&Discriminant(priority)).unwrap();
+            let parsed: Discriminant<Priority> = from_bytes(&data).unwrap();
+
+            assert_eq!(parsed, Discriminant(priority));
+        }
+    }
+
+    #[test]
+    fn serialize() {
+        assert_eq!(to_vec(&Discriminant(Priority::Low)).unwrap(), b":-1\r\n");
+    }
+
+    #[test]
+    fn from_string() {
+        assert_eq!(
+            from_bytes::<Discriminant<Priority>>(b"$3\r\n100\r\n").unwrap(),
+            Discriminant(Priority::Urgent)
+        );
+    }
+
+    #[test]
+    fn not_a_number() {
+        assert_matches!(
+            from_bytes::<Discriminant<Priority>>(b"+high\r\n"),
+            Err(Error::Custom(_))
+        );
+    }
+
+    #[test]
+    fn unknown_discriminant() {
+        assert_matches!(
+            from_bytes::<Discriminant<Priority>>(b":2\r\n"),
+            Err(Error::Custom(msg)) => assert_eq!(
+                msg,
+                "invalid value: integer 2, expected one of the discriminants -1, 10, 100"
+            )
+        );
+    }
+}
@@ -0,0 +1,232 @@
+use alloc::string::String;
+use core::{fmt, marker::PhantomData};
+
+use serde::{de, ser, ser::SerializeStruct as _};
+
+/**
+A structured Redis error, made of an error code (like `ERR` or `WRONGTYPE`)
+and a human-readable message.
+
+By convention, Redis errors begin with an upper-case code, followed by a
+space and the message. When used as the `Err` variant of a [`Result`], a
+`RedisError` is serialized by joining the code and message with a space,
+and deserialized by splitting the error at the first space. An error with
+an empty message is serialized as just the code, like `-ERR`. The code can be
+any string-like type, including a unit-only enum of known codes.
+
+# Example
+
+```
+use serde::{Deserialize, Serialize};
+use seredies::components::RedisError;
+use seredies::de::from_bytes;
+use seredies::ser::to_vec;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Code {
+    ERR,
+    WRONGTYPE,
+}
+
+let error = RedisError {
+    code: Code::WRONGTYPE,
+    message: "Operation against a key holding the wrong kind of value",
+};
+let resp = to_vec(&Err::<(), _>(&error)).unwrap();
+assert_eq!(
+    resp,
+    b"-WRONGTYPE Operation against a key holding the wrong kind of value\r\n",
+);
+
+let result: Result<(), RedisError<Code, &str>> = from_bytes(&resp).unwrap();
+assert_eq!(result, Err(error));
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct RedisError<C = String, M = String> {
+    /// The error code, such as `ERR` or `WRONGTYPE`.
+    pub code: C,
+
+    /// The error message that follows the code.
+    pub message: M,
+}
+
+impl<C: fmt::Display, M: fmt::Display> fmt::Display for RedisError<C, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code)?;
+
+        let mut message = MessageWriter {
+            formatter: f,
+            separated: false,
+        };
+
+        fmt::Write::write_fmt(&mut message, format_args!("{}", self.message))
+    }
+}
+
+/// Writer for the message of a [`RedisError`], which writes the space
+/// between the code and the message, but only if the message isn't empty.
+struct MessageWriter<'a, 'b> {
+    formatter: &'a mut fmt::Formatter<'b>,
+    separated: bool,
+}
+
+impl fmt::Write for MessageWriter<'_, '_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if !self.separated && !s.is_empty() {
+            self.formatter.write_str(" ")?;
+            self.separated = true;
+        }
+
+        self.formatter.write_str(s)
+    }
+}
+
+impl<C: ser::Serialize, M: ser::Serialize> ser::Serialize for RedisError<C, M> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        let mut error = serializer.serialize_struct("RedisError", 2)?;
+        error.serialize_field("code", &self.code)?;
+        error.serialize_field("message", &self.message)?;
+        error.end()
+    }
+}
+
+impl<'de, C, M> de::Deserialize<'de> for RedisError<C, M>
+where
+    C: de::Deserialize<'de>,
+    M: de::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor<C, M>(PhantomData<(C, M)>);
+
+        impl<'de, C, M> de::Visitor<'de> for Visitor<C, M>
+        where
+            C: de::Deserialize<'de>,
+            M: de::Deserialize<'de>,
+        {
+            type Value = RedisError<C, M>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(formatter, "a redis error code and message")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let code = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+
+                let message = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+                match seq.next_element::<de::IgnoredAny>()? {
+                    None => Ok(RedisError { code, message }),
+                    Some(_) => Err(de::Error::invalid_length(3, &self)),
+                }
+            }
+        }
+
+        deserializer.deserialize_struct("RedisError", &["code", "message"], Visitor(PhantomData))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::{de::from_bytes, ser::to_vec};
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    #[allow(clippy::upper_case_acronyms)]
+    enum Code {
+        ERR,
+        NOSCRIPT,
+    }
+
+    #[test]
+    fn round_trip_enum_code() {
+        let error: Result<(), _> = Err(RedisError {
+            code: Code::NOSCRIPT,
+            message: "No matching script.".to_owned(),
+        });
+
+        let resp = to_vec(&error).unwrap();
+        assert_eq!(resp, b"-NOSCRIPT No matching script.\r\n");
+
+        let parsed: Result<(), RedisError<Code>> = from_bytes(&resp).unwrap();
+        assert_eq!(parsed, error);
+    }
+
+    #[test]
+    fn unknown_code() {
+        let parsed: Result<Result<(), RedisError<Code>>, _> =
+            from_bytes(b"-MOVED 3999 127.0.0.1:6381\r\n");
+        assert!(parsed.is_err());
+    }
+
+    #[test]
+    fn code_only() {
+        let parsed: Result<(), RedisError<&str, &str>> = from_bytes(b"-ERR\r\n").unwrap();
+        assert_eq!(
+            parsed,
+            Err(RedisError {
+                code: "ERR",
+                message: ""
+            })
+        );
+    }
+
+    #[test]
+    fn serialize_code_only() {
+        let error = RedisError {
+            code: "ERR",
+            message: "",
+        };
+
+        assert_eq!(to_vec(&Err::<(), _>(&error)).unwrap(), b"-ERR\r\n");
+        assert_eq!(error.to_string(), "ERR");
+    }
+
+    #[test]
+    fn message_with_spaces() {
+        let parsed: Result<i64, RedisError<&str, &str>> =
+            from_bytes(b"-ERR unknown command 'FOO'\r\n").unwrap();
+        assert_eq!(
+            parsed,
+            Err(RedisError {
+                code: "ERR",
+                message: "unknown command 'FOO'"
+            })
+        );
+    }
+
+    #[test]
+    fn newline_rejected() {
+        let error: Result<(), _> = Err(RedisError {
+            code: "ERR",
+            message: "bad\nmessage",
+        });
+
+        assert!(to_vec(&error).is_err());
+    }
+
+    #[test]
+    fn display() {
+        let error = RedisError {
+            code: "ERR",
+            message: "syntax error",
+        };
+
+        assert_eq!(error.to_string(), "ERR syntax error");
+    }
+}
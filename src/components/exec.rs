@@ -0,0 +1,247 @@
+use core::{fmt, marker::PhantomData};
+
+use serde::{de, ser};
+
+use crate::ser::NULL_ARRAY_TOKEN;
+
+/**
+Adapter type for the reply to `EXEC`, which ends a `MULTI` transaction.
+
+When a transaction runs, `EXEC` replies with an array containing the reply
+to each queued command, in order. Any of those commands can fail without
+affecting the others, so each element can be either a value or an error.
+If the transaction was aborted instead, because a key watched with `WATCH`
+was modified, `EXEC` replies with a null.
+
+An `ExecResponse` deserializes a null as [`ExecResponse::Aborted`], and
+anything else as [`ExecResponse::Committed`] containing the `T`. `T` is
+usually a tuple with a [`Result`] for each queued command, or a [`Vec`][alloc::vec::Vec] of
+`Result`s when the commands all have the same kind of reply; using
+[`RedisError`][super::RedisError] as the error type gives structured error
+codes. If the transaction was discarded before it ran, because one of the
+commands couldn't be queued, `EXEC` replies with an `EXECABORT` error,
+which is an error unless the `ExecResponse` is itself wrapped in a
+`Result`.
+
+When serialized, `Committed` is serialized as the inner value, and
+`Aborted` as a null array (`*-1`), like `EXEC` itself sends, or the RESP3
+null.
+
+# Example
+
+```
+use seredies::components::{ExecResponse, RedisError};
+use seredies::de::from_bytes;
+
+type Reply<'a> = ExecResponse<(
+    Result<(), RedisError<&'a str, &'a str>>,
+    Result<i64, RedisError<&'a str, &'a str>>,
+)>;
+
+let reply: Reply = from_bytes(
+    b"*2\r\n+OK\r\n-ERR value is not an integer or out of range\r\n",
+)
+.unwrap();
+
+let ExecResponse::Committed((set, incr)) = reply else { panic!() };
+assert_eq!(set, Ok(()));
+assert_eq!(incr.unwrap_err().code, "ERR");
+
+let reply: Reply = from_bytes(b"*-1\r\n").unwrap();
+assert!(reply.is_aborted());
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ExecResponse<T> {
+    /// The transaction ran, and this is the reply to each of its commands
+    Committed(T),
+
+    /// The transaction was aborted because a watched key was modified
+    Aborted,
+}
+
+impl<T> ExecResponse<T> {
+    /// True if the transaction was aborted.
+    #[inline]
+    #[must_use]
+    pub fn is_aborted(&self) -> bool {
+        matches!(*self, ExecResponse::Aborted)
+    }
+
+    /// Get the replies to the transaction's commands, or `None` if it was
+    /// aborted.
+    #[inline]
+    #[must_use]
+    pub fn committed(self) -> Option<T> {
+        match self {
+            ExecResponse::Committed(value) => Some(value),
+            ExecResponse::Aborted => None,
+        }
+    }
+}
+
+impl<T> From<ExecResponse<T>> for Option<T> {
+    #[inline]
+    fn from(value: ExecResponse<T>) -> Self {
+        value.committed()
+    }
+}
+
+impl<T> From<Option<T>> for ExecResponse<T> {
+    #[inline]
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => ExecResponse::Committed(value),
+            None => ExecResponse::Aborted,
+        }
+    }
+}
+
+impl<T> ser::Serialize for ExecResponse<T>
+where
+    T: ser::Serialize,
+{
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        match *self {
+            ExecResponse::Committed(ref value) => value.serialize(serializer),
+            ExecResponse::Aborted => serializer.serialize_newtype_struct(NULL_ARRAY_TOKEN, &()),
+        }
+    }
+}
+
+impl<'de, T> de::Deserialize<'de> for ExecResponse<T>
+where
+    T: de::Deserialize<'de>,
+{
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_option(ExecResponseVisitor { value: PhantomData })
+    }
+}
+
+struct ExecResponseVisitor<T> {
+    value: PhantomData<fn() -> T>,
+}
+
+impl<'de, T> de::Visitor<'de> for ExecResponseVisitor<T>
+where
+    T: de::Deserialize<'de>,
+{
+    type Value = ExecResponse<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("the replies to a transaction, or a null")
+    }
+
+    #[inline]
+    fn visit_none<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ExecResponse::Aborted)
+    }
+
+    #[inline]
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ExecResponse::Aborted)
+    }
+
+    #[inline]
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        T::deserialize(deserializer).map(ExecResponse::Committed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cool_asserts::assert_matches;
+
+    use super::*;
+    use crate::{components::RedisError, de, protocol::Version, ser, Config};
+
+    type Replies = Vec<Result<String, RedisError>>;
+
+    #[test]
+    fn mixed_replies() {
+        let reply: ExecResponse<Replies> =
+            de::from_bytes(b"*3\r\n+OK\r\n-WRONGTYPE bad key\r\n$3\r\nabc\r\n").unwrap();
+
+        assert_eq!(
+            reply.committed().unwrap(),
+            [
+                Ok("OK".to_owned()),
+                Err(RedisError {
+                    code: "WRONGTYPE".to_owned(),
+                    message: "bad key".to_owned()
+                }),
+                Ok("abc".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn aborted() {
+        let reply: ExecResponse<Replies> = de::from_bytes(b"*-1\r\n").unwrap();
+        assert_eq!(reply, ExecResponse::Aborted);
+
+        // RESP2 servers can send either kind of null
+        let reply: ExecResponse<Replies> = de::from_bytes(b"$-1\r\n").unwrap();
+        assert!(reply.is_aborted());
+    }
+
+    #[test]
+    fn empty_transaction() {
+        let reply: ExecResponse<Replies> = de::from_bytes(b"*0\r\n").unwrap();
+        assert_eq!(reply, ExecResponse::Committed(Vec::new()));
+    }
+
+    #[test]
+    fn exec_abort() {
+        const EXEC_ABORT: &[u8] =
+            b"-EXECABORT Transaction discarded because of previous errors.\r\n";
+
+        assert_matches!(
+            de::from_bytes::<ExecResponse<Replies>>(EXEC_ABORT),
+            Err(de::Error::Redis(_))
+        );
+
+        let reply: Result<ExecResponse<Replies>, RedisError> = de::from_bytes(EXEC_ABORT).unwrap();
+        assert_matches!(reply, Err(RedisError { code, .. }) => assert_eq!(code, "EXECABORT"));
+    }
+
+    #[test]
+    fn tuple_length_mismatch() {
+        assert_matches!(
+            de::from_bytes::<ExecResponse<(Result<i64, String>,)>>(b"*2\r\n:1\r\n:2\r\n"),
+            Err(de::Error::UnfinishedArray)
+        );
+    }
+
+    #[test]
+    fn serialize() {
+        type Reply<'a> = ExecResponse<(Result<i64, &'a str>, Result<i64, &'a str>)>;
+
+        let reply: Reply = ExecResponse::Committed((Ok(1), Err("ERR oops")));
+
+        assert_eq!(ser::to_vec(&reply).unwrap(), b"*2\r\n:1\r\n-ERR oops\r\n");
+
+        let reply: ExecResponse<Vec<i64>> = ExecResponse::Aborted;
+        assert_eq!(ser::to_vec(&reply).unwrap(), b"*-1\r\n");
+
+        let config = Config::new().with_version(Version::Resp3);
+        assert_eq!(ser::to_vec_with_config(&reply, config).unwrap(), b"_\r\n");
+    }
+}
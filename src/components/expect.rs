@@ -0,0 +1,254 @@
+use core::{fmt, marker::PhantomData};
+
+use serde::{de, ser};
+
+use crate::value::Kind;
+
+/// Visitor that deserializes a `T` from a value of exactly the expected
+/// RESP type, using the same `VALUE_TOKEN` protocol as [`Value`][crate::value::Value].
+struct ExpectVisitor<T> {
+    kind: Kind,
+    value: PhantomData<T>,
+}
+
+impl<T> ExpectVisitor<T> {
+    #[inline]
+    #[must_use]
+    const fn new(kind: Kind) -> Self {
+        Self {
+            kind,
+            value: PhantomData,
+        }
+    }
+}
+
+impl<'de, T> de::Visitor<'de> for ExpectVisitor<T>
+where
+    T: de::Deserialize<'de>,
+{
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "a RESP {}", self.kind.description())
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::EnumAccess<'de>,
+    {
+        use de::VariantAccess as _;
+
+        let (kind, variant) = data.variant::<Kind>()?;
+
+        if kind == self.kind {
+            variant.newtype_variant()
+        } else if kind == Kind::Null {
+            // Nulls are handed to `T`, so that an `Option<T>` can accept them
+            variant.unit_variant()?;
+            T::deserialize(de::value::UnitDeserializer::new())
+        } else {
+            Err(de::Error::invalid_type(
+                de::Unexpected::Other(kind.description()),
+                &self,
+            ))
+        }
+    }
+
+    // Other deserializers don't know about RESP types, so there's nothing to
+    // check.
+    #[inline]
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        T::deserialize(deserializer)
+    }
+}
+
+macro_rules! expect_types {
+    ($(
+        $(#[$attr:meta])*
+        $name:ident: $kind:ident,
+    )*) => {$(
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #[repr(transparent)]
+        pub struct $name<T>(pub T);
+
+        impl<T> ser::Serialize for $name<T>
+        where
+            T: ser::Serialize,
+        {
+            #[inline]
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: ser::Serializer,
+            {
+                self.0.serialize(serializer)
+            }
+        }
+
+        impl<'de, T> de::Deserialize<'de> for $name<T>
+        where
+            T: de::Deserialize<'de>,
+        {
+            #[inline]
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                deserializer
+                    .deserialize_newtype_struct(
+                        crate::de::VALUE_TOKEN,
+                        ExpectVisitor::new(Kind::$kind),
+                    )
+                    .map($name)
+            }
+        }
+    )*};
+}
+
+expect_types! {
+    /**
+    Adapter type that only deserializes from a RESP
+    [Array](https://redis.io/docs/reference/protocol-spec/#arrays).
+
+    The seredies deserializer is usually lenient about RESP types, since it's
+    mostly interested in the data. The `Expect` wrappers instead check the
+    exact type of the reply before deserializing the wrapped value, and fail
+    with a precise error if it differs, even if the value could have been
+    deserialized anyway. This is useful for conformance tests and other
+    tooling that cares about the wire format. RESP3 Push values are
+    accepted as arrays.
+
+    A null is passed through to the wrapped type, so use an `Option` to
+    accept a null as well. With other deserializers, and when serializing,
+    the wrappers are transparent.
+
+    # Example
+
+    ```
+    use seredies::components::{ExpectArray, ExpectBulk, ExpectInteger};
+    use seredies::de::from_bytes;
+
+    let ExpectBulk(value): ExpectBulk<String> = from_bytes(b"$2\r\nOK\r\n").unwrap();
+    assert_eq!(value, "OK");
+
+    // A `String` would accept a simple string, but `ExpectBulk` doesn't
+    let err = from_bytes::<ExpectBulk<String>>(b"+OK\r\n").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "error from Deserialize type: invalid type: simple string, expected a RESP bulk string",
+    );
+
+    // Likewise, an `i64` would accept a bulk string of digits
+    assert!(from_bytes::<ExpectInteger<i64>>(b"$2\r\n10\r\n").is_err());
+
+    let ExpectArray(items): ExpectArray<Vec<ExpectInteger<i64>>> =
+        from_bytes(b"*2\r\n:1\r\n:2\r\n").unwrap();
+    assert_eq!(items, [ExpectInteger(1), ExpectInteger(2)]);
+
+    let missing: ExpectBulk<Option<String>> = from_bytes(b"$-1\r\n").unwrap();
+    assert_eq!(missing, ExpectBulk(None));
+    ```
+    */
+    ExpectArray: Array,
+
+    /// Adapter type that only deserializes from a RESP
+    /// [Integer](https://redis.io/docs/reference/protocol-spec/#integers).
+    /// RESP3 Big Numbers are accepted if they fit in an `i64`. See
+    /// [`ExpectArray`] for details.
+    ExpectInteger: Integer,
+
+    /// Adapter type that only deserializes from a RESP
+    /// [Bulk String](https://redis.io/docs/reference/protocol-spec/#bulk-strings).
+    /// RESP3 Verbatim Strings are accepted as bulk strings of their content.
+    /// See [`ExpectArray`] for details.
+    ExpectBulk: BulkString,
+}
+
+#[cfg(test)]
+mod tests {
+    use cool_asserts::assert_matches;
+
+    use super::*;
+    use crate::{
+        components::RedisError,
+        de::{from_bytes, Error},
+        ser::to_vec,
+    };
+
+    #[test]
+    fn matching_types() {
+        assert_eq!(
+            from_bytes::<ExpectInteger<i64>>(b":-5\r\n").unwrap(),
+            ExpectInteger(-5)
+        );
+        assert_eq!(
+            from_bytes::<ExpectBulk<&str>>(b"$3\r\nabc\r\n").unwrap(),
+            ExpectBulk("abc")
+        );
+        assert_eq!(
+            from_bytes::<ExpectArray<(i64, &str)>>(b"*2\r\n:1\r\n+OK\r\n").unwrap(),
+            ExpectArray((1, "OK"))
+        );
+    }
+
+    #[test]
+    fn coercible_types() {
+        assert_matches!(
+            from_bytes::<ExpectBulk<&str>>(b"+OK\r\n"),
+            Err(Error::Custom(_))
+        );
+        assert_matches!(
+            from_bytes::<ExpectInteger<i64>>(b"+10\r\n"),
+            Err(Error::Custom(_))
+        );
+        assert_matches!(
+            from_bytes::<ExpectBulk<i64>>(b":10\r\n"),
+            Err(Error::Custom(_))
+        );
+        assert_matches!(
+            from_bytes::<ExpectArray<Vec<i64>>>(b":10\r\n"),
+            Err(Error::Custom(_))
+        );
+    }
+
+    #[test]
+    fn null() {
+        assert_eq!(
+            from_bytes::<ExpectArray<Option<Vec<i64>>>>(b"*-1\r\n").unwrap(),
+            ExpectArray(None)
+        );
+        assert_eq!(
+            from_bytes::<ExpectInteger<Option<i64>>>(b":1\r\n").unwrap(),
+            ExpectInteger(Some(1))
+        );
+        assert_matches!(
+            from_bytes::<ExpectInteger<i64>>(b"$-1\r\n"),
+            Err(Error::Custom(_))
+        );
+    }
+
+    #[test]
+    fn redis_error() {
+        assert_matches!(
+            from_bytes::<ExpectInteger<i64>>(b"-ERR bad\r\n"),
+            Err(Error::Custom(_))
+        );
+
+        let result: Result<ExpectInteger<i64>, RedisError<&str, &str>> =
+            from_bytes(b"-ERR bad\r\n").unwrap();
+        assert_matches!(result, Err(RedisError { code: "ERR", .. }));
+
+        let result: Result<ExpectInteger<i64>, RedisError<&str, &str>> =
+            from_bytes(b":1\r\n").unwrap();
+        assert_matches!(result, Ok(ExpectInteger(1)));
+    }
+
+    #[test]
+    fn serialize() {
+        assert_eq!(to_vec(&ExpectBulk("abc")).unwrap(), b"$3\r\nabc\r\n");
+        assert_eq!(to_vec(&ExpectInteger(5)).unwrap(), b":5\r\n");
+    }
+}
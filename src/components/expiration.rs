@@ -0,0 +1,204 @@
+use serde::ser;
+
+/**
+The expiration options for commands like [`SET`](https://redis.io/commands/set/).
+
+Redis commands that set a time-to-live accept exactly one of `EX`, `PX`,
+`EXAT`, `PXAT`, or `KEEPTTL`; sending more than one of them is an error.
+Modeling these as separate fields in a command struct makes it easy to
+accidentally send conflicting options, so this type models them as a single
+enum, which makes the invalid combinations unrepresentable.
+
+When used as an optional field in a [`Command`][crate::components::Command],
+it serializes as the option name followed by its value (if any). Redis also
+rejects zero expirations, so attempting to serialize a zero duration or
+timestamp is an error.
+
+# Example
+
+```
+use serde::Serialize;
+use serde_test::{assert_ser_tokens, assert_ser_tokens_error, Token};
+use seredies::components::{Command, Expiration};
+
+#[derive(Serialize)]
+#[serde(rename = "SET")]
+struct Set {
+    key: &'static str,
+    value: &'static str,
+    expiration: Option<Expiration>,
+}
+
+assert_ser_tokens(
+    &Command(Set {
+        key: "key",
+        value: "value",
+        expiration: Some(Expiration::Seconds(60)),
+    }),
+    &[
+        Token::Seq { len: Some(5) },
+        Token::Str("SET"),
+        Token::Str("key"),
+        Token::Str("value"),
+        Token::Str("EX"),
+        Token::Str("60"),
+        Token::SeqEnd,
+    ],
+);
+
+assert_ser_tokens(
+    &Command(Set {
+        key: "key",
+        value: "value",
+        expiration: Some(Expiration::KeepTtl),
+    }),
+    &[
+        Token::Seq { len: Some(4) },
+        Token::Str("SET"),
+        Token::Str("key"),
+        Token::Str("value"),
+        Token::Str("KEEPTTL"),
+        Token::SeqEnd,
+    ],
+);
+
+assert_ser_tokens_error(
+    &Command(Set {
+        key: "key",
+        value: "value",
+        expiration: Some(Expiration::Millis(0)),
+    }),
+    &[],
+    "expiration times must be greater than zero",
+);
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Expiration {
+    /// `EX`: expire after this many seconds.
+    Seconds(u64),
+
+    /// `PX`: expire after this many milliseconds.
+    Millis(u64),
+
+    /// `EXAT`: expire at this unix timestamp, in seconds.
+    Timestamp(u64),
+
+    /// `PXAT`: expire at this unix timestamp, in milliseconds.
+    TimestampMillis(u64),
+
+    /// `KEEPTTL`: retain the time-to-live already associated with the key.
+    KeepTtl,
+}
+
+impl Expiration {
+    /// Get the Redis option name for this expiration, such as `"EX"`.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match *self {
+            Expiration::Seconds(_) => "EX",
+            Expiration::Millis(_) => "PX",
+            Expiration::Timestamp(_) => "EXAT",
+            Expiration::TimestampMillis(_) => "PXAT",
+            Expiration::KeepTtl => "KEEPTTL",
+        }
+    }
+
+    /// Get the numeric argument for this expiration, if it has one.
+    #[must_use]
+    pub const fn value(&self) -> Option<u64> {
+        match *self {
+            Expiration::Seconds(value)
+            | Expiration::Millis(value)
+            | Expiration::Timestamp(value)
+            | Expiration::TimestampMillis(value) => Some(value),
+            Expiration::KeepTtl => None,
+        }
+    }
+}
+
+impl ser::Serialize for Expiration {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let index = match *self {
+            Expiration::Seconds(_) => 0,
+            Expiration::Millis(_) => 1,
+            Expiration::Timestamp(_) => 2,
+            Expiration::TimestampMillis(_) => 3,
+            Expiration::KeepTtl => 4,
+        };
+
+        match self.value() {
+            None => serializer.serialize_unit_variant("Expiration", index, self.name()),
+            Some(0) => Err(ser::Error::custom(
+                "expiration times must be greater than zero",
+            )),
+            Some(value) => {
+                serializer.serialize_newtype_variant("Expiration", index, self.name(), &value)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+    use serde_test::{assert_ser_tokens, Token};
+
+    use super::*;
+    use crate::components::Command;
+
+    #[derive(Serialize)]
+    #[serde(rename = "SET")]
+    struct Set {
+        key: &'static str,
+        value: &'static str,
+        expiration: Option<Expiration>,
+    }
+
+    fn test_set(expiration: Option<Expiration>, tail: &[Token]) {
+        let mut tokens = Vec::from([
+            Token::Seq {
+                len: Some(3 + tail.len()),
+            },
+            Token::Str("SET"),
+            Token::Str("key"),
+            Token::Str("value"),
+        ]);
+        tokens.extend_from_slice(tail);
+        tokens.push(Token::SeqEnd);
+
+        assert_ser_tokens(
+            &Command(Set {
+                key: "key",
+                value: "value",
+                expiration,
+            }),
+            &tokens,
+        );
+    }
+
+    #[test]
+    fn no_expiration() {
+        test_set(None, &[]);
+    }
+
+    #[test]
+    fn every_expiration() {
+        test_set(
+            Some(Expiration::Millis(1500)),
+            &[Token::Str("PX"), Token::Str("1500")],
+        );
+        test_set(
+            Some(Expiration::Timestamp(1700000000)),
+            &[Token::Str("EXAT"), Token::Str("1700000000")],
+        );
+        test_set(
+            Some(Expiration::TimestampMillis(1700000000000)),
+            &[Token::Str("PXAT"), Token::Str("1700000000000")],
+        );
+        test_set(Some(Expiration::KeepTtl), &[Token::Str("KEEPTTL")]);
+    }
+}
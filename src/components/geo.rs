@@ -0,0 +1,144 @@
+use core::fmt;
+
+use serde::{de, ser, ser::SerializeTuple as _};
+
+use super::Score;
+
+/**
+The position of a member of a geospatial index, as returned by `GEOPOS`,
+and by `GEORADIUS` and `GEOSEARCH` with `WITHCOORD`.
+
+Redis sends a position as a 2-element array of the longitude and latitude,
+each as a bulk string. They're parsed like a [`Score`], so a `GeoPosition`
+can also be deserialized from an array of numbers. `GEOPOS` replies with a
+null in place of the position of each member that doesn't exist, so its
+reply is usually deserialized as a `Vec<Option<GeoPosition>>`.
+
+# Example
+
+```
+use seredies::components::GeoPosition;
+use seredies::de::from_bytes;
+
+let input = b"\
+    *2\r\n\
+    *2\r\n$8\r\n13.36138\r\n$8\r\n38.11555\r\n\
+    *-1\r\n";
+
+let positions: Vec<Option<GeoPosition>> = from_bytes(input).unwrap();
+
+assert_eq!(
+    positions,
+    [
+        Some(GeoPosition {
+            longitude: 13.36138,
+            latitude: 38.11555
+        }),
+        None,
+    ]
+);
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct GeoPosition {
+    /// The longitude, in degrees.
+    pub longitude: f64,
+
+    /// The latitude, in degrees.
+    pub latitude: f64,
+}
+
+impl ser::Serialize for GeoPosition {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        let coordinate =
+            |value| Score::new(value).ok_or_else(|| ser::Error::custom("coordinate is NaN"));
+
+        let mut position = serializer.serialize_tuple(2)?;
+        position.serialize_element(&coordinate(self.longitude)?)?;
+        position.serialize_element(&coordinate(self.latitude)?)?;
+        position.end()
+    }
+}
+
+impl<'de> de::Deserialize<'de> for GeoPosition {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(2, GeoPositionVisitor)
+    }
+}
+
+struct GeoPositionVisitor;
+
+impl<'de> de::Visitor<'de> for GeoPositionVisitor {
+    type Value = GeoPosition;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a longitude and a latitude")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let longitude: Score = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+
+        let latitude: Score = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+        Ok(GeoPosition {
+            longitude: longitude.get(),
+            latitude: latitude.get(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cool_asserts::assert_matches;
+
+    use super::*;
+    use crate::{de, ser};
+
+    #[test]
+    fn round_trip() {
+        let position = GeoPosition {
+            longitude: -0.5,
+            latitude: 51.25,
+        };
+
+        let data = ser::to_vec(&position).unwrap();
+        assert_eq!(data, b"*2\r\n$4\r\n-0.5\r\n$5\r\n51.25\r\n");
+
+        let parsed: GeoPosition = de::from_bytes(&data).unwrap();
+        assert_eq!(parsed, position);
+    }
+
+    #[test]
+    fn integers() {
+        let parsed: GeoPosition = de::from_bytes(b"*2\r\n:1\r\n:-2\r\n").unwrap();
+        assert_eq!(
+            parsed,
+            GeoPosition {
+                longitude: 1.0,
+                latitude: -2.0
+            }
+        );
+    }
+
+    #[test]
+    fn missing_latitude() {
+        assert_matches!(
+            de::from_bytes::<GeoPosition>(b"*1\r\n$1\r\n1\r\n"),
+            Err(de::Error::Custom(_))
+        );
+    }
+}
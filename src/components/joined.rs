@@ -0,0 +1,932 @@
+use alloc::{string::String, vec::Vec};
+use core::{fmt, marker::PhantomData};
+
+use serde::{de, ser};
+
+use super::string::RedisStringAdapter;
+use crate::ser::util::TupleSeqAdapter;
+
+/**
+Adapter type that (de)serializes a sequence as a single string, with the
+elements joined by `SEP` (a space, by default).
+
+Some commands accept a list as a single space-separated argument, and some
+replies pack a list into one string, but these are most naturally modeled
+as a `Vec<String>` or similar. Wrapping such a sequence in a `Joined`
+serializes it as one string containing all of its elements, and
+deserializes it by splitting that string back into elements. The elements
+are (de)serialized like a [`RedisString`][crate::components::RedisString],
+so they can be strings, bytes, or primitives like integers.
+
+# Escaping
+
+So that any sequence can be round-tripped, a backslash is used as an escape
+character. When serializing, backslashes and separators inside of an
+element are escaped with a backslash. When deserializing, a backslash
+followed by any character is replaced with that character, and it's an
+error for the string to end with an unescaped backslash. This means that
+the separator itself can't be a backslash.
+
+Deserializing an empty string produces an empty sequence, so a sequence
+containing a single empty element can't be round-tripped. Otherwise, each
+separator separates two elements, so consecutive, leading, or trailing
+separators produce empty elements.
+
+Elements are borrowed from the input when possible, but an element that
+contained an escape has to be unescaped into a new buffer, so prefer owned
+types like `String` over `&str` for elements that might contain escapes.
+
+Note that Rust doesn't fall back to the default separator during type
+inference, so a `Joined` using a space may need an annotation like
+`Joined<_>`, as in the example below.
+
+# Example
+
+```
+use seredies::components::Joined;
+use seredies::de::from_bytes;
+use seredies::ser::to_vec;
+
+let tags: Joined<_> = Joined(["red", "green", "blue"]);
+assert_eq!(to_vec(&tags).unwrap(), b"$14\r\nred green blue\r\n");
+
+let Joined(ids): Joined<Vec<u32>, ','> = from_bytes(b"$5\r\n1,2,3\r\n").unwrap();
+assert_eq!(ids, [1, 2, 3]);
+
+let Joined(words): Joined<Vec<String>> = from_bytes(b"$10\r\nhello\\ big\r\n").unwrap();
+assert_eq!(words, ["hello big"]);
+```
+*/
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Joined<T, const SEP: char = ' '>(pub T);
+
+/// The separator, encoded as UTF-8
+#[derive(Clone, Copy)]
+struct Separator {
+    buffer: [u8; 4],
+    len: usize,
+}
+
+impl Separator {
+    fn new(separator: char) -> Option<Self> {
+        if separator == '\\' {
+            return None;
+        }
+
+        let mut buffer = [0; 4];
+        let len = separator.encode_utf8(&mut buffer).len();
+        Some(Self { buffer, len })
+    }
+
+    #[inline]
+    #[must_use]
+    fn as_bytes(&self) -> &[u8] {
+        &self.buffer[..self.len]
+    }
+}
+
+const BAD_SEPARATOR: &str = "a backslash can't be used as a `Joined` separator";
+
+impl<T, const SEP: char> ser::Serialize for Joined<T, SEP>
+where
+    T: ser::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        let separator = Separator::new(SEP).ok_or_else(|| ser::Error::custom(BAD_SEPARATOR))?;
+
+        self.0.serialize(JoinSerializer {
+            serializer,
+            separator,
+        })
+    }
+}
+
+impl<'de, T, const SEP: char> de::Deserialize<'de> for Joined<T, SEP>
+where
+    T: de::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let separator = Separator::new(SEP).ok_or_else(|| de::Error::custom(BAD_SEPARATOR))?;
+
+        deserializer
+            .deserialize_bytes(JoinedVisitor {
+                separator,
+                element: PhantomData,
+            })
+            .map(Joined)
+    }
+}
+
+/// Serializer for the sequence inside of a `Joined`, which collects the
+/// elements into a buffer and serializes the result as a single string.
+struct JoinSerializer<S> {
+    serializer: S,
+    separator: Separator,
+}
+
+impl<S> JoinSerializer<S>
+where
+    S: ser::Serializer,
+{
+    #[inline]
+    fn start(self) -> JoinSequencer<S> {
+        JoinSequencer {
+            serializer: self.serializer,
+            separator: self.separator,
+            buffer: Vec::new(),
+            first: true,
+        }
+    }
+}
+
+#[inline]
+fn not_a_sequence<T, E: ser::Error>(kind: &str) -> Result<T, E> {
+    Err(ser::Error::custom(format_args!(
+        "can't join {kind}; `Joined` must contain a sequence"
+    )))
+}
+
+impl<S> ser::Serializer for JoinSerializer<S>
+where
+    S: ser::Serializer,
+{
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    type SerializeSeq = JoinSequencer<S>;
+    type SerializeTuple = TupleSeqAdapter<JoinSequencer<S>>;
+    type SerializeTupleStruct = TupleSeqAdapter<JoinSequencer<S>>;
+    type SerializeTupleVariant = ser::Impossible<S::Ok, S::Error>;
+    type SerializeMap = ser::Impossible<S::Ok, S::Error>;
+    type SerializeStruct = ser::Impossible<S::Ok, S::Error>;
+    type SerializeStructVariant = ser::Impossible<S::Ok, S::Error>;
+
+    #[inline]
+    fn serialize_bool(self, _v: bool) -> Result<Self::Ok, Self::Error> {
+        not_a_sequence("a bool")
+    }
+
+    #[inline]
+    fn serialize_i8(self, _v: i8) -> Result<Self::Ok, Self::Error> {
+        not_a_sequence("an integer")
+    }
+
+    #[inline]
+    fn serialize_i16(self, _v: i16) -> Result<Self::Ok, Self::Error> {
+        not_a_sequence("an integer")
+    }
+
+    #[inline]
+    fn serialize_i32(self, _v: i32) -> Result<Self::Ok, Self::Error> {
+        not_a_sequence("an integer")
+    }
+
+    #[inline]
+    fn serialize_i64(self, _v: i64) -> Result<Self::Ok, Self::Error> {
+        not_a_sequence("an integer")
+    }
+
+    #[inline]
+    fn serialize_i128(self, _v: i128) -> Result<Self::Ok, Self::Error> {
+        not_a_sequence("an integer")
+    }
+
+    #[inline]
+    fn serialize_u8(self, _v: u8) -> Result<Self::Ok, Self::Error> {
+        not_a_sequence("an integer")
+    }
+
+    #[inline]
+    fn serialize_u16(self, _v: u16) -> Result<Self::Ok, Self::Error> {
+        not_a_sequence("an integer")
+    }
+
+    #[inline]
+    fn serialize_u32(self, _v: u32) -> Result<Self::Ok, Self::Error> {
+        not_a_sequence("an integer")
+    }
+
+    #[inline]
+    fn serialize_u64(self, _v: u64) -> Result<Self::Ok, Self::Error> {
+        not_a_sequence("an integer")
+    }
+
+    #[inline]
+    fn serialize_u128(self, _v: u128) -> Result<Self::Ok, Self::Error> {
+        not_a_sequence("an integer")
+    }
+
+    #[inline]
+    fn serialize_f32(self, _v: f32) -> Result<Self::Ok, Self::Error> {
+        not_a_sequence("a float")
+    }
+
+    #[inline]
+    fn serialize_f64(self, _v: f64) -> Result<Self::Ok, Self::Error> {
+        not_a_sequence("a float")
+    }
+
+    #[inline]
+    fn serialize_char(self, _v: char) -> Result<Self::Ok, Self::Error> {
+        not_a_sequence("a char")
+    }
+
+    #[inline]
+    fn serialize_str(self, _v: &str) -> Result<Self::Ok, Self::Error> {
+        not_a_sequence("a string")
+    }
+
+    #[inline]
+    fn serialize_bytes(self, _v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        not_a_sequence("bytes")
+    }
+
+    #[inline]
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        not_a_sequence("an option")
+    }
+
+    #[inline]
+    fn serialize_some<T: ?Sized>(self, _value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: serde::Serialize,
+    {
+        not_a_sequence("an option")
+    }
+
+    #[inline]
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        not_a_sequence("a unit")
+    }
+
+    #[inline]
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        not_a_sequence("a unit struct")
+    }
+
+    #[inline]
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        not_a_sequence("an enum")
+    }
+
+    #[inline]
+    fn serialize_newtype_struct<T: ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: serde::Serialize,
+    {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_newtype_variant<T: ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: serde::Serialize,
+    {
+        not_a_sequence("an enum")
+    }
+
+    #[inline]
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Ok(self.start())
+    }
+
+    #[inline]
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Ok(TupleSeqAdapter::new(self.start()))
+    }
+
+    #[inline]
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Ok(TupleSeqAdapter::new(self.start()))
+    }
+
+    #[inline]
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        not_a_sequence("an enum")
+    }
+
+    #[inline]
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        not_a_sequence("a map")
+    }
+
+    #[inline]
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        not_a_sequence("a struct")
+    }
+
+    #[inline]
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        not_a_sequence("an enum")
+    }
+}
+
+/// Collects the escaped elements of a `Joined` sequence, then serializes them
+/// as a string (or as bytes, if any of the elements weren't UTF-8).
+struct JoinSequencer<S> {
+    serializer: S,
+    separator: Separator,
+    buffer: Vec<u8>,
+    first: bool,
+}
+
+impl<S> ser::SerializeSeq for JoinSequencer<S>
+where
+    S: ser::Serializer,
+{
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: serde::Serialize,
+    {
+        if !self.first {
+            self.buffer.extend_from_slice(self.separator.as_bytes());
+        }
+
+        self.first = false;
+
+        value.serialize(RedisStringAdapter(ElementSerializer {
+            buffer: &mut self.buffer,
+            separator: self.separator,
+            error: PhantomData,
+        }))
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        match String::from_utf8(self.buffer) {
+            Ok(joined) => self.serializer.serialize_str(&joined),
+            Err(err) => self.serializer.serialize_bytes(err.as_bytes()),
+        }
+    }
+}
+
+/// Serializer that appends a single element to the joined buffer, escaping
+/// it as necessary. It's always wrapped in a `RedisStringAdapter`, which
+/// takes care of converting primitives to strings and rejecting everything
+/// else, so only strings and bytes will ever reach it.
+struct ElementSerializer<'a, E> {
+    buffer: &'a mut Vec<u8>,
+    separator: Separator,
+    error: PhantomData<E>,
+}
+
+impl<E: ser::Error> ElementSerializer<'_, E> {
+    fn write(self, element: &[u8]) -> Result<(), E> {
+        let separator = self.separator.as_bytes();
+        let mut rest = element;
+
+        while let Some((&byte, tail)) = rest.split_first() {
+            if byte == b'\\' || rest.starts_with(separator) {
+                self.buffer.push(b'\\');
+            }
+
+            self.buffer.push(byte);
+            rest = tail;
+        }
+
+        Ok(())
+    }
+}
+
+#[inline]
+fn not_an_element<T, E: ser::Error>() -> Result<T, E> {
+    Err(ser::Error::custom(
+        "elements of a `Joined` sequence must be strings",
+    ))
+}
+
+impl<E: ser::Error> ser::Serializer for ElementSerializer<'_, E> {
+    type Ok = ();
+    type Error = E;
+
+    type SerializeSeq = ser::Impossible<(), E>;
+    type SerializeTuple = ser::Impossible<(), E>;
+    type SerializeTupleStruct = ser::Impossible<(), E>;
+    type SerializeTupleVariant = ser::Impossible<(), E>;
+    type SerializeMap = ser::Impossible<(), E>;
+    type SerializeStruct = ser::Impossible<(), E>;
+    type SerializeStructVariant = ser::Impossible<(), E>;
+
+    #[inline]
+    fn serialize_bool(self, _v: bool) -> Result<Self::Ok, Self::Error> {
+        not_an_element()
+    }
+
+    #[inline]
+    fn serialize_i8(self, _v: i8) -> Result<Self::Ok, Self::Error> {
+        not_an_element()
+    }
+
+    #[inline]
+    fn serialize_i16(self, _v: i16) -> Result<Self::Ok, Self::Error> {
+        not_an_element()
+    }
+
+    #[inline]
+    fn serialize_i32(self, _v: i32) -> Result<Self::Ok, Self::Error> {
+        not_an_element()
+    }
+
+    #[inline]
+    fn serialize_i64(self, _v: i64) -> Result<Self::Ok, Self::Error> {
+        not_an_element()
+    }
+
+    #[inline]
+    fn serialize_i128(self, _v: i128) -> Result<Self::Ok, Self::Error> {
+        not_an_element()
+    }
+
+    #[inline]
+    fn serialize_u8(self, _v: u8) -> Result<Self::Ok, Self::Error> {
+        not_an_element()
+    }
+
+    #[inline]
+    fn serialize_u16(self, _v: u16) -> Result<Self::Ok, Self::Error> {
+        not_an_element()
+    }
+
+    #[inline]
+    fn serialize_u32(self, _v: u32) -> Result<Self::Ok, Self::Error> {
+        not_an_element()
+    }
+
+    #[inline]
+    fn serialize_u64(self, _v: u64) -> Result<Self::Ok, Self::Error> {
+        not_an_element()
+    }
+
+    #[inline]
+    fn serialize_u128(self, _v: u128) -> Result<Self::Ok, Self::Error> {
+        not_an_element()
+    }
+
+    #[inline]
+    fn serialize_f32(self, _v: f32) -> Result<Self::Ok, Self::Error> {
+        not_an_element()
+    }
+
+    #[inline]
+    fn serialize_f64(self, _v: f64) -> Result<Self::Ok, Self::Error> {
+        not_an_element()
+    }
+
+    #[inline]
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
+        let mut buffer = [0; 4];
+        self.serialize_str(v.encode_utf8(&mut buffer))
+    }
+
+    #[inline]
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        self.write(v.as_bytes())
+    }
+
+    #[inline]
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        self.write(v)
+    }
+
+    #[inline]
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        not_an_element()
+    }
+
+    #[inline]
+    fn serialize_some<T: ?Sized>(self, _value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: serde::Serialize,
+    {
+        not_an_element()
+    }
+
+    #[inline]
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        not_an_element()
+    }
+
+    #[inline]
+    fn serialize_unit_struct(self, name: &'static str) -> Result<Self::Ok, Self::Error> {
+        self.serialize_str(name)
+    }
+
+    #[inline]
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.serialize_str(variant)
+    }
+
+    #[inline]
+    fn serialize_newtype_struct<T: ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: serde::Serialize,
+    {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_newtype_variant<T: ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: serde::Serialize,
+    {
+        not_an_element()
+    }
+
+    #[inline]
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        not_an_element()
+    }
+
+    #[inline]
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        not_an_element()
+    }
+
+    #[inline]
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        not_an_element()
+    }
+
+    #[inline]
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        not_an_element()
+    }
+
+    #[inline]
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        not_an_element()
+    }
+
+    #[inline]
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        not_an_element()
+    }
+
+    #[inline]
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        not_an_element()
+    }
+}
+
+struct JoinedVisitor<T> {
+    separator: Separator,
+    element: PhantomData<fn() -> T>,
+}
+
+impl<'de, T> de::Visitor<'de> for JoinedVisitor<T>
+where
+    T: de::Deserialize<'de>,
+{
+    type Value = T;
+
+    #[inline]
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string of joined elements")
+    }
+
+    #[inline]
+    fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        T::deserialize(de::value::SeqAccessDeserializer::new(BorrowedSplit(
+            Split::new(v, self.separator),
+        )))
+    }
+
+    #[inline]
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        T::deserialize(de::value::SeqAccessDeserializer::new(Split::new(
+            v,
+            self.separator,
+        )))
+    }
+
+    #[inline]
+    fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.visit_borrowed_bytes(v.as_bytes())
+    }
+
+    #[inline]
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.visit_bytes(v.as_bytes())
+    }
+}
+
+/// A single element split from the joined string
+enum Element<'a> {
+    /// An element without any escapes, which can be used as-is
+    Plain(&'a [u8]),
+
+    /// An element that had escapes, which were removed
+    Unescaped(Vec<u8>),
+}
+
+/// Splits a joined string into elements. This is a `SeqAccess` for
+/// transient input; see [`BorrowedSplit`] for borrowed input.
+struct Split<'a, E> {
+    /// The input that hasn't been split yet. This is `None` after the last
+    /// element has been split off.
+    input: Option<&'a [u8]>,
+    separator: Separator,
+    error: PhantomData<E>,
+}
+
+impl<'a, E: de::Error> Split<'a, E> {
+    #[inline]
+    #[must_use]
+    fn new(input: &'a [u8], separator: Separator) -> Self {
+        Self {
+            input: match input.is_empty() {
+                true => None,
+                false => Some(input),
+            },
+            separator,
+            error: PhantomData,
+        }
+    }
+
+    fn next_element(&mut self) -> Result<Option<Element<'a>>, E> {
+        let Some(input) = self.input else {
+            return Ok(None);
+        };
+
+        let separator = self.separator.as_bytes();
+        let mut unescaped: Option<Vec<u8>> = None;
+        let mut start = 0;
+        let mut index = 0;
+
+        let (end, rest) = loop {
+            let Some(&byte) = input.get(index) else {
+                break (index, None);
+            };
+
+            if byte == b'\\' {
+                let &escaped = input.get(index + 1).ok_or_else(|| {
+                    de::Error::custom("joined string ends with an unescaped backslash")
+                })?;
+
+                let buffer = unescaped.get_or_insert_with(Vec::new);
+                buffer.extend_from_slice(&input[start..index]);
+                buffer.push(escaped);
+
+                index += 2;
+                start = index;
+            } else if input[index..].starts_with(separator) {
+                break (index, Some(&input[index + separator.len()..]));
+            } else {
+                index += 1;
+            }
+        };
+
+        self.input = rest;
+
+        Ok(Some(match unescaped {
+            None => Element::Plain(&input[..end]),
+            Some(mut buffer) => {
+                buffer.extend_from_slice(&input[start..end]);
+                Element::Unescaped(buffer)
+            }
+        }))
+    }
+}
+
+impl<'de, E: de::Error> de::SeqAccess<'de> for Split<'_, E> {
+    type Error = E;
+
+    fn next_element_seed<S>(&mut self, seed: S) -> Result<Option<S::Value>, Self::Error>
+    where
+        S: de::DeserializeSeed<'de>,
+    {
+        self.next_element()?
+            .map(|element| {
+                let bytes = match element {
+                    Element::Plain(bytes) => bytes,
+                    Element::Unescaped(ref buffer) => buffer.as_slice(),
+                };
+
+                seed.deserialize(RedisStringAdapter(de::value::BytesDeserializer::new(bytes)))
+            })
+            .transpose()
+    }
+}
+
+/// Splits a borrowed joined string into elements, borrowing the elements
+/// that didn't contain any escapes.
+struct BorrowedSplit<'de, E>(Split<'de, E>);
+
+impl<'de, E: de::Error> de::SeqAccess<'de> for BorrowedSplit<'de, E> {
+    type Error = E;
+
+    fn next_element_seed<S>(&mut self, seed: S) -> Result<Option<S::Value>, Self::Error>
+    where
+        S: de::DeserializeSeed<'de>,
+    {
+        self.0
+            .next_element()?
+            .map(|element| match element {
+                Element::Plain(bytes) => seed.deserialize(RedisStringAdapter(
+                    de::value::BorrowedBytesDeserializer::new(bytes),
+                )),
+                Element::Unescaped(buffer) => seed.deserialize(RedisStringAdapter(
+                    de::value::BytesDeserializer::new(&buffer),
+                )),
+            })
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cool_asserts::assert_matches;
+
+    use super::*;
+    use crate::{de, ser};
+
+    #[test]
+    fn serialize() {
+        assert_eq!(
+            ser::to_vec(&Joined::<_>(["a", "b", "c"])).unwrap(),
+            b"$5\r\na b c\r\n"
+        );
+        assert_eq!(
+            ser::to_vec(&Joined::<_, ','>((1, "two", 3.5))).unwrap(),
+            b"$9\r\n1,two,3.5\r\n"
+        );
+        assert_eq!(
+            ser::to_vec(&Joined::<_>(Vec::<&str>::new())).unwrap(),
+            b"$0\r\n\r\n"
+        );
+    }
+
+    #[test]
+    fn serialize_escapes() {
+        assert_eq!(
+            ser::to_vec(&Joined::<_>(["a b", "c\\d"])).unwrap(),
+            b"$9\r\na\\ b c\\\\d\r\n"
+        );
+    }
+
+    #[test]
+    fn serialize_multibyte_separator() {
+        assert_eq!(
+            ser::to_vec(&Joined::<_, '→'>(["a", "b→c"])).unwrap(),
+            "$10\r\na→b\\→c\r\n".as_bytes()
+        );
+    }
+
+    #[test]
+    fn serialize_invalid() {
+        assert_matches!(
+            ser::to_vec(&Joined::<_>("abc")),
+            Err(ser::Error::Custom(message)) => assert!(message.contains("must contain a sequence"))
+        );
+        assert_matches!(
+            ser::to_vec(&Joined::<_>([["a"]])),
+            Err(ser::Error::Custom(_))
+        );
+        assert_matches!(
+            ser::to_vec(&Joined::<_, '\\'>(["a"])),
+            Err(ser::Error::Custom(_))
+        );
+    }
+
+    #[test]
+    fn deserialize_borrowed() {
+        let Joined(words): Joined<Vec<&str>> = de::from_bytes(b"$11\r\nhello world\r\n").unwrap();
+        assert_eq!(words, ["hello", "world"]);
+    }
+
+    #[test]
+    fn deserialize_escapes() {
+        let Joined(words): Joined<Vec<String>> = de::from_bytes(b"$9\r\na\\ b c\\\\d\r\n").unwrap();
+        assert_eq!(words, ["a b", "c\\d"]);
+    }
+
+    #[test]
+    fn deserialize_empty() {
+        let Joined(words): Joined<Vec<String>> = de::from_bytes(b"$0\r\n\r\n").unwrap();
+        assert!(words.is_empty());
+
+        let Joined(words): Joined<Vec<&str>> = de::from_bytes(b"$3\r\n a \r\n").unwrap();
+        assert_eq!(words, ["", "a", ""]);
+    }
+
+    #[test]
+    fn deserialize_tuple() {
+        let Joined(value): Joined<(i64, &str, u8), ':'> =
+            de::from_bytes(b"+10:ten:255\r\n").unwrap();
+        assert_eq!(value, (10, "ten", 255));
+    }
+
+    #[test]
+    fn deserialize_trailing_backslash() {
+        assert_matches!(
+            de::from_bytes::<Joined<Vec<String>>>(b"$3\r\na \\\r\n"),
+            Err(de::Error::Custom(_))
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let value = Joined::<_, ','>(Vec::from([
+            String::from(""),
+            String::from("a,b"),
+            String::from("\\"),
+            String::from("c"),
+        ]));
+
+        let encoded = ser::to_vec(&value).unwrap();
+        let decoded: Joined<Vec<String>, ','> = de::from_bytes(&encoded).unwrap();
+
+        assert_eq!(decoded, value);
+    }
+}
@@ -1,5 +1,17 @@
+mod content;
+mod flatten;
+
+use alloc::{borrow::Cow, collections::BTreeMap, string::String, vec, vec::Vec};
+use core::{fmt, marker::PhantomData};
+
+use paste::paste;
 use serde::{de, forward_to_deserialize_any, ser};
 
+use self::content::{Content, ContentDeserializer};
+use self::flatten::{EntryCounter, EntrySerializer};
+use super::case::forward_deserialize;
+use crate::value::{Value, ValueDeserializer};
+
 /// Adapter for key-value pairs in Redis.
 ///
 /// Commonly, Redis will express a set of key-value pairs as a flattened array
@@ -9,6 +21,8 @@ use serde::{de, forward_to_deserialize_any, ser};
 /// wrapped in `KeyValuePairs` will serialize to, and deserialize from, a
 /// flattened array of key-value pairs.
 ///
+/// Any map type can be used, such as `HashMap` or `BTreeMap`.
+///
 /// # Example
 ///
 /// ```
@@ -34,9 +48,106 @@ use serde::{de, forward_to_deserialize_any, ser};
 /// assert_eq!(key1, "value1");
 /// assert_eq!(key2, "value2");
 /// ```
+///
+/// # Duplicate fields
+///
+/// A Redis reply can contain the same key more than once. When deserializing
+/// a struct, `KeyValuePairs` itself checks for duplicate field names, so the
+/// behavior doesn't depend on how the struct's `Deserialize` is implemented.
+/// By default a duplicate field is an error. Wrap the struct in [`FirstWins`]
+/// or [`LastWins`] to instead keep the first or last value of each field.
+/// `LastWins` has to buffer the whole array before deserializing the struct,
+/// since any field may appear again later.
+///
+/// Maps handle duplicate keys themselves; `HashMap` and `BTreeMap` keep the
+/// last value.
+///
+/// # Binary keys
+///
+/// Redis keys and values are binary-safe, so a map's keys and values can be
+/// any byte type, including `&[u8]`, `Vec<u8>`, and their
+/// [`serde_bytes`](https://docs.rs/serde_bytes) equivalents. Borrowed keys
+/// and values are borrowed directly from the input.
+///
+/// ```
+/// use std::collections::{BTreeMap, HashMap};
+///
+/// use seredies::components::KeyValuePairs;
+/// use seredies::de::from_bytes;
+///
+/// let input = b"*4\r\n$2\r\n\xff\x00\r\n$1\r\na\r\n$1\r\nb\r\n$-1\r\n";
+///
+/// let KeyValuePairs(map): KeyValuePairs<BTreeMap<&[u8], Option<&[u8]>>> =
+///     from_bytes(input).unwrap();
+/// assert_eq!(map[&b"\xff\x00"[..]], Some(&b"a"[..]));
+///
+/// let KeyValuePairs(map): KeyValuePairs<HashMap<Vec<u8>, Option<Vec<u8>>>> =
+///     from_bytes(input).unwrap();
+/// assert_eq!(map[&b"b"[..]], None);
+/// ```
+///
+/// ```
+/// use serde::Deserialize;
+/// use seredies::components::{FirstWins, KeyValuePairs, LastWins};
+/// use seredies::de::from_bytes;
+///
+/// #[derive(Debug, PartialEq, Deserialize)]
+/// struct Data {
+///     key: i64,
+/// }
+///
+/// let input = b"*4\r\n$3\r\nkey\r\n:1\r\n$3\r\nkey\r\n:2\r\n";
+///
+/// assert!(from_bytes::<KeyValuePairs<Data>>(input).is_err());
+///
+/// let KeyValuePairs(FirstWins(data)): KeyValuePairs<FirstWins<Data>> =
+///     from_bytes(input).unwrap();
+/// assert_eq!(data, Data { key: 1 });
+///
+/// let KeyValuePairs(LastWins(data)): KeyValuePairs<LastWins<Data>> =
+///     from_bytes(input).unwrap();
+/// assert_eq!(data, Data { key: 2 });
+/// ```
 #[derive(Debug, Copy, Clone, Default)]
 pub struct KeyValuePairs<T>(pub T);
 
+/**
+A flattened array of key-value pairs with string keys and values of any RESP
+type. Some replies, like `MEMORY STATS` and `XINFO STREAM`, mix integers,
+strings, and nested arrays in a single reply; a `LooseMap` can hold all of
+them without a dedicated type. Nested arrays of
+pairs can be converted with [`Value::into_map`].
+
+# Example
+
+```
+use seredies::components::{KeyValuePairs, LooseMap};
+use seredies::de::from_bytes;
+use seredies::value::Value;
+
+let input = b"\
+    *6\r\n\
+        $14\r\npeak.allocated\r\n\
+        :1048576\r\n\
+        $14\r\nallocator.name\r\n\
+        +jemalloc\r\n\
+        $4\r\ndb.0\r\n\
+        *2\r\n\
+            $4\r\nkeys\r\n\
+            :3\r\n\
+";
+
+let KeyValuePairs(stats): LooseMap = from_bytes(input).unwrap();
+
+assert_eq!(stats["peak.allocated"].as_integer(), Some(1048576));
+assert_eq!(stats["allocator.name"].as_str(), Some("jemalloc"));
+
+let db = stats["db.0"].clone().into_map().unwrap();
+assert_eq!(db["keys"], Value::Integer(3));
+```
+*/
+pub type LooseMap = KeyValuePairs<BTreeMap<String, Value>>;
+
 impl<T> From<T> for KeyValuePairs<T> {
     fn from(value: T) -> Self {
         Self(value)
@@ -299,11 +410,277 @@ where
     where
         D: serde::Deserializer<'de>,
     {
-        T::deserialize(KeyValuePairsAdapter(deserializer)).map(Self)
+        T::deserialize(KeyValuePairsDeserializer::new(deserializer)).map(Self)
+    }
+}
+
+/// Wrapper type for a struct inside of a [`KeyValuePairs`] that keeps the
+/// first value of any field that appears more than once, ignoring the rest.
+/// Outside of a `KeyValuePairs`, it's (de)serialized exactly like the inner
+/// type. See [`KeyValuePairs`] for details.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct FirstWins<T>(pub T);
+
+/// Wrapper type for a struct inside of a [`KeyValuePairs`] that keeps the
+/// last value of any field that appears more than once, ignoring the rest.
+/// Outside of a `KeyValuePairs`, it's (de)serialized exactly like the inner
+/// type. See [`KeyValuePairs`] for details.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct LastWins<T>(pub T);
+
+pub(crate) const FIRST_WINS_TOKEN: &str = "$seredies::FirstWins";
+pub(crate) const LAST_WINS_TOKEN: &str = "$seredies::LastWins";
+pub(crate) const WITH_EXTRA_TOKEN: &str = "$seredies::WithExtra";
+
+macro_rules! duplicates_wrapper {
+    ($Wrapper:ident, $token:ident) => {
+        impl<T: ser::Serialize> ser::Serialize for $Wrapper<T> {
+            #[inline]
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: ser::Serializer,
+            {
+                self.0.serialize(serializer)
+            }
+        }
+
+        impl<'de, T> de::Deserialize<'de> for $Wrapper<T>
+        where
+            T: de::Deserialize<'de>,
+        {
+            #[inline]
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                struct Visitor<T>(PhantomData<T>);
+
+                impl<'de, T> de::Visitor<'de> for Visitor<T>
+                where
+                    T: de::Deserialize<'de>,
+                {
+                    type Value = $Wrapper<T>;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                        formatter.write_str("a struct")
+                    }
+
+                    #[inline]
+                    fn visit_newtype_struct<D>(
+                        self,
+                        deserializer: D,
+                    ) -> Result<Self::Value, D::Error>
+                    where
+                        D: de::Deserializer<'de>,
+                    {
+                        T::deserialize(deserializer).map($Wrapper)
+                    }
+                }
+
+                deserializer.deserialize_newtype_struct($token, Visitor(PhantomData))
+            }
+        }
+    };
+}
+
+duplicates_wrapper!(FirstWins, FIRST_WINS_TOKEN);
+duplicates_wrapper!(LastWins, LAST_WINS_TOKEN);
+
+/**
+Wrapper type for a struct inside of a [`KeyValuePairs`] that collects any
+fields the struct doesn't know about into a map, rather than ignoring them.
+
+Hashes tend to grow new fields over time, and a program that reads a hash,
+modifies it, and writes it back shouldn't lose the fields that were added
+by a newer version of itself. A `WithExtra` deserializes `value` from the
+struct's own fields, and `extra` from everything else. The values of unknown
+fields are deserialized as [`Value`]s, so they keep their exact RESP type.
+
+The map is a `BTreeMap<String, Value>` by default, but any map type can be
+used for `M`, including a `HashMap`, or a map with more specific value types.
+Unknown fields are inserted into it in order, so if the same unknown field
+appears more than once, it's up to the map which value is kept. Outside of a
+`KeyValuePairs`, a `WithExtra` is deserialized exactly like `T`, and `extra`
+is always empty.
+
+A `WithExtra` is serialized as a map of the struct's fields, followed by
+the entries in `extra`, so inside a `KeyValuePairs` it's written back as the
+same flattened array of pairs it was read from, though not necessarily in
+the same order.
+
+`WithExtra` can be combined with [`FirstWins`] and [`LastWins`], in either
+order, to also control how the struct's duplicate fields are handled.
+
+# Example
+
+```
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use seredies::components::{KeyValuePairs, WithExtra};
+use seredies::de::from_bytes;
+use seredies::ser::to_vec;
+use seredies::value::Value;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct User<'a> {
+    name: &'a str,
+    age: i64,
+}
+
+let input = b"\
+    *6\r\n\
+        $4\r\nname\r\n$5\r\nalice\r\n\
+        $5\r\ntheme\r\n$4\r\ndark\r\n\
+        $3\r\nage\r\n:30\r\n\
+";
+
+let KeyValuePairs(user): KeyValuePairs<WithExtra<User>> = from_bytes(input).unwrap();
+assert_eq!(user.value, User { name: "alice", age: 30 });
+assert_eq!(user.extra["theme"], Value::from("dark"));
+
+let KeyValuePairs(user): KeyValuePairs<WithExtra<User, HashMap<String, String>>> =
+    from_bytes(input).unwrap();
+assert_eq!(user.extra["theme"], "dark");
+
+assert_eq!(
+    to_vec(&KeyValuePairs(&user)).unwrap(),
+    b"*6\r\n\
+        $4\r\nname\r\n$5\r\nalice\r\n\
+        $3\r\nage\r\n:30\r\n\
+        $5\r\ntheme\r\n$4\r\ndark\r\n\
+    ",
+);
+```
+*/
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct WithExtra<T, M = BTreeMap<String, Value>> {
+    /// The struct, deserialized from its known fields.
+    pub value: T,
+
+    /// The fields that `T` doesn't know about.
+    pub extra: M,
+}
+
+impl<'de, T, M> de::Deserialize<'de> for WithExtra<T, M>
+where
+    T: de::Deserialize<'de>,
+    M: de::Deserialize<'de> + Default,
+{
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_newtype_struct(WITH_EXTRA_TOKEN, WithExtraVisitor(PhantomData))
+    }
+}
+
+impl<T, M> ser::Serialize for WithExtra<T, M>
+where
+    T: ser::Serialize,
+    M: ser::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        use ser::SerializeMap as _;
+
+        let len = self
+            .value
+            .serialize(EntryCounter::new())?
+            .checked_add(self.extra.serialize(EntryCounter::new())?)
+            .ok_or_else(|| ser::Error::custom("overflowed a usize"))?;
+
+        let mut map = serializer.serialize_map(Some(len))?;
+        self.value.serialize(EntrySerializer(&mut map))?;
+        self.extra.serialize(EntrySerializer(&mut map))?;
+        map.end()
+    }
+}
+
+struct WithExtraVisitor<T, M>(PhantomData<(T, M)>);
+
+impl<'de, T, M> de::Visitor<'de> for WithExtraVisitor<T, M>
+where
+    T: de::Deserialize<'de>,
+    M: de::Deserialize<'de> + Default,
+{
+    type Value = WithExtra<T, M>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a struct")
+    }
+
+    /// A deserializer that doesn't know about `WithExtra` has no way to
+    /// report unknown fields, so there aren't any.
+    #[inline]
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        T::deserialize(deserializer).map(|value| WithExtra {
+            value,
+            extra: M::default(),
+        })
+    }
+
+    /// The `KeyValuePairs` deserializer provides the struct, and then the
+    /// unknown fields it collected while deserializing it.
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let value = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+
+        let extra = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+        Ok(WithExtra { value, extra })
+    }
+}
+
+/// What to do with a struct field that appears more than once
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Duplicates {
+    Error,
+    FirstWins,
+    LastWins,
+}
+
+/// The unknown fields of a struct, collected for a [`WithExtra`].
+type Extra = Vec<(String, Value)>;
+
+/// Deserializer that deserializes maps and structs from the flattened
+/// key-value pairs of the `inner` deserializer.
+pub(crate) struct KeyValuePairsDeserializer<'a, D> {
+    inner: D,
+    duplicates: Duplicates,
+
+    /// If present, a struct's unknown fields are collected here, rather
+    /// than passed to its visitor.
+    extra: Option<&'a mut Extra>,
+}
+
+impl<D> KeyValuePairsDeserializer<'_, D> {
+    #[inline]
+    #[must_use]
+    pub(crate) fn new(inner: D) -> Self {
+        Self {
+            inner,
+            duplicates: Duplicates::Error,
+            extra: None,
+        }
     }
 }
 
-impl<'de, D> de::Deserializer<'de> for KeyValuePairsAdapter<D>
+impl<'de, D> de::Deserializer<'de> for KeyValuePairsDeserializer<'_, D>
 where
     D: de::Deserializer<'de>,
 {
@@ -319,25 +696,37 @@ where
     where
         V: de::Visitor<'de>,
     {
-        self.0.deserialize_any(KeyValuePairsAdapter(visitor))
+        self.inner.deserialize_any(KeyValuePairsAdapter(visitor))
     }
 
     fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        self.0.deserialize_seq(KeyValuePairsAdapter(visitor))
+        self.inner.deserialize_seq(KeyValuePairsAdapter(visitor))
     }
 
     fn deserialize_newtype_struct<V>(
         self,
-        _name: &'static str,
+        name: &'static str,
         visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_newtype_struct(self)
+        let duplicates = match name {
+            FIRST_WINS_TOKEN => Duplicates::FirstWins,
+            LAST_WINS_TOKEN => Duplicates::LastWins,
+            WITH_EXTRA_TOKEN => {
+                return visitor.visit_seq(WithExtraAccess {
+                    inner: Some((self.inner, self.duplicates)),
+                    extra: Some(Vec::new()),
+                })
+            }
+            _ => self.duplicates,
+        };
+
+        visitor.visit_newtype_struct(Self { duplicates, ..self })
     }
 
     fn deserialize_struct<V>(
@@ -349,13 +738,18 @@ where
     where
         V: de::Visitor<'de>,
     {
-        self.0.deserialize_tuple_struct(
+        self.inner.deserialize_tuple_struct(
             name,
             fields
                 .len()
                 .checked_mul(2)
                 .ok_or_else(|| de::Error::custom("overflowed a usize"))?,
-            KeyValuePairsAdapter(visitor),
+            StructVisitor {
+                visitor,
+                fields,
+                duplicates: self.duplicates,
+                extra: self.extra,
+            },
         )
     }
 }
@@ -366,8 +760,8 @@ where
 {
     type Value = V::Value;
 
-    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(formatter, "an flattened array of key-value pairs")?;
+    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(formatter, "a flattened array of key-value pairs")?;
         self.0.expecting(formatter)
     }
 
@@ -396,18 +790,1028 @@ where
     where
         K: de::DeserializeSeed<'de>,
     {
-        self.0.next_element_seed(seed)
+        self.0.next_element_seed(BinarySafe(seed))
     }
 
     fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
     where
         V: de::DeserializeSeed<'de>,
     {
-        self.0.next_element_seed(seed)?.ok_or_else(|| {
-            de::Error::custom(
-                "underlying array contained an odd number of \
-                elements while deserializing as key value pairs",
-            )
+        self.0
+            .next_element_seed(BinarySafe(seed))?
+            .ok_or_else(odd_length_error)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        self.0.size_hint().map(|len| len / 2)
+    }
+}
+
+/// Adapter for the keys and values of a map, which allows them to be
+/// deserialized from bulk strings even when they're byte sequences, like
+/// `Vec<u8>`, rather than bytes. As a seed, it wraps the deserializer in a
+/// `BinarySafe`, and as a visitor, it presents bytes as a sequence.
+struct BinarySafe<T>(T);
+
+impl<'de, S> de::DeserializeSeed<'de> for BinarySafe<S>
+where
+    S: de::DeserializeSeed<'de>,
+{
+    type Value = S::Value;
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        self.0.deserialize(BinarySafeDeserializer {
+            inner: deserializer,
+        })
+    }
+}
+
+/// Deserializer that forwards everything to the underlying deserializer,
+/// except that sequences also accept bytes.
+struct BinarySafeDeserializer<D> {
+    inner: D,
+}
+
+impl<'de, D> de::Deserializer<'de> for BinarySafeDeserializer<D>
+where
+    D: de::Deserializer<'de>,
+{
+    type Error = D::Error;
+
+    forward_deserialize! {
+        any ignored_any bool
+        i8 i16 i32 i64 i128
+        u8 u16 u32 u64 u128
+        f32 f64
+        char str string bytes byte_buf identifier
+        unit map
+
+        unit_struct(name: &'static str)
+        newtype_struct(name: &'static str)
+        tuple(len: usize)
+        tuple_struct(name: &'static str, len: usize)
+        struct(name: &'static str, fields: &'static[&'static str])
+        enum(name: &'static str, variants: &'static[&'static str])
+    }
+
+    #[inline]
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.inner.deserialize_seq(BinarySafe(visitor))
+    }
+
+    // So that `Option<Vec<u8>>` works too
+    #[inline]
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.inner.deserialize_option(BinarySafe(visitor))
+    }
+
+    #[inline]
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+}
+
+impl<'de, V> de::Visitor<'de> for BinarySafe<V>
+where
+    V: de::Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.expecting(formatter)
+    }
+
+    #[inline]
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        self.0.visit_seq(seq)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        let mut seq = de::value::SeqDeserializer::new(v.iter().copied());
+        let value = self.0.visit_seq(&mut seq)?;
+        seq.end()?;
+        Ok(value)
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        let mut seq = de::value::SeqDeserializer::new(v.into_iter());
+        let value = self.0.visit_seq(&mut seq)?;
+        seq.end()?;
+        Ok(value)
+    }
+
+    #[inline]
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        self.0.visit_none()
+    }
+
+    #[inline]
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        self.0.visit_unit()
+    }
+
+    #[inline]
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        self.0.visit_some(BinarySafeDeserializer {
+            inner: deserializer,
+        })
+    }
+}
+
+fn odd_length_error<E: de::Error>() -> E {
+    de::Error::custom(
+        "underlying array contained an odd number of \
+        elements while deserializing as key value pairs",
+    )
+}
+
+/// Sequence access for a [`WithExtra`], which yields the struct and then the
+/// unknown fields that were collected while deserializing it.
+struct WithExtraAccess<D> {
+    inner: Option<(D, Duplicates)>,
+    extra: Option<Extra>,
+}
+
+impl<'de, D> de::SeqAccess<'de> for WithExtraAccess<D>
+where
+    D: de::Deserializer<'de>,
+{
+    type Error = D::Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        if let Some((inner, duplicates)) = self.inner.take() {
+            return seed
+                .deserialize(KeyValuePairsDeserializer {
+                    inner,
+                    duplicates,
+                    extra: self.extra.as_mut(),
+                })
+                .map(Some);
+        }
+
+        let Some(extra) = self.extra.take() else {
+            return Ok(None);
+        };
+
+        seed.deserialize(de::value::MapDeserializer::new(
+            extra
+                .into_iter()
+                .map(|(key, value)| (key, ValueDeserializer::new(value))),
+        ))
+        .map(Some)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        Some(usize::from(self.inner.is_some()) + usize::from(self.extra.is_some()))
+    }
+}
+
+/// Visitor for a struct as key-value pairs, which (unlike maps) handles
+/// duplicate fields according to a `Duplicates` policy.
+struct StructVisitor<'a, V> {
+    visitor: V,
+    fields: &'static [&'static str],
+    duplicates: Duplicates,
+    extra: Option<&'a mut Extra>,
+}
+
+impl<'de, V> de::Visitor<'de> for StructVisitor<'_, V>
+where
+    V: de::Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a flattened array of key-value pairs")?;
+        self.visitor.expecting(formatter)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        match self.duplicates {
+            Duplicates::Error | Duplicates::FirstWins => self.visitor.visit_map(StructFields {
+                seq,
+                fields: self.fields,
+                seen: vec![false; self.fields.len()],
+                first_wins: self.duplicates == Duplicates::FirstWins,
+                extra: self.extra,
+            }),
+            Duplicates::LastWins => {
+                // We can't know which value of a field is the last one until
+                // we've seen the whole array, so buffer all of the pairs.
+                // `positions` tracks where each known field is in `pairs`,
+                // so that a later value can replace it.
+                let mut pairs: Vec<(FieldName<'de>, Content<'de>)> =
+                    Vec::with_capacity(seq.size_hint().unwrap_or(0) / 2);
+                let mut positions = vec![None; self.fields.len()];
+                let mut extra = self.extra;
+
+                while let Some(key) = seq.next_element::<FieldName<'de>>()? {
+                    let index = key.index(self.fields);
+
+                    if let (None, Some(extra)) = (index, extra.as_deref_mut()) {
+                        let value = seq.next_element()?.ok_or_else(odd_length_error)?;
+                        extra.push((key.into_string()?, value));
+                        continue;
+                    }
+
+                    let value = seq.next_element()?.ok_or_else(odd_length_error)?;
+
+                    match index {
+                        Some(index) => match positions[index] {
+                            Some(position) => pairs[position] = (key, value),
+                            None => {
+                                positions[index] = Some(pairs.len());
+                                pairs.push((key, value));
+                            }
+                        },
+                        None => pairs.push((key, value)),
+                    }
+                }
+
+                let mut map = de::value::MapDeserializer::new(pairs.into_iter());
+                let value = self.visitor.visit_map(&mut map)?;
+                map.end()?;
+                Ok(value)
+            }
+        }
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::MapAccess<'de>,
+    {
+        self.visitor.visit_map(map)
+    }
+}
+
+/// A key from a struct's key-value pairs, which is buffered so that it can
+/// be checked against the struct's field names.
+struct FieldName<'de>(Cow<'de, [u8]>);
+
+impl FieldName<'_> {
+    #[inline]
+    #[must_use]
+    fn index(&self, fields: &[&str]) -> Option<usize> {
+        fields.iter().position(|field| field.as_bytes() == &*self.0)
+    }
+
+    /// Convert an unknown field name into a key for a [`WithExtra`] map.
+    fn into_string<E: de::Error>(self) -> Result<String, E> {
+        String::from_utf8(self.0.into_owned()).map_err(|err| {
+            E::invalid_value(de::Unexpected::Bytes(err.as_bytes()), &"a UTF-8 field name")
         })
     }
 }
+
+impl<'de> de::Deserialize<'de> for FieldName<'de> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = FieldName<'de>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a field name")
+            }
+
+            #[inline]
+            fn visit_borrowed_bytes<E: de::Error>(self, v: &'de [u8]) -> Result<Self::Value, E> {
+                Ok(FieldName(Cow::Borrowed(v)))
+            }
+
+            #[inline]
+            fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                Ok(FieldName(Cow::Owned(v.to_vec())))
+            }
+
+            #[inline]
+            fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+                Ok(FieldName(Cow::Owned(v)))
+            }
+
+            #[inline]
+            fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+                self.visit_borrowed_bytes(v.as_bytes())
+            }
+
+            #[inline]
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                self.visit_bytes(v.as_bytes())
+            }
+
+            #[inline]
+            fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+                self.visit_byte_buf(v.into_bytes())
+            }
+        }
+
+        deserializer.deserialize_bytes(Visitor)
+    }
+}
+
+impl<'de, E: de::Error> de::IntoDeserializer<'de, E> for FieldName<'de> {
+    type Deserializer = ContentDeserializer<'de, E>;
+
+    #[inline]
+    fn into_deserializer(self) -> Self::Deserializer {
+        Content::Bytes(self.0).into_deserializer()
+    }
+}
+
+/// Map access for a struct as key-value pairs, which either rejects
+/// duplicate fields or skips all but the first of them.
+struct StructFields<'a, S> {
+    seq: S,
+    fields: &'static [&'static str],
+    seen: Vec<bool>,
+    first_wins: bool,
+    extra: Option<&'a mut Extra>,
+}
+
+impl<'de, S> de::MapAccess<'de> for StructFields<'_, S>
+where
+    S: de::SeqAccess<'de>,
+{
+    type Error = S::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        loop {
+            let Some(key) = self.seq.next_element::<FieldName<'de>>()? else {
+                return Ok(None);
+            };
+
+            if let Some(index) = key.index(self.fields) {
+                if !self.seen[index] {
+                    self.seen[index] = true;
+                } else if self.first_wins {
+                    self.seq
+                        .next_element::<de::IgnoredAny>()?
+                        .ok_or_else(odd_length_error)?;
+                    continue;
+                } else {
+                    return Err(de::Error::duplicate_field(self.fields[index]));
+                }
+            } else if let Some(extra) = self.extra.as_deref_mut() {
+                let value = self.seq.next_element()?.ok_or_else(odd_length_error)?;
+                extra.push((key.into_string()?, value));
+                continue;
+            }
+
+            return seed
+                .deserialize(de::IntoDeserializer::<S::Error>::into_deserializer(key))
+                .map(Some);
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        self.seq
+            .next_element_seed(seed)?
+            .ok_or_else(odd_length_error)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        self.seq.size_hint().map(|len| len / 2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::{de::from_bytes, ser::to_vec};
+
+    fn round_trip<T>(value: T, expected: &[u8])
+    where
+        T: Serialize + for<'de> Deserialize<'de> + PartialEq + std::fmt::Debug,
+    {
+        let value = KeyValuePairs(value);
+        let encoded = to_vec(&value).expect("failed to serialize");
+        assert_eq!(encoded, expected);
+
+        let decoded: KeyValuePairs<T> = from_bytes(&encoded).expect("failed to deserialize");
+        assert_eq!(decoded.0, value.0);
+    }
+
+    #[test]
+    fn empty_map() {
+        round_trip(BTreeMap::<String, String>::new(), b"*0\r\n");
+    }
+
+    #[test]
+    fn map_of_nulls() {
+        round_trip(
+            BTreeMap::<String, Option<String>>::from([
+                ("a".to_owned(), None),
+                ("b".to_owned(), None),
+            ]),
+            b"*4\r\n$1\r\na\r\n$-1\r\n$1\r\nb\r\n$-1\r\n",
+        );
+    }
+
+    #[test]
+    fn map_of_empty_values() {
+        round_trip(
+            BTreeMap::from([
+                (String::new(), Vec::<String>::new()),
+                ("b".to_owned(), Vec::from([String::new()])),
+            ]),
+            b"*4\r\n$0\r\n\r\n*0\r\n$1\r\nb\r\n*1\r\n$0\r\n\r\n",
+        );
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Data {
+        key: String,
+        value: Option<String>,
+    }
+
+    #[test]
+    fn struct_with_empty_fields() {
+        round_trip(
+            Data {
+                key: String::new(),
+                value: None,
+            },
+            b"*4\r\n$3\r\nkey\r\n$0\r\n\r\n$5\r\nvalue\r\n$-1\r\n",
+        );
+    }
+
+    #[test]
+    fn loose_map() {
+        let input = b"\
+            *8\r\n\
+                $10\r\npeak.alloc\r\n\
+                :100\r\n\
+                $14\r\nallocator.name\r\n\
+                +jemalloc\r\n\
+                $7\r\nmissing\r\n\
+                $-1\r\n\
+                $4\r\ndb.0\r\n\
+                *2\r\n\
+                    $8\r\noverhead\r\n\
+                    :5\r\n\
+        ";
+
+        let KeyValuePairs(stats): LooseMap = from_bytes(input).expect("failed to deserialize");
+
+        assert_eq!(
+            stats,
+            BTreeMap::from([
+                ("peak.alloc".to_owned(), Value::Integer(100)),
+                (
+                    "allocator.name".to_owned(),
+                    Value::SimpleString("jemalloc".to_owned())
+                ),
+                ("missing".to_owned(), Value::Null),
+                (
+                    "db.0".to_owned(),
+                    Value::from_iter([Value::from("overhead"), Value::Integer(5)])
+                ),
+            ])
+        );
+    }
+
+    mod binary_keys {
+        use std::collections::HashMap;
+
+        use serde_bytes::Bytes;
+
+        use super::*;
+        use crate::de::Error;
+
+        const INPUT: &[u8] = b"*6\r\n\
+            $2\r\n\xff\x00\r\n$1\r\na\r\n\
+            $1\r\nb\r\n$-1\r\n\
+            $4\r\na\r\nb\r\n$4\r\n\r\n\r\n\r\n\
+        ";
+
+        #[test]
+        fn borrowed() {
+            let KeyValuePairs(map): KeyValuePairs<BTreeMap<&[u8], Option<&[u8]>>> =
+                from_bytes(INPUT).unwrap();
+
+            assert_eq!(
+                map,
+                BTreeMap::from([
+                    (&b"\xff\x00"[..], Some(&b"a"[..])),
+                    (b"b", None),
+                    (b"a\r\nb", Some(b"\r\n\r\n")),
+                ])
+            );
+        }
+
+        #[test]
+        fn owned() {
+            let KeyValuePairs(map): KeyValuePairs<HashMap<Vec<u8>, Option<Vec<u8>>>> =
+                from_bytes(INPUT).unwrap();
+
+            assert_eq!(
+                map,
+                HashMap::from([
+                    (b"\xff\x00".to_vec(), Some(b"a".to_vec())),
+                    (b"b".to_vec(), None),
+                    (b"a\r\nb".to_vec(), Some(b"\r\n\r\n".to_vec())),
+                ])
+            );
+        }
+
+        #[test]
+        fn serde_bytes() {
+            let KeyValuePairs(map): KeyValuePairs<BTreeMap<&Bytes, Option<&Bytes>>> =
+                from_bytes(INPUT).unwrap();
+
+            assert_eq!(map[Bytes::new(b"\xff\x00")], Some(Bytes::new(b"a")));
+            assert_eq!(map.len(), 3);
+        }
+
+        #[test]
+        fn arrays_are_still_arrays() {
+            let KeyValuePairs(map): KeyValuePairs<BTreeMap<&str, Vec<u8>>> =
+                from_bytes(b"*2\r\n$1\r\na\r\n*2\r\n:1\r\n:2\r\n").unwrap();
+
+            assert_eq!(map, BTreeMap::from([("a", Vec::from([1, 2]))]));
+        }
+
+        #[test]
+        fn non_utf8_string_key() {
+            assert!(matches!(
+                from_bytes::<KeyValuePairs<BTreeMap<String, Vec<u8>>>>(INPUT),
+                Err(Error::Custom(_))
+            ));
+        }
+    }
+
+    mod enums {
+        use super::*;
+
+        #[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Debug)]
+        #[serde(rename_all = "lowercase")]
+        enum KeyType {
+            String,
+            Zset,
+        }
+
+        #[derive(Serialize, Deserialize, PartialEq, Debug)]
+        struct Key {
+            name: String,
+            r#type: KeyType,
+            encoding: Option<KeyType>,
+        }
+
+        #[test]
+        fn struct_field() {
+            round_trip(
+                Key {
+                    name: "leaderboard".to_owned(),
+                    r#type: KeyType::Zset,
+                    encoding: None,
+                },
+                b"*6\r\n\
+                    $4\r\nname\r\n$11\r\nleaderboard\r\n\
+                    $4\r\ntype\r\n$4\r\nzset\r\n\
+                    $8\r\nencoding\r\n$-1\r\n",
+            );
+        }
+
+        #[test]
+        fn map_values() {
+            round_trip(
+                BTreeMap::from([
+                    ("a".to_owned(), KeyType::String),
+                    ("b".to_owned(), KeyType::Zset),
+                ]),
+                b"*4\r\n$1\r\na\r\n$6\r\nstring\r\n$1\r\nb\r\n$4\r\nzset\r\n",
+            );
+        }
+
+        #[test]
+        fn simple_string_value() {
+            let KeyValuePairs(map): KeyValuePairs<BTreeMap<&str, KeyType>> =
+                from_bytes(b"*2\r\n$4\r\ntype\r\n+zset\r\n").unwrap();
+
+            assert_eq!(map, BTreeMap::from([("type", KeyType::Zset)]));
+        }
+
+        #[test]
+        fn unknown_variant() {
+            assert!(from_bytes::<KeyValuePairs<BTreeMap<&str, KeyType>>>(
+                b"*2\r\n$4\r\ntype\r\n$4\r\nlist\r\n"
+            )
+            .is_err());
+        }
+
+        #[derive(Deserialize, PartialEq, Debug)]
+        #[serde(tag = "type", rename_all = "lowercase")]
+        enum Entry {
+            String { length: i64 },
+            Zset { members: i64 },
+        }
+
+        #[test]
+        fn internally_tagged() {
+            let KeyValuePairs(entry): KeyValuePairs<Entry> =
+                from_bytes(b"*4\r\n$7\r\nmembers\r\n:3\r\n$4\r\ntype\r\n$4\r\nzset\r\n").unwrap();
+
+            assert_eq!(entry, Entry::Zset { members: 3 });
+        }
+    }
+
+    mod duplicates {
+        use cool_asserts::assert_matches;
+
+        use super::*;
+        use crate::de::Error;
+
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct Config<'a> {
+            name: &'a str,
+            size: i64,
+            tags: Option<Vec<&'a str>>,
+        }
+
+        /// `name` appears three times, `size` twice, and there's an unknown
+        /// field between them.
+        const INPUT: &[u8] = b"*12\r\n\
+            $4\r\nname\r\n$5\r\nfirst\r\n\
+            $4\r\nsize\r\n:1\r\n\
+            $4\r\nname\r\n$6\r\nsecond\r\n\
+            $5\r\nextra\r\n*1\r\n:0\r\n\
+            $4\r\nsize\r\n:2\r\n\
+            $4\r\nname\r\n$5\r\nthird\r\n\
+        ";
+
+        #[test]
+        fn error_by_default() {
+            assert_matches!(
+                from_bytes::<KeyValuePairs<Config>>(INPUT),
+                Err(Error::Custom(msg)) => assert!(msg.contains("duplicate field `name`"))
+            );
+        }
+
+        /// A hand-written visitor that would silently accept duplicates is
+        /// still protected by the adapter
+        #[test]
+        fn error_with_lenient_visitor() {
+            struct Names(Vec<String>);
+
+            impl<'de> Deserialize<'de> for Names {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: de::Deserializer<'de>,
+                {
+                    struct Visitor;
+
+                    impl<'de> de::Visitor<'de> for Visitor {
+                        type Value = Names;
+
+                        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                            f.write_str("names")
+                        }
+
+                        fn visit_map<A>(self, mut map: A) -> Result<Names, A::Error>
+                        where
+                            A: de::MapAccess<'de>,
+                        {
+                            let mut names = Vec::new();
+
+                            while let Some((_, value)) = map.next_entry::<String, String>()? {
+                                names.push(value);
+                            }
+
+                            Ok(Names(names))
+                        }
+                    }
+
+                    deserializer.deserialize_struct("Names", &["name"], Visitor)
+                }
+            }
+
+            let input = b"*4\r\n$4\r\nname\r\n$1\r\na\r\n$4\r\nname\r\n$1\r\nb\r\n";
+            assert!(from_bytes::<KeyValuePairs<Names>>(input).is_err());
+
+            let KeyValuePairs(FirstWins(Names(names))) = from_bytes(input).unwrap();
+            assert_eq!(names, ["a"]);
+
+            let KeyValuePairs(LastWins(Names(names))) = from_bytes(input).unwrap();
+            assert_eq!(names, ["b"]);
+        }
+
+        #[test]
+        fn first_wins() {
+            let KeyValuePairs(FirstWins(config)): KeyValuePairs<FirstWins<Config>> =
+                from_bytes(INPUT).unwrap();
+
+            assert_eq!(
+                config,
+                Config {
+                    name: "first",
+                    size: 1,
+                    tags: None,
+                }
+            );
+        }
+
+        #[test]
+        fn last_wins() {
+            let KeyValuePairs(LastWins(config)): KeyValuePairs<LastWins<Config>> =
+                from_bytes(INPUT).unwrap();
+
+            assert_eq!(
+                config,
+                Config {
+                    name: "third",
+                    size: 2,
+                    tags: None,
+                }
+            );
+        }
+
+        #[test]
+        fn last_wins_nested_values() {
+            let input = b"*6\r\n\
+                $4\r\ntags\r\n*1\r\n$1\r\na\r\n\
+                $4\r\nname\r\n$1\r\nx\r\n\
+                $4\r\nsize\r\n:5\r\n\
+            ";
+
+            let KeyValuePairs(LastWins(config)): KeyValuePairs<LastWins<Config>> =
+                from_bytes(input).unwrap();
+
+            assert_eq!(
+                config,
+                Config {
+                    name: "x",
+                    size: 5,
+                    tags: Some(Vec::from(["a"])),
+                }
+            );
+        }
+
+        #[test]
+        fn odd_length() {
+            let input = b"*3\r\n$4\r\nname\r\n$1\r\na\r\n$4\r\nname\r\n";
+
+            assert_matches!(
+                from_bytes::<KeyValuePairs<FirstWins<Config>>>(input),
+                Err(Error::Custom(_))
+            );
+            assert_matches!(
+                from_bytes::<KeyValuePairs<LastWins<Config>>>(input),
+                Err(Error::Custom(_))
+            );
+        }
+
+        #[test]
+        fn maps_are_unaffected() {
+            let KeyValuePairs(FirstWins(map)): KeyValuePairs<FirstWins<BTreeMap<String, i64>>> =
+                from_bytes(b"*4\r\n$1\r\na\r\n:1\r\n$1\r\na\r\n:2\r\n").unwrap();
+
+            assert_eq!(map, BTreeMap::from([("a".to_owned(), 2)]));
+        }
+
+        #[test]
+        fn transparent_outside_key_value_pairs() {
+            let FirstWins(value): FirstWins<Vec<i64>> = from_bytes(b"*1\r\n:1\r\n").unwrap();
+            assert_eq!(value, [1]);
+
+            assert_eq!(to_vec(&LastWins(5)).unwrap(), b":5\r\n");
+        }
+    }
+
+    mod with_extra {
+        use std::collections::HashMap;
+
+        use cool_asserts::assert_matches;
+
+        use super::*;
+        use crate::{
+            de::{from_bytes_with_config, Error},
+            Config,
+        };
+
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct User<'a> {
+            name: &'a str,
+            age: i64,
+        }
+
+        const INPUT: &[u8] = b"*10\r\n\
+            $5\r\ntheme\r\n+dark\r\n\
+            $4\r\nname\r\n$5\r\nalice\r\n\
+            $6\r\nscores\r\n*2\r\n:1\r\n$-1\r\n\
+            $3\r\nage\r\n:30\r\n\
+            $6\r\nbroken\r\n-ERR oops\r\n\
+        ";
+
+        fn alice() -> User<'static> {
+            User {
+                name: "alice",
+                age: 30,
+            }
+        }
+
+        #[test]
+        fn collects_unknown_fields() {
+            let KeyValuePairs(user): KeyValuePairs<WithExtra<User>> = from_bytes(INPUT).unwrap();
+
+            assert_eq!(user.value, alice());
+            assert_eq!(
+                user.extra,
+                BTreeMap::from([
+                    ("theme".to_owned(), Value::SimpleString("dark".to_owned())),
+                    (
+                        "scores".to_owned(),
+                        Value::from_iter([Value::Integer(1), Value::Null])
+                    ),
+                    ("broken".to_owned(), Value::Error("ERR oops".to_owned())),
+                ])
+            );
+        }
+
+        #[test]
+        fn no_unknown_fields() {
+            let input = b"*4\r\n$4\r\nname\r\n$5\r\nalice\r\n$3\r\nage\r\n:30\r\n";
+            let KeyValuePairs(user): KeyValuePairs<WithExtra<User>> = from_bytes(input).unwrap();
+
+            assert_eq!(user.value, alice());
+            assert!(user.extra.is_empty());
+        }
+
+        #[test]
+        fn custom_map() {
+            let input = b"*8\r\n\
+                $4\r\nname\r\n$5\r\nalice\r\n\
+                $5\r\ntheme\r\n$4\r\ndark\r\n\
+                $3\r\nage\r\n:30\r\n\
+                $4\r\nnote\r\n$-1\r\n\
+            ";
+
+            let KeyValuePairs(user): KeyValuePairs<
+                WithExtra<User, HashMap<String, Option<String>>>,
+            > = from_bytes(input).unwrap();
+
+            assert_eq!(user.value, alice());
+            assert_eq!(
+                user.extra,
+                HashMap::from([
+                    ("theme".to_owned(), Some("dark".to_owned())),
+                    ("note".to_owned(), None),
+                ])
+            );
+        }
+
+        #[test]
+        fn custom_map_wrong_type() {
+            assert_matches!(
+                from_bytes::<KeyValuePairs<WithExtra<User, HashMap<String, String>>>>(INPUT),
+                Err(Error::Custom(_))
+            );
+        }
+
+        #[test]
+        fn duplicates() {
+            let input = b"*8\r\n\
+                $4\r\nname\r\n$5\r\nalice\r\n\
+                $3\r\nage\r\n:30\r\n\
+                $4\r\nname\r\n$3\r\nbob\r\n\
+                $5\r\ntheme\r\n$4\r\ndark\r\n\
+            ";
+
+            assert!(from_bytes::<KeyValuePairs<WithExtra<User>>>(input).is_err());
+
+            let KeyValuePairs(FirstWins(user)): KeyValuePairs<FirstWins<WithExtra<User>>> =
+                from_bytes(input).unwrap();
+            assert_eq!(user.value.name, "alice");
+            assert_eq!(user.extra["theme"], Value::from("dark"));
+
+            let KeyValuePairs(user): KeyValuePairs<WithExtra<LastWins<User>>> =
+                from_bytes(input).unwrap();
+            assert_eq!(user.value.0.name, "bob");
+            assert_eq!(user.extra["theme"], Value::from("dark"));
+        }
+
+        #[test]
+        fn map() {
+            let KeyValuePairs(map): KeyValuePairs<WithExtra<BTreeMap<&str, i64>>> =
+                from_bytes(b"*2\r\n$1\r\na\r\n:1\r\n").unwrap();
+
+            assert_eq!(map.value, BTreeMap::from([("a", 1)]));
+            assert!(map.extra.is_empty());
+        }
+
+        #[test]
+        fn non_utf8_field() {
+            let input = b"*6\r\n\
+                $4\r\nname\r\n$5\r\nalice\r\n\
+                $1\r\n\xff\r\n:1\r\n\
+                $3\r\nage\r\n:30\r\n\
+            ";
+
+            assert_matches!(
+                from_bytes::<KeyValuePairs<WithExtra<User>>>(input),
+                Err(Error::Custom(_))
+            );
+
+            // Without `WithExtra`, the field is ignored as usual
+            let KeyValuePairs(user): KeyValuePairs<User> = from_bytes(input).unwrap();
+            assert_eq!(user, alice());
+        }
+
+        #[test]
+        fn flattened_maps() {
+            let user: WithExtra<User> =
+                from_bytes_with_config(INPUT, Config::new().with_flattened_maps(true)).unwrap();
+
+            assert_eq!(user.value, alice());
+            assert_eq!(user.extra.len(), 3);
+        }
+
+        #[test]
+        fn round_trip() {
+            let KeyValuePairs(user): KeyValuePairs<WithExtra<User>> = from_bytes(INPUT).unwrap();
+
+            assert_eq!(
+                to_vec(&KeyValuePairs(&user)).unwrap(),
+                b"*10\r\n\
+                    $4\r\nname\r\n$5\r\nalice\r\n\
+                    $3\r\nage\r\n:30\r\n\
+                    $6\r\nbroken\r\n-ERR oops\r\n\
+                    $6\r\nscores\r\n*2\r\n:1\r\n$-1\r\n\
+                    $5\r\ntheme\r\n+dark\r\n\
+                "
+            );
+        }
+
+        #[test]
+        fn serialize_with_first_wins() {
+            let user = FirstWins(WithExtra {
+                value: alice(),
+                extra: BTreeMap::from([("theme", "dark")]),
+            });
+
+            assert_eq!(
+                to_vec(&KeyValuePairs(&user)).unwrap(),
+                b"*6\r\n\
+                    $4\r\nname\r\n$5\r\nalice\r\n\
+                    $3\r\nage\r\n:30\r\n\
+                    $5\r\ntheme\r\n$4\r\ndark\r\n\
+                "
+            );
+        }
+
+        #[test]
+        fn serialize_non_struct() {
+            let value = WithExtra {
+                value: [1, 2],
+                extra: BTreeMap::<String, i64>::new(),
+            };
+
+            assert!(to_vec(&KeyValuePairs(&value)).is_err());
+        }
+
+        #[test]
+        fn transparent_outside_key_value_pairs() {
+            let value: WithExtra<Vec<i64>> = from_bytes(b"*1\r\n:1\r\n").unwrap();
+
+            assert_eq!(value.value, [1]);
+            assert!(value.extra.is_empty());
+        }
+    }
+}
@@ -0,0 +1,289 @@
+//! A buffer for arbitrary self-describing values, so that they can be
+//! deserialized later. This is used by [`LastWins`][super::LastWins], which
+//! can't know which occurrence of a field is the last one until it has read
+//! the whole array.
+
+use alloc::{
+    borrow::{Cow, ToOwned},
+    string::String,
+    vec::Vec,
+};
+use core::{fmt, marker::PhantomData};
+
+use serde::{de, forward_to_deserialize_any};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Content<'de> {
+    Bool(bool),
+    I64(i64),
+    U64(u64),
+    F64(f64),
+    Str(Cow<'de, str>),
+    Bytes(Cow<'de, [u8]>),
+    Unit,
+    Seq(Vec<Content<'de>>),
+    Map(Vec<(Content<'de>, Content<'de>)>),
+}
+
+impl<'de> de::Deserialize<'de> for Content<'de> {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(ContentVisitor)
+    }
+}
+
+struct ContentVisitor;
+
+impl<'de> de::Visitor<'de> for ContentVisitor {
+    type Value = Content<'de>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("any value")
+    }
+
+    #[inline]
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(Content::Bool(v))
+    }
+
+    #[inline]
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(Content::I64(v))
+    }
+
+    #[inline]
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(Content::U64(v))
+    }
+
+    #[inline]
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Self::Value, E> {
+        Ok(Content::F64(v))
+    }
+
+    #[inline]
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(Content::Str(Cow::Owned(v.to_owned())))
+    }
+
+    #[inline]
+    fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+        Ok(Content::Str(Cow::Borrowed(v)))
+    }
+
+    #[inline]
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(Content::Str(Cow::Owned(v)))
+    }
+
+    #[inline]
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(Content::Bytes(Cow::Owned(v.to_vec())))
+    }
+
+    #[inline]
+    fn visit_borrowed_bytes<E: de::Error>(self, v: &'de [u8]) -> Result<Self::Value, E> {
+        Ok(Content::Bytes(Cow::Borrowed(v)))
+    }
+
+    #[inline]
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(Content::Bytes(Cow::Owned(v)))
+    }
+
+    #[inline]
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(Content::Unit)
+    }
+
+    #[inline]
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(Content::Unit)
+    }
+
+    #[inline]
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        de::Deserialize::deserialize(deserializer)
+    }
+
+    #[inline]
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        de::Deserialize::deserialize(deserializer)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let mut elements = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+
+        while let Some(element) = seq.next_element()? {
+            elements.push(element);
+        }
+
+        Ok(Content::Seq(elements))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::MapAccess<'de>,
+    {
+        let mut entries = Vec::with_capacity(map.size_hint().unwrap_or(0));
+
+        while let Some(entry) = map.next_entry()? {
+            entries.push(entry);
+        }
+
+        Ok(Content::Map(entries))
+    }
+}
+
+impl<'de, E: de::Error> de::IntoDeserializer<'de, E> for Content<'de> {
+    type Deserializer = ContentDeserializer<'de, E>;
+
+    #[inline]
+    fn into_deserializer(self) -> Self::Deserializer {
+        ContentDeserializer {
+            content: self,
+            error: PhantomData,
+        }
+    }
+}
+
+/// Deserializer that replays buffered [`Content`]. It mirrors the seredies
+/// [`Deserializer`][crate::de::Deserializer] where it differs from plain
+/// `deserialize_any`: nulls are `None`, and `0` and `1` are booleans.
+pub struct ContentDeserializer<'de, E> {
+    content: Content<'de>,
+    error: PhantomData<E>,
+}
+
+impl<'de, E: de::Error> de::Deserializer<'de> for ContentDeserializer<'de, E> {
+    type Error = E;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.content {
+            Content::Bool(v) => visitor.visit_bool(v),
+            Content::I64(v) => visitor.visit_i64(v),
+            Content::U64(v) => visitor.visit_u64(v),
+            Content::F64(v) => visitor.visit_f64(v),
+            Content::Str(Cow::Borrowed(v)) => visitor.visit_borrowed_str(v),
+            Content::Str(Cow::Owned(v)) => visitor.visit_string(v),
+            Content::Bytes(Cow::Borrowed(v)) => visitor.visit_borrowed_bytes(v),
+            Content::Bytes(Cow::Owned(v)) => visitor.visit_byte_buf(v),
+            Content::Unit => visitor.visit_unit(),
+            Content::Seq(elements) => {
+                let mut seq = de::value::SeqDeserializer::new(elements.into_iter());
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+                Ok(value)
+            }
+            Content::Map(entries) => {
+                let mut map = de::value::MapDeserializer::new(entries.into_iter());
+                let value = visitor.visit_map(&mut map)?;
+                map.end()?;
+                Ok(value)
+            }
+        }
+    }
+
+    #[inline]
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.content {
+            Content::I64(0) | Content::U64(0) => visitor.visit_bool(false),
+            Content::I64(1) | Content::U64(1) => visitor.visit_bool(true),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    #[inline]
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.content {
+            Content::Unit => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    #[inline]
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        enum identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::de::from_bytes;
+
+    fn replay<'de, T: Deserialize<'de>>(content: Content<'de>) -> Result<T, de::value::Error> {
+        T::deserialize(de::IntoDeserializer::into_deserializer(content))
+    }
+
+    #[test]
+    fn buffer_resp_values() {
+        let content: Content =
+            from_bytes(b"*4\r\n$5\r\nhello\r\n:-3\r\n$-1\r\n*1\r\n+OK\r\n").unwrap();
+
+        assert_eq!(
+            content,
+            Content::Seq(Vec::from([
+                Content::Bytes(Cow::Borrowed(b"hello")),
+                Content::I64(-3),
+                Content::Unit,
+                Content::Seq(Vec::from([Content::Bytes(Cow::Borrowed(b"OK"))])),
+            ]))
+        );
+    }
+
+    #[test]
+    fn replay_values() {
+        let content: Content = from_bytes(b"*4\r\n$5\r\nhello\r\n:1\r\n$-1\r\n:4\r\n").unwrap();
+
+        let (s, flag, missing, present): (&str, bool, Option<i64>, Option<i64>) =
+            replay(content).unwrap();
+
+        assert_eq!(s, "hello");
+        assert!(flag);
+        assert_eq!(missing, None);
+        assert_eq!(present, Some(4));
+    }
+
+    #[test]
+    fn replay_length_mismatch() {
+        let content: Content = from_bytes(b"*2\r\n:1\r\n:2\r\n").unwrap();
+        assert!(replay::<(i64,)>(content).is_err());
+    }
+}
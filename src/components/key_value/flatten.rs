@@ -0,0 +1,349 @@
+//! Serializers that flatten the fields of a struct or the entries of a map
+//! into an enclosing map. This is used by [`WithExtra`][super::WithExtra],
+//! which serializes its struct's fields and its extra entries as a single
+//! map.
+
+use core::marker::PhantomData;
+
+use paste::paste;
+use serde::ser;
+
+macro_rules! reject_serialize {
+    ($($method:ident($($type:ty),*);)*) => {
+        $(
+            paste! {
+                #[inline]
+                fn [<serialize_ $method>](self, $(_: $type),*) -> Result<Self::Ok, Self::Error> {
+                    Err(not_flattenable())
+                }
+            }
+        )*
+
+        #[inline]
+        fn serialize_some<T: ?Sized>(self, _value: &T) -> Result<Self::Ok, Self::Error>
+        where
+            T: ser::Serialize,
+        {
+            Err(not_flattenable())
+        }
+
+        #[inline]
+        fn serialize_newtype_variant<T: ?Sized>(
+            self,
+            _name: &'static str,
+            _variant_index: u32,
+            _variant: &'static str,
+            _value: &T,
+        ) -> Result<Self::Ok, Self::Error>
+        where
+            T: ser::Serialize,
+        {
+            Err(not_flattenable())
+        }
+
+        #[inline]
+        fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+            Err(not_flattenable())
+        }
+
+        #[inline]
+        fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+            Err(not_flattenable())
+        }
+
+        #[inline]
+        fn serialize_tuple_struct(
+            self,
+            _name: &'static str,
+            _len: usize,
+        ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+            Err(not_flattenable())
+        }
+
+        #[inline]
+        fn serialize_tuple_variant(
+            self,
+            _name: &'static str,
+            _variant_index: u32,
+            _variant: &'static str,
+            _len: usize,
+        ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+            Err(not_flattenable())
+        }
+
+        #[inline]
+        fn serialize_struct_variant(
+            self,
+            _name: &'static str,
+            _variant_index: u32,
+            _variant: &'static str,
+            _len: usize,
+        ) -> Result<Self::SerializeStructVariant, Self::Error> {
+            Err(not_flattenable())
+        }
+    };
+}
+
+#[inline]
+fn not_flattenable<E: ser::Error>() -> E {
+    E::custom("WithExtra can only flatten structs and maps")
+}
+
+/// Serializer that counts the fields of a struct or the entries of a map,
+/// without serializing any of them. The map has to know its length.
+pub struct EntryCounter<E>(PhantomData<E>);
+
+impl<E> EntryCounter<E> {
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// The number of entries found by an [`EntryCounter`].
+pub struct Counted<E> {
+    len: usize,
+    error: PhantomData<E>,
+}
+
+impl<E: ser::Error> ser::Serializer for EntryCounter<E> {
+    type Ok = usize;
+    type Error = E;
+
+    type SerializeSeq = ser::Impossible<usize, E>;
+    type SerializeTuple = ser::Impossible<usize, E>;
+    type SerializeTupleStruct = ser::Impossible<usize, E>;
+    type SerializeTupleVariant = ser::Impossible<usize, E>;
+    type SerializeMap = Counted<E>;
+    type SerializeStruct = Counted<E>;
+    type SerializeStructVariant = ser::Impossible<usize, E>;
+
+    reject_serialize! {
+        bool(bool);
+        i8(i8);
+        i16(i16);
+        i32(i32);
+        i64(i64);
+        i128(i128);
+        u8(u8);
+        u16(u16);
+        u32(u32);
+        u64(u64);
+        u128(u128);
+        f32(f32);
+        f64(f64);
+        char(char);
+        str(&str);
+        bytes(&[u8]);
+        none();
+        unit();
+        unit_variant(&'static str, u32, &'static str);
+    }
+
+    #[inline]
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        Ok(0)
+    }
+
+    #[inline]
+    fn serialize_newtype_struct<T: ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ser::Serialize,
+    {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        match len {
+            Some(len) => Ok(Counted {
+                len,
+                error: PhantomData,
+            }),
+            None => Err(E::custom("WithExtra can't flatten a map of unknown length")),
+        }
+    }
+
+    #[inline]
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Ok(Counted {
+            len,
+            error: PhantomData,
+        })
+    }
+}
+
+impl<E: ser::Error> ser::SerializeMap for Counted<E> {
+    type Ok = usize;
+    type Error = E;
+
+    #[inline]
+    fn serialize_key<T: ?Sized>(&mut self, _key: &T) -> Result<(), Self::Error>
+    where
+        T: ser::Serialize,
+    {
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_value<T: ?Sized>(&mut self, _value: &T) -> Result<(), Self::Error>
+    where
+        T: ser::Serialize,
+    {
+        Ok(())
+    }
+
+    #[inline]
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(self.len)
+    }
+}
+
+impl<E: ser::Error> ser::SerializeStruct for Counted<E> {
+    type Ok = usize;
+    type Error = E;
+
+    #[inline]
+    fn serialize_field<T: ?Sized>(
+        &mut self,
+        _key: &'static str,
+        _value: &T,
+    ) -> Result<(), Self::Error>
+    where
+        T: ser::Serialize,
+    {
+        Ok(())
+    }
+
+    #[inline]
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(self.len)
+    }
+}
+
+/// Serializer that writes the fields of a struct or the entries of a map
+/// into an enclosing map, which has already been started.
+pub struct EntrySerializer<'a, M>(pub &'a mut M);
+
+impl<'a, M: ser::SerializeMap> ser::Serializer for EntrySerializer<'a, M> {
+    type Ok = ();
+    type Error = M::Error;
+
+    type SerializeSeq = ser::Impossible<(), M::Error>;
+    type SerializeTuple = ser::Impossible<(), M::Error>;
+    type SerializeTupleStruct = ser::Impossible<(), M::Error>;
+    type SerializeTupleVariant = ser::Impossible<(), M::Error>;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = ser::Impossible<(), M::Error>;
+
+    reject_serialize! {
+        bool(bool);
+        i8(i8);
+        i16(i16);
+        i32(i32);
+        i64(i64);
+        i128(i128);
+        u8(u8);
+        u16(u16);
+        u32(u32);
+        u64(u64);
+        u128(u128);
+        f32(f32);
+        f64(f64);
+        char(char);
+        str(&str);
+        bytes(&[u8]);
+        none();
+        unit();
+        unit_variant(&'static str, u32, &'static str);
+    }
+
+    #[inline]
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_newtype_struct<T: ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ser::Serialize,
+    {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Ok(self)
+    }
+
+    #[inline]
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Ok(self)
+    }
+}
+
+impl<M: ser::SerializeMap> ser::SerializeMap for EntrySerializer<'_, M> {
+    type Ok = ();
+    type Error = M::Error;
+
+    #[inline]
+    fn serialize_key<T: ?Sized>(&mut self, key: &T) -> Result<(), Self::Error>
+    where
+        T: ser::Serialize,
+    {
+        self.0.serialize_key(key)
+    }
+
+    #[inline]
+    fn serialize_value<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ser::Serialize,
+    {
+        self.0.serialize_value(value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+}
+
+impl<M: ser::SerializeMap> ser::SerializeStruct for EntrySerializer<'_, M> {
+    type Ok = ();
+    type Error = M::Error;
+
+    #[inline]
+    fn serialize_field<T: ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error>
+    where
+        T: ser::Serialize,
+    {
+        self.0.serialize_entry(key, value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+}
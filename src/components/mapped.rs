@@ -0,0 +1,221 @@
+use core::{fmt, marker::PhantomData};
+
+use serde::{de, ser};
+
+use super::{Case, Cased};
+
+/**
+An explicit mapping between the names of unit enum variants and their
+tokens on the wire. This is used by [`Mapped`].
+
+Implement it for the enum itself, so that `Mapped<T>` uses it by default,
+or for a separate marker type when the same enum has different tokens in
+different contexts.
+*/
+pub trait Mapping {
+    /// `(variant, token)` pairs. Variants that don't appear here use their
+    /// name as their token.
+    const TOKENS: &'static [(&'static str, &'static str)];
+}
+
+/// [`Case`] that looks up variant names in a [`Mapping`].
+struct MappingCase<M> {
+    mapping: PhantomData<M>,
+}
+
+impl<M: Mapping> Case for MappingCase<M> {
+    #[inline]
+    fn write_name<W: fmt::Write>(name: &str, dest: &mut W) -> fmt::Result {
+        let token = M::TOKENS
+            .iter()
+            .find(|&&(variant, _)| variant == name)
+            .map_or(name, |&(_, token)| token);
+
+        dest.write_str(token)
+    }
+}
+
+/**
+Adapter type that (de)serializes unit enum variants as tokens from an
+explicit [`Mapping`].
+
+Some Redis options and replies use tokens that aren't valid Rust
+identifiers, like `1` and `0`, or that contain spaces, or that differ from
+one command to the next, like `on`/`off` in one place and `yes`/`no` in
+another. A `Mapped` serializes each unit variant as the token that the
+mapping pairs with it, and deserializes a variant by finding its token,
+which can be a string or an integer reply. Like [`Cased`], it applies only
+to the wrapped value itself, and any other value is (de)serialized
+unchanged.
+
+The mapping is `T` itself by default; use a separate `M` to map the same
+enum to different tokens.
+
+# Example
+
+```
+use serde::{Deserialize, Serialize};
+use seredies::components::{Mapped, Mapping};
+use seredies::de::from_bytes;
+use seredies::ser::to_vec;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+enum Toggle {
+    On,
+    Off,
+}
+
+impl Mapping for Toggle {
+    const TOKENS: &'static [(&'static str, &'static str)] = &[("On", "1"), ("Off", "0")];
+}
+
+struct YesNo;
+
+impl Mapping for YesNo {
+    const TOKENS: &'static [(&'static str, &'static str)] = &[("On", "yes"), ("Off", "no")];
+}
+
+assert_eq!(to_vec(&Mapped::<_>::new(Toggle::On)).unwrap(), b"$1\r\n1\r\n");
+
+let toggle: Mapped<Toggle> = from_bytes(b":0\r\n").unwrap();
+assert_eq!(toggle.value, Toggle::Off);
+
+let toggle: Mapped<Toggle, YesNo> = from_bytes(b"$3\r\nyes\r\n").unwrap();
+assert_eq!(toggle.value, Toggle::On);
+```
+*/
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Mapped<T, M = T> {
+    /// The wrapped value.
+    pub value: T,
+    mapping: PhantomData<M>,
+}
+
+impl<T, M> Mapped<T, M> {
+    /// Wrap a value, so that its variant is mapped to a token with `M`.
+    #[inline]
+    #[must_use]
+    pub const fn new(value: T) -> Self {
+        Self {
+            value,
+            mapping: PhantomData,
+        }
+    }
+
+    /// Get the wrapped value.
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T, M> ser::Serialize for Mapped<T, M>
+where
+    T: ser::Serialize,
+    M: Mapping,
+{
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        Cased::<&T, MappingCase<M>>::new(&self.value).serialize(serializer)
+    }
+}
+
+impl<'de, T, M> de::Deserialize<'de> for Mapped<T, M>
+where
+    T: de::Deserialize<'de>,
+    M: Mapping,
+{
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        Cased::<T, MappingCase<M>>::deserialize(deserializer).map(|cased| Self::new(cased.value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::{de::from_bytes, ser::to_vec};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+    enum Encoding {
+        ListPack,
+        HashTable,
+        Raw,
+    }
+
+    impl Mapping for Encoding {
+        const TOKENS: &'static [(&'static str, &'static str)] =
+            &[("ListPack", "list pack"), ("HashTable", "hashtable")];
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+    enum Flag {
+        Set,
+        Unset,
+    }
+
+    impl Mapping for Flag {
+        const TOKENS: &'static [(&'static str, &'static str)] = &[("Set", "1"), ("Unset", "0")];
+    }
+
+    #[test]
+    fn round_trip() {
+        for encoding in [Encoding::ListPack, Encoding::HashTable, Encoding::Raw] {
+            let mapped: Mapped<Encoding> = Mapped::new(encoding);
+            let data = to_vec(&mapped).unwrap();
+            let parsed: Mapped<Encoding> = from_bytes(&data).unwrap();
+
+            assert_eq!(parsed, mapped);
+        }
+    }
+
+    #[test]
+    fn token_with_spaces() {
+        assert_eq!(
+            to_vec(&Mapped::<_>::new(Encoding::ListPack)).unwrap(),
+            b"$9\r\nlist pack\r\n"
+        );
+
+        let encoding: Mapped<Encoding> = from_bytes(b"+list pack\r\n").unwrap();
+        assert_eq!(encoding.value, Encoding::ListPack);
+    }
+
+    #[test]
+    fn unmapped_variant_uses_name() {
+        assert_eq!(
+            to_vec(&Mapped::<_>::new(Encoding::Raw)).unwrap(),
+            b"$3\r\nRaw\r\n"
+        );
+
+        // A mapped variant's name isn't accepted in place of its token
+        assert!(from_bytes::<Mapped<Encoding>>(b"$8\r\nListPack\r\n").is_err());
+    }
+
+    #[test]
+    fn integer_tokens() {
+        let flag: Mapped<Flag> = from_bytes(b":1\r\n").unwrap();
+        assert_eq!(flag.value, Flag::Set);
+
+        let flag: Mapped<Flag> = from_bytes(b"$1\r\n0\r\n").unwrap();
+        assert_eq!(flag.value, Flag::Unset);
+
+        assert!(from_bytes::<Mapped<Flag>>(b":2\r\n").is_err());
+    }
+
+    #[test]
+    fn collection() {
+        let flags: Vec<Mapped<Flag>> = from_bytes(b"*3\r\n:1\r\n:0\r\n$1\r\n1\r\n").unwrap();
+        let flags: Vec<Flag> = flags.into_iter().map(Mapped::into_inner).collect();
+
+        assert_eq!(flags, [Flag::Set, Flag::Unset, Flag::Set]);
+    }
+}
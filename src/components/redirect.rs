@@ -0,0 +1,417 @@
+use alloc::string::String;
+use core::{fmt, marker::PhantomData, str};
+
+use serde::{de, ser, ser::SerializeStruct as _};
+
+use super::RedisError;
+use crate::cluster::HASH_SLOTS;
+
+/// The kind of a [`Redirect`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RedirectKind {
+    /// The slot has been permanently moved to another node, so the client
+    /// should update its slot map and send the command there.
+    Moved,
+
+    /// The slot is being migrated to another node, so the client should
+    /// send just this command there, preceded by `ASKING`.
+    Ask,
+}
+
+impl RedirectKind {
+    /// The error code for this kind of redirection, `MOVED` or `ASK`.
+    #[inline]
+    #[must_use]
+    pub const fn code(self) -> &'static str {
+        match self {
+            RedirectKind::Moved => "MOVED",
+            RedirectKind::Ask => "ASK",
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    fn from_code(code: &[u8]) -> Option<Self> {
+        match code {
+            b"MOVED" => Some(RedirectKind::Moved),
+            b"ASK" => Some(RedirectKind::Ask),
+            _ => None,
+        }
+    }
+}
+
+impl ser::Serialize for RedirectKind {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.serialize_unit_variant("RedirectKind", *self as u32, self.code())
+    }
+}
+
+impl<'de> de::Deserialize<'de> for RedirectKind {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = RedirectKind;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("MOVED or ASK")
+            }
+
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                RedirectKind::from_code(v)
+                    .ok_or_else(|| de::Error::invalid_value(de::Unexpected::Bytes(v), &self))
+            }
+
+            #[inline]
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                self.visit_bytes(v.as_bytes())
+            }
+        }
+
+        deserializer.deserialize_identifier(Visitor)
+    }
+}
+
+/**
+A Redis Cluster redirection: a `MOVED` or `ASK` error, which tells the
+client to send a command to a different node.
+
+These errors have the form `MOVED <slot> <host:port>`. When used as the
+`Err` variant of a [`Result`], a `Redirect` is deserialized from a Redis
+error with either code, and any other error fails to deserialize. Since
+replies can also contain other errors, the usual approach is to deserialize
+a [`RedisError`], and get the redirection from it with
+[`from_error`][Redirect::from_error]; [`parse`][Redirect::parse] does the
+same for an error that's been captured as a single string, like a
+[`RedisMessage`][crate::de::RedisMessage].
+
+The address can be any type that deserializes from a string, like a `&str`,
+a `String`, or a [`SocketAddr`][core::net::SocketAddr] if the cluster
+doesn't use host names. A `Redirect` is serialized back into the same form.
+
+# Example
+
+```
+use seredies::components::{RedisError, Redirect, RedirectKind};
+use seredies::de::from_bytes;
+
+let reply: Result<i64, Redirect<&str>> = from_bytes(b"-MOVED 3999 127.0.0.1:6381\r\n").unwrap();
+assert_eq!(
+    reply,
+    Err(Redirect {
+        kind: RedirectKind::Moved,
+        slot: 3999,
+        address: "127.0.0.1:6381",
+    }),
+);
+
+let reply: Result<i64, RedisError<&str, &str>> =
+    from_bytes(b"-ASK 12182 cache-3.internal:6379\r\n").unwrap();
+let error = reply.unwrap_err();
+let redirect = Redirect::from_error(&error).unwrap();
+assert_eq!(redirect.kind, RedirectKind::Ask);
+assert_eq!(redirect.address, "cache-3.internal:6379");
+
+// Other errors aren't redirects
+assert!(from_bytes::<Result<i64, Redirect>>(b"-ERR unknown command\r\n").is_err());
+assert_eq!(Redirect::parse("ERR unknown command"), None);
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Redirect<A = String> {
+    /// Whether this is a `MOVED` or `ASK` redirection.
+    pub kind: RedirectKind,
+
+    /// The hash slot of the key in the command.
+    pub slot: u16,
+
+    /// The address of the node to send the command to, as `host:port`.
+    pub address: A,
+}
+
+impl<'a> Redirect<&'a str> {
+    /// Parse a redirection from the message of a Redis error, such as
+    /// `MOVED 3999 127.0.0.1:6381`. Returns `None` if the message isn't a
+    /// `MOVED` or `ASK` error, or if it's malformed.
+    #[must_use]
+    pub fn parse(message: &'a str) -> Option<Self> {
+        let (code, target) = message.split_once(' ')?;
+        Self::from_parts(code, target)
+    }
+
+    /// Get the redirection from a [`RedisError`], if it's a `MOVED` or
+    /// `ASK` error.
+    #[inline]
+    #[must_use]
+    pub fn from_error<C, M>(error: &'a RedisError<C, M>) -> Option<Self>
+    where
+        C: AsRef<str>,
+        M: AsRef<str>,
+    {
+        Self::from_parts(error.code.as_ref(), error.message.as_ref())
+    }
+
+    fn from_parts(code: &str, target: &'a str) -> Option<Self> {
+        let kind = RedirectKind::from_code(code.as_bytes())?;
+        let (slot, address) = split_target(target)?;
+
+        Some(Self {
+            kind,
+            slot,
+            address,
+        })
+    }
+}
+
+/// Split the `<slot> <host:port>` part of a redirection.
+fn split_target(target: &str) -> Option<(u16, &str)> {
+    let (slot, address) = target.split_once(' ')?;
+    let slot: u16 = slot.parse().ok()?;
+
+    match slot < HASH_SLOTS && !address.is_empty() && !address.contains(' ') {
+        true => Some((slot, address)),
+        false => None,
+    }
+}
+
+impl<A: fmt::Display> fmt::Display for Redirect<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", self.kind.code(), self.slot, self.address)
+    }
+}
+
+impl<A: fmt::Display> ser::Serialize for Redirect<A> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        struct Target<'a, A>(&'a Redirect<A>);
+
+        impl<A: fmt::Display> ser::Serialize for Target<'_, A> {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: ser::Serializer,
+            {
+                serializer.collect_str(&format_args!("{} {}", self.0.slot, self.0.address))
+            }
+        }
+
+        let mut error = serializer.serialize_struct("Redirect", 2)?;
+        error.serialize_field("code", &self.kind)?;
+        error.serialize_field("message", &Target(self))?;
+        error.end()
+    }
+}
+
+/// Seed for the `<slot> <host:port>` part of a redirection, which
+/// deserializes the address as an `A`.
+struct TargetSeed<A>(PhantomData<A>);
+
+impl<'de, A: de::Deserialize<'de>> de::DeserializeSeed<'de> for TargetSeed<A> {
+    type Value = (u16, A);
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl<'de, A: de::Deserialize<'de>> de::Visitor<'de> for TargetSeed<A> {
+    type Value = (u16, A);
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a hash slot and an address")
+    }
+
+    fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        let (slot, address) = split_target(v)
+            .ok_or_else(|| de::Error::invalid_value(de::Unexpected::Str(v), &self))?;
+
+        A::deserialize(de::value::BorrowedStrDeserializer::new(address)).map(|a| (slot, a))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        let (slot, address) = split_target(v)
+            .ok_or_else(|| de::Error::invalid_value(de::Unexpected::Str(v), &self))?;
+
+        A::deserialize(de::value::StrDeserializer::new(address)).map(|a| (slot, a))
+    }
+
+    fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match str::from_utf8(v) {
+            Ok(v) => self.visit_borrowed_str(v),
+            Err(_) => Err(de::Error::invalid_value(de::Unexpected::Bytes(v), &self)),
+        }
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match str::from_utf8(v) {
+            Ok(v) => self.visit_str(v),
+            Err(_) => Err(de::Error::invalid_value(de::Unexpected::Bytes(v), &self)),
+        }
+    }
+}
+
+impl<'de, A: de::Deserialize<'de>> de::Deserialize<'de> for Redirect<A> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor<A>(PhantomData<A>);
+
+        impl<'de, A: de::Deserialize<'de>> de::Visitor<'de> for Visitor<A> {
+            type Value = Redirect<A>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a MOVED or ASK redirection")
+            }
+
+            fn visit_seq<S>(self, mut seq: S) -> Result<Self::Value, S::Error>
+            where
+                S: de::SeqAccess<'de>,
+            {
+                let kind = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+
+                let (slot, address) = seq
+                    .next_element_seed(TargetSeed(PhantomData))?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+                match seq.next_element::<de::IgnoredAny>()? {
+                    None => Ok(Redirect {
+                        kind,
+                        slot,
+                        address,
+                    }),
+                    Some(_) => Err(de::Error::invalid_length(3, &self)),
+                }
+            }
+        }
+
+        deserializer.deserialize_struct("Redirect", &["code", "message"], Visitor(PhantomData))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use super::*;
+    use crate::{de::from_bytes, ser::to_vec};
+
+    #[test]
+    fn moved() {
+        let reply: Result<(), Redirect> = from_bytes(b"-MOVED 3999 127.0.0.1:6381\r\n").unwrap();
+        assert_eq!(
+            reply,
+            Err(Redirect {
+                kind: RedirectKind::Moved,
+                slot: 3999,
+                address: "127.0.0.1:6381".to_owned(),
+            })
+        );
+    }
+
+    #[test]
+    fn socket_addr() {
+        let reply: Result<(), Redirect<SocketAddr>> =
+            from_bytes(b"-ASK 1 10.0.0.2:7000\r\n").unwrap();
+        assert_eq!(
+            reply,
+            Err(Redirect {
+                kind: RedirectKind::Ask,
+                slot: 1,
+                address: "10.0.0.2:7000".parse().unwrap(),
+            })
+        );
+    }
+
+    #[test]
+    fn not_a_redirect() {
+        assert!(from_bytes::<Result<(), Redirect>>(b"-ERR bad\r\n").is_err());
+        assert!(from_bytes::<Result<(), Redirect>>(b"-MOVED 16384 a:1\r\n").is_err());
+        assert!(from_bytes::<Result<(), Redirect>>(b"-MOVED 1\r\n").is_err());
+    }
+
+    #[test]
+    fn round_trip() {
+        let redirect = Redirect {
+            kind: RedirectKind::Ask,
+            slot: 12182,
+            address: "node-2:6379",
+        };
+        let resp = to_vec(&Err::<(), _>(&redirect)).unwrap();
+        assert_eq!(resp, b"-ASK 12182 node-2:6379\r\n");
+
+        let parsed: Result<(), Redirect<&str>> = from_bytes(&resp).unwrap();
+        assert_eq!(parsed, Err(redirect));
+        assert_eq!(redirect.to_string(), "ASK 12182 node-2:6379");
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            Redirect::parse("MOVED 0 [::1]:6379"),
+            Some(Redirect {
+                kind: RedirectKind::Moved,
+                slot: 0,
+                address: "[::1]:6379",
+            })
+        );
+        assert_eq!(Redirect::parse("MOVED"), None);
+        assert_eq!(Redirect::parse("MOVED x a:1"), None);
+        assert_eq!(Redirect::parse("TRYAGAIN 1 a:1"), None);
+        assert_eq!(Redirect::parse("ASK 1 a:1 extra"), None);
+    }
+
+    #[test]
+    fn from_error() {
+        let error = RedisError {
+            code: "MOVED".to_owned(),
+            message: "42 10.0.0.1:6379".to_owned(),
+        };
+        assert_eq!(
+            Redirect::from_error(&error),
+            Some(Redirect {
+                kind: RedirectKind::Moved,
+                slot: 42,
+                address: "10.0.0.1:6379",
+            })
+        );
+
+        let error = RedisError {
+            code: "WRONGTYPE",
+            message: "Operation against a key holding the wrong kind of value",
+        };
+        assert_eq!(Redirect::from_error(&error), None);
+    }
+}
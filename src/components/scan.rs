@@ -0,0 +1,180 @@
+use alloc::vec::Vec;
+use core::{fmt, marker::PhantomData};
+
+use serde::{de, ser, ser::SerializeTuple as _};
+
+use super::RedisString;
+
+/**
+The reply to one of the `SCAN` family of commands: `SCAN`, `SSCAN`,
+`HSCAN`, and `ZSCAN`.
+
+Each of these replies with a 2-element array, containing the cursor for the
+next call and an array of items. The cursor is sent as a bulk string, even
+though it's a number; `ScanResponse` parses it into a `u64`, so it can be
+passed straight back to the next call. A cursor of 0 means that the
+iteration is [finished][ScanResponse::is_finished].
+
+The items of `HSCAN` and `ZSCAN` alternate between fields and values, or
+members and scores, so they're usually deserialized as strings and paired
+up afterwards.
+
+# Example
+
+```
+use seredies::components::ScanResponse;
+use seredies::de::from_bytes;
+
+let reply: ScanResponse<&str> = from_bytes(
+    b"*2\r\n$2\r\n17\r\n*2\r\n$5\r\nkey:1\r\n$5\r\nkey:2\r\n",
+)
+.unwrap();
+
+assert_eq!(reply.cursor, 17);
+assert_eq!(reply.items, ["key:1", "key:2"]);
+assert!(!reply.is_finished());
+```
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct ScanResponse<T> {
+    /// The cursor to pass to the next call, or 0 if the iteration is
+    /// finished.
+    pub cursor: u64,
+
+    /// The items returned by this call. This can be empty, even when the
+    /// iteration isn't finished.
+    pub items: Vec<T>,
+}
+
+impl<T> ScanResponse<T> {
+    /// True if this is the last reply of the iteration.
+    #[inline]
+    #[must_use]
+    pub const fn is_finished(&self) -> bool {
+        self.cursor == 0
+    }
+}
+
+impl<T> ser::Serialize for ScanResponse<T>
+where
+    T: ser::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        let mut reply = serializer.serialize_tuple(2)?;
+        reply.serialize_element(&RedisString(self.cursor))?;
+        reply.serialize_element(&self.items)?;
+        reply.end()
+    }
+}
+
+impl<'de, T> de::Deserialize<'de> for ScanResponse<T>
+where
+    T: de::Deserialize<'de>,
+{
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(2, ScanResponseVisitor { items: PhantomData })
+    }
+}
+
+struct ScanResponseVisitor<T> {
+    items: PhantomData<fn() -> T>,
+}
+
+impl<'de, T> de::Visitor<'de> for ScanResponseVisitor<T>
+where
+    T: de::Deserialize<'de>,
+{
+    type Value = ScanResponse<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a cursor and an array of items")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let RedisString(cursor) = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+
+        let items = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+        Ok(ScanResponse { cursor, items })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cool_asserts::assert_matches;
+
+    use super::*;
+    use crate::{de, ser};
+
+    #[test]
+    fn finished() {
+        let reply: ScanResponse<String> = de::from_bytes(b"*2\r\n$1\r\n0\r\n*0\r\n").unwrap();
+
+        assert!(reply.is_finished());
+        assert!(reply.items.is_empty());
+    }
+
+    #[test]
+    fn large_cursor() {
+        let reply: ScanResponse<i64> =
+            de::from_bytes(b"*2\r\n$20\r\n18446744073709551615\r\n*1\r\n:5\r\n").unwrap();
+
+        assert_eq!(
+            reply,
+            ScanResponse {
+                cursor: u64::MAX,
+                items: Vec::from([5]),
+            }
+        );
+    }
+
+    #[test]
+    fn bad_cursor() {
+        assert_matches!(
+            de::from_bytes::<ScanResponse<String>>(b"*2\r\n$3\r\nabc\r\n*0\r\n"),
+            Err(de::Error::Custom(_))
+        );
+    }
+
+    #[test]
+    fn missing_items() {
+        assert_matches!(
+            de::from_bytes::<ScanResponse<String>>(b"*1\r\n$1\r\n0\r\n"),
+            Err(de::Error::Custom(message)) => assert_eq!(
+                message,
+                "invalid length 1, expected a cursor and an array of items"
+            )
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let reply = ScanResponse {
+            cursor: 42,
+            items: Vec::from(["field", "value"]),
+        };
+
+        let data = ser::to_vec(&reply).unwrap();
+        assert_eq!(
+            data,
+            b"*2\r\n$2\r\n42\r\n*2\r\n$5\r\nfield\r\n$5\r\nvalue\r\n"
+        );
+
+        let parsed: ScanResponse<&str> = de::from_bytes(&data).unwrap();
+        assert_eq!(parsed, reply);
+    }
+}
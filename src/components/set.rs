@@ -0,0 +1,62 @@
+use serde::{de, ser};
+
+use crate::ser::SET_TOKEN;
+
+/**
+Adapter type that serializes the contained sequence as a RESP3
+[set](https://redis.io/docs/reference/protocol-spec/#sets).
+
+serde doesn't distinguish sets from other sequences, so a `HashSet` or
+`BTreeSet` is normally serialized as an array. Wrapping it in a `RedisSet`
+causes a [RESP3 serializer][crate::ser::Serializer::new_resp3] to serialize
+it as a set instead. Other serializers (including the RESP2
+[`Serializer`][crate::ser::Serializer]) serialize it as a plain sequence.
+It deserializes exactly like the inner type.
+
+# Example
+
+```
+use std::collections::BTreeSet;
+
+use serde::Serialize;
+use seredies::components::RedisSet;
+use seredies::ser::{to_vec, Serializer};
+
+let set = RedisSet(BTreeSet::from([1, 2]));
+
+let mut buffer = Vec::new();
+set.serialize(Serializer::new_resp3(&mut buffer)).unwrap();
+assert_eq!(buffer, b"~2\r\n:1\r\n:2\r\n");
+
+assert_eq!(to_vec(&set).unwrap(), b"*2\r\n:1\r\n:2\r\n");
+```
+*/
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct RedisSet<T>(pub T);
+
+impl<T> ser::Serialize for RedisSet<T>
+where
+    T: ser::Serialize,
+{
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.serialize_newtype_struct(SET_TOKEN, &self.0)
+    }
+}
+
+impl<'de, T> de::Deserialize<'de> for RedisSet<T>
+where
+    T: de::Deserialize<'de>,
+{
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        T::deserialize(deserializer).map(RedisSet)
+    }
+}
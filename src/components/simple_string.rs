@@ -0,0 +1,159 @@
+use serde::{de, ser};
+
+use crate::ser::SIMPLE_STRING_TOKEN;
+
+/**
+Adapter type that serializes the contained string as a RESP
+[Simple String](https://redis.io/docs/reference/protocol-spec/#simple-strings).
+
+The RESP [`Serializer`][crate::ser::Serializer] normally serializes all
+strings as Bulk Strings. Wrapping a string in a `SimpleString` causes it to
+be serialized as a Simple String instead, which is useful for status replies
+like `+OK` or `+QUEUED` when implementing a server. The inner value must
+serialize as a string, bytes, a char, an integer, or a unit variant, or with
+[`collect_str`][serde::Serializer::collect_str] (like [`SocketAddr`][core::net::SocketAddr]
+and [`fmt::Arguments`][core::fmt::Arguments]), which is formatted directly
+into the output. It must not contain a `\r` or `\n`; otherwise,
+serialization fails with
+[`BadSimpleString`][crate::ser::Error::BadSimpleString]. Other serializers
+serialize it as the inner type. It deserializes exactly like the inner type.
+
+# Example
+
+```
+use seredies::components::SimpleString;
+use seredies::ser::{to_vec, Error};
+
+assert_eq!(to_vec(&SimpleString("QUEUED")).unwrap(), b"+QUEUED\r\n");
+assert_eq!(to_vec(&"QUEUED").unwrap(), b"$6\r\nQUEUED\r\n");
+
+assert!(matches!(
+    to_vec(&SimpleString("bad\r\nstatus")),
+    Err(Error::BadSimpleString),
+));
+
+let addr: std::net::SocketAddr = "127.0.0.1:6379".parse().unwrap();
+assert_eq!(to_vec(&SimpleString(addr)).unwrap(), b"+127.0.0.1:6379\r\n");
+assert_eq!(to_vec(&SimpleString(42)).unwrap(), b"+42\r\n");
+assert_eq!(
+    to_vec(&SimpleString(format_args!("slot {} of {}", 3, 16384))).unwrap(),
+    b"+slot 3 of 16384\r\n",
+);
+```
+*/
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct SimpleString<T>(pub T);
+
+impl<T> ser::Serialize for SimpleString<T>
+where
+    T: ser::Serialize,
+{
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.serialize_newtype_struct(SIMPLE_STRING_TOKEN, &self.0)
+    }
+}
+
+impl<'de, T> de::Deserialize<'de> for SimpleString<T>
+where
+    T: de::Deserialize<'de>,
+{
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        T::deserialize(deserializer).map(SimpleString)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cool_asserts::assert_matches;
+    use serde::Serialize;
+
+    use super::*;
+    use crate::{de, ser};
+
+    #[test]
+    fn serialize() {
+        assert_eq!(ser::to_vec(&SimpleString("OK")).unwrap(), b"+OK\r\n");
+        assert_eq!(ser::to_vec(&SimpleString("")).unwrap(), b"+\r\n");
+        assert_eq!(
+            ser::to_vec(&SimpleString(serde_bytes::Bytes::new(b"PONG"))).unwrap(),
+            b"+PONG\r\n"
+        );
+    }
+
+    #[test]
+    fn serialize_resp3() {
+        let mut buffer = Vec::new();
+        SimpleString("OK")
+            .serialize(ser::Serializer::new_resp3(&mut buffer))
+            .unwrap();
+
+        assert_eq!(buffer, b"+OK\r\n");
+    }
+
+    #[test]
+    fn in_array() {
+        assert_eq!(
+            ser::to_vec(&(SimpleString("OK"), "OK")).unwrap(),
+            b"*2\r\n+OK\r\n$2\r\nOK\r\n"
+        );
+    }
+
+    #[test]
+    fn reject_newlines() {
+        assert_matches!(
+            ser::to_vec(&SimpleString("a\rb")),
+            Err(ser::Error::BadSimpleString)
+        );
+        assert_matches!(
+            ser::to_vec(&SimpleString("a\nb")),
+            Err(ser::Error::BadSimpleString)
+        );
+    }
+
+    #[test]
+    fn integers() {
+        assert_eq!(ser::to_vec(&SimpleString(-10i8)).unwrap(), b"+-10\r\n");
+        assert_eq!(
+            ser::to_vec(&SimpleString(u128::MAX)).unwrap(),
+            b"+340282366920938463463374607431768211455\r\n"
+        );
+    }
+
+    #[test]
+    fn display() {
+        let addr = std::net::Ipv6Addr::LOCALHOST;
+        assert_eq!(ser::to_vec(&SimpleString(addr)).unwrap(), b"+::1\r\n");
+
+        assert_matches!(
+            ser::to_vec(&SimpleString(format_args!("a{}b", '\n'))),
+            Err(ser::Error::BadSimpleString)
+        );
+    }
+
+    #[test]
+    fn reject_non_strings() {
+        assert_matches!(
+            ser::to_vec(&SimpleString(1.5)),
+            Err(ser::Error::UnsupportedType(_))
+        );
+        assert_matches!(
+            ser::to_vec(&SimpleString(true)),
+            Err(ser::Error::UnsupportedType(_))
+        );
+    }
+
+    #[test]
+    fn deserialize() {
+        let SimpleString(status): SimpleString<&str> = de::from_bytes(b"+OK\r\n").unwrap();
+        assert_eq!(status, "OK");
+    }
+}
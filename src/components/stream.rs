@@ -0,0 +1,425 @@
+use alloc::{string::String, vec::Vec};
+use core::{borrow::Borrow, fmt, marker::PhantomData};
+
+use serde::{de, ser, ser::SerializeTuple as _};
+
+use super::KeyValuePairs;
+use crate::de::parse::parse_integer;
+
+/**
+The ID of an entry in a Redis stream, like `1526919030474-55`.
+
+An ID is made of the millisecond timestamp when the entry was added, and a
+sequence number that orders entries added in the same millisecond. IDs are
+ordered the same way as the entries in a stream. They're (de)serialized as
+strings, in the same format that Redis uses.
+
+# Example
+
+```
+use seredies::components::StreamId;
+use seredies::de::from_bytes;
+
+let id: StreamId = from_bytes(b"$16\r\n1526919030474-55\r\n").unwrap();
+assert_eq!(id, StreamId::new(1526919030474, 55));
+assert_eq!(id.to_string(), "1526919030474-55");
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct StreamId {
+    /// The Unix time when the entry was added, in milliseconds.
+    pub millis: u64,
+
+    /// The sequence number of the entry, among the entries that were added
+    /// in the same millisecond.
+    pub seq: u64,
+}
+
+impl StreamId {
+    /// Create a new stream ID.
+    #[inline]
+    #[must_use]
+    pub const fn new(millis: u64, seq: u64) -> Self {
+        Self { millis, seq }
+    }
+
+    /// Parse a stream ID in the format Redis uses, like `1526919030474-55`.
+    #[must_use]
+    pub fn parse(id: &[u8]) -> Option<Self> {
+        let split = id.iter().position(|&b| b == b'-')?;
+        let (millis, seq) = id.split_at(split);
+
+        Some(Self {
+            millis: parse_integer(millis)?,
+            seq: parse_integer(&seq[1..])?,
+        })
+    }
+}
+
+impl fmt::Display for StreamId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.millis, self.seq)
+    }
+}
+
+impl ser::Serialize for StreamId {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+struct StreamIdVisitor;
+
+impl de::Visitor<'_> for StreamIdVisitor {
+    type Value = StreamId;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a stream ID, like 1526919030474-55")
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        StreamId::parse(v).ok_or_else(|| E::invalid_value(de::Unexpected::Bytes(v), &self))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        StreamId::parse(v.as_bytes()).ok_or_else(|| E::invalid_value(de::Unexpected::Str(v), &self))
+    }
+}
+
+impl<'de> de::Deserialize<'de> for StreamId {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_str(StreamIdVisitor)
+    }
+}
+
+/**
+An entry in a Redis stream, as returned by `XRANGE`, `XREVRANGE`, `XREAD`,
+and `XREADGROUP`.
+
+Redis sends each entry as a 2-element array of the entry's ID and a
+flattened array of its fields and values. `StreamEntry` parses the ID, and
+deserializes the fields like [`KeyValuePairs`], so they can be any map or
+struct type.
+
+# Example
+
+```
+use serde::Deserialize;
+use seredies::components::{RedisString, StreamEntry, StreamId};
+use seredies::de::from_bytes;
+
+// Stream values are always strings, so numbers need `RedisString`
+#[derive(Deserialize, Debug, PartialEq)]
+struct Reading<'a> {
+    sensor: &'a str,
+    value: RedisString<i64>,
+}
+
+let input = b"\
+    *1\r\n\
+    *2\r\n\
+        $3\r\n5-0\r\n\
+        *4\r\n\
+            $6\r\nsensor\r\n$4\r\ntemp\r\n\
+            $5\r\nvalue\r\n$2\r\n21\r\n";
+
+let entries: Vec<StreamEntry<Reading>> = from_bytes(input).unwrap();
+
+assert_eq!(entries[0].id, StreamId::new(5, 0));
+assert_eq!(entries[0].fields, Reading { sensor: "temp", value: RedisString(21) });
+```
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct StreamEntry<T> {
+    /// The ID of the entry.
+    pub id: StreamId,
+
+    /// The fields of the entry.
+    pub fields: T,
+}
+
+impl<T> ser::Serialize for StreamEntry<T>
+where
+    T: ser::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        let mut entry = serializer.serialize_tuple(2)?;
+        entry.serialize_element(&self.id)?;
+        entry.serialize_element(&KeyValuePairs(&self.fields))?;
+        entry.end()
+    }
+}
+
+impl<'de, T> de::Deserialize<'de> for StreamEntry<T>
+where
+    T: de::Deserialize<'de>,
+{
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(
+            2,
+            StreamEntryVisitor {
+                fields: PhantomData,
+            },
+        )
+    }
+}
+
+struct StreamEntryVisitor<T> {
+    fields: PhantomData<fn() -> T>,
+}
+
+impl<'de, T> de::Visitor<'de> for StreamEntryVisitor<T>
+where
+    T: de::Deserialize<'de>,
+{
+    type Value = StreamEntry<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a stream entry ID and an array of fields")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let id = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+
+        let KeyValuePairs(fields) = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+        Ok(StreamEntry { id, fields })
+    }
+}
+
+/**
+The reply to `XREAD` or `XREADGROUP`: the new entries in each of the
+streams that were read, as [`StreamEntry`]s.
+
+Redis sends an array with a 2-element array for each stream that has new
+entries, containing the stream's key and an array of its entries. Streams
+without new entries are left out. If a blocking read times out, Redis
+replies with a null, which is deserialized as an `XReadResponse` with no
+streams.
+
+# Example
+
+```
+use std::collections::HashMap;
+
+use seredies::components::{StreamId, XReadResponse};
+use seredies::de::from_bytes;
+
+type Fields<'a> = HashMap<&'a str, &'a str>;
+
+let input = b"\
+    *1\r\n\
+    *2\r\n\
+        $6\r\nevents\r\n\
+        *1\r\n\
+            *2\r\n\
+            $3\r\n7-1\r\n\
+            *2\r\n$4\r\nkind\r\n$5\r\nlogin\r\n";
+
+let reply: XReadResponse<Fields> = from_bytes(input).unwrap();
+let events = reply.get("events").unwrap();
+
+assert_eq!(events[0].id, StreamId::new(7, 1));
+assert_eq!(events[0].fields["kind"], "login");
+
+// A blocking read that timed out
+let reply: XReadResponse<Fields> = from_bytes(b"*-1\r\n").unwrap();
+assert!(reply.streams.is_empty());
+```
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct XReadResponse<T, K = String> {
+    /// The key of each stream with new entries, and its entries.
+    pub streams: Vec<(K, Vec<StreamEntry<T>>)>,
+}
+
+impl<T, K> XReadResponse<T, K> {
+    /// Get the entries read from the stream with the given key, if it had
+    /// any.
+    #[must_use]
+    pub fn get<Q>(&self, key: &Q) -> Option<&[StreamEntry<T>]>
+    where
+        K: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+    {
+        self.streams
+            .iter()
+            .find(|(stream, _)| stream.borrow() == key)
+            .map(|(_, entries)| entries.as_slice())
+    }
+}
+
+impl<T, K> ser::Serialize for XReadResponse<T, K>
+where
+    T: ser::Serialize,
+    K: ser::Serialize,
+{
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        self.streams.serialize(serializer)
+    }
+}
+
+impl<'de, T, K> de::Deserialize<'de> for XReadResponse<T, K>
+where
+    T: de::Deserialize<'de>,
+    K: de::Deserialize<'de>,
+{
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let streams: Option<_> = de::Deserialize::deserialize(deserializer)?;
+
+        Ok(Self {
+            streams: streams.unwrap_or_default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use cool_asserts::assert_matches;
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::{components::RedisString, de, ser};
+
+    #[test]
+    fn stream_id() {
+        assert_eq!(StreamId::parse(b"0-1"), Some(StreamId::new(0, 1)));
+        assert_eq!(
+            StreamId::parse(b"18446744073709551615-18446744073709551615"),
+            Some(StreamId::new(u64::MAX, u64::MAX))
+        );
+        assert_eq!(StreamId::parse(b"12"), None);
+        assert_eq!(StreamId::parse(b"12-"), None);
+        assert_eq!(StreamId::parse(b"-1-2"), None);
+        assert_eq!(StreamId::parse(b"1-2-3"), None);
+
+        assert!(StreamId::new(1, 5) < StreamId::new(2, 0));
+    }
+
+    #[test]
+    fn bad_stream_id() {
+        assert_matches!(
+            de::from_bytes::<StreamId>(b"+abc\r\n"),
+            Err(de::Error::Custom(message)) => assert_eq!(
+                message,
+                "invalid value: simple string of length 3 (\"abc\"), expected a stream ID, like 1526919030474-55"
+            )
+        );
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Event {
+        kind: String,
+        user: RedisString<u64>,
+    }
+
+    #[test]
+    fn entry_round_trip() {
+        let entry = StreamEntry {
+            id: StreamId::new(10, 2),
+            fields: BTreeMap::from([("a", "1"), ("b", "2")]),
+        };
+
+        let data = ser::to_vec(&entry).unwrap();
+        assert_eq!(
+            data,
+            b"*2\r\n$4\r\n10-2\r\n*4\r\n$1\r\na\r\n$1\r\n1\r\n$1\r\nb\r\n$1\r\n2\r\n"
+        );
+
+        let parsed: StreamEntry<BTreeMap<&str, &str>> = de::from_bytes(&data).unwrap();
+        assert_eq!(parsed, entry);
+    }
+
+    #[test]
+    fn xrange() {
+        let input = b"*2\r\n\
+            *2\r\n$3\r\n1-0\r\n*4\r\n$4\r\nkind\r\n$5\r\nlogin\r\n$4\r\nuser\r\n$1\r\n3\r\n\
+            *2\r\n$3\r\n2-0\r\n*4\r\n$4\r\nuser\r\n$1\r\n4\r\n$4\r\nkind\r\n$6\r\nlogout\r\n";
+
+        let entries: Vec<StreamEntry<Event>> = de::from_bytes(input).unwrap();
+
+        assert_eq!(
+            entries,
+            [
+                StreamEntry {
+                    id: StreamId::new(1, 0),
+                    fields: Event {
+                        kind: "login".to_owned(),
+                        user: RedisString(3)
+                    }
+                },
+                StreamEntry {
+                    id: StreamId::new(2, 0),
+                    fields: Event {
+                        kind: "logout".to_owned(),
+                        user: RedisString(4)
+                    }
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn xread_round_trip() {
+        let reply: XReadResponse<Event> = XReadResponse {
+            streams: Vec::from([
+                (
+                    "a".to_owned(),
+                    Vec::from([StreamEntry {
+                        id: StreamId::new(1, 1),
+                        fields: Event {
+                            kind: "x".to_owned(),
+                            user: RedisString(1),
+                        },
+                    }]),
+                ),
+                ("b".to_owned(), Vec::new()),
+            ]),
+        };
+
+        let data = ser::to_vec(&reply).unwrap();
+        let parsed: XReadResponse<Event> = de::from_bytes(&data).unwrap();
+
+        assert_eq!(parsed, reply);
+        assert_eq!(parsed.get("b"), Some(&[][..]));
+        assert_eq!(parsed.get("c"), None);
+    }
+}
@@ -1,4 +1,5 @@
-use std::{
+use alloc::{string::String, vec::Vec};
+use core::{
     any::type_name,
     fmt::{self, Display},
     marker::PhantomData,
@@ -77,7 +78,7 @@ where
 
 impl<'de, T> de::Deserialize<'de> for RedisString<T>
 where
-    T: de::Deserialize<'de> + ?Sized,
+    T: de::Deserialize<'de>,
 {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -101,7 +102,7 @@ where
 }
 
 /// Internal adapter type for serializers, deserializers, visitors, etc.
-struct RedisStringAdapter<T>(T);
+pub(super) struct RedisStringAdapter<T>(pub(super) T);
 
 impl<S> RedisStringAdapter<S>
 where
@@ -525,7 +526,7 @@ impl<'de, D: de::Deserializer<'de>> de::Deserializer<'de> for RedisStringAdapter
         impl<'de, V: de::Visitor<'de>> de::Visitor<'de> for Visitor<V> {
             type Value = V::Value;
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
                 write!(formatter, "a byte slice containing {:?}", self.name)
             }
 
@@ -597,7 +598,7 @@ impl<'de, D: de::Deserializer<'de>> de::Deserializer<'de> for RedisStringAdapter
         impl<'de, V: de::Visitor<'de>, E2> de::Visitor<'de> for Adapter<V, E2> {
             type Value = V::Value;
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
                 write!(formatter, "a redis byte slice containing ")?;
                 self.inner.expecting(formatter)
             }
@@ -619,7 +620,7 @@ impl<'de, D: de::Deserializer<'de>> de::Deserializer<'de> for RedisStringAdapter
             }
         }
 
-        impl<'de, 'a, E: de::Error> de::EnumAccess<'de> for Adapter<&'a [u8], E> {
+        impl<'de, E: de::Error> de::EnumAccess<'de> for Adapter<&[u8], E> {
             type Error = E;
             type Variant = Adapter<(), E>;
 
@@ -632,7 +633,7 @@ impl<'de, D: de::Deserializer<'de>> de::Deserializer<'de> for RedisStringAdapter
             }
         }
 
-        impl<'de, 'a, E: de::Error> de::EnumAccess<'de> for Adapter<&'a str, E> {
+        impl<'de, E: de::Error> de::EnumAccess<'de> for Adapter<&str, E> {
             type Error = E;
             type Variant = Adapter<(), E>;
 
@@ -747,7 +748,7 @@ where
 {
     type Value = V::Value;
 
-    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
         self.visitor.expecting(formatter)
     }
 
@@ -829,7 +830,7 @@ where
 {
     type Value = V::Value;
 
-    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
         write!(formatter, "{}", type_name::<T>())
     }
 
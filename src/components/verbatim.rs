@@ -0,0 +1,322 @@
+use core::{fmt, marker::PhantomData};
+
+use serde::{de, ser};
+use serde_bytes::Bytes;
+
+/// The newtype struct name used by [`Verbatim`] to request that the
+/// serializer and deserializer handle a RESP3 Verbatim String as a
+/// `(format, content)` pair.
+pub(crate) const VERBATIM_TOKEN: &str = "$seredies::Verbatim";
+
+/**
+Adapter type for RESP3
+[Verbatim Strings](https://redis.io/docs/reference/protocol-spec/#verbatim-strings),
+which are strings with a 3 byte format, like `txt` for plain text or `mkd`
+for markdown. Commands like `LOLWUT`, `INFO`, and `CLIENT INFO` reply with
+Verbatim Strings when the connection uses RESP3.
+
+The RESP [`Deserializer`][crate::de::Deserializer] normally deserializes a
+Verbatim String as its content, discarding the format. A `Verbatim`
+deserializes both of them; it also accepts Simple Strings and Bulk Strings,
+which are given the `txt` format, so the same type works with RESP2
+replies. The content is deserialized from the bytes after the format, so it
+can be a `String`, `&str`, or any other type that can be deserialized from
+bytes.
+
+When serialized with a [RESP3 serializer][crate::ser::Serializer::new_resp3],
+a `Verbatim` is written as a Verbatim String; the content must serialize as
+a string or bytes. RESP2 doesn't have Verbatim Strings, so a RESP2
+serializer writes only the content, as a Bulk String.
+
+# Example
+
+```
+use serde::Serialize;
+use seredies::components::Verbatim;
+use seredies::de::from_bytes;
+use seredies::ser::{to_vec, Serializer};
+
+let reply: Verbatim<&str> = from_bytes(b"=15\r\ntxt:Some string\r\n").unwrap();
+assert_eq!(reply, Verbatim::text("Some string"));
+
+// Without the wrapper, only the content is kept
+let reply: &str = from_bytes(b"=15\r\ntxt:Some string\r\n").unwrap();
+assert_eq!(reply, "Some string");
+
+let mut buffer = Vec::new();
+Verbatim::markdown("# Title")
+    .serialize(Serializer::new_resp3(&mut buffer))
+    .unwrap();
+assert_eq!(buffer, b"=11\r\nmkd:# Title\r\n");
+
+assert_eq!(to_vec(&Verbatim::markdown("# Title")).unwrap(), b"$7\r\n# Title\r\n");
+```
+*/
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Verbatim<T> {
+    /// The format of the string, like `txt` or `mkd`.
+    pub format: [u8; 3],
+
+    /// The content of the string, not including the format.
+    pub content: T,
+}
+
+impl<T> Verbatim<T> {
+    /// Create a new `Verbatim` string with the given format and content.
+    #[inline]
+    #[must_use]
+    pub const fn new(format: [u8; 3], content: T) -> Self {
+        Self { format, content }
+    }
+
+    /// Create a new plain text (`txt`) `Verbatim` string.
+    #[inline]
+    #[must_use]
+    pub const fn text(content: T) -> Self {
+        Self::new(*b"txt", content)
+    }
+
+    /// Create a new markdown (`mkd`) `Verbatim` string.
+    #[inline]
+    #[must_use]
+    pub const fn markdown(content: T) -> Self {
+        Self::new(*b"mkd", content)
+    }
+
+    /// Discard the format and return the content.
+    #[inline]
+    #[must_use]
+    pub fn into_content(self) -> T {
+        self.content
+    }
+}
+
+impl<T> ser::Serialize for Verbatim<T>
+where
+    T: ser::Serialize,
+{
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer
+            .serialize_newtype_struct(VERBATIM_TOKEN, &(Bytes::new(&self.format), &self.content))
+    }
+}
+
+impl<'de, T> de::Deserialize<'de> for Verbatim<T>
+where
+    T: de::Deserialize<'de>,
+{
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_newtype_struct(
+            VERBATIM_TOKEN,
+            VerbatimVisitor {
+                content: PhantomData,
+            },
+        )
+    }
+}
+
+struct VerbatimVisitor<T> {
+    content: PhantomData<T>,
+}
+
+impl<'de, T> de::Visitor<'de> for VerbatimVisitor<T>
+where
+    T: de::Deserialize<'de>,
+{
+    type Value = Verbatim<T>;
+
+    #[inline]
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a verbatim string")
+    }
+
+    #[inline]
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(2, self)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let Format(format) = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+
+        let content = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+        Ok(Verbatim { format, content })
+    }
+}
+
+/// The format of a verbatim string, which is deserialized from exactly 3
+/// bytes.
+struct Format([u8; 3]);
+
+impl<'de> de::Deserialize<'de> for Format {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_bytes(FormatVisitor)
+    }
+}
+
+struct FormatVisitor;
+
+impl<'de> de::Visitor<'de> for FormatVisitor {
+    type Value = Format;
+
+    #[inline]
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a 3 byte verbatim string format")
+    }
+
+    #[inline]
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        v.try_into()
+            .map(Format)
+            .map_err(|_| de::Error::invalid_length(v.len(), &self))
+    }
+
+    #[inline]
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.visit_bytes(v.as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cool_asserts::assert_matches;
+    use serde::Serialize;
+    use serde_test::{assert_tokens, Token};
+
+    use super::*;
+    use crate::{de, ser};
+
+    fn to_resp3(value: &impl Serialize) -> Result<Vec<u8>, ser::Error> {
+        let mut buffer = Vec::new();
+        value.serialize(ser::Serializer::new_resp3(&mut buffer))?;
+        Ok(buffer)
+    }
+
+    #[test]
+    fn round_trip() {
+        let input: &[u8] = b"=15\r\ntxt:Some string\r\n";
+        let value: Verbatim<String> = de::from_bytes(input).unwrap();
+
+        assert_eq!(value, Verbatim::text("Some string".to_owned()));
+        assert_eq!(to_resp3(&value).unwrap(), input);
+    }
+
+    #[test]
+    fn empty_content() {
+        let value: Verbatim<&str> = de::from_bytes(b"=4\r\nmkd:\r\n").unwrap();
+        assert_eq!(value, Verbatim::markdown(""));
+        assert_eq!(to_resp3(&value).unwrap(), b"=4\r\nmkd:\r\n");
+    }
+
+    #[test]
+    fn content_with_newlines() {
+        let value: Verbatim<&str> = de::from_bytes(b"=10\r\ntxt:a\r\nb:c\r\n").unwrap();
+        assert_eq!(value.content, "a\r\nb:c");
+    }
+
+    #[test]
+    fn from_resp2_strings() {
+        let value: Verbatim<&str> = de::from_bytes(b"$5\r\nhello\r\n").unwrap();
+        assert_eq!(value, Verbatim::text("hello"));
+
+        let value: Verbatim<&str> = de::from_bytes(b"+hello\r\n").unwrap();
+        assert_eq!(value, Verbatim::text("hello"));
+    }
+
+    #[test]
+    fn in_array() {
+        let value: Vec<Verbatim<&str>> =
+            de::from_bytes(b"*2\r\n=5\r\ntxt:a\r\n=5\r\nmkd:b\r\n").unwrap();
+        assert_eq!(value, [Verbatim::text("a"), Verbatim::markdown("b")]);
+    }
+
+    #[test]
+    fn missing_format() {
+        assert_matches!(
+            de::from_bytes::<Verbatim<&str>>(b"=3\r\ntxt\r\n"),
+            Err(de::Error::Parse(de::parse::Error::Verbatim))
+        );
+        assert_matches!(
+            de::from_bytes::<&str>(b"=5\r\ntext:\r\n"),
+            Err(de::Error::Parse(de::parse::Error::Verbatim))
+        );
+    }
+
+    #[test]
+    fn not_a_string() {
+        assert_matches!(
+            de::from_bytes::<Verbatim<&str>>(b":10\r\n"),
+            Err(de::Error::Custom(_))
+        );
+    }
+
+    #[test]
+    fn serialize_resp2() {
+        assert_eq!(
+            ser::to_vec(&Verbatim::text("hello")).unwrap(),
+            b"$5\r\nhello\r\n"
+        );
+    }
+
+    #[test]
+    fn serialize_bytes() {
+        let value = Verbatim::text(Bytes::new(b"\xFF"));
+        assert_eq!(to_resp3(&value).unwrap(), b"=5\r\ntxt:\xFF\r\n");
+    }
+
+    #[test]
+    fn serialize_invalid() {
+        assert_matches!(
+            to_resp3(&Verbatim::text(10)),
+            Err(ser::Error::InvalidVerbatimPayload)
+        );
+        assert_matches!(
+            to_resp3(&Verbatim::text(["a"])),
+            Err(ser::Error::InvalidVerbatimPayload)
+        );
+    }
+
+    #[test]
+    fn tokens() {
+        assert_tokens(
+            &Verbatim::text("hello"),
+            &[
+                Token::NewtypeStruct {
+                    name: VERBATIM_TOKEN,
+                },
+                Token::Tuple { len: 2 },
+                Token::BorrowedBytes(b"txt"),
+                Token::BorrowedStr("hello"),
+                Token::TupleEnd,
+            ],
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use alloc::vec::Vec;
+use core::{fmt, marker::PhantomData};
+
+use serde::{de, ser, ser::SerializeSeq as _};
+
+use super::Score;
+
+/**
+Adapter type for sorted set replies that include scores, like `ZRANGE ...
+WITHSCORES`, `ZPOPMIN`, and `ZRANDMEMBER ... WITHSCORES`.
+
+Redis sends these as a flattened array of alternating members and scores,
+with each score sent as a bulk string. A `WithScores` pairs up each member
+with its score, parsed into an `f64` like a [`Score`], so `inf` and `-inf`
+are accepted and NaN is rejected. When serialized, it's flattened back into
+an array of members and scores, again like `Score`.
+
+# Example
+
+```
+use seredies::components::WithScores;
+use seredies::de::from_bytes;
+
+let input = b"*4\r\n$5\r\nalice\r\n$3\r\n1.5\r\n$3\r\nbob\r\n$3\r\ninf\r\n";
+let WithScores(members): WithScores<&str> = from_bytes(input).unwrap();
+
+assert_eq!(members, [("alice", 1.5), ("bob", f64::INFINITY)]);
+```
+*/
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct WithScores<T>(pub Vec<(T, f64)>);
+
+impl<T> ser::Serialize for WithScores<T>
+where
+    T: ser::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(self.0.len() * 2))?;
+
+        for (member, score) in &self.0 {
+            let score = Score::new(*score).ok_or_else(|| ser::Error::custom("score is NaN"))?;
+
+            seq.serialize_element(member)?;
+            seq.serialize_element(&score)?;
+        }
+
+        seq.end()
+    }
+}
+
+impl<'de, T> de::Deserialize<'de> for WithScores<T>
+where
+    T: de::Deserialize<'de>,
+{
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(WithScoresVisitor {
+            member: PhantomData,
+        })
+    }
+}
+
+struct WithScoresVisitor<T> {
+    member: PhantomData<fn() -> T>,
+}
+
+impl<'de, T> de::Visitor<'de> for WithScoresVisitor<T>
+where
+    T: de::Deserialize<'de>,
+{
+    type Value = WithScores<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a flattened array of members and scores")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let mut members = Vec::with_capacity(seq.size_hint().unwrap_or(0) / 2);
+
+        while let Some(member) = seq.next_element()? {
+            let score: Score = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(members.len() * 2 + 1, &self))?;
+
+            members.push((member, score.get()));
+        }
+
+        Ok(WithScores(members))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cool_asserts::assert_matches;
+
+    use super::*;
+    use crate::{de, ser};
+
+    #[test]
+    fn round_trip() {
+        let scores = WithScores(Vec::from([("a", -2.0), ("b", 0.25)]));
+
+        let data = ser::to_vec(&scores).unwrap();
+        assert_eq!(
+            data,
+            b"*4\r\n$1\r\na\r\n$2\r\n-2\r\n$1\r\nb\r\n$4\r\n0.25\r\n"
+        );
+
+        let parsed: WithScores<&str> = de::from_bytes(&data).unwrap();
+        assert_eq!(parsed, scores);
+    }
+
+    #[test]
+    fn empty() {
+        let WithScores(members): WithScores<String> = de::from_bytes(b"*0\r\n").unwrap();
+        assert!(members.is_empty());
+    }
+
+    #[test]
+    fn missing_score() {
+        assert_matches!(
+            de::from_bytes::<WithScores<&str>>(b"*3\r\n$1\r\na\r\n$1\r\n1\r\n$1\r\nb\r\n"),
+            Err(de::Error::Custom(message)) => assert_eq!(
+                message,
+                "invalid length 3, expected a flattened array of members and scores"
+            )
+        );
+    }
+
+    #[test]
+    fn bad_score() {
+        assert_matches!(
+            de::from_bytes::<WithScores<&str>>(b"*2\r\n$1\r\na\r\n$3\r\nnan\r\n"),
+            Err(de::Error::Custom(_))
+        );
+    }
+
+    #[test]
+    fn nan_score() {
+        assert_matches!(
+            ser::to_vec(&WithScores(Vec::from([("a", f64::NAN)]))),
+            Err(ser::Error::Custom(_))
+        );
+    }
+}
@@ -0,0 +1,320 @@
+use core::{fmt, marker::PhantomData};
+
+use serde::{de, ser};
+
+/// The enum name used by [`OrWrongType`] to request that the deserializer
+/// report `WRONGTYPE` errors as the `WrongType` variant.
+pub(crate) const OR_WRONG_TYPE_TOKEN: &str = "$seredies::OrWrongType";
+
+const VARIANTS: &[&str] = &["Ok", "WrongType"];
+
+/// The error code Redis uses when a command is used against a key holding
+/// the wrong kind of value.
+const WRONG_TYPE_CODE: &[u8] = b"WRONGTYPE";
+
+/// The full error Redis sends for a `WRONGTYPE` error, used when serializing
+/// [`OrWrongType::WrongType`].
+const WRONG_TYPE_MESSAGE: &str =
+    "WRONGTYPE Operation against a key holding the wrong kind of value";
+
+/// True if this Redis error message has the `WRONGTYPE` error code.
+#[inline]
+#[must_use]
+pub(crate) fn is_wrong_type(message: &[u8]) -> bool {
+    message.split(|&b| b == b' ').next() == Some(WRONG_TYPE_CODE)
+}
+
+/**
+Adapter type that deserializes a `WRONGTYPE` error as a value, rather than
+failing.
+
+Redis replies with a `-WRONGTYPE` error when a command is used against a
+key holding the wrong kind of value, like `LRANGE` on a hash. Tools that
+scan a keyspace full of different kinds of keys, such as migration scripts,
+usually want to skip or report those keys rather than give up. An
+`OrWrongType` deserializes as [`OrWrongType::WrongType`] for a `WRONGTYPE`
+error, and as [`OrWrongType::Ok`] containing the `T` for anything else. Other
+errors are still errors, unless `T` itself handles them (for instance,
+because it's a [`Result`]).
+
+When serialized, `Ok` is serialized as the inner value, and `WrongType` as a
+`WRONGTYPE` error with Redis's usual message.
+
+# Example
+
+```
+use seredies::components::OrWrongType;
+use seredies::de::{from_bytes, Error};
+
+type Items<'a> = OrWrongType<Vec<&'a str>>;
+
+let reply: Items = from_bytes(b"*2\r\n$1\r\na\r\n$1\r\nb\r\n").unwrap();
+assert_eq!(reply, OrWrongType::Ok(Vec::from(["a", "b"])));
+
+let reply: Items = from_bytes(
+    b"-WRONGTYPE Operation against a key holding the wrong kind of value\r\n",
+).unwrap();
+assert!(reply.is_wrong_type());
+
+// Other errors are still errors
+let reply = from_bytes::<Items>(b"-ERR unknown command\r\n");
+assert!(matches!(reply, Err(Error::Redis(_))));
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum OrWrongType<T> {
+    /// The reply was successfully deserialized
+    Ok(T),
+
+    /// The reply was a `WRONGTYPE` error
+    WrongType,
+}
+
+impl<T> OrWrongType<T> {
+    /// True if the reply was a `WRONGTYPE` error.
+    #[inline]
+    #[must_use]
+    pub fn is_wrong_type(&self) -> bool {
+        matches!(*self, OrWrongType::WrongType)
+    }
+
+    /// Get the reply, or `None` if it was a `WRONGTYPE` error.
+    #[inline]
+    #[must_use]
+    pub fn ok(self) -> Option<T> {
+        match self {
+            OrWrongType::Ok(value) => Some(value),
+            OrWrongType::WrongType => None,
+        }
+    }
+}
+
+impl<T> From<OrWrongType<T>> for Option<T> {
+    #[inline]
+    fn from(value: OrWrongType<T>) -> Self {
+        value.ok()
+    }
+}
+
+impl<T> ser::Serialize for OrWrongType<T>
+where
+    T: ser::Serialize,
+{
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        match *self {
+            OrWrongType::Ok(ref value) => value.serialize(serializer),
+            OrWrongType::WrongType => {
+                serializer.serialize_newtype_variant("Result", 1, "Err", WRONG_TYPE_MESSAGE)
+            }
+        }
+    }
+}
+
+impl<'de, T> de::Deserialize<'de> for OrWrongType<T>
+where
+    T: de::Deserialize<'de>,
+{
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_enum(
+            OR_WRONG_TYPE_TOKEN,
+            VARIANTS,
+            OrWrongTypeVisitor { value: PhantomData },
+        )
+    }
+}
+
+struct OrWrongTypeVisitor<T> {
+    value: PhantomData<fn() -> T>,
+}
+
+impl<'de, T> de::Visitor<'de> for OrWrongTypeVisitor<T>
+where
+    T: de::Deserialize<'de>,
+{
+    type Value = OrWrongType<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a value or a WRONGTYPE error")
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::EnumAccess<'de>,
+    {
+        use de::VariantAccess as _;
+
+        let (kind, variant) = data.variant()?;
+
+        match kind {
+            Kind::Ok => variant.newtype_variant().map(OrWrongType::Ok),
+            Kind::WrongType => variant.unit_variant().map(|()| OrWrongType::WrongType),
+        }
+    }
+}
+
+enum Kind {
+    Ok,
+    WrongType,
+}
+
+impl<'de> de::Deserialize<'de> for Kind {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_identifier(KindVisitor)
+    }
+}
+
+struct KindVisitor;
+
+impl de::Visitor<'_> for KindVisitor {
+    type Value = Kind;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("Ok or WrongType")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        match v {
+            "Ok" => Ok(Kind::Ok),
+            "WrongType" => Ok(Kind::WrongType),
+            _ => Err(E::unknown_variant(v, VARIANTS)),
+        }
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        match v {
+            b"Ok" => Ok(Kind::Ok),
+            b"WrongType" => Ok(Kind::WrongType),
+            _ => Err(E::invalid_value(de::Unexpected::Bytes(v), &self)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use cool_asserts::assert_matches;
+    use serde_test::{assert_de_tokens, Token};
+
+    use super::*;
+    use crate::{components::RedisError, de, ser};
+
+    const WRONG_TYPE: &[u8] =
+        b"-WRONGTYPE Operation against a key holding the wrong kind of value\r\n";
+
+    #[test]
+    fn ok() {
+        let value: OrWrongType<Vec<i64>> = de::from_bytes(b"*2\r\n:1\r\n:2\r\n").unwrap();
+        assert_eq!(value, OrWrongType::Ok(Vec::from([1, 2])));
+    }
+
+    #[test]
+    fn wrong_type() {
+        let value: OrWrongType<HashMap<String, String>> = de::from_bytes(WRONG_TYPE).unwrap();
+        assert_eq!(value, OrWrongType::WrongType);
+        assert_eq!(value.ok(), None);
+    }
+
+    #[test]
+    fn bare_code() {
+        let value: OrWrongType<i64> = de::from_bytes(b"-WRONGTYPE\r\n").unwrap();
+        assert!(value.is_wrong_type());
+    }
+
+    #[test]
+    fn other_errors() {
+        assert_matches!(
+            de::from_bytes::<OrWrongType<i64>>(b"-ERR syntax error\r\n"),
+            Err(de::Error::Redis(message)) => assert_eq!(message, b"ERR syntax error")
+        );
+
+        // The code has to match exactly
+        assert_matches!(
+            de::from_bytes::<OrWrongType<i64>>(b"-WRONGTYPES x\r\n"),
+            Err(de::Error::Redis(_))
+        );
+    }
+
+    #[test]
+    fn nested_result() {
+        type Reply<'a> = OrWrongType<Result<i64, RedisError<&'a str, &'a str>>>;
+
+        let value: Reply = de::from_bytes(b"-ERR syntax error\r\n").unwrap();
+        assert_eq!(
+            value,
+            OrWrongType::Ok(Err(RedisError {
+                code: "ERR",
+                message: "syntax error"
+            }))
+        );
+
+        let value: Reply = de::from_bytes(WRONG_TYPE).unwrap();
+        assert_eq!(value, OrWrongType::WrongType);
+    }
+
+    #[test]
+    fn ok_reply() {
+        let value: OrWrongType<()> = de::from_bytes(b"+OK\r\n").unwrap();
+        assert_eq!(value, OrWrongType::Ok(()));
+    }
+
+    #[test]
+    fn in_array() {
+        let input = b"*3\r\n:1\r\n-WRONGTYPE wrong kind\r\n:3\r\n";
+        let value: Vec<OrWrongType<i64>> = de::from_bytes(input).unwrap();
+
+        assert_eq!(
+            value,
+            [
+                OrWrongType::Ok(1),
+                OrWrongType::WrongType,
+                OrWrongType::Ok(3),
+            ]
+        );
+    }
+
+    #[test]
+    fn serialize() {
+        assert_eq!(ser::to_vec(&OrWrongType::Ok(10)).unwrap(), b":10\r\n");
+        assert_eq!(
+            ser::to_vec(&OrWrongType::<i64>::WrongType).unwrap(),
+            WRONG_TYPE
+        );
+    }
+
+    #[test]
+    fn tokens() {
+        assert_de_tokens(
+            &OrWrongType::Ok(5),
+            &[
+                Token::Enum {
+                    name: OR_WRONG_TYPE_TOKEN,
+                },
+                Token::Str("Ok"),
+                Token::I32(5),
+            ],
+        );
+        assert_de_tokens(
+            &OrWrongType::<i32>::WrongType,
+            &[
+                Token::Enum {
+                    name: OR_WRONG_TYPE_TOKEN,
+                },
+                Token::Str("WrongType"),
+                Token::Unit,
+            ],
+        );
+    }
+}
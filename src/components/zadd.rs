@@ -0,0 +1,445 @@
+use alloc::string::ToString;
+use core::fmt::{self, Write as _};
+
+use arrayvec::ArrayString;
+use serde::{de, ser, ser::SerializeTuple as _};
+
+/**
+A sorted set score, as used by commands like
+[`ZADD`](https://redis.io/commands/zadd/) and
+[`ZSCORE`](https://redis.io/commands/zscore/).
+
+Redis rejects NaN scores, so a `Score` can never be NaN; this is checked
+when it's created (and when it's deserialized). Infinities are allowed, and
+are sent as `+inf` and `-inf`.
+
+# Example
+
+```
+use seredies::components::Score;
+use seredies::de::from_bytes;
+use seredies::ser::to_vec;
+
+assert!(Score::new(f64::NAN).is_none());
+
+let score = Score::new(1.5).unwrap();
+assert_eq!(to_vec(&score).unwrap(), b"$3\r\n1.5\r\n");
+assert_eq!(to_vec(&Score::INFINITY).unwrap(), b"$4\r\n+inf\r\n");
+
+let score: Score = from_bytes(b"$4\r\n-inf\r\n").unwrap();
+assert_eq!(score, Score::NEG_INFINITY);
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Score(f64);
+
+impl Score {
+    /// Positive infinity, sent to Redis as `+inf`.
+    pub const INFINITY: Self = Self(f64::INFINITY);
+
+    /// Negative infinity, sent to Redis as `-inf`.
+    pub const NEG_INFINITY: Self = Self(f64::NEG_INFINITY);
+
+    /// Create a new score, or return `None` if `value` is NaN.
+    #[inline]
+    #[must_use]
+    pub fn new(value: f64) -> Option<Self> {
+        (!value.is_nan()).then_some(Self(value))
+    }
+
+    /// Get the score as an `f64`. It's guaranteed not to be NaN.
+    #[inline]
+    #[must_use]
+    pub const fn get(self) -> f64 {
+        self.0
+    }
+}
+
+impl Eq for Score {}
+
+impl From<i32> for Score {
+    #[inline]
+    fn from(value: i32) -> Self {
+        Self(value.into())
+    }
+}
+
+impl ser::Serialize for Score {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self.0 {
+            f64::INFINITY => serializer.serialize_str("+inf"),
+            f64::NEG_INFINITY => serializer.serialize_str("-inf"),
+            value => {
+                // The longest possible f64 representation is a bit over 300
+                // bytes (for values near f64::MAX), so fall back to an
+                // allocation for those.
+                let mut buffer: ArrayString<64> = ArrayString::new();
+
+                match write!(&mut buffer, "{value}") {
+                    Ok(()) => serializer.serialize_str(&buffer),
+                    Err(_) => serializer.serialize_str(&value.to_string()),
+                }
+            }
+        }
+    }
+}
+
+impl<'de> de::Deserialize<'de> for Score {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = Score;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(formatter, "a sorted set score")
+            }
+
+            fn visit_f64<E>(self, v: f64) -> Result<Score, E>
+            where
+                E: de::Error,
+            {
+                Score::new(v).ok_or_else(|| E::invalid_value(de::Unexpected::Float(v), &self))
+            }
+
+            fn visit_i64<E>(self, v: i64) -> Result<Score, E>
+            where
+                E: de::Error,
+            {
+                Ok(Score(v as f64))
+            }
+
+            fn visit_u64<E>(self, v: u64) -> Result<Score, E>
+            where
+                E: de::Error,
+            {
+                Ok(Score(v as f64))
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Score, E>
+            where
+                E: de::Error,
+            {
+                v.parse()
+                    .ok()
+                    .and_then(Score::new)
+                    .ok_or_else(|| E::invalid_value(de::Unexpected::Str(v), &self))
+            }
+
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Score, E>
+            where
+                E: de::Error,
+            {
+                match core::str::from_utf8(v) {
+                    Ok(v) => self.visit_str(v),
+                    Err(_) => Err(E::invalid_value(de::Unexpected::Bytes(v), &self)),
+                }
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
+}
+
+/// The condition under which [`ZADD`](https://redis.io/commands/zadd/)
+/// updates a member. Redis only permits certain combinations of the `NX`,
+/// `XX`, `GT`, and `LT` flags; this enum contains exactly the valid ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ZaddCondition {
+    /// `NX`: only add new members; never update existing ones.
+    IfAbsent,
+
+    /// `XX`: only update existing members; never add new ones.
+    IfExists,
+
+    /// `GT`: only update existing members if the new score is greater. New
+    /// members are still added.
+    IfGreater,
+
+    /// `LT`: only update existing members if the new score is less. New
+    /// members are still added.
+    IfLess,
+
+    /// `XX GT`: only update existing members, and only if the new score is
+    /// greater.
+    IfExistsAndGreater,
+
+    /// `XX LT`: only update existing members, and only if the new score is
+    /// less.
+    IfExistsAndLess,
+}
+
+impl ZaddCondition {
+    /// Get the flags for this condition, in the order Redis requires.
+    #[must_use]
+    pub const fn flags(self) -> &'static [&'static str] {
+        match self {
+            ZaddCondition::IfAbsent => &["NX"],
+            ZaddCondition::IfExists => &["XX"],
+            ZaddCondition::IfGreater => &["GT"],
+            ZaddCondition::IfLess => &["LT"],
+            ZaddCondition::IfExistsAndGreater => &["XX", "GT"],
+            ZaddCondition::IfExistsAndLess => &["XX", "LT"],
+        }
+    }
+}
+
+/// Serialize a list of flags as a tuple, which a
+/// [`Command`][crate::components::Command] flattens into its arguments.
+fn serialize_flags<S: ser::Serializer>(
+    serializer: S,
+    condition: Option<ZaddCondition>,
+    tail: Option<&'static str>,
+) -> Result<S::Ok, S::Error> {
+    let flags = condition.map(ZaddCondition::flags).unwrap_or_default();
+    let mut tuple = serializer.serialize_tuple(flags.len() + tail.is_some() as usize)?;
+
+    flags
+        .iter()
+        .chain(tail.as_ref())
+        .try_for_each(|flag| tuple.serialize_element(flag))?;
+
+    tuple.end()
+}
+
+/// Options for a [`ZADD`](https://redis.io/commands/zadd/) command.
+///
+/// This trait is sealed; it's implemented only by [`ZaddOptions`] and
+/// [`ZaddIncrOptions`], since the reply type depends on how Redis treats
+/// the options.
+pub trait ZaddMode: ser::Serialize + private::Sealed {
+    /// The type of the reply to a `ZADD` with these options.
+    type Reply: de::DeserializeOwned;
+}
+
+/**
+The options for a regular [`ZADD`](https://redis.io/commands/zadd/), which
+replies with the number of members added (or, with
+[`changed`][ZaddOptions::changed], the number added or updated).
+
+When used as a field in a [`Command`][crate::components::Command], it
+serializes as its flags, in the order Redis requires.
+
+# Example
+
+```
+use serde::Serialize;
+use serde_test::{assert_ser_tokens, Token};
+use seredies::components::{Command, Score, ZaddCondition, ZaddOptions};
+
+#[derive(Serialize)]
+#[serde(rename = "ZADD")]
+struct Zadd {
+    key: &'static str,
+    options: ZaddOptions,
+    score: Score,
+    member: &'static str,
+}
+
+assert_ser_tokens(
+    &Command(Zadd {
+        key: "leaderboard",
+        options: ZaddOptions {
+            condition: Some(ZaddCondition::IfExistsAndGreater),
+            changed: true,
+        },
+        score: Score::from(100),
+        member: "alice",
+    }),
+    &[
+        Token::Seq { len: Some(7) },
+        Token::Str("ZADD"),
+        Token::Str("leaderboard"),
+        Token::Str("XX"),
+        Token::Str("GT"),
+        Token::Str("CH"),
+        Token::Str("100"),
+        Token::Str("alice"),
+        Token::SeqEnd,
+    ],
+);
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ZaddOptions {
+    /// The condition under which members are added or updated.
+    pub condition: Option<ZaddCondition>,
+
+    /// `CH`: reply with the number of members that were added *or updated*,
+    /// rather than just the number added.
+    pub changed: bool,
+}
+
+impl ser::Serialize for ZaddOptions {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serialize_flags(serializer, self.condition, self.changed.then_some("CH"))
+    }
+}
+
+impl private::Sealed for ZaddOptions {}
+
+impl ZaddMode for ZaddOptions {
+    type Reply = i64;
+}
+
+/**
+The options for a [`ZADD`](https://redis.io/commands/zadd/) with the `INCR`
+flag, which behaves like [`ZINCRBY`](https://redis.io/commands/zincrby/).
+Only a single score-member pair may be given, and the reply is the new score
+of the member, or null if the condition prevented the update.
+
+When used as a field in a [`Command`][crate::components::Command], it
+serializes as its condition flags followed by `INCR`.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ZaddIncrOptions {
+    /// The condition under which the member is added or updated.
+    pub condition: Option<ZaddCondition>,
+}
+
+impl ser::Serialize for ZaddIncrOptions {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serialize_flags(serializer, self.condition, Some("INCR"))
+    }
+}
+
+impl private::Sealed for ZaddIncrOptions {}
+
+impl ZaddMode for ZaddIncrOptions {
+    type Reply = Option<Score>;
+}
+
+mod private {
+    pub trait Sealed {}
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+    use serde_test::{assert_ser_tokens, Token};
+
+    use super::*;
+    use crate::{components::Command, de::from_bytes, ser::to_vec};
+
+    #[derive(Serialize)]
+    #[serde(rename = "ZADD")]
+    struct Zadd<O> {
+        key: &'static str,
+        options: O,
+        score: Score,
+        member: &'static str,
+    }
+
+    fn test_zadd<O: Serialize>(options: O, flags: &[&'static str]) {
+        let mut tokens = Vec::from([
+            Token::Seq {
+                len: Some(4 + flags.len()),
+            },
+            Token::Str("ZADD"),
+            Token::Str("key"),
+        ]);
+        tokens.extend(flags.iter().map(|&flag| Token::Str(flag)));
+        tokens.extend([Token::Str("-2.5"), Token::Str("member"), Token::SeqEnd]);
+
+        assert_ser_tokens(
+            &Command(Zadd {
+                key: "key",
+                options,
+                score: Score::new(-2.5).unwrap(),
+                member: "member",
+            }),
+            &tokens,
+        );
+    }
+
+    #[test]
+    fn no_options() {
+        test_zadd(ZaddOptions::default(), &[]);
+    }
+
+    #[test]
+    fn every_condition() {
+        let cases = [
+            (ZaddCondition::IfAbsent, &["NX"][..]),
+            (ZaddCondition::IfExists, &["XX"]),
+            (ZaddCondition::IfGreater, &["GT"]),
+            (ZaddCondition::IfLess, &["LT"]),
+            (ZaddCondition::IfExistsAndGreater, &["XX", "GT"]),
+            (ZaddCondition::IfExistsAndLess, &["XX", "LT"]),
+        ];
+
+        for (condition, flags) in cases {
+            test_zadd(
+                ZaddOptions {
+                    condition: Some(condition),
+                    changed: false,
+                },
+                flags,
+            );
+
+            let mut with_ch = flags.to_vec();
+            with_ch.push("CH");
+            test_zadd(
+                ZaddOptions {
+                    condition: Some(condition),
+                    changed: true,
+                },
+                &with_ch,
+            );
+
+            let mut with_incr = flags.to_vec();
+            with_incr.push("INCR");
+            test_zadd(
+                ZaddIncrOptions {
+                    condition: Some(condition),
+                },
+                &with_incr,
+            );
+        }
+    }
+
+    #[test]
+    fn incr_reply() {
+        type Reply = <ZaddIncrOptions as ZaddMode>::Reply;
+
+        let reply: Reply = from_bytes(b"$3\r\n3.5\r\n").unwrap();
+        assert_eq!(reply, Score::new(3.5));
+
+        let reply: Reply = from_bytes(b"$-1\r\n").unwrap();
+        assert_eq!(reply, None);
+    }
+
+    #[test]
+    fn score_round_trip() {
+        for value in [0.0, -0.0, 1.0, -1.5, 1e-300, f64::MAX, f64::MIN_POSITIVE] {
+            let score = Score::new(value).unwrap();
+            let decoded: Score = from_bytes(&to_vec(&score).unwrap()).unwrap();
+            assert_eq!(decoded.get().to_bits(), value.to_bits());
+        }
+    }
+
+    #[test]
+    fn reject_nan() {
+        assert!(from_bytes::<Score>(b"$3\r\nnan\r\n").is_err());
+        assert!(from_bytes::<Score>(b"$3\r\nNaN\r\n").is_err());
+        assert!(from_bytes::<Score>(b"$3\r\nabc\r\n").is_err());
+    }
+
+    #[test]
+    fn integer_score() {
+        let score: Score = from_bytes(b":10\r\n").unwrap();
+        assert_eq!(score, Score::from(10));
+    }
+}
@@ -0,0 +1,323 @@
+/*!
+Protocol configuration shared by the serializer and the deserializer.
+*/
+
+use crate::{de::UnitSimpleString, protocol::Version};
+
+/**
+Options for the RESP [`Serializer`][crate::ser::Serializer] and
+[`Deserializer`][crate::de::Deserializer].
+
+An application usually talks to a server in one consistent way, so a
+`Config` can be built once and passed to
+[`Serializer::with_config`][crate::ser::Serializer::with_config],
+[`Deserializer::with_config`][crate::de::Deserializer::with_config], or
+the `_with_config` variants of the convenience functions, like
+[`to_vec_with_config`][crate::ser::to_vec_with_config] and
+[`from_bytes_with_config`][crate::de::from_bytes_with_config]. Each option
+applies only to the side that it affects, and is ignored by the other. For
+the serializer, these are the protocol version, the depth limit, string
+floats, `Ok(())` as a null, and space reservation.
+
+The default configuration is the same as that of
+[`Serializer::new`][crate::ser::Serializer::new] and
+[`Deserializer::new`][crate::de::Deserializer::new].
+
+# Example
+
+```
+use seredies::{de, ser, Config};
+use seredies::protocol::Version;
+
+let config = Config::new()
+    .with_version(Version::Resp2)
+    .with_max_depth(2)
+    .with_string_floats(true);
+
+let command = ("ZINCRBY", "scores", 1.5, "alice");
+let buffer = ser::to_vec_with_config(&command, config).unwrap();
+assert_eq!(
+    buffer,
+    b"*4\r\n$7\r\nZINCRBY\r\n$6\r\nscores\r\n$3\r\n1.5\r\n$5\r\nalice\r\n"
+);
+
+let score: f64 = de::from_bytes_with_config(b"$3\r\n2.5\r\n", config).unwrap();
+assert_eq!(score, 2.5);
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Config {
+    pub(crate) version: Version,
+    pub(crate) max_depth: Option<usize>,
+    pub(crate) string_floats: bool,
+    pub(crate) unit_simple_string: UnitSimpleString,
+    pub(crate) flatten_maps: bool,
+    pub(crate) integer_bools: bool,
+    pub(crate) error_strings: bool,
+    pub(crate) max_array_len: Option<usize>,
+    pub(crate) max_total_len: Option<usize>,
+    pub(crate) ok_as_null: bool,
+    pub(crate) no_reserve: bool,
+    pub(crate) keepalives: &'static [&'static [u8]],
+}
+
+impl Config {
+    /// Create a new configuration with the default options.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            version: Version::Resp2,
+            max_depth: None,
+            string_floats: false,
+            unit_simple_string: UnitSimpleString::Ok,
+            flatten_maps: false,
+            integer_bools: false,
+            error_strings: false,
+            max_array_len: None,
+            max_total_len: None,
+            ok_as_null: false,
+            no_reserve: false,
+            keepalives: &[],
+        }
+    }
+
+    /// Set the protocol version used by the serializer. See
+    /// [`Serializer::new_resp3`][crate::ser::Serializer::new_resp3] for the
+    /// differences with RESP3.
+    #[inline]
+    #[must_use]
+    pub const fn with_version(self, version: Version) -> Self {
+        Self { version, ..self }
+    }
+
+    /// Limit the nesting depth of arrays, for both serialization and
+    /// deserialization. See
+    /// [`Serializer::with_max_depth`][crate::ser::Serializer::with_max_depth]
+    /// and [`Deserializer::with_max_depth`][crate::de::Deserializer::with_max_depth].
+    /// Without a limit, the serializer allows any depth, and the deserializer
+    /// uses [`DEFAULT_MAX_DEPTH`][crate::de::DEFAULT_MAX_DEPTH].
+    #[inline]
+    #[must_use]
+    pub const fn with_max_depth(self, limit: usize) -> Self {
+        Self {
+            max_depth: Some(limit),
+            ..self
+        }
+    }
+
+    /// Serialize `Ok(())` as a null rather than as `+OK`. See
+    /// [`Serializer::with_ok_as_null`][crate::ser::Serializer::with_ok_as_null].
+    #[inline]
+    #[must_use]
+    pub const fn with_ok_as_null(self, enabled: bool) -> Self {
+        Self {
+            ok_as_null: enabled,
+            ..self
+        }
+    }
+
+    /// Enable or disable the serializer's space reservation. See
+    /// [`Serializer::with_reserve`][crate::ser::Serializer::with_reserve].
+    #[inline]
+    #[must_use]
+    pub const fn with_reserve(self, enabled: bool) -> Self {
+        Self {
+            no_reserve: !enabled,
+            ..self
+        }
+    }
+
+    /// Serialize and deserialize floats as strings. See
+    /// [`Serializer::with_string_floats`][crate::ser::Serializer::with_string_floats]
+    /// and
+    /// [`Deserializer::with_string_floats`][crate::de::Deserializer::with_string_floats].
+    #[inline]
+    #[must_use]
+    pub const fn with_string_floats(self, enabled: bool) -> Self {
+        Self {
+            string_floats: enabled,
+            ..self
+        }
+    }
+
+    /// Choose which Simple Strings the deserializer will accept as a unit.
+    /// See
+    /// [`Deserializer::with_unit_simple_string`][crate::de::Deserializer::with_unit_simple_string].
+    #[inline]
+    #[must_use]
+    pub const fn with_unit_simple_string(self, policy: UnitSimpleString) -> Self {
+        Self {
+            unit_simple_string: policy,
+            ..self
+        }
+    }
+
+    /// Deserialize maps and structs from flattened arrays of key-value
+    /// pairs. See
+    /// [`Deserializer::with_flattened_maps`][crate::de::Deserializer::with_flattened_maps].
+    #[inline]
+    #[must_use]
+    pub const fn with_flattened_maps(self, enabled: bool) -> Self {
+        Self {
+            flatten_maps: enabled,
+            ..self
+        }
+    }
+
+    /// Present the integers 0 and 1 as booleans to types that accept any
+    /// value. See
+    /// [`Deserializer::with_integer_bools`][crate::de::Deserializer::with_integer_bools].
+    #[inline]
+    #[must_use]
+    pub const fn with_integer_bools(self, enabled: bool) -> Self {
+        Self {
+            integer_bools: enabled,
+            ..self
+        }
+    }
+
+    /// Present Redis errors as strings of their message, instead of failing.
+    /// See
+    /// [`Deserializer::with_error_strings`][crate::de::Deserializer::with_error_strings].
+    #[inline]
+    #[must_use]
+    pub const fn with_error_strings(self, enabled: bool) -> Self {
+        Self {
+            error_strings: enabled,
+            ..self
+        }
+    }
+
+    /// Skip keepalive frames before each top-level value. See
+    /// [`Deserializer::with_keepalives`][crate::de::Deserializer::with_keepalives].
+    #[inline]
+    #[must_use]
+    pub const fn with_keepalives(self, keepalives: &'static [&'static [u8]]) -> Self {
+        Self { keepalives, ..self }
+    }
+
+    /// Limit the number of elements in each deserialized array. See
+    /// [`Deserializer::with_max_array_len`][crate::de::Deserializer::with_max_array_len].
+    #[inline]
+    #[must_use]
+    pub const fn with_max_array_len(self, limit: usize) -> Self {
+        Self {
+            max_array_len: Some(limit),
+            ..self
+        }
+    }
+
+    /// Limit the total size of each deserialized value. See
+    /// [`Deserializer::with_max_total_len`][crate::de::Deserializer::with_max_total_len].
+    #[inline]
+    #[must_use]
+    pub const fn with_max_total_len(self, limit: usize) -> Self {
+        Self {
+            max_total_len: Some(limit),
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use cool_asserts::assert_matches;
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::{de, ser};
+
+    #[test]
+    fn default_is_new() {
+        assert_eq!(Config::default(), Config::new());
+    }
+
+    #[test]
+    fn resp3() {
+        let config = Config::new().with_version(Version::Resp3);
+        let data = BTreeMap::from([("a", 1.5)]);
+
+        assert_eq!(
+            ser::to_vec_with_config(&data, config).unwrap(),
+            b"%1\r\n$1\r\na\r\n,1.5\r\n"
+        );
+    }
+
+    #[test]
+    fn max_depth() {
+        let config = Config::new().with_max_depth(1);
+
+        assert_matches!(
+            ser::to_vec_with_config(&[[1]], config),
+            Err(ser::Error::DepthLimit(1))
+        );
+        assert_eq!(
+            ser::to_string_with_config(&[1], config).unwrap(),
+            "*1\r\n:1\r\n"
+        );
+    }
+
+    #[test]
+    fn string_floats() {
+        let config = Config::new().with_string_floats(true);
+
+        assert_eq!(
+            ser::to_vec_with_config(&0.5, config).unwrap(),
+            b"$3\r\n0.5\r\n"
+        );
+        assert_eq!(
+            de::from_str_with_config::<f64>("$3\r\n0.5\r\n", config).unwrap(),
+            0.5
+        );
+    }
+
+    #[test]
+    fn deserializer_options() {
+        #[derive(Deserialize, Debug, PartialEq, Eq)]
+        struct Reply {
+            status: (),
+        }
+
+        let config = Config::new()
+            .with_unit_simple_string(UnitSimpleString::Any)
+            .with_flattened_maps(true);
+
+        let reply: Reply =
+            de::from_bytes_with_config(b"*2\r\n$6\r\nstatus\r\n+QUEUED\r\n", config).unwrap();
+        assert_eq!(reply, Reply { status: () });
+
+        assert_matches!(
+            de::from_bytes::<Reply>(b"*2\r\n$6\r\nstatus\r\n+QUEUED\r\n"),
+            Err(de::Error::Custom(_))
+        );
+    }
+
+    #[test]
+    fn error_strings() {
+        let config = Config::new().with_error_strings(true);
+        let exec = b"*3\r\n+OK\r\n-ERR value is not an integer\r\n$1\r\n2\r\n";
+
+        let replies: Vec<String> = de::from_bytes_with_config(exec, config).unwrap();
+        assert_eq!(replies, ["OK", "ERR value is not an integer", "2"]);
+
+        assert_matches!(
+            de::from_bytes::<Vec<String>>(exec),
+            Err(de::Error::Redis(_))
+        );
+
+        // Results still see errors as errors
+        let replies: Vec<Result<String, String>> =
+            de::from_bytes_with_config(exec, config).unwrap();
+        assert_eq!(
+            replies,
+            [
+                Ok("OK".to_owned()),
+                Err("ERR value is not an integer".to_owned()),
+                Ok("2".to_owned())
+            ]
+        );
+    }
+}
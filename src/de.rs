@@ -84,20 +84,148 @@ let result: BoringResult = from_bytes(b"-ERR error message\r\n")
 assert_eq!(result, Err("ERR error message"));
 ```
 
+# Fixed-size byte arrays
+
+Binary values of a known size, like the SHA1 digest returned by
+`SCRIPT LOAD`, can be deserialized from a bulk string directly into a
+`[u8; N]`. The length in the bulk string header is checked before the
+payload is read, and a mismatch is reported as [`Error::ArrayLength`]. To
+borrow the bytes instead, use [`&serde_bytes::ByteArray<N>`][serde_bytes::ByteArray].
+
+```
+use seredies::de::{from_bytes, Error};
+
+let digest: [u8; 4] = from_bytes(b"$4\r\n\x12\x34\x56\x78\r\n").unwrap();
+assert_eq!(digest, [0x12, 0x34, 0x56, 0x78]);
+
+let borrowed: &serde_bytes::ByteArray<4> = from_bytes(b"$4\r\nabcd\r\n").unwrap();
+assert_eq!(**borrowed, *b"abcd");
+
+let error = from_bytes::<[u8; 20]>(b"$3\r\nabc\r\n").unwrap_err();
+assert!(matches!(error, Error::ArrayLength { expected: 20, actual: 3 }));
+```
+
+# Configuration
+
+Options that change how the deserializer behaves are collected in a
+[`Config`], which is built once and passed to [`Deserializer::with_config`]
+or [`from_bytes_with_config`]. Each option also has a builder method on
+[`Deserializer`], like [`Deserializer::with_max_depth`], for one-off use.
+New options are added to `Config`, so these entry points don't change.
+
+The deserializer-side options are:
+
+- [`with_max_depth`][Config::with_max_depth],
+  [`with_max_array_len`][Config::with_max_array_len], and
+  [`with_max_total_len`][Config::with_max_total_len], which limit the shape
+  and size of untrusted input.
+- [`with_string_floats`][Config::with_string_floats], which parses floats
+  from strings.
+- [`with_unit_simple_string`][Config::with_unit_simple_string], which
+  chooses the Simple Strings that can be deserialized as `()`.
+- [`with_flattened_maps`][Config::with_flattened_maps], which deserializes
+  maps and structs from flattened arrays of key-value pairs.
+- [`with_integer_bools`][Config::with_integer_bools], which presents the
+  integers 0 and 1 as booleans.
+- [`with_error_strings`][Config::with_error_strings], which presents Redis
+  errors as strings instead of failing.
+
+[`ReplyHint`] chooses these options for the replies to well-known commands,
+so that RESP2 replies are presented the way a RESP3 server would send them.
+
+There's no option to choose between RESP2 and RESP3, because the deserializer
+always accepts the RESP3 types that it supports. Likewise, whether a Redis
+error is a value or a failure is usually chosen by the type being
+deserialized: see the error example above, as well as
+[`Value`][crate::value::Value] and
+[`OrWrongType`][crate::components::OrWrongType]. `with_error_strings` is
+for types that can't make that choice, like untagged enums.
+
+```
+use serde::Deserialize;
+use seredies::de::{from_bytes_with_config, Deserializer, Error};
+use seredies::Config;
+
+let config = Config::new()
+    .with_max_depth(4)
+    .with_max_array_len(1000)
+    .with_string_floats(true);
+
+let scores: Vec<f64> = from_bytes_with_config(b"*2\r\n$3\r\n1.5\r\n:2\r\n", config).unwrap();
+assert_eq!(scores, [1.5, 2.0]);
+
+let mut input: &[u8] = b"*3\r\n:1\r\n:2\r\n:3\r\n";
+let deserializer = Deserializer::with_config(&mut input, config.with_max_array_len(2));
+let result = Vec::<i64>::deserialize(deserializer);
+assert!(matches!(result, Err(Error::LengthLimit(2))));
+```
+
 [error type]: https://redis.io/docs/reference/protocol-spec/#resp-errors
 */
 
+#[cfg(feature = "futures-io")]
+mod async_io;
+mod cursor;
+mod fold;
+mod hint;
+mod iter;
+mod message;
+mod mismatch;
 pub mod parse;
+mod pipeline;
+mod push;
+#[cfg(any(feature = "futures-io", feature = "tokio"))]
+mod read_error;
 mod result;
+mod round_trip;
+mod value;
+mod variant;
+mod window;
 
-use std::fmt::Display;
+use alloc::string::{String, ToString};
+use core::{
+    fmt::{self, Display},
+    str::{self, FromStr},
+};
 
 use paste::paste;
 use serde::{de, forward_to_deserialize_any};
-use thiserror::Error;
 
+#[cfg(feature = "futures-io")]
+pub use self::async_io::from_async_reader;
+pub use self::cursor::{ArrayCursor, Progress};
+pub use self::fold::fold_array;
+pub use self::hint::ReplyHint;
+pub use self::iter::ArrayIter;
+pub use self::message::RedisMessage;
+use self::mismatch::{visit_item, VisitError, VisitSeq};
 use self::parse::{ParseResult, TaggedHeader};
+pub use self::pipeline::{
+    from_bytes_pipeline, from_bytes_pipeline_with_config, Pipeline, PipelineError,
+};
+pub use self::push::Incoming;
+use self::push::{IncomingAccess, INCOMING_TOKEN};
+#[cfg(any(feature = "futures-io", feature = "tokio"))]
+pub use self::read_error::ReadError;
 use self::result::ResultAccess;
+pub use self::round_trip::{from_bytes_round_trip, RoundTripError};
+use self::value::ValueAccess;
+use self::variant::UnitVariantAccess;
+pub use self::window::{Window, WindowError};
+use crate::{
+    components::{
+        is_wrong_type, KeyValuePairsDeserializer, FIRST_WINS_TOKEN, LAST_WINS_TOKEN,
+        OR_WRONG_TYPE_TOKEN, VERBATIM_TOKEN, WITH_EXTRA_TOKEN,
+    },
+    fmt::Item,
+    protocol::MAX_BULK_LEN,
+    ser::RAW_VALUE_TOKEN,
+    Config,
+};
+
+/// The newtype struct name used by [`Value`][crate::value::Value] to request
+/// that the deserializer report the exact RESP type of the next value.
+pub(crate) const VALUE_TOKEN: &str = "$seredies::Value";
 
 /// Deserialize a `T` object from a string containing RESP data.
 pub fn from_str<'a, T: de::Deserialize<'a>>(input: &'a str) -> Result<T, Error> {
@@ -105,54 +233,188 @@ pub fn from_str<'a, T: de::Deserialize<'a>>(input: &'a str) -> Result<T, Error>
 }
 
 /// Deserialize a `T` object from a byte slice containing RESP data.
-pub fn from_bytes<'a, T>(mut input: &'a [u8]) -> Result<T, Error>
+pub fn from_bytes<'a, T>(input: &'a [u8]) -> Result<T, Error>
+where
+    T: de::Deserialize<'a>,
+{
+    from_bytes_with_config(input, Config::new())
+}
+
+/// Deserialize a `T` object from a string containing RESP data, using the
+/// given [`Config`].
+pub fn from_str_with_config<'a, T>(input: &'a str, config: Config) -> Result<T, Error>
+where
+    T: de::Deserialize<'a>,
+{
+    from_bytes_with_config(input.as_bytes(), config)
+}
+
+/// Deserialize a `T` object from a byte slice containing RESP data, using the
+/// given [`Config`].
+pub fn from_bytes_with_config<'a, T>(mut input: &'a [u8], config: Config) -> Result<T, Error>
 where
     T: de::Deserialize<'a>,
 {
-    let deserializer = Deserializer::new(&mut input);
+    let deserializer = Deserializer::with_config(&mut input, config);
     let value = T::deserialize(deserializer)?;
-    input.is_empty().then_some(value).ok_or(Error::TrailingData)
+
+    parse::skip_keepalives(input, config.keepalives)
+        .is_empty()
+        .then_some(value)
+        .ok_or(Error::TrailingData)
 }
 
+/// The result of deserializing RESP data. Naming this, rather than spelling
+/// out `Result<T, de::Error>`, keeps signatures stable if the error type
+/// gains a type parameter in the future.
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+
 /// Errors that can occur while deserializing RESP data.
-#[derive(Debug, Clone, Error)]
+#[derive(Debug, Clone)]
 #[non_exhaustive]
 pub enum Error {
     /// There was an error during parsing (such as a \r without a \n).
-    #[error("parsing error")]
-    Parse(#[from] parse::Error),
+    Parse(parse::Error),
 
     /// The length of an array or bulk string was out of bounds. It might
     /// have been negative, or exceeded the 512MB limit for bulk strings.
-    #[error("an array or bulk string length was out of bounds")]
     Length,
 
     /// There was leftover data in the input after the deserialize operation.
     /// This is only returned by [`from_str`] and similar functions; the
     /// [`Deserializer`] itself will normally just leave that data untouched,
     /// to facilitate response pipelining.
-    #[error("the deserialize completed, but didn't consume the entire input")]
     TrailingData,
 
     /// The `Deserialize` type successfully deserialized from a Redis array,
     /// but didn't consume the whole thing.
-    #[error("the `Deserialize` type didn't consume the entire array")]
     UnfinishedArray,
 
-    /// There was an error from the `Deserialize` type
-    #[error("error from Deserialize type: {0}")]
+    /// There was an error from the `Deserialize` type. If the type didn't
+    /// accept the RESP value it was given, the message describes the value,
+    /// like `invalid type: bulk string of length 5 ("hello"), expected i32`.
     Custom(String),
 
+    /// A fixed-size array, such as a `[u8; 20]`, was deserialized from a bulk
+    /// string of a different length.
+    ArrayLength {
+        /// The length of the array
+        expected: usize,
+
+        /// The length of the bulk string
+        actual: usize,
+    },
+
     /// We *successfully* deserialized a Redis Error value (with the `-` tag)
     /// See the module docs on `Result` deserialization for how to avoid this
-    /// error.
-    #[error("successfully deserialized a Redis Error containing this message")]
-    Redis(Vec<u8>),
+    /// error. Before 2.0, this held a `Vec<u8>`; use
+    /// [`RedisMessage::into_vec`] to get one.
+    Redis(RedisMessage),
+
+    /// Arrays in the input were nested more deeply than the limit set with
+    /// [`Deserializer::with_max_depth`] (or [`DEFAULT_MAX_DEPTH`]). The
+    /// argument contains the limit.
+    DepthLimit(usize),
+
+    /// An array in the input was longer than the limit set with
+    /// [`Deserializer::with_max_array_len`]. The argument contains the limit.
+    LengthLimit(usize),
+
+    /// The input was larger than the limit set with
+    /// [`Deserializer::with_max_total_len`]. The argument contains the limit.
+    SizeLimit(usize),
 }
 
-impl de::Error for Error {
+impl Error {
+    /**
+    If the input ended before the value was complete, get the minimum number
+    of additional bytes needed before the deserialize should be retried. This
+    is the count from a [`parse::Error::UnexpectedEof`], without having to
+    match on the [`Parse`][Error::Parse] variant to find it.
+
+    The count is an estimate, not an exact size: it only accounts for the
+    part of the value that has been seen so far. For instance, when an array
+    is incomplete, each of its missing elements is assumed to be a minimal
+    RESP value. After reading this many more bytes, the deserialize might
+    fail again with another count.
+
+    # Example
+
+    ```
+    use seredies::de::from_bytes;
+
+    let err = from_bytes::<Vec<String>>(b"*2\r\n$5\r\nhel").unwrap_err();
+    assert_eq!(err.bytes_needed(), Some(4 + 5));
+
+    let err = from_bytes::<i64>(b"+OK\r\n").unwrap_err();
+    assert_eq!(err.bytes_needed(), None);
+    ```
+    */
     #[inline]
     #[must_use]
+    pub const fn bytes_needed(&self) -> Option<usize> {
+        match *self {
+            Error::Parse(ref err) => err.bytes_needed(),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Error::Parse(_) => f.write_str("parsing error"),
+            Error::Length => f.write_str("an array or bulk string length was out of bounds"),
+            Error::TrailingData => {
+                f.write_str("the deserialize completed, but didn't consume the entire input")
+            }
+            Error::UnfinishedArray => {
+                f.write_str("the `Deserialize` type didn't consume the entire array")
+            }
+            Error::Custom(ref msg) => write!(f, "error from Deserialize type: {msg}"),
+            Error::ArrayLength { expected, actual } => write!(
+                f,
+                "expected a bulk string of {expected} bytes for a fixed-size array, \
+                but it was {actual} bytes"
+            ),
+            Error::Redis(ref msg) => write!(
+                f,
+                "successfully deserialized a Redis Error containing this message: {msg}"
+            ),
+            Error::DepthLimit(limit) => {
+                write!(
+                    f,
+                    "arrays were nested more deeply than the limit of {limit}"
+                )
+            }
+            Error::LengthLimit(limit) => {
+                write!(f, "an array was longer than the limit of {limit} elements")
+            }
+            Error::SizeLimit(limit) => {
+                write!(f, "the input was larger than the limit of {limit} bytes")
+            }
+        }
+    }
+}
+
+impl de::StdError for Error {
+    fn source(&self) -> Option<&(dyn de::StdError + 'static)> {
+        match *self {
+            Error::Parse(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<parse::Error> for Error {
+    #[inline]
+    fn from(err: parse::Error) -> Self {
+        Self::Parse(err)
+    }
+}
+
+impl de::Error for Error {
+    #[inline]
     fn custom<T>(msg: T) -> Self
     where
         T: Display,
@@ -172,6 +434,10 @@ fn apply_parser<'de, T>(
     })
 }
 
+/// The default limit on the nesting depth of arrays for the
+/// [`Deserializer`]. See [`Deserializer::with_max_depth`].
+pub const DEFAULT_MAX_DEPTH: usize = 128;
+
 /// A RESP Deserializer.
 ///
 /// This is the core serde [`Deserializer`][de::Deserializer] for RESP data.
@@ -186,11 +452,49 @@ fn apply_parser<'de, T>(
 ///
 /// See also `from_bytes` and `from_str` for more convenient deserialize
 /// behavior.
+///
+/// Options can be set individually with the `with_*` methods, or all at once
+/// with a [`Config`], using [`with_config`][Deserializer::with_config].
 #[derive(Debug)]
 pub struct Deserializer<'a, 'de> {
     inner: UnparsedDeserializer<'a, 'de>,
 }
 
+/// Which RESP [Simple Strings] can be deserialized as a unit (`()` or a unit
+/// struct). A null can always be deserialized as a unit.
+///
+/// Many commands reply with `+OK\r\n`, which callers often want to discard;
+/// by default, this is accepted as a unit so that `from_bytes::<()>` works
+/// for these replies.
+///
+/// [Simple Strings]: https://redis.io/docs/reference/protocol-spec/#resp-simple-strings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum UnitSimpleString {
+    /// Only `+OK` can be deserialized as a unit.
+    #[default]
+    Ok,
+
+    /// Any Simple String can be deserialized as a unit, discarding its
+    /// content.
+    Any,
+
+    /// No Simple String can be deserialized as a unit; only a null can.
+    Reject,
+}
+
+impl UnitSimpleString {
+    #[inline]
+    #[must_use]
+    fn accepts(self, payload: &[u8]) -> bool {
+        match self {
+            UnitSimpleString::Ok => payload == b"OK",
+            UnitSimpleString::Any => true,
+            UnitSimpleString::Reject => false,
+        }
+    }
+}
+
 impl<'a, 'de> Deserializer<'a, 'de> {
     /// Create a new RESP deserializer.
     ///
@@ -201,9 +505,328 @@ impl<'a, 'de> Deserializer<'a, 'de> {
     #[inline]
     #[must_use]
     pub fn new(input: &'a mut &'de [u8]) -> Self {
+        Self::with_config(input, Config::new())
+    }
+
+    /// Create a new RESP deserializer with the given [`Config`]. The
+    /// configuration applies to every nested value.
+    #[inline]
+    #[must_use]
+    pub fn with_config(input: &'a mut &'de [u8], config: Config) -> Self {
         Self {
-            inner: UnparsedDeserializer::new(input),
+            inner: UnparsedDeserializer::new(input, config),
+        }
+    }
+
+    /**
+    Choose which Simple Strings this deserializer will accept as a unit
+    (`()` or a unit struct). By default, only `+OK` is accepted.
+
+    # Example
+
+    ```
+    use serde::Deserialize;
+    use seredies::de::{Deserializer, UnitSimpleString};
+
+    let mut input: &[u8] = b"+QUEUED\r\n";
+    let deserializer = Deserializer::new(&mut input)
+        .with_unit_simple_string(UnitSimpleString::Any);
+
+    <()>::deserialize(deserializer).unwrap();
+    assert!(input.is_empty());
+    ```
+    */
+    #[inline]
+    #[must_use]
+    pub fn with_unit_simple_string(mut self, policy: UnitSimpleString) -> Self {
+        self.inner.config.unit_simple_string = policy;
+        self
+    }
+
+    /**
+    Deserialize maps and structs from flattened arrays of key-value pairs,
+    as though every map and struct in the data were wrapped in
+    [`KeyValuePairs`][crate::components::KeyValuePairs]. This applies at every
+    level of nesting, which is convenient for deeply nested reply types.
+    Duplicate struct fields are an error, unless the struct is wrapped in
+    [`FirstWins`][crate::components::FirstWins] or
+    [`LastWins`][crate::components::LastWins].
+
+    # Example
+
+    ```
+    use std::collections::HashMap;
+
+    use serde::Deserialize;
+    use seredies::de::Deserializer;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Stream {
+        length: i64,
+        groups: HashMap<String, i64>,
+    }
+
+    let mut input: &[u8] = b"\
+        *4\r\n\
+            $6\r\nlength\r\n\
+            :3\r\n\
+            $6\r\ngroups\r\n\
+            *2\r\n\
+                $7\r\nworkers\r\n\
+                :2\r\n\
+    ";
+
+    let deserializer = Deserializer::new(&mut input).with_flattened_maps(true);
+    let stream = Stream::deserialize(deserializer).unwrap();
+
+    assert_eq!(
+        stream,
+        Stream {
+            length: 3,
+            groups: HashMap::from([("workers".to_owned(), 2)]),
         }
+    );
+    ```
+    */
+    #[inline]
+    #[must_use]
+    pub fn with_flattened_maps(mut self, enabled: bool) -> Self {
+        self.inner.config.flatten_maps = enabled;
+        self
+    }
+
+    /**
+    Present the integers 0 and 1 as booleans, even to types that accept any
+    value, like untagged enums or a dynamically typed value from another
+    crate. `bool` always accepts 0 and 1; this option is for replies that
+    RESP2 servers send as integers, but that are really booleans, like the
+    reply to `SISMEMBER`. Other integers are unaffected.
+
+    Because the integers are presented only as booleans, an integer type
+    like `i64` can't be deserialized from a 0 or 1 with this option, so it
+    should only be used for replies that are known to be booleans.
+
+    # Example
+
+    ```
+    use serde::Deserialize;
+    use seredies::de::Deserializer;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    #[serde(untagged)]
+    enum Reply {
+        Flag(bool),
+        Count(i64),
+    }
+
+    let mut input: &[u8] = b"*2\r\n:1\r\n:5\r\n";
+    let deserializer = Deserializer::new(&mut input).with_integer_bools(true);
+    let replies = <Vec<Reply>>::deserialize(deserializer).unwrap();
+
+    assert_eq!(replies, [Reply::Flag(true), Reply::Count(5)]);
+    ```
+    */
+    #[inline]
+    #[must_use]
+    pub fn with_integer_bools(mut self, enabled: bool) -> Self {
+        self.inner.config.integer_bools = enabled;
+        self
+    }
+
+    /**
+    Present Redis errors as strings of their message, instead of failing
+    with [`Error::Redis`]. This is for replies that can contain errors in
+    place of their elements, like the reply to `EXEC`, when they're
+    deserialized into types that can't handle errors themselves, like
+    untagged enums, `String`, or a dynamically typed value from another
+    crate.
+
+    [`Result`] and [`Value`][crate::value::Value] always deserialize errors
+    as errors, with or without this option, so they should be preferred
+    when the type can be chosen. With this option, other types see an error
+    as its message, so they can't tell it apart from a string, and a type
+    like `i64` fails with a type error rather than with the Redis error.
+
+    # Example
+
+    ```
+    use serde::Deserialize;
+    use seredies::de::Deserializer;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    #[serde(untagged)]
+    enum Reply {
+        Count(i64),
+        Text(String),
+    }
+
+    let mut input: &[u8] = b"*2\r\n:3\r\n-WRONGTYPE bad key\r\n";
+    let deserializer = Deserializer::new(&mut input).with_error_strings(true);
+    let replies = <Vec<Reply>>::deserialize(deserializer).unwrap();
+
+    assert_eq!(
+        replies,
+        [Reply::Count(3), Reply::Text("WRONGTYPE bad key".to_owned())],
+    );
+    ```
+    */
+    #[inline]
+    #[must_use]
+    pub fn with_error_strings(mut self, enabled: bool) -> Self {
+        self.inner.config.error_strings = enabled;
+        self
+    }
+
+    /**
+    Deserialize `f32` and `f64` from Simple Strings and Bulk Strings
+    containing a decimal number, like the replies to `ZSCORE` and
+    `INCRBYFLOAT`. Integers are also accepted as floats. The strings `inf`,
+    `+inf`, and `-inf` are parsed as infinities, matching Redis, but NaN is
+    rejected.
+
+    # Example
+
+    ```
+    use serde::Deserialize;
+    use seredies::de::Deserializer;
+
+    let mut input: &[u8] = b"*2\r\n$3\r\n1.5\r\n$4\r\n-inf\r\n";
+    let deserializer = Deserializer::new(&mut input).with_string_floats(true);
+    let scores = <Vec<f64>>::deserialize(deserializer).unwrap();
+
+    assert_eq!(scores, [1.5, f64::NEG_INFINITY]);
+    ```
+    */
+    #[inline]
+    #[must_use]
+    pub fn with_string_floats(mut self, enabled: bool) -> Self {
+        self.inner.config.string_floats = enabled;
+        self
+    }
+
+    /**
+    Limit the nesting depth of arrays in the input. A top-level array has a
+    depth of 1, an array inside of it has a depth of 2, and so on; exceeding
+    the limit is an [`Error::DepthLimit`]. Values are deserialized
+    recursively, so this protects against malicious input like
+    `*1\r\n*1\r\n*1\r\n...` overflowing the stack. The limit is
+    [`DEFAULT_MAX_DEPTH`] unless it's set with this method or
+    [`Config::with_max_depth`].
+
+    # Example
+
+    ```
+    use serde::Deserialize;
+    use seredies::de::{Deserializer, Error};
+
+    let mut input: &[u8] = b"*1\r\n*1\r\n:1\r\n";
+    let deserializer = Deserializer::new(&mut input).with_max_depth(1);
+    let result = <Vec<Vec<i64>>>::deserialize(deserializer);
+    assert!(matches!(result, Err(Error::DepthLimit(1))));
+
+    let mut input: &[u8] = b"*1\r\n*1\r\n:1\r\n";
+    let deserializer = Deserializer::new(&mut input).with_max_depth(2);
+    let value = <Vec<Vec<i64>>>::deserialize(deserializer).unwrap();
+    assert_eq!(value, [[1]]);
+    ```
+    */
+    #[inline]
+    #[must_use]
+    pub fn with_max_depth(mut self, limit: usize) -> Self {
+        self.inner.config.max_depth = Some(limit);
+        self
+    }
+
+    /**
+    Limit the number of elements in each array in the input. An array whose
+    header claims more elements is an [`Error::LengthLimit`], which is
+    returned before any of its elements are read. By default, there's no
+    limit, other than what fits in the input.
+
+    # Example
+
+    ```
+    use serde::Deserialize;
+    use seredies::de::{Deserializer, Error};
+
+    let mut input: &[u8] = b"*2147483647\r\n:1\r\n";
+    let deserializer = Deserializer::new(&mut input).with_max_array_len(1000);
+    let result = <Vec<i64>>::deserialize(deserializer);
+    assert!(matches!(result, Err(Error::LengthLimit(1000))));
+    ```
+    */
+    #[inline]
+    #[must_use]
+    pub fn with_max_array_len(mut self, limit: usize) -> Self {
+        self.inner.config.max_array_len = Some(limit);
+        self
+    }
+
+    /**
+    Limit the total size, in bytes, of the value being deserialized,
+    including all of its headers and bulk string payloads. Exceeding the
+    limit is an [`Error::SizeLimit`]; bulk strings are checked against the
+    limit based on their header, before their payload is read. By default,
+    there's no limit, other than the 512MB limit on each bulk string.
+
+    # Example
+
+    ```
+    use serde::Deserialize;
+    use seredies::de::{Deserializer, Error};
+
+    let mut input: &[u8] = b"*2\r\n$5\r\nhello\r\n$5\r\nworld\r\n";
+    let deserializer = Deserializer::new(&mut input).with_max_total_len(16);
+    let result = <Vec<&str>>::deserialize(deserializer);
+    assert!(matches!(result, Err(Error::SizeLimit(16))));
+
+    let mut input: &[u8] = b"*2\r\n$5\r\nhello\r\n$5\r\nworld\r\n";
+    let deserializer = Deserializer::new(&mut input).with_max_total_len(64);
+    let value = <Vec<&str>>::deserialize(deserializer).unwrap();
+    assert_eq!(value, ["hello", "world"]);
+    ```
+    */
+    #[inline]
+    #[must_use]
+    pub fn with_max_total_len(mut self, limit: usize) -> Self {
+        self.inner.config.max_total_len = Some(limit);
+        self
+    }
+
+    /**
+    Skip keepalive frames, such as [`PING`][crate::protocol::PING], that
+    appear before the value. Some proxies inject keepalives into a stream of
+    replies; skipping them here means that the `Deserialize` type doesn't have
+    to model them. Each keepalive is a complete RESP value, which is skipped
+    wherever the input starts with exactly those bytes, any number of times.
+    Only top-level values are affected; keepalives inside of an array aren't
+    skipped.
+
+    A reply that's identical to a keepalive is skipped too, so `+OK` should
+    only be a keepalive when the reply can never be `+OK`. The skipped bytes
+    don't count towards [`with_max_total_len`][Self::with_max_total_len].
+
+    # Example
+
+    ```
+    use serde::Deserialize;
+    use seredies::de::Deserializer;
+    use seredies::protocol::{PING, PONG};
+
+    const KEEPALIVES: &[&[u8]] = &[PING.as_bytes(), PONG.as_bytes()];
+
+    let mut input: &[u8] = b"+PONG\r\n*1\r\n$4\r\nPING\r\n:10\r\n";
+    let deserializer = Deserializer::new(&mut input).with_keepalives(KEEPALIVES);
+
+    assert_eq!(i64::deserialize(deserializer).unwrap(), 10);
+    assert!(input.is_empty());
+    ```
+    */
+    #[inline]
+    #[must_use]
+    pub fn with_keepalives(mut self, keepalives: &'static [&'static [u8]]) -> Self {
+        self.inner.config.keepalives = keepalives;
+        self
     }
 }
 
@@ -219,7 +842,7 @@ macro_rules! forward {
             where
                 V: de::Visitor<'de>
             {
-                self.inner.[<deserialize_ $method>]($($($arg,)*)? visitor)
+                self.inner.skip_keepalives().[<deserialize_ $method>]($($($arg,)*)? visitor)
             }
         }
     )*}
@@ -254,11 +877,19 @@ impl<'a, 'de> de::Deserializer<'de> for Deserializer<'a, 'de> {
 /// is retained by the deserializer passed into `deserialize_some`. This trait
 /// abstracts over the presence or absence of a parsed header.
 trait ReadHeader<'de>: Sized {
+    /// True if the header has already been parsed, in which case it's also
+    /// already been checked against the deserializer's limits. Wrapper types
+    /// like `Option` and `Result` each dispatch on the same pre-parsed
+    /// header, so this avoids checking it again at every level of nesting.
+    const PARSED: bool;
+
     /// Read a header, possibly from the `input`.
     fn read_header(self, input: &mut &'de [u8]) -> Result<TaggedHeader<'de>, parse::Error>;
 }
 
 impl<'de> ReadHeader<'de> for TaggedHeader<'de> {
+    const PARSED: bool = true;
+
     /// A `TaggedHeader` can simply return itself without touching the input
     #[inline]
     fn read_header(self, _input: &mut &'de [u8]) -> Result<TaggedHeader<'de>, parse::Error> {
@@ -270,6 +901,8 @@ impl<'de> ReadHeader<'de> for TaggedHeader<'de> {
 struct ParseHeader;
 
 impl<'de> ReadHeader<'de> for ParseHeader {
+    const PARSED: bool = false;
+
     /// We don't have a header; we must try to read one from the input.
     #[inline]
     fn read_header(self, input: &mut &'de [u8]) -> Result<TaggedHeader<'de>, parse::Error> {
@@ -277,10 +910,23 @@ impl<'de> ReadHeader<'de> for ParseHeader {
     }
 }
 
+/// Where a value is within the top-level value being deserialized, which is
+/// tracked to enforce the deserializer's limits.
+#[derive(Debug, Clone, Copy)]
+struct Position {
+    /// The number of arrays that this value is nested inside of
+    depth: usize,
+
+    /// The length of the input at the start of the top-level value
+    start: usize,
+}
+
 #[derive(Debug)]
 struct BaseDeserializer<'a, 'de, H> {
     header: H,
     input: &'a mut &'de [u8],
+    config: Config,
+    position: Position,
 }
 
 type UnparsedDeserializer<'a, 'de> = BaseDeserializer<'a, 'de, ParseHeader>;
@@ -288,115 +934,433 @@ type PreParsedDeserializer<'a, 'de> = BaseDeserializer<'a, 'de, TaggedHeader<'de
 
 impl<'a, 'de> UnparsedDeserializer<'a, 'de> {
     #[inline]
-    pub fn new(input: &'a mut &'de [u8]) -> Self {
+    pub fn new(input: &'a mut &'de [u8], config: Config) -> Self {
+        let position = Position {
+            depth: 0,
+            start: input.len(),
+        };
+
+        Self::nested(input, config, position)
+    }
+
+    /// Skip any keepalives at the start of the input, if this is a top-level
+    /// value. The value starts after them.
+    #[inline]
+    fn skip_keepalives(self) -> Self {
+        match self.config.keepalives {
+            [] => self,
+            _ if self.position.depth > 0 => self,
+            keepalives => {
+                *self.input = parse::skip_keepalives(self.input, keepalives);
+                Self::new(self.input, self.config)
+            }
+        }
+    }
+
+    #[inline]
+    fn nested(input: &'a mut &'de [u8], config: Config, position: Position) -> Self {
         Self {
             input,
             header: ParseHeader,
+            config,
+            position,
         }
     }
 }
 
 impl<'a, 'de> PreParsedDeserializer<'a, 'de> {
     #[inline]
-    fn new(header: TaggedHeader<'de>, input: &'a mut &'de [u8]) -> Self {
-        Self { input, header }
+    fn new(
+        header: TaggedHeader<'de>,
+        input: &'a mut &'de [u8],
+        config: Config,
+        position: Position,
+    ) -> Self {
+        Self {
+            input,
+            header,
+            config,
+            position,
+        }
+    }
+
+    /// Get the position of the elements of this array, or an error if that
+    /// would exceed the depth limit.
+    #[inline]
+    fn enter_array(&self) -> Result<Position, Error> {
+        let limit = self.config.max_depth.unwrap_or(DEFAULT_MAX_DEPTH);
+
+        match self.position.depth < limit {
+            true => Ok(Position {
+                depth: self.position.depth + 1,
+                ..self.position
+            }),
+            false => Err(Error::DepthLimit(limit)),
+        }
+    }
+
+    /// Check the header against the array length and total size limits,
+    /// before any of its elements or payload are read.
+    fn check_limits(&self) -> Result<(), Error> {
+        let payload = match self.header {
+            TaggedHeader::Array(len) | TaggedHeader::Push(len) => match self.config.max_array_len {
+                Some(limit) if len > 0 && len as u64 > limit as u64 => {
+                    return Err(Error::LengthLimit(limit))
+                }
+                _ => return Ok(()),
+            },
+            TaggedHeader::BulkString(len) | TaggedHeader::Verbatim(len) => {
+                usize::try_from(len).unwrap_or(0)
+            }
+            _ => 0,
+        };
+
+        match self.config.max_total_len {
+            Some(limit) => {
+                let consumed = self.position.start.saturating_sub(self.input.len());
+
+                match consumed.saturating_add(payload) > limit {
+                    true => Err(Error::SizeLimit(limit)),
+                    false => Ok(()),
+                }
+            }
+            None => Ok(()),
+        }
     }
 }
 
-// Bulk strings can be up to 512 MB
-const MAX_BULK_LENGTH: i64 = 512 * 1024 * 1024;
+impl<'a, 'de> PreParsedDeserializer<'a, 'de> {
+    /// Deserialize a string as a `(format, content)` pair, for
+    /// [`Verbatim`][crate::components::Verbatim]. Simple Strings and Bulk
+    /// Strings have the `txt` format; everything else is deserialized
+    /// normally.
+    fn deserialize_verbatim<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        use de::Deserializer as _;
+
+        let (format, content): (&'de [u8], &'de [u8]) = match self.header {
+            TaggedHeader::SimpleString(payload) => (b"txt", payload),
+            TaggedHeader::BulkString(len) | TaggedHeader::Verbatim(len)
+                if !(0..=MAX_BULK_LEN).contains(&len) =>
+            {
+                return Err(Error::Length)
+            }
+            TaggedHeader::BulkString(len) => (
+                b"txt",
+                apply_parser(self.input, |input| parse::read_exact(len as usize, input))?,
+            ),
+            TaggedHeader::Verbatim(len) => {
+                let payload =
+                    apply_parser(self.input, |input| parse::read_exact(len as usize, input))?;
+                let (format, content) = parse::split_verbatim(payload)?;
+                (format, content)
+            }
+            _ => return self.deserialize_any(visitor),
+        };
+
+        let mut seq: de::value::SeqDeserializer<_, Error> = de::value::SeqDeserializer::new(
+            [format, content]
+                .into_iter()
+                .map(de::value::BorrowedBytesDeserializer::new),
+        );
+        let value = visitor.visit_seq(&mut seq)?;
+        seq.end()?;
+        Ok(value)
+    }
+}
 
 impl<'a, 'de, H: ReadHeader<'de>> BaseDeserializer<'a, 'de, H> {
     /// Read the header from a RESP value. The header consists of a single
     /// tag byte, followed by some kind of payload (which may not contain \r
     /// or \n), followed by \r\n.
     #[inline]
-    fn read_header(self) -> Result<PreParsedDeserializer<'a, 'de>, parse::Error> {
+    fn read_header(self) -> Result<PreParsedDeserializer<'a, 'de>, Error> {
         let input = self.input;
+        let config = self.config;
+        let position = self.position;
 
-        self.header
-            .read_header(input)
-            .map(|header| PreParsedDeserializer::new(header, input))
-    }
-}
+        let header = self.header.read_header(input)?;
+        let parsed = PreParsedDeserializer::new(header, input, config, position);
 
-impl<'de, P: ReadHeader<'de>> de::Deserializer<'de> for BaseDeserializer<'_, 'de, P> {
-    type Error = Error;
+        if !H::PARSED {
+            parsed.check_limits()?;
+        }
 
-    forward_to_deserialize_any! {
-        i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
-        bytes byte_buf unit_struct seq tuple unit
-        tuple_struct map struct identifier ignored_any
+        Ok(parsed)
     }
 
-    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    /// Deserialize a string or bytes. This is the same as `deserialize_any`,
+    /// except that big numbers are deserialized as their digits rather than
+    /// as integers.
+    fn deserialize_text<V>(self, visitor: V) -> Result<V::Value, Error>
     where
         V: de::Visitor<'de>,
     {
+        use de::Deserializer as _;
+
         let parsed = self.read_header()?;
 
         match parsed.header {
-            // Simple Strings are handled as byte arrays
-            TaggedHeader::SimpleString(payload) => visitor.visit_borrowed_bytes(payload),
-
-            // Errors are handled by default as actual deserialization errors.
-            // (see deserialize_enum for how to circumvent this)
-            TaggedHeader::Error(payload) => Err(Error::Redis(payload.to_owned())),
-
-            // Integers are parsed then handled as i64. All Redis integers are
-            // guaranteed to fit in a signed 64 bit int.
-            TaggedHeader::Integer(value) => visitor.visit_i64(value),
-
-            // Bulk strings are handled as byte arrays
-            TaggedHeader::BulkString(len) if len > MAX_BULK_LENGTH => Err(Error::Length),
-            TaggedHeader::BulkString(len) => visitor.visit_borrowed_bytes({
-                let len = len.try_into().map_err(|_| Error::Length)?;
-                apply_parser(parsed.input, |input| parse::read_exact(len, input))?
+            TaggedHeader::BigNumber(digits) => visit_item(Item::BigNumber(digits), || {
+                visitor.visit_borrowed_bytes(digits)
             }),
+            _ => parsed.deserialize_any(visitor),
+        }
+    }
 
-            // Arrays are handled as serde sequences.
-            TaggedHeader::Array(len) => {
-                let mut seq = SeqAccess {
-                    input: parsed.input,
-                    length: len.try_into().map_err(|_| Error::Length)?,
-                };
-
-                match visitor.visit_seq(&mut seq) {
-                    Ok(..) if seq.length > 0 => Err(Error::UnfinishedArray),
-                    Ok(value) => Ok(value),
-
-                    // If there was an unexpected EOF from inside the array,
-                    // increase the size. We know that the minimum size of a
-                    // RESP value is 3 bytes, plus the array itself has a 2
-                    // byte terminator.
-                    // TODO: include both a minimum and recommended byte count
-                    // (since in practice data in an array will usually be
-                    // bulk strings, which are minimum 5 bytes)
-                    Err(Error::Parse(parse::Error::UnexpectedEof(len))) => {
-                        Err(Error::Parse(parse::Error::UnexpectedEof(
-                            len.saturating_add(seq.length.saturating_mul(3))
-                                .saturating_add(2),
-                        )))
-                    }
+    /// Deserialize a `str` or `String`. Simple Strings that are valid UTF-8
+    /// are passed to the visitor as borrowed `str`s, so that the visitor
+    /// doesn't have to check them itself; everything else is deserialized
+    /// like any other text.
+    fn deserialize_str_text<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        let parsed = self.read_header()?;
 
-                    Err(err) => Err(err),
+        match parsed.header {
+            // Simple Strings are usually short ASCII status replies like
+            // `PONG`, for which `from_utf8`'s ASCII fast path is very cheap.
+            // Invalid UTF-8 is left to the visitor, so that its error is the
+            // same as for bulk strings.
+            TaggedHeader::SimpleString(payload) => visit_item(Item::SimpleString(payload), || {
+                match str::from_utf8(payload) {
+                    Ok(payload) => visitor.visit_borrowed_str(payload),
+                    Err(_) => visitor.visit_borrowed_bytes(payload),
                 }
-            }
-
-            // Null (technically a Bulk String with a length of -1) is a unit
-            TaggedHeader::Null => visitor.visit_unit(),
+            }),
+            _ => parsed.deserialize_text(visitor),
         }
     }
 
-    #[inline]
-    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    /// Deserialize a float. When string floats are enabled, the payload of a
+    /// string is parsed as a `T` and passed to `visit`; everything else is
+    /// deserialized normally.
+    fn deserialize_float<T, V>(
+        self,
+        visitor: V,
+        visit: impl FnOnce(V, T) -> Result<V::Value, VisitError>,
+    ) -> Result<V::Value, Error>
     where
+        T: FromStr,
         V: de::Visitor<'de>,
     {
-        // Use deserialize_any, but provide a variant `Visitor` that treats
-        // 0 and 1 as true and false
-        struct BoolVisitAdapter<V> {
-            inner: V,
+        use de::Deserializer as _;
+
+        if !self.config.string_floats {
+            return self.deserialize_any(visitor);
+        }
+
+        let parsed = self.read_header()?;
+
+        let (payload, item) = match parsed.header {
+            TaggedHeader::SimpleString(payload) => (payload, Item::SimpleString(payload)),
+            TaggedHeader::BulkString(len) if (0..=MAX_BULK_LEN).contains(&len) => {
+                let payload =
+                    apply_parser(parsed.input, |input| parse::read_exact(len as usize, input))?;
+                (payload, Item::BulkString(payload))
+            }
+            _ => return parsed.deserialize_any(visitor),
+        };
+
+        visit_item(item, || {
+            // Redis never sends NaN, and rejects it as an argument, so it's
+            // treated like any other invalid float
+            match str::from_utf8(payload)
+                .ok()
+                .filter(|s| !s.trim_start_matches(['+', '-']).eq_ignore_ascii_case("nan"))
+                .and_then(|s| s.parse().ok())
+            {
+                Some(value) => visit(visitor, value),
+                None => Err(de::Error::invalid_value(
+                    de::Unexpected::Bytes(payload),
+                    &visitor,
+                )),
+            }
+        })
+    }
+}
+
+impl<'de, P: ReadHeader<'de>> de::Deserializer<'de> for BaseDeserializer<'_, 'de, P> {
+    type Error = Error;
+
+    forward_to_deserialize_any! {
+        i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 char
+        seq tuple_struct identifier ignored_any
+    }
+
+    #[inline]
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_str_text(visitor)
+    }
+
+    #[inline]
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_str_text(visitor)
+    }
+
+    #[inline]
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_text(visitor)
+    }
+
+    #[inline]
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_text(visitor)
+    }
+
+    #[inline]
+    fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_float(visitor, |visitor, value: f32| visitor.visit_f32(value))
+    }
+
+    #[inline]
+    fn deserialize_f64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_float(visitor, |visitor, value: f64| visitor.visit_f64(value))
+    }
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        let parsed = self.read_header()?;
+
+        match parsed.header {
+            // Simple Strings are handled as byte arrays
+            TaggedHeader::SimpleString(payload) => visit_item(Item::SimpleString(payload), || {
+                visitor.visit_borrowed_bytes(payload)
+            }),
+
+            // Errors are handled by default as actual deserialization errors.
+            // (see deserialize_enum for how to circumvent this)
+            TaggedHeader::Error(payload) if parsed.config.error_strings => {
+                visit_item(Item::Error(payload), || {
+                    visitor.visit_borrowed_bytes(payload)
+                })
+            }
+            TaggedHeader::Error(payload) => Err(Error::Redis(RedisMessage::new(payload))),
+
+            // Integers are parsed then handled as i64. All Redis integers are
+            // guaranteed to fit in a signed 64 bit int.
+            TaggedHeader::Integer(value @ (0 | 1)) if parsed.config.integer_bools => {
+                visit_item(Item::Integer(value), || visitor.visit_bool(value == 1))
+            }
+            TaggedHeader::Integer(value) => {
+                visit_item(Item::Integer(value), || visitor.visit_i64(value))
+            }
+
+            // Bulk strings are handled as byte arrays
+            TaggedHeader::BulkString(len) if len > MAX_BULK_LEN => Err(Error::Length),
+            TaggedHeader::BulkString(len) => {
+                let len = len.try_into().map_err(|_| Error::Length)?;
+                let payload = apply_parser(parsed.input, |input| parse::read_exact(len, input))?;
+                visit_item(Item::BulkString(payload), || {
+                    visitor.visit_borrowed_bytes(payload)
+                })
+            }
+
+            // Arrays are handled as serde sequences. So are pushes, which
+            // only need to be distinguished from arrays by `Incoming`.
+            header @ (TaggedHeader::Array(len) | TaggedHeader::Push(len)) => {
+                let length = len.try_into().map_err(|_| Error::Length)?;
+                let item = match header {
+                    TaggedHeader::Push(_) => Item::Push(length),
+                    _ => Item::Array(length),
+                };
+
+                let position = parsed.enter_array()?;
+                let mut seq = SeqAccess {
+                    input: parsed.input,
+                    config: parsed.config,
+                    position,
+                    length,
+                };
+
+                match visit_item(item, || visitor.visit_seq(VisitSeq(&mut seq))) {
+                    Ok(..) if seq.length > 0 => Err(Error::UnfinishedArray),
+                    Ok(value) => Ok(value),
+
+                    // If there was an unexpected EOF from inside the array,
+                    // increase the size. We know that the minimum size of a
+                    // RESP value is 3 bytes, plus the array itself has a 2
+                    // byte terminator.
+                    // TODO: include both a minimum and recommended byte count
+                    // (since in practice data in an array will usually be
+                    // bulk strings, which are minimum 5 bytes)
+                    Err(Error::Parse(parse::Error::UnexpectedEof(len))) => {
+                        Err(Error::Parse(parse::Error::UnexpectedEof(
+                            len.saturating_add(seq.length.saturating_mul(3))
+                                .saturating_add(2),
+                        )))
+                    }
+
+                    Err(err) => Err(err),
+                }
+            }
+
+            // Null (technically a Bulk String with a length of -1) is a unit
+            TaggedHeader::Null => visit_item(Item::Null, || visitor.visit_unit()),
+
+            // Verbatim strings are handled as byte arrays of their content;
+            // the format is discarded (see `Verbatim` for how to keep it)
+            TaggedHeader::Verbatim(len) if len > MAX_BULK_LEN => Err(Error::Length),
+            TaggedHeader::Verbatim(len) => {
+                let len = len.try_into().map_err(|_| Error::Length)?;
+                let payload = apply_parser(parsed.input, |input| parse::read_exact(len, input))?;
+                let (_, content) = parse::split_verbatim(payload)?;
+                visit_item(Item::Verbatim(content), || {
+                    visitor.visit_borrowed_bytes(content)
+                })
+            }
+
+            // Big numbers are handled as the first of i64, u64, i128, or u128
+            // that fits them. If none of them do, they're handled as byte
+            // arrays of their digits.
+            TaggedHeader::BigNumber(digits) => visit_item(Item::BigNumber(digits), || {
+                if let Some(value) = parse::parse_integer(digits) {
+                    visitor.visit_i64(value)
+                } else if let Some(value) = parse::parse_integer(digits) {
+                    visitor.visit_u64(value)
+                } else if let Some(value) = parse::parse_integer(digits) {
+                    visitor.visit_i128(value)
+                } else if let Some(value) = parse::parse_integer(digits) {
+                    visitor.visit_u128(value)
+                } else {
+                    visitor.visit_borrowed_bytes(digits)
+                }
+            }),
+        }
+    }
+
+    #[inline]
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        // Use deserialize_any, but provide a variant `Visitor` that treats
+        // 0 and 1 as true and false
+        struct BoolVisitAdapter<V> {
+            inner: V,
         }
 
         impl<'de, V> de::Visitor<'de> for BoolVisitAdapter<V>
@@ -406,7 +1370,7 @@ impl<'de, P: ReadHeader<'de>> de::Deserializer<'de> for BaseDeserializer<'_, 'de
             type Value = V::Value;
 
             #[inline]
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
                 self.inner.expecting(formatter)
             }
 
@@ -470,6 +1434,68 @@ impl<'de, P: ReadHeader<'de>> de::Deserializer<'de> for BaseDeserializer<'_, 'de
         self.deserialize_any(BoolVisitAdapter { inner: visitor })
     }
 
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        let parsed = self.read_header()?;
+
+        match parsed.header {
+            // Replies like "+OK\r\n" are usually discarded, so they can be
+            // treated as a unit
+            TaggedHeader::SimpleString(payload)
+                if parsed.config.unit_simple_string.accepts(payload) =>
+            {
+                visitor.visit_unit()
+            }
+            _ => parsed.deserialize_any(visitor),
+        }
+    }
+
+    #[inline]
+    fn deserialize_unit_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        let parsed = self.read_header()?;
+
+        match parsed.header {
+            // Fixed-size arrays, like `[u8; 20]`, are deserialized as tuples;
+            // a bulk string of exactly the right length provides the bytes.
+            // The length is checked against the header before the payload is
+            // read.
+            TaggedHeader::BulkString(actual) if actual > MAX_BULK_LEN => Err(Error::Length),
+            TaggedHeader::BulkString(actual) => match usize::try_from(actual) {
+                Ok(actual) if actual == len => {
+                    let payload =
+                        apply_parser(parsed.input, |input| parse::read_exact(len, input))?;
+                    let mut seq: de::value::SeqDeserializer<_, Error> =
+                        de::value::SeqDeserializer::new(payload.iter().copied());
+                    let value = visitor.visit_seq(&mut seq)?;
+                    seq.end()?;
+                    Ok(value)
+                }
+                Ok(actual) => Err(Error::ArrayLength {
+                    expected: len,
+                    actual,
+                }),
+                Err(_) => Err(Error::Length),
+            },
+            _ => parsed.deserialize_any(visitor),
+        }
+    }
+
     fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
@@ -485,13 +1511,57 @@ impl<'de, P: ReadHeader<'de>> de::Deserializer<'de> for BaseDeserializer<'_, 'de
     #[inline]
     fn deserialize_newtype_struct<V>(
         self,
-        _name: &'static str,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match name {
+            VALUE_TOKEN => visitor.visit_enum(ValueAccess::new(self.read_header()?)),
+            VERBATIM_TOKEN => self.read_header()?.deserialize_verbatim(visitor),
+            // A raw value needs the bytes of its header, which are already
+            // gone if something like an `Option` parsed it first.
+            RAW_VALUE_TOKEN if P::PARSED => Err(de::Error::custom(
+                "a RawValue can't be nested in an Option or Result",
+            )),
+            RAW_VALUE_TOKEN => {
+                let (raw, tail) = parse::skip_value(self.input)?;
+                *self.input = tail;
+                visitor.visit_borrowed_bytes(raw)
+            }
+            FIRST_WINS_TOKEN | LAST_WINS_TOKEN | WITH_EXTRA_TOKEN if self.config.flatten_maps => {
+                KeyValuePairsDeserializer::new(self).deserialize_newtype_struct(name, visitor)
+            }
+            _ => visitor.visit_newtype_struct(self),
+        }
+    }
+
+    #[inline]
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.config.flatten_maps {
+            true => KeyValuePairsDeserializer::new(self).deserialize_map(visitor),
+            false => self.deserialize_any(visitor),
+        }
+    }
+
+    #[inline]
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_newtype_struct(self)
+        match self.config.flatten_maps {
+            true => KeyValuePairsDeserializer::new(self).deserialize_struct(name, fields, visitor),
+            false => self.deserialize_any(visitor),
+        }
     }
 
     #[inline]
@@ -505,6 +1575,20 @@ impl<'de, P: ReadHeader<'de>> de::Deserializer<'de> for BaseDeserializer<'_, 'de
         V: de::Visitor<'de>,
     {
         match (name, variants) {
+            (INCOMING_TOKEN, _) => visitor.visit_enum(IncomingAccess::new(self.read_header()?)),
+            (OR_WRONG_TYPE_TOKEN, _) => {
+                let parsed = self.read_header()?;
+
+                match parsed.header {
+                    TaggedHeader::Error(message) if is_wrong_type(message) => {
+                        visitor.visit_enum(UnitVariantAccess::new(b"WrongType"))
+                    }
+
+                    // Anything else, including other errors, is deserialized
+                    // as the `Ok` value, which can handle or reject them
+                    _ => visitor.visit_enum(ResultAccess::new_ok(parsed)),
+                }
+            }
             ("Result", ["Ok", "Err"] | ["Err", "Ok"]) => {
                 let parsed = self.read_header()?;
 
@@ -525,7 +1609,24 @@ impl<'de, P: ReadHeader<'de>> de::Deserializer<'de> for BaseDeserializer<'_, 'de
                     _ => visitor.visit_enum(ResultAccess::new_ok(parsed)),
                 }
             }
-            _ => self.deserialize_any(visitor),
+            _ => {
+                let parsed = self.read_header()?;
+
+                match parsed.header {
+                    // Unit variants are serialized as their names
+                    TaggedHeader::SimpleString(variant) => {
+                        visitor.visit_enum(UnitVariantAccess::new(variant))
+                    }
+                    TaggedHeader::BulkString(len) if (0..=MAX_BULK_LEN).contains(&len) => {
+                        let variant = apply_parser(parsed.input, |input| {
+                            parse::read_exact(len as usize, input)
+                        })?;
+
+                        visitor.visit_enum(UnitVariantAccess::new(variant))
+                    }
+                    _ => parsed.deserialize_any(visitor),
+                }
+            }
         }
     }
 }
@@ -533,6 +1634,8 @@ impl<'de, P: ReadHeader<'de>> de::Deserializer<'de> for BaseDeserializer<'_, 'de
 struct SeqAccess<'a, 'de> {
     length: usize,
     input: &'a mut &'de [u8],
+    config: Config,
+    position: Position,
 }
 
 impl<'de> de::SeqAccess<'de> for SeqAccess<'_, 'de> {
@@ -548,13 +1651,19 @@ impl<'de> de::SeqAccess<'de> for SeqAccess<'_, 'de> {
             None => return Ok(None),
         };
 
-        seed.deserialize(Deserializer::new(self.input)).map(Some)
+        seed.deserialize(Deserializer {
+            inner: UnparsedDeserializer::nested(self.input, self.config, self.position),
+        })
+        .map(Some)
     }
 
     #[inline]
-    #[must_use]
     fn size_hint(&self) -> Option<usize> {
-        Some(self.length)
+        // The length comes from untrusted input, so we cap it by the number
+        // of elements that could possibly fit in the remaining input (every
+        // RESP value is at least 3 bytes). This prevents a malicious array
+        // header from causing a huge up-front allocation.
+        Some(self.length.min(self.input.len() / 3))
     }
 }
 
@@ -567,6 +1676,7 @@ mod tests {
     use serde::Deserialize as _;
 
     use super::*;
+    use crate::test_util::xorshift;
 
     #[derive(PartialEq, Eq, Debug)]
     enum Data<'a> {
@@ -689,6 +1799,9 @@ mod tests {
         negative_int: ":-1000\r\n" => -1000;
         bulk_string: "$5\r\nhello\r\n" => "hello";
         empty_bulk_string: "$0\r\n\r\n" => "";
+        empty_array: "*0\r\n" => Data::Array(Vec::new());
+        array_of_nulls: "*3\r\n$-1\r\n$-1\r\n$-1\r\n" => [Null, Null, Null];
+        nested_empty: "*2\r\n*0\r\n*1\r\n$0\r\n\r\n" => [Data::Array(Vec::new()), Data::from([""])];
         null: "$-1\r\n" => Null;
         weird_null: "$-001\r\n" => Null;
         array: "*2\r\n$5\r\nhello\r\n$5\r\nworld\r\n" => ["hello", "world"];
@@ -732,6 +1845,20 @@ mod tests {
         assert_matches!(result, Error::Redis(message) => assert_eq!(message, b"ERROR bad data"));
     }
 
+    #[test]
+    fn test_error_source() {
+        use std::error::Error as _;
+
+        let error =
+            from_bytes::<i64>(b":12x\r\n").expect_err("deserialization unexpectedly succeeded");
+
+        assert_eq!(error.to_string(), "parsing error");
+        assert_matches!(
+            error.source().map(ToString::to_string),
+            Some(message) => assert_eq!(message, "failed to parse a decimal integer")
+        );
+    }
+
     fn test_result_deserializer<'a, T, E>(mut input: &'a [u8], expected: Result<T, E>)
     where
         T: de::Deserialize<'a> + Eq + Debug,
@@ -773,4 +1900,1086 @@ mod tests {
     fn test_result_error_msg() {
         test_result_deserializer::<&str, &str>(b"-ERROR bad data\r\n", Err("ERROR bad data"));
     }
+
+    #[test]
+    fn test_result_error_parts() {
+        test_result_deserializer::<&str, (&str, &str)>(
+            b"-ERROR bad data\r\n",
+            Err(("ERROR", "bad data")),
+        );
+    }
+
+    /// Each `Option` / `Result` nesting, deserialized from each kind of
+    /// RESP value that those types treat specially.
+    mod nesting {
+        use super::*;
+
+        type Str = &'static str;
+
+        macro_rules! nesting_tests {
+            ($($name:ident: $input:literal as $Type:ty => $expected:expr;)*) => {$(
+                #[test]
+                fn $name() {
+                    let value: $Type = from_bytes($input).expect("failed to deserialize");
+                    assert_eq!(value, $expected);
+                }
+            )*};
+        }
+
+        nesting_tests! {
+            option_result_ok: b"+OK\r\n" as Option<Result<(), Str>> => Some(Ok(()));
+            option_result_value: b"$3\r\nabc\r\n" as Option<Result<Str, Str>> => Some(Ok("abc"));
+            option_result_null: b"$-1\r\n" as Option<Result<Str, Str>> => None;
+            option_result_err: b"-ERR x\r\n" as Option<Result<Str, Str>> => Some(Err("ERR x"));
+
+            result_option_ok: b"+OK\r\n" as Result<Option<()>, Str> => Ok(Some(()));
+            result_option_ok_str: b"+OK\r\n" as Result<Option<Str>, Str> => Ok(Some("OK"));
+            result_option_value: b"$3\r\nabc\r\n" as Result<Option<Str>, Str> => Ok(Some("abc"));
+            result_option_null: b"$-1\r\n" as Result<Option<Str>, Str> => Ok(None);
+            result_option_err: b"-ERR x\r\n" as Result<Option<Str>, Str> => Err("ERR x");
+
+            result_result_ok: b"+OK\r\n" as Result<Result<(), Str>, Str> => Ok(Ok(()));
+            result_result_value: b"$3\r\nabc\r\n" as Result<Result<Str, Str>, Str> => Ok(Ok("abc"));
+            result_result_err: b"-ERR x\r\n" as Result<Result<Str, Str>, Str> => Err("ERR x");
+
+            result_option_result_ok: b"+OK\r\n" as Result<Option<Result<(), Str>>, Str> => Ok(Some(Ok(())));
+            result_option_result_null: b"$-1\r\n" as Result<Option<Result<Str, Str>>, Str> => Ok(None);
+
+            err_option: b"-ERR x\r\n" as Result<Str, Option<Str>> => Err(Some("ERR x"));
+            err_option_ok: b"+OK\r\n" as Result<(), Option<Str>> => Ok(());
+
+            option_option_null: b"$-1\r\n" as Option<Option<Str>> => None;
+            option_option_value: b"$3\r\nabc\r\n" as Option<Option<Str>> => Some(Some("abc"));
+        }
+
+        #[test]
+        fn option_without_result_rejects_errors() {
+            assert_matches!(
+                from_bytes::<Option<Option<Str>>>(b"-ERR x\r\n"),
+                Err(Error::Redis(msg)) => assert_eq!(msg, b"ERR x")
+            );
+        }
+    }
+
+    mod depth_limit {
+        use super::*;
+        use crate::value::Value;
+
+        fn nested_arrays(depth: usize) -> Vec<u8> {
+            let mut input = b"*1\r\n".repeat(depth);
+            input.extend_from_slice(b":1\r\n");
+            input
+        }
+
+        #[test]
+        fn default_limit() {
+            let input = nested_arrays(100_000);
+
+            assert_matches!(
+                from_bytes::<de::IgnoredAny>(&input),
+                Err(Error::DepthLimit(DEFAULT_MAX_DEPTH))
+            );
+            assert_matches!(
+                from_bytes::<Value>(&input),
+                Err(Error::DepthLimit(DEFAULT_MAX_DEPTH))
+            );
+        }
+
+        #[test]
+        fn at_default_limit() {
+            let input = nested_arrays(DEFAULT_MAX_DEPTH);
+            let _: de::IgnoredAny = from_bytes(&input).unwrap();
+
+            let input = nested_arrays(DEFAULT_MAX_DEPTH + 1);
+            assert_matches!(
+                from_bytes::<de::IgnoredAny>(&input),
+                Err(Error::DepthLimit(DEFAULT_MAX_DEPTH))
+            );
+        }
+
+        #[test]
+        fn custom_limit() {
+            let input = nested_arrays(3);
+
+            let mut tail = input.as_slice();
+            let value: Vec<Vec<Vec<i64>>> =
+                Vec::deserialize(Deserializer::new(&mut tail).with_max_depth(3)).unwrap();
+            assert_eq!(value, [[[1]]]);
+
+            let mut tail = input.as_slice();
+            assert_matches!(
+                Value::deserialize(Deserializer::new(&mut tail).with_max_depth(2)),
+                Err(Error::DepthLimit(2))
+            );
+        }
+
+        #[test]
+        fn zero_limit() {
+            let mut input: &[u8] = b":1\r\n";
+            let value = i64::deserialize(Deserializer::new(&mut input).with_max_depth(0)).unwrap();
+            assert_eq!(value, 1);
+
+            let mut input: &[u8] = b"*0\r\n";
+            assert_matches!(
+                <Vec<i64>>::deserialize(Deserializer::new(&mut input).with_max_depth(0)),
+                Err(Error::DepthLimit(0))
+            );
+        }
+
+        #[test]
+        fn siblings_dont_accumulate() {
+            let input = b"*3\r\n*1\r\n:1\r\n*1\r\n:2\r\n*1\r\n:3\r\n";
+            let mut tail: &[u8] = input;
+            let value: Vec<Vec<i64>> =
+                Vec::deserialize(Deserializer::new(&mut tail).with_max_depth(2)).unwrap();
+            assert_eq!(value, [[1], [2], [3]]);
+        }
+    }
+
+    mod size_limits {
+        use super::*;
+
+        fn deserialize_limited<'de, T: de::Deserialize<'de>>(
+            mut input: &'de [u8],
+            config: Config,
+        ) -> Result<T, Error> {
+            T::deserialize(Deserializer::with_config(&mut input, config))
+        }
+
+        #[test]
+        fn huge_array_header() {
+            let config = Config::new().with_max_array_len(10);
+
+            assert_matches!(
+                deserialize_limited::<Vec<i64>>(b"*2147483647\r\n", config),
+                Err(Error::LengthLimit(10))
+            );
+            assert_matches!(
+                deserialize_limited::<de::IgnoredAny>(b"*2147483647\r\n", config),
+                Err(Error::LengthLimit(10))
+            );
+        }
+
+        #[test]
+        fn array_at_limit() {
+            let config = Config::new().with_max_array_len(2);
+
+            let value: Vec<i64> = deserialize_limited(b"*2\r\n:1\r\n:2\r\n", config).unwrap();
+            assert_eq!(value, [1, 2]);
+
+            let value: Option<Vec<i64>> = deserialize_limited(b"*-1\r\n", config).unwrap();
+            assert_eq!(value, None);
+
+            assert_matches!(
+                deserialize_limited::<Vec<i64>>(b"*3\r\n:1\r\n:2\r\n:3\r\n", config),
+                Err(Error::LengthLimit(2))
+            );
+        }
+
+        #[test]
+        fn nested_array_len() {
+            let config = Config::new().with_max_array_len(2);
+
+            assert_matches!(
+                deserialize_limited::<Vec<Vec<i64>>>(b"*1\r\n*3\r\n:1\r\n:2\r\n:3\r\n", config),
+                Err(Error::LengthLimit(2))
+            );
+        }
+
+        #[test]
+        fn huge_bulk_string_header() {
+            // The bulk string is rejected based on its header, rather than
+            // waiting for more input
+            let config = Config::new().with_max_total_len(1024);
+
+            assert_matches!(
+                deserialize_limited::<&str>(b"$100000\r\nabc", config),
+                Err(Error::SizeLimit(1024))
+            );
+        }
+
+        #[test]
+        fn total_across_elements() {
+            let input = b"*3\r\n$3\r\nabc\r\n$3\r\ndef\r\n$3\r\nghi\r\n";
+
+            let value: Vec<&str> =
+                deserialize_limited(input, Config::new().with_max_total_len(input.len())).unwrap();
+            assert_eq!(value, ["abc", "def", "ghi"]);
+
+            assert_matches!(
+                deserialize_limited::<Vec<&str>>(input, Config::new().with_max_total_len(20)),
+                Err(Error::SizeLimit(20))
+            );
+        }
+
+        #[test]
+        fn total_is_per_value() {
+            // Each value in a pipeline is checked separately
+            let mut input: &[u8] = b"$3\r\nabc\r\n$3\r\ndef\r\n";
+
+            for expected in ["abc", "def"] {
+                let deserializer = Deserializer::new(&mut input).with_max_total_len(10);
+                assert_eq!(<&str>::deserialize(deserializer).unwrap(), expected);
+            }
+        }
+    }
+
+    /// Deserializing simple strings like `+OK` as a unit, with each
+    /// `UnitSimpleString` policy.
+    mod unit_variants {
+        use serde::Deserialize;
+
+        use super::*;
+
+        #[derive(Deserialize, Debug, PartialEq, Eq)]
+        enum Kind {
+            Hash,
+            Zset,
+            Other(i64),
+        }
+
+        #[test]
+        fn from_bulk_string() {
+            assert_eq!(from_bytes::<Kind>(b"$4\r\nHash\r\n").unwrap(), Kind::Hash);
+        }
+
+        #[test]
+        fn from_simple_string() {
+            assert_eq!(from_bytes::<Kind>(b"+Zset\r\n").unwrap(), Kind::Zset);
+        }
+
+        #[test]
+        fn unknown_variant() {
+            assert_matches!(from_bytes::<Kind>(b"+List\r\n"), Err(Error::Custom(_)));
+        }
+
+        #[test]
+        fn not_a_unit_variant() {
+            assert_matches!(from_bytes::<Kind>(b"+Other\r\n"), Err(Error::Custom(_)));
+        }
+
+        #[test]
+        fn not_a_string() {
+            assert_matches!(from_bytes::<Kind>(b":1\r\n"), Err(Error::Custom(_)));
+        }
+    }
+
+    mod flattened_maps {
+        use std::collections::{BTreeMap, HashMap};
+
+        use serde::Deserialize;
+
+        use super::*;
+        use crate::components::{KeyValuePairs, LastWins};
+
+        fn deserialize_flattened<'de, T: de::Deserialize<'de>>(
+            mut input: &'de [u8],
+        ) -> Result<T, Error> {
+            let deserializer = Deserializer::new(&mut input).with_flattened_maps(true);
+            let value = T::deserialize(deserializer)?;
+            assert!(input.is_empty());
+            Ok(value)
+        }
+
+        #[derive(Deserialize, Debug, PartialEq, Eq)]
+        struct Inner {
+            value: i64,
+        }
+
+        #[derive(Deserialize, Debug, PartialEq, Eq)]
+        struct Outer<'a> {
+            name: &'a str,
+            inner: Inner,
+            tags: Option<BTreeMap<&'a str, i64>>,
+        }
+
+        const OUTER: &[u8] = b"\
+            *6\r\n\
+                $4\r\nname\r\n\
+                $3\r\nabc\r\n\
+                $5\r\ninner\r\n\
+                *2\r\n$5\r\nvalue\r\n:1\r\n\
+                $4\r\ntags\r\n\
+                *2\r\n$1\r\nx\r\n:2\r\n\
+        ";
+
+        #[test]
+        fn nested_struct() {
+            assert_eq!(
+                deserialize_flattened::<Outer>(OUTER).unwrap(),
+                Outer {
+                    name: "abc",
+                    inner: Inner { value: 1 },
+                    tags: Some(BTreeMap::from([("x", 2)])),
+                }
+            );
+        }
+
+        #[test]
+        fn disabled_by_default() {
+            assert!(from_bytes::<Outer>(OUTER).is_err());
+        }
+
+        #[test]
+        fn map() {
+            let map: HashMap<String, i64> =
+                deserialize_flattened(b"*4\r\n$1\r\na\r\n:1\r\n$1\r\nb\r\n:2\r\n").unwrap();
+
+            assert_eq!(
+                map,
+                HashMap::from([("a".to_owned(), 1), ("b".to_owned(), 2)])
+            );
+        }
+
+        #[test]
+        fn sequence_of_maps() {
+            let maps: Vec<BTreeMap<&str, i64>> =
+                deserialize_flattened(b"*2\r\n*2\r\n$1\r\na\r\n:1\r\n*0\r\n").unwrap();
+
+            assert_eq!(maps, [BTreeMap::from([("a", 1)]), BTreeMap::new()]);
+        }
+
+        #[test]
+        fn odd_length() {
+            assert!(deserialize_flattened::<HashMap<String, i64>>(b"*1\r\n$1\r\na\r\n").is_err());
+        }
+
+        #[test]
+        fn duplicate_fields() {
+            let input = b"*4\r\n$5\r\nvalue\r\n:1\r\n$5\r\nvalue\r\n:2\r\n";
+
+            assert!(deserialize_flattened::<Inner>(input).is_err());
+
+            let LastWins(inner): LastWins<Inner> = deserialize_flattened(input).unwrap();
+            assert_eq!(inner, Inner { value: 2 });
+        }
+
+        #[test]
+        fn explicit_key_value_pairs() {
+            let KeyValuePairs(inner): KeyValuePairs<Inner> =
+                deserialize_flattened(b"*2\r\n$5\r\nvalue\r\n:1\r\n").unwrap();
+            assert_eq!(inner, Inner { value: 1 });
+        }
+    }
+
+    mod string_floats {
+        use serde::Deserialize;
+
+        use super::*;
+
+        fn deserialize_floats<'de, T: de::Deserialize<'de>>(
+            mut input: &'de [u8],
+        ) -> Result<T, Error> {
+            let deserializer = Deserializer::new(&mut input).with_string_floats(true);
+            let value = T::deserialize(deserializer)?;
+            assert!(input.is_empty());
+            Ok(value)
+        }
+
+        #[test]
+        fn bulk_string() {
+            let value: f64 = deserialize_floats(b"$4\r\n2.75\r\n").unwrap();
+            assert_eq!(value, 2.75);
+        }
+
+        #[test]
+        fn simple_string() {
+            let value: f32 = deserialize_floats(b"+-0.5\r\n").unwrap();
+            assert_eq!(value, -0.5);
+        }
+
+        #[test]
+        fn integer() {
+            let value: f64 = deserialize_floats(b":3\r\n").unwrap();
+            assert_eq!(value, 3.0);
+        }
+
+        #[test]
+        fn infinities() {
+            let values: Vec<f64> =
+                deserialize_floats(b"*3\r\n+inf\r\n$4\r\n+inf\r\n$4\r\n-inf\r\n").unwrap();
+            assert_eq!(values, [f64::INFINITY, f64::INFINITY, f64::NEG_INFINITY]);
+        }
+
+        #[test]
+        fn nested() {
+            #[derive(Deserialize, Debug, PartialEq)]
+            struct Member<'a>(&'a str, f64);
+
+            let members: Vec<Member> = deserialize_floats(
+                b"*2\r\n*2\r\n$1\r\na\r\n$3\r\n1.5\r\n*2\r\n$1\r\nb\r\n$2\r\n10\r\n",
+            )
+            .unwrap();
+            assert_eq!(members, [Member("a", 1.5), Member("b", 10.0)]);
+        }
+
+        #[test]
+        fn optional() {
+            let value: Option<f64> = deserialize_floats(b"$-1\r\n").unwrap();
+            assert_eq!(value, None);
+
+            let value: Option<f64> = deserialize_floats(b"$3\r\n0.1\r\n").unwrap();
+            assert_eq!(value, Some(0.1));
+        }
+
+        #[test]
+        fn not_a_number() {
+            assert_matches!(
+                deserialize_floats::<f64>(b"$3\r\nabc\r\n"),
+                Err(Error::Custom(_))
+            );
+        }
+
+        #[test]
+        fn nan() {
+            for input in [&b"$3\r\nnan\r\n"[..], b"+NaN\r\n", b"$4\r\n-nan\r\n"] {
+                assert_matches!(deserialize_floats::<f64>(input), Err(Error::Custom(_)));
+            }
+        }
+
+        #[test]
+        fn disabled() {
+            assert_matches!(from_bytes::<f64>(b"$3\r\n1.5\r\n"), Err(Error::Custom(_)));
+        }
+    }
+
+    mod big_numbers {
+        use super::*;
+        use crate::value::Value;
+
+        const HUGE: &[u8] = b"(3492890328409238509324850943850943825024385\r\n";
+
+        #[test]
+        fn small() {
+            let value: i64 = from_bytes(b"(-12\r\n").unwrap();
+            assert_eq!(value, -12);
+
+            let value: u8 = from_bytes(b"(+12\r\n").unwrap();
+            assert_eq!(value, 12);
+        }
+
+        #[test]
+        fn wide() {
+            let value: i128 = from_bytes(b"(-170141183460469231731687303715884105728\r\n").unwrap();
+            assert_eq!(value, i128::MIN);
+
+            let value: u128 = from_bytes(b"(340282366920938463463374607431768211455\r\n").unwrap();
+            assert_eq!(value, u128::MAX);
+
+            let value: u64 = from_bytes(b"(18446744073709551615\r\n").unwrap();
+            assert_eq!(value, u64::MAX);
+        }
+
+        #[test]
+        fn out_of_range() {
+            assert_matches!(
+                from_bytes::<i64>(b"(9223372036854775808\r\n"),
+                Err(Error::Custom(_))
+            );
+            assert_matches!(from_bytes::<u128>(HUGE), Err(Error::Custom(_)));
+        }
+
+        #[test]
+        fn as_string() {
+            let value: &str = from_bytes(HUGE).unwrap();
+            assert_eq!(value, "3492890328409238509324850943850943825024385");
+
+            let value: String = from_bytes(b"(-1\r\n").unwrap();
+            assert_eq!(value, "-1");
+
+            let value: &[u8] = from_bytes(b"(10\r\n").unwrap();
+            assert_eq!(value, b"10");
+        }
+
+        #[test]
+        fn as_value() {
+            let value: Value = from_bytes(b"(10\r\n").unwrap();
+            assert_eq!(value, Value::Integer(10));
+
+            let value: Value = from_bytes(HUGE).unwrap();
+            assert_eq!(
+                value,
+                Value::BulkString(b"3492890328409238509324850943850943825024385".to_vec())
+            );
+        }
+
+        #[test]
+        fn malformed() {
+            for input in [&b"(\r\n"[..], b"(-\r\n", b"(1.5\r\n", b"(12a\r\n"] {
+                assert_matches!(
+                    from_bytes::<i64>(input),
+                    Err(Error::Parse(parse::Error::Number))
+                );
+            }
+        }
+    }
+
+    mod byte_array {
+        use super::*;
+
+        #[test]
+        fn from_bulk_string() {
+            let digest: [u8; 20] = from_bytes(b"$20\r\n0123456789abcdefghij\r\n").unwrap();
+            assert_eq!(&digest, b"0123456789abcdefghij");
+        }
+
+        #[test]
+        fn empty() {
+            let empty: [u8; 0] = from_bytes(b"$0\r\n\r\n").unwrap();
+            assert_eq!(empty, []);
+        }
+
+        #[test]
+        fn length_mismatch() {
+            assert_matches!(
+                from_bytes::<[u8; 4]>(b"$5\r\nabcde\r\n"),
+                Err(Error::ArrayLength {
+                    expected: 4,
+                    actual: 5
+                })
+            );
+        }
+
+        #[test]
+        fn length_checked_before_payload() {
+            // The payload is missing, but the header alone shows that the
+            // length is wrong
+            assert_matches!(
+                from_bytes::<[u8; 4]>(b"$100\r\n"),
+                Err(Error::ArrayLength {
+                    expected: 4,
+                    actual: 100
+                })
+            );
+        }
+
+        #[test]
+        fn bad_length() {
+            assert_matches!(from_bytes::<[u8; 4]>(b"$-3\r\n"), Err(Error::Length));
+        }
+
+        #[test]
+        fn from_array() {
+            let bytes: [u8; 2] = from_bytes(b"*2\r\n:1\r\n:2\r\n").unwrap();
+            assert_eq!(bytes, [1, 2]);
+        }
+
+        #[test]
+        fn tuple_from_array() {
+            let pair: (&str, i64) = from_bytes(b"*2\r\n$3\r\nabc\r\n:2\r\n").unwrap();
+            assert_eq!(pair, ("abc", 2));
+        }
+
+        #[test]
+        fn borrowed() {
+            let bytes: &serde_bytes::ByteArray<3> = from_bytes(b"$3\r\nabc\r\n").unwrap();
+            assert_eq!(**bytes, *b"abc");
+        }
+    }
+
+    mod borrowed_str {
+        use super::*;
+
+        #[test]
+        fn zero_copy() {
+            let input = b"+PONG\r\n";
+            let reply: &str = from_bytes(input).unwrap();
+
+            assert_eq!(reply, "PONG");
+            assert_eq!(reply.as_ptr(), input[1..].as_ptr());
+        }
+
+        #[test]
+        fn zero_copy_in_array() {
+            let input = b"*2\r\n+OK\r\n+QUEUED\r\n";
+            let replies: Vec<&str> = from_bytes(input).unwrap();
+
+            assert_eq!(replies, ["OK", "QUEUED"]);
+            assert_eq!(replies[1].as_ptr(), input[10..].as_ptr());
+        }
+
+        /// The visitor gets a `str`, not bytes, so it doesn't have to check
+        /// the UTF-8 itself
+        #[test]
+        fn visits_str() {
+            struct StrOnly<'de>(&'de str);
+
+            impl<'de> de::Deserialize<'de> for StrOnly<'de> {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: de::Deserializer<'de>,
+                {
+                    struct Visitor;
+
+                    impl<'de> de::Visitor<'de> for Visitor {
+                        type Value = StrOnly<'de>;
+
+                        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                            f.write_str("a borrowed str")
+                        }
+
+                        fn visit_borrowed_str<E: de::Error>(
+                            self,
+                            v: &'de str,
+                        ) -> Result<Self::Value, E> {
+                            Ok(StrOnly(v))
+                        }
+                    }
+
+                    deserializer.deserialize_str(Visitor)
+                }
+            }
+
+            let StrOnly(reply) = from_bytes(b"+caf\xc3\xa9\r\n").unwrap();
+            assert_eq!(reply, "café");
+        }
+
+        #[test]
+        fn owned() {
+            let reply: String = from_bytes(b"+PONG\r\n").unwrap();
+            assert_eq!(reply, "PONG");
+        }
+
+        #[test]
+        fn invalid_utf8() {
+            assert_matches!(from_bytes::<&str>(b"+\xff\r\n"), Err(Error::Custom(_)));
+
+            // Bytes are unaffected
+            let reply: &[u8] = from_bytes(b"+\xff\r\n").unwrap();
+            assert_eq!(reply, b"\xff");
+        }
+    }
+
+    mod keepalives {
+        use super::*;
+        use crate::protocol::{PING, PONG};
+
+        const KEEPALIVES: &[&[u8]] = &[PING.as_bytes(), PONG.as_bytes()];
+
+        fn config() -> Config {
+            Config::new().with_keepalives(KEEPALIVES)
+        }
+
+        #[test]
+        fn skipped() {
+            let input = b"+PONG\r\n+PONG\r\n*2\r\n$1\r\na\r\n$1\r\nb\r\n+PONG\r\n";
+            let value: Vec<&str> = from_bytes_with_config(input, config()).unwrap();
+
+            assert_eq!(value, ["a", "b"]);
+        }
+
+        #[test]
+        fn between_replies() {
+            let mut input: &[u8] = b":1\r\n*1\r\n$4\r\nPING\r\n:2\r\n";
+
+            for expected in [1, 2] {
+                let deserializer = Deserializer::with_config(&mut input, config());
+                assert_eq!(i64::deserialize(deserializer).unwrap(), expected);
+            }
+
+            assert!(input.is_empty());
+        }
+
+        #[test]
+        fn not_nested() {
+            let input = b"*2\r\n+PONG\r\n:1\r\n";
+            let value: (&str, i64) = from_bytes_with_config(input, config()).unwrap();
+
+            assert_eq!(value, ("PONG", 1));
+        }
+
+        #[test]
+        fn not_configured() {
+            assert_matches!(from_bytes::<i64>(b"+PONG\r\n:1\r\n"), Err(Error::Custom(_)));
+        }
+
+        #[test]
+        fn only_keepalives() {
+            assert_matches!(
+                from_bytes_with_config::<i64>(b"+PONG\r\n", config()),
+                Err(Error::Parse(parse::Error::UnexpectedEof(_)))
+            );
+        }
+
+        #[test]
+        fn size_limit() {
+            let config = config().with_max_total_len(4);
+            let value: i64 = from_bytes_with_config(b"+PONG\r\n:1\r\n", config).unwrap();
+
+            assert_eq!(value, 1);
+        }
+    }
+
+    mod unit {
+        use super::*;
+
+        #[derive(serde::Deserialize, Debug, PartialEq, Eq)]
+        struct Discarded;
+
+        fn deserialize_with<'de, T: de::Deserialize<'de>>(
+            mut input: &'de [u8],
+            policy: UnitSimpleString,
+        ) -> Result<T, Error> {
+            let deserializer = Deserializer::new(&mut input).with_unit_simple_string(policy);
+            let value = T::deserialize(deserializer)?;
+            assert!(input.is_empty());
+            Ok(value)
+        }
+
+        #[test]
+        fn ok_as_unit() {
+            let () = from_bytes(b"+OK\r\n").unwrap();
+        }
+
+        #[test]
+        fn ok_as_unit_struct() {
+            assert_eq!(from_bytes::<Discarded>(b"+OK\r\n").unwrap(), Discarded);
+        }
+
+        #[test]
+        fn null_as_unit() {
+            let () = from_bytes(b"$-1\r\n").unwrap();
+            let () = deserialize_with(b"$-1\r\n", UnitSimpleString::Reject).unwrap();
+        }
+
+        #[test]
+        fn other_simple_string() {
+            assert!(from_bytes::<()>(b"+QUEUED\r\n").is_err());
+            assert!(from_bytes::<()>(b"$2\r\nOK\r\n").is_err());
+        }
+
+        #[test]
+        fn any_simple_string() {
+            let () = deserialize_with(b"+QUEUED\r\n", UnitSimpleString::Any).unwrap();
+
+            let units: Vec<Discarded> =
+                deserialize_with(b"*2\r\n+OK\r\n+QUEUED\r\n", UnitSimpleString::Any).unwrap();
+            assert_eq!(units, [Discarded, Discarded]);
+        }
+
+        #[test]
+        fn reject_simple_strings() {
+            assert!(deserialize_with::<()>(b"+OK\r\n", UnitSimpleString::Reject).is_err());
+            assert!(
+                deserialize_with::<Vec<()>>(b"*1\r\n+OK\r\n", UnitSimpleString::Reject).is_err()
+            );
+        }
+
+        #[test]
+        fn errors_are_not_units() {
+            assert_matches!(
+                deserialize_with::<()>(b"-ERR x\r\n", UnitSimpleString::Any),
+                Err(Error::Redis(_))
+            );
+        }
+    }
+
+    /// Try to deserialize `input` as a handful of different types, ignoring
+    /// the result. This exists to assert that none of these deserializes
+    /// panic.
+    fn deserialize_everything(input: &[u8]) {
+        let _ = from_bytes::<Data>(input);
+        let _ = from_bytes::<Vec<Option<String>>>(input);
+        let _ = from_bytes::<Result<Vec<i64>, &str>>(input);
+        let _ = from_bytes::<Result<(), String>>(input);
+        let _ = from_bytes::<(bool, Option<&str>, de::IgnoredAny)>(input);
+        let _ = from_bytes::<Vec<Vec<u8>>>(input);
+    }
+
+    #[test]
+    fn test_no_panics() {
+        let corpus: &[&[u8]] = &[
+            b"+OK\r\n",
+            b"-ERR message\r\n",
+            b":-1000\r\n",
+            b"$5\r\nhello\r\n",
+            b"$-1\r\n",
+            b"*-1\r\n",
+            b"*0\r\n",
+            b"*3\r\n:10\r\n$5\r\nhello\r\n$-1\r\n",
+            b"*2\r\n*2\r\n+a\r\n+b\r\n*1\r\n-ERR\r\n",
+            b"$9223372036854775807\r\n",
+            b"*9223372036854775807\r\n",
+            b"$-9223372036854775808\r\n",
+            b"*-2\r\n",
+        ];
+
+        let interesting = [
+            0, b'\r', b'\n', b'-', b'+', b':', b'$', b'*', b'0', b'1', b'9', 0xFF,
+        ];
+
+        for &frame in corpus {
+            // Every truncation
+            for len in 0..=frame.len() {
+                deserialize_everything(&frame[..len]);
+            }
+
+            // Every single-byte substitution
+            for idx in 0..frame.len() {
+                for &b in &interesting {
+                    let mut mutated = frame.to_owned();
+                    mutated[idx] = b;
+                    deserialize_everything(&mutated);
+                }
+            }
+        }
+
+        // Random soup built from protocol-relevant bytes
+        let mut state = 0x2545F4914F6CDD1D;
+        for _ in 0..20_000 {
+            let len = (xorshift(&mut state) % 32) as usize;
+            let input: Vec<u8> = (0..len)
+                .map(|_| interesting[(xorshift(&mut state) as usize) % interesting.len()])
+                .collect();
+
+            deserialize_everything(&input);
+        }
+    }
+
+    mod collections {
+        use std::collections::{
+            BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque,
+        };
+        use std::fmt::Debug;
+
+        use serde::{Deserialize, Serialize};
+
+        use crate::{components::KeyValuePairs, de::from_bytes, ser::to_vec};
+
+        fn round_trip<T>(value: T, expected: &[u8]) -> T
+        where
+            T: Serialize + for<'de> Deserialize<'de>,
+        {
+            let encoded = to_vec(&value).expect("failed to serialize");
+            assert_eq!(encoded, expected);
+            from_bytes(&encoded).expect("failed to deserialize")
+        }
+
+        macro_rules! collection_tests {
+            ($($name:ident: $value:expr => $expected:literal;)*) => {$(
+                #[test]
+                fn $name() {
+                    let value = $value;
+                    assert_eq!(round_trip(value.clone(), $expected), value);
+                }
+            )*};
+        }
+
+        collection_tests! {
+            vec: Vec::from([1, 2, 3]) => b"*3\r\n:1\r\n:2\r\n:3\r\n";
+            vec_deque: VecDeque::from([1, 2, 3]) => b"*3\r\n:1\r\n:2\r\n:3\r\n";
+            linked_list: LinkedList::from(["a", "b"].map(String::from)) => b"*2\r\n$1\r\na\r\n$1\r\nb\r\n";
+            btree_set: BTreeSet::from([3, 1, 2]) => b"*3\r\n:1\r\n:2\r\n:3\r\n";
+            boxed_slice: Box::<[i64]>::from([4, 5]) => b"*2\r\n:4\r\n:5\r\n";
+            empty_vec_deque: VecDeque::<i64>::new() => b"*0\r\n";
+
+            // No defined iteration order, so only a single element
+            hash_set: HashSet::from([7]) => b"*1\r\n:7\r\n";
+        }
+
+        #[test]
+        fn key_value_maps() {
+            let map = BTreeMap::from([("a".to_owned(), 1), ("b".to_owned(), 2)]);
+            let KeyValuePairs(decoded) = round_trip(
+                KeyValuePairs(map.clone()),
+                b"*4\r\n$1\r\na\r\n:1\r\n$1\r\nb\r\n:2\r\n",
+            );
+            assert_eq!(decoded, map);
+
+            let map = HashMap::from([("a".to_owned(), 1)]);
+            let KeyValuePairs(decoded) =
+                round_trip(KeyValuePairs(map.clone()), b"*2\r\n$1\r\na\r\n:1\r\n");
+            assert_eq!(decoded, map);
+        }
+
+        #[test]
+        fn binary_heap() {
+            let heap = round_trip(BinaryHeap::from([2]), b"*1\r\n:2\r\n");
+            assert_eq!(heap.into_sorted_vec(), [2]);
+
+            let heap: BinaryHeap<i64> = from_bytes(b"*3\r\n:2\r\n:3\r\n:1\r\n").unwrap();
+            assert_eq!(heap.into_sorted_vec(), [1, 2, 3]);
+        }
+
+        /// A collection that is only constructable via `FromIterator`
+        #[derive(Debug, PartialEq)]
+        struct Total(i64);
+
+        impl FromIterator<i64> for Total {
+            fn from_iter<I: IntoIterator<Item = i64>>(iter: I) -> Self {
+                Self(iter.into_iter().sum())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for Total {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct Visitor;
+
+                impl<'de> serde::de::Visitor<'de> for Visitor {
+                    type Value = Total;
+
+                    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                        write!(f, "an array of integers")
+                    }
+
+                    fn visit_seq<A>(self, mut seq: A) -> Result<Total, A::Error>
+                    where
+                        A: serde::de::SeqAccess<'de>,
+                    {
+                        itertools::process_results(
+                            std::iter::from_fn(|| seq.next_element().transpose()),
+                            |iter| iter.collect(),
+                        )
+                    }
+                }
+
+                deserializer.deserialize_seq(Visitor)
+            }
+        }
+
+        #[test]
+        fn from_iterator() {
+            let total: Total = from_bytes(b"*4\r\n:1\r\n:2\r\n:3\r\n:4\r\n").unwrap();
+            assert_eq!(total, Total(10));
+        }
+
+        #[test]
+        fn kv_index_map() {
+            use indexmap::IndexMap;
+
+            // IndexMap preserves the order of the keys in the array
+            let map: IndexMap<String, i64> = [("z", 1), ("a", 2), ("m", 3)]
+                .into_iter()
+                .map(|(key, value)| (key.to_owned(), value))
+                .collect();
+
+            let KeyValuePairs(decoded) = round_trip(
+                KeyValuePairs(map.clone()),
+                b"*6\r\n$1\r\nz\r\n:1\r\n$1\r\na\r\n:2\r\n$1\r\nm\r\n:3\r\n",
+            );
+
+            assert!(decoded.iter().eq(map.iter()));
+        }
+    }
+
+    mod mismatches {
+        use super::*;
+
+        fn message<'a, T: de::Deserialize<'a> + Debug>(input: &'a [u8]) -> String {
+            match from_bytes::<T>(input) {
+                Err(Error::Custom(message)) => message,
+                result => panic!("expected a custom error, got {result:?}"),
+            }
+        }
+
+        #[test]
+        fn bulk_string() {
+            assert_eq!(
+                message::<i32>(b"$5\r\nhello\r\n"),
+                r#"invalid type: bulk string of length 5 ("hello"), expected i32"#
+            );
+        }
+
+        #[test]
+        fn simple_string() {
+            assert_eq!(
+                message::<u8>(b"+OK\r\n"),
+                r#"invalid type: simple string of length 2 ("OK"), expected u8"#
+            );
+        }
+
+        #[test]
+        fn integer() {
+            assert_eq!(
+                message::<String>(b":12\r\n"),
+                "invalid type: integer 12, expected a string"
+            );
+        }
+
+        #[test]
+        fn null() {
+            assert_eq!(
+                message::<i64>(b"$-1\r\n"),
+                "invalid type: null, expected i64"
+            );
+        }
+
+        #[test]
+        fn array() {
+            assert_eq!(
+                message::<String>(b"*2\r\n:1\r\n:2\r\n"),
+                "invalid type: array of length 2, expected a string"
+            );
+        }
+
+        #[test]
+        fn invalid_value() {
+            assert_eq!(
+                message::<u8>(b":300\r\n"),
+                "invalid value: integer 300, expected u8"
+            );
+        }
+
+        /// A mismatch inside an array describes the element, not the array
+        #[test]
+        fn nested() {
+            assert_eq!(
+                message::<Vec<i64>>(b"*2\r\n:1\r\n$1\r\nx\r\n"),
+                r#"invalid type: bulk string of length 1 ("x"), expected i64"#
+            );
+        }
+
+        #[test]
+        fn long_binary_payload() {
+            let mut input = b"$40\r\n\x00\xff".to_vec();
+            input.extend_from_slice(&[b'a'; 38]);
+            input.extend_from_slice(b"\r\n");
+
+            assert_eq!(
+                message::<bool>(&input),
+                r#"invalid type: bulk string of length 40 ("\x00\xffaaaaaaaaaaaaaaaaaaaaaa"...), expected a boolean"#
+            );
+        }
+
+        /// Errors that aren't mismatches are unchanged
+        #[test]
+        fn other_errors() {
+            assert_matches!(
+                from_bytes::<(i64, i64)>(b"*1\r\n:1\r\n"),
+                Err(Error::Custom(message)) => assert_eq!(
+                    message,
+                    "invalid length 1, expected a tuple of size 2"
+                )
+            );
+        }
+    }
+
+    mod bytes_needed {
+        use super::*;
+
+        #[test]
+        fn incomplete() {
+            let err = from_bytes::<&str>(b"$10\r\nhello").unwrap_err();
+            assert_eq!(err.bytes_needed(), Some(7));
+        }
+
+        #[test]
+        fn option() {
+            let err = from_bytes::<Option<&str>>(b"$3\r\nab").unwrap_err();
+            assert_eq!(err.bytes_needed(), Some(3));
+        }
+
+        #[test]
+        fn not_incomplete() {
+            let err = from_bytes::<i64>(b"?1\r\n").unwrap_err();
+            assert_matches!(err, Error::Parse(_));
+            assert_eq!(err.bytes_needed(), None);
+
+            let err = from_bytes::<(i64, i64)>(b"*1\r\n:1\r\n").unwrap_err();
+            assert_eq!(err.bytes_needed(), None);
+        }
+    }
 }
@@ -0,0 +1,166 @@
+use std::{future::poll_fn, io, pin::Pin};
+
+use futures_io::AsyncBufRead;
+use serde::de;
+
+use super::{from_bytes, parse, Error, ReadError};
+
+/**
+Deserialize a `T` object from a [`futures_io::AsyncBufRead`] source. This
+works with any runtime that uses the `futures-io` traits, such as `smol` or
+`async-std`.
+
+Exactly one RESP value is read; any data after it is left in the reader, so
+this function can be called repeatedly to read pipelined responses. The
+value is framed with the [`UnexpectedEof`][parse::Error::UnexpectedEof]
+hints from the parser, so each part of it is only parsed once, and it's
+only deserialized when it's complete. Because the value is read into an
+internal buffer, `T` must own its data.
+
+# Example
+
+```
+use seredies::de::from_async_reader;
+
+# futures_executor::block_on(async {
+let mut input: &[u8] = b"*2\r\n:1\r\n:2\r\n+OK\r\n";
+
+let numbers: Vec<i64> = from_async_reader(&mut input).await.unwrap();
+assert_eq!(numbers, [1, 2]);
+
+let status: String = from_async_reader(&mut input).await.unwrap();
+assert_eq!(status, "OK");
+# })
+```
+*/
+pub async fn from_async_reader<T, R>(mut reader: R) -> Result<T, ReadError>
+where
+    T: de::DeserializeOwned,
+    R: AsyncBufRead + Unpin,
+{
+    let mut buffer = Vec::new();
+    let mut frame = parse::FrameScanner::new();
+
+    loop {
+        match frame.scan(&buffer) {
+            Ok(_) => break,
+
+            // Only take as much of the reader's buffer as the value is known
+            // to need, so that any pipelined data stays in the reader.
+            Err(parse::Error::UnexpectedEof(needed)) => {
+                let read = poll_fn(|cx| {
+                    Pin::new(&mut reader).poll_fill_buf(cx).map_ok(|chunk| {
+                        let chunk = &chunk[..chunk.len().min(needed)];
+                        buffer.extend_from_slice(chunk);
+                        chunk.len()
+                    })
+                })
+                .await?;
+
+                if read == 0 {
+                    return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+                }
+
+                Pin::new(&mut reader).consume(read);
+            }
+            Err(err) => return Err(Error::Parse(err).into()),
+        }
+    }
+
+    from_bytes(&buffer).map_err(ReadError::Deserialize)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::task::{Context, Poll};
+
+    use cool_asserts::assert_matches;
+    use futures_executor::block_on;
+    use futures_io::AsyncRead;
+
+    use super::*;
+
+    /// A reader that returns its data a few bytes at a time, and returns
+    /// `Pending` before every chunk.
+    struct Trickle<'a> {
+        data: &'a [u8],
+        chunk: usize,
+        ready: bool,
+    }
+
+    impl AsyncRead for Trickle<'_> {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut [u8],
+        ) -> Poll<io::Result<usize>> {
+            let chunk = match self.as_mut().poll_fill_buf(cx) {
+                Poll::Ready(Ok(chunk)) => chunk,
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                Poll::Pending => return Poll::Pending,
+            };
+
+            let len = chunk.len().min(buf.len());
+            buf[..len].copy_from_slice(&chunk[..len]);
+            self.consume(len);
+            Poll::Ready(Ok(len))
+        }
+    }
+
+    impl AsyncBufRead for Trickle<'_> {
+        fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+            let this = self.get_mut();
+            this.ready = !this.ready;
+
+            match this.ready {
+                false => {
+                    cx.waker().wake_by_ref();
+                    Poll::Pending
+                }
+                true => Poll::Ready(Ok(&this.data[..this.chunk.min(this.data.len())])),
+            }
+        }
+
+        fn consume(self: Pin<&mut Self>, amount: usize) {
+            let this = self.get_mut();
+            this.data = &this.data[amount..];
+        }
+    }
+
+    #[test]
+    fn trickled_pipeline() {
+        let mut reader = Trickle {
+            data: b"*3\r\n$5\r\nhello\r\n$-1\r\n:10\r\n+OK\r\n",
+            chunk: 3,
+            ready: false,
+        };
+
+        let value: (String, Option<String>, i64) =
+            block_on(from_async_reader(&mut reader)).unwrap();
+        assert_eq!(value, ("hello".to_owned(), None, 10));
+
+        let status: String = block_on(from_async_reader(&mut reader)).unwrap();
+        assert_eq!(status, "OK");
+        assert!(reader.data.is_empty());
+    }
+
+    #[test]
+    fn unexpected_eof() {
+        let mut input: &[u8] = b"*2\r\n:1\r\n";
+
+        assert_matches!(
+            block_on(from_async_reader::<Vec<i64>, _>(&mut input)),
+            Err(ReadError::Io(err)) => assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof)
+        );
+    }
+
+    #[test]
+    fn deserialize_error() {
+        let mut input: &[u8] = b"-ERR oops\r\n";
+
+        assert_matches!(
+            block_on(from_async_reader::<Vec<i64>, _>(&mut input)),
+            Err(ReadError::Deserialize(Error::Redis(msg))) => assert_eq!(msg, b"ERR oops")
+        );
+    }
+}
@@ -0,0 +1,215 @@
+//! Resumable, bounded-work deserialization of large RESP arrays.
+
+use serde::de;
+
+use super::parse::{self, TaggedHeader};
+use super::{apply_parser, Deserializer, Error, RedisMessage};
+
+/// The outcome of a call to [`ArrayCursor::step`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Progress {
+    /// Every element of the array has been deserialized.
+    Complete,
+
+    /// The work budget for this call was exhausted, but there are still
+    /// elements remaining in the array. Call [`step`][ArrayCursor::step]
+    /// again to continue.
+    Paused,
+}
+
+/**
+A resumable cursor over the elements of a top-level RESP array.
+
+Deserializing a very large reply in a single call can monopolize a thread for
+a long time, which is a problem for single-threaded async executors. An
+`ArrayCursor` instead deserializes an array a bounded number of elements at a
+time, keeping track of how many elements remain, so that callers can yield
+between chunks.
+
+Each element is deserialized atomically: if an element fails to deserialize
+(including because of an [`UnexpectedEof`][parse::Error::UnexpectedEof]), the
+input and the cursor are left untouched. This means that a caller can read
+more data into its buffer and simply retry from the same position.
+
+# Example
+
+```
+use seredies::de::{ArrayCursor, Progress};
+
+let mut input: &[u8] = b"*5\r\n:1\r\n:2\r\n:3\r\n:4\r\n:5\r\n";
+let mut cursor = ArrayCursor::new(&mut input).unwrap();
+let mut total = 0;
+
+// Process at most 2 elements per call
+assert_eq!(cursor.step(&mut input, 2, |n: i64| total += n).unwrap(), Progress::Paused);
+assert_eq!(total, 3);
+assert_eq!(cursor.remaining(), 3);
+
+// ... yield to the executor here ...
+
+assert_eq!(cursor.step(&mut input, 2, |n: i64| total += n).unwrap(), Progress::Paused);
+assert_eq!(cursor.step(&mut input, 2, |n: i64| total += n).unwrap(), Progress::Complete);
+assert_eq!(total, 15);
+assert!(input.is_empty());
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArrayCursor {
+    remaining: usize,
+}
+
+impl ArrayCursor {
+    /// Read an array header from the input and create a cursor over its
+    /// elements. The input is advanced past the header only if it was
+    /// successfully read. It's an error if the next value in the input is
+    /// anything other than an array.
+    pub fn new(input: &mut &[u8]) -> Result<Self, Error> {
+        let mut tail = *input;
+
+        let remaining = match apply_parser(&mut tail, parse::read_header)? {
+            TaggedHeader::Array(len) => len.try_into().map_err(|_| Error::Length)?,
+            TaggedHeader::Error(message) => return Err(Error::Redis(RedisMessage::new(message))),
+            _ => {
+                return Err(de::Error::invalid_type(
+                    de::Unexpected::Other("a non-array RESP value"),
+                    &"a RESP array",
+                ))
+            }
+        };
+
+        *input = tail;
+        Ok(Self { remaining })
+    }
+
+    /// The number of elements that haven't been deserialized yet.
+    #[inline]
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    /// True if every element of the array has been deserialized.
+    #[inline]
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.remaining == 0
+    }
+
+    /// Deserialize the next element of the array, or return `None` if the
+    /// array is complete. On error, neither the cursor nor the input are
+    /// modified.
+    pub fn next<'de, T>(&mut self, input: &mut &'de [u8]) -> Result<Option<T>, Error>
+    where
+        T: de::Deserialize<'de>,
+    {
+        let Some(remaining) = self.remaining.checked_sub(1) else {
+            return Ok(None);
+        };
+
+        let mut tail = *input;
+        let value = T::deserialize(Deserializer::new(&mut tail))?;
+
+        *input = tail;
+        self.remaining = remaining;
+        Ok(Some(value))
+    }
+
+    /// Deserialize at most `budget` elements from the array, passing each
+    /// one to `handler`. Returns [`Progress::Complete`] once the whole array
+    /// has been deserialized, or [`Progress::Paused`] if the budget ran out
+    /// first.
+    ///
+    /// If an error occurs, every element successfully deserialized before
+    /// the error has been passed to `handler`, and the cursor and input are
+    /// positioned at the element that failed.
+    pub fn step<'de, T, F>(
+        &mut self,
+        input: &mut &'de [u8],
+        budget: usize,
+        mut handler: F,
+    ) -> Result<Progress, Error>
+    where
+        T: de::Deserialize<'de>,
+        F: FnMut(T),
+    {
+        for _ in 0..budget {
+            match self.next(input)? {
+                Some(value) => handler(value),
+                None => return Ok(Progress::Complete),
+            }
+        }
+
+        Ok(match self.is_complete() {
+            true => Progress::Complete,
+            false => Progress::Paused,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cool_asserts::assert_matches;
+
+    use super::*;
+
+    #[test]
+    fn resume_after_eof() {
+        let full: &[u8] = b"*3\r\n$3\r\nabc\r\n$3\r\ndef\r\n$3\r\nghi\r\n";
+
+        // Only part of the second element has arrived
+        let mut input = &full[..17];
+        let mut cursor = ArrayCursor::new(&mut input).unwrap();
+        let mut items: Vec<String> = Vec::new();
+
+        assert_matches!(
+            cursor.step(&mut input, 10, |s| items.push(s)),
+            Err(Error::Parse(parse::Error::UnexpectedEof(_)))
+        );
+        assert_eq!(items, ["abc"]);
+        assert_eq!(cursor.remaining(), 2);
+
+        // The rest of the data arrives; resume from the same offset.
+        let consumed = 17 - input.len();
+        let mut input = &full[consumed..];
+
+        assert_eq!(
+            cursor.step(&mut input, 10, |s| items.push(s)).unwrap(),
+            Progress::Complete
+        );
+        assert_eq!(items, ["abc", "def", "ghi"]);
+        assert!(input.is_empty());
+    }
+
+    #[test]
+    fn zero_budget() {
+        let mut input: &[u8] = b"*1\r\n:1\r\n";
+        let mut cursor = ArrayCursor::new(&mut input).unwrap();
+
+        assert_eq!(
+            cursor.step(&mut input, 0, |_: i64| panic!()).unwrap(),
+            Progress::Paused
+        );
+        assert_eq!(cursor.remaining(), 1);
+    }
+
+    #[test]
+    fn empty_array() {
+        let mut input: &[u8] = b"*0\r\n";
+        let mut cursor = ArrayCursor::new(&mut input).unwrap();
+
+        assert_eq!(
+            cursor.step(&mut input, 0, |_: i64| panic!()).unwrap(),
+            Progress::Complete
+        );
+    }
+
+    #[test]
+    fn not_an_array() {
+        let mut input: &[u8] = b":1\r\n";
+        assert_matches!(ArrayCursor::new(&mut input), Err(Error::Custom(_)));
+        assert_eq!(input, b":1\r\n");
+
+        let mut input: &[u8] = b"-ERR bad\r\n";
+        assert_matches!(ArrayCursor::new(&mut input), Err(Error::Redis(msg)) => assert_eq!(msg, b"ERR bad"));
+    }
+}
@@ -0,0 +1,139 @@
+//! Streaming aggregation over the elements of a RESP array.
+
+use core::{fmt, marker::PhantomData};
+
+use serde::de::{self, Deserializer as _};
+
+use super::{Deserializer, Error};
+
+/**
+Deserialize each element of a top-level RESP array, one at a time, and fold
+them into an accumulator.
+
+This is useful for aggregating large replies, like summing `MEMORY USAGE`
+results or hashing the members of a `SMEMBERS` reply, without first
+collecting every element into a `Vec`. A null array is treated as empty. As
+with [`from_bytes`][super::from_bytes], the input must contain exactly one
+RESP value.
+
+# Example
+
+```
+use seredies::de::fold_array;
+
+let input = b"*3\r\n:10\r\n:20\r\n:30\r\n";
+let total = fold_array(input, 0, |total, n: i64| total + n).unwrap();
+assert_eq!(total, 60);
+
+// Elements can borrow from the input
+let input = b"*2\r\n$5\r\nhello\r\n$5\r\nworld\r\n";
+let longest = fold_array(input, "", |longest, s: &str| longest.max(s)).unwrap();
+assert_eq!(longest, "world");
+```
+*/
+pub fn fold_array<'de, T, B>(
+    mut input: &'de [u8],
+    init: B,
+    f: impl FnMut(B, T) -> B,
+) -> Result<B, Error>
+where
+    T: de::Deserialize<'de>,
+{
+    let value = Deserializer::new(&mut input).deserialize_seq(FoldVisitor {
+        init,
+        f,
+        element: PhantomData,
+    })?;
+
+    input.is_empty().then_some(value).ok_or(Error::TrailingData)
+}
+
+struct FoldVisitor<T, B, F> {
+    init: B,
+    f: F,
+    element: PhantomData<fn() -> T>,
+}
+
+impl<'de, T, B, F> de::Visitor<'de> for FoldVisitor<T, B, F>
+where
+    T: de::Deserialize<'de>,
+    F: FnMut(B, T) -> B,
+{
+    type Value = B;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a RESP array")
+    }
+
+    fn visit_seq<A>(mut self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let mut accumulator = self.init;
+
+        while let Some(element) = seq.next_element()? {
+            accumulator = (self.f)(accumulator, element);
+        }
+
+        Ok(accumulator)
+    }
+
+    #[inline]
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(self.init)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cool_asserts::assert_matches;
+
+    use super::*;
+
+    fn count(input: &[u8]) -> Result<usize, Error> {
+        fold_array(input, 0, |count, _: de::IgnoredAny| count + 1)
+    }
+
+    #[test]
+    fn empty_and_null() {
+        assert_eq!(count(b"*0\r\n").unwrap(), 0);
+        assert_eq!(count(b"*-1\r\n").unwrap(), 0);
+    }
+
+    #[test]
+    fn nested_elements() {
+        let input = b"*2\r\n*2\r\n:1\r\n:2\r\n*1\r\n:3\r\n";
+        let total = fold_array(input, 0, |total, v: Vec<i64>| total + v.iter().sum::<i64>());
+        assert_eq!(total.unwrap(), 6);
+    }
+
+    #[test]
+    fn bad_element() {
+        let input = b"*2\r\n:1\r\n$3\r\nabc\r\n";
+        let result = fold_array(input, 0, |total, n: i64| total + n);
+        assert_matches!(result, Err(Error::Custom(_)));
+    }
+
+    #[test]
+    fn redis_error() {
+        assert_matches!(count(b"-ERR no such key\r\n"), Err(Error::Redis(_)));
+    }
+
+    #[test]
+    fn not_an_array() {
+        assert_matches!(count(b":12\r\n"), Err(Error::Custom(_)));
+    }
+
+    #[test]
+    fn trailing_data() {
+        assert_matches!(count(b"*1\r\n:1\r\n:2\r\n"), Err(Error::TrailingData));
+    }
+
+    #[test]
+    fn truncated() {
+        assert_matches!(count(b"*2\r\n:1\r\n"), Err(Error::Parse(_)));
+    }
+}
@@ -0,0 +1,203 @@
+//! Presenting RESP2 replies in the shape that RESP3 would give them.
+
+use crate::Config;
+
+/**
+A hint about the shape of a reply, which lets RESP2 replies be deserialized
+the way a RESP3 server would send them.
+
+RESP3 has dedicated types for maps, booleans, and doubles, but RESP2
+servers send them by convention as flattened arrays, integers, and
+strings. Clients that want the same data regardless of the server's
+protocol can pass the hint for each command to
+[`apply`][ReplyHint::apply], which chooses the matching [`Config`] options
+for the reply. [`for_command`][ReplyHint::for_command] knows the hints for
+common commands.
+
+# Example
+
+```
+use std::collections::HashMap;
+
+use seredies::de::{from_bytes_with_config, ReplyHint};
+use seredies::Config;
+
+let hint = ReplyHint::for_command("HGETALL");
+assert_eq!(hint, ReplyHint::Map);
+
+let fields: HashMap<String, String> = from_bytes_with_config(
+    b"*4\r\n$4\r\nname\r\n$5\r\nalice\r\n$4\r\nrole\r\n$5\r\nadmin\r\n",
+    hint.apply(Config::new()),
+)
+.unwrap();
+assert_eq!(fields["role"], "admin");
+
+let score: f64 = from_bytes_with_config(
+    b"$4\r\n10.5\r\n",
+    ReplyHint::for_command("zscore").apply(Config::new()),
+)
+.unwrap();
+assert_eq!(score, 10.5);
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum ReplyHint {
+    /// The reply doesn't need any conversion.
+    #[default]
+    Plain,
+
+    /// The reply is a map, which RESP2 sends as a flattened array of
+    /// alternating keys and values, like the reply to `HGETALL`. This
+    /// enables [`with_flattened_maps`][Config::with_flattened_maps].
+    Map,
+
+    /// The reply is a boolean, or an array of them, which RESP2 sends as the
+    /// integers 0 and 1, like the reply to `SISMEMBER`. This enables
+    /// [`with_integer_bools`][Config::with_integer_bools].
+    Boolean,
+
+    /// The reply is a floating point number, or an array of them, which
+    /// RESP2 sends as strings, like the reply to `ZSCORE`. This enables
+    /// [`with_string_floats`][Config::with_string_floats].
+    Double,
+}
+
+impl ReplyHint {
+    /// Get the hint for the reply to a command, by its name, ignoring case.
+    /// Subcommands are included in the name, separated by a space, like
+    /// `CONFIG GET`. Commands whose replies don't need conversion, or that
+    /// aren't known, are [`Plain`][ReplyHint::Plain].
+    ///
+    /// The hint is based only on the name, so commands whose reply depends
+    /// on their arguments, like `ZRANGE` with `WITHSCORES`, are `Plain`;
+    /// use a hint directly for those.
+    #[must_use]
+    pub fn for_command(name: &str) -> Self {
+        const MAPS: &[&str] = &[
+            "HGETALL",
+            "CONFIG GET",
+            "HELLO",
+            "XINFO STREAM",
+            "XINFO GROUPS",
+            "XINFO CONSUMERS",
+            "MEMORY STATS",
+            "CLIENT TRACKINGINFO",
+        ];
+
+        const BOOLEANS: &[&str] = &[
+            "SISMEMBER",
+            "SMISMEMBER",
+            "HEXISTS",
+            "HSETNX",
+            "SETNX",
+            "MSETNX",
+            "SMOVE",
+            "RENAMENX",
+            "MOVE",
+            "COPY",
+            "PERSIST",
+            "EXPIRE",
+            "PEXPIRE",
+            "EXPIREAT",
+            "PEXPIREAT",
+            "SCRIPT EXISTS",
+        ];
+
+        const DOUBLES: &[&str] = &[
+            "ZSCORE",
+            "ZMSCORE",
+            "ZINCRBY",
+            "INCRBYFLOAT",
+            "HINCRBYFLOAT",
+            "GEODIST",
+        ];
+
+        let matches = |names: &[&str]| names.iter().any(|known| known.eq_ignore_ascii_case(name));
+
+        if matches(MAPS) {
+            ReplyHint::Map
+        } else if matches(BOOLEANS) {
+            ReplyHint::Boolean
+        } else if matches(DOUBLES) {
+            ReplyHint::Double
+        } else {
+            ReplyHint::Plain
+        }
+    }
+
+    /// Enable the options in `config` that this hint needs. Other options
+    /// are left as they are.
+    #[inline]
+    #[must_use]
+    pub const fn apply(self, config: Config) -> Config {
+        match self {
+            ReplyHint::Plain => config,
+            ReplyHint::Map => config.with_flattened_maps(true),
+            ReplyHint::Boolean => config.with_integer_bools(true),
+            ReplyHint::Double => config.with_string_floats(true),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::de::from_bytes_with_config;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    #[serde(untagged)]
+    enum Dynamic {
+        Bool(bool),
+        Integer(i64),
+        Double(f64),
+        Text(String),
+    }
+
+    fn deserialize<'a, T: Deserialize<'a>>(input: &'a [u8], command: &str) -> T {
+        from_bytes_with_config(input, ReplyHint::for_command(command).apply(Config::new())).unwrap()
+    }
+
+    #[test]
+    fn for_command() {
+        assert_eq!(ReplyHint::for_command("hgetall"), ReplyHint::Map);
+        assert_eq!(ReplyHint::for_command("Config Get"), ReplyHint::Map);
+        assert_eq!(ReplyHint::for_command("CONFIG SET"), ReplyHint::Plain);
+        assert_eq!(ReplyHint::for_command("SISMEMBER"), ReplyHint::Boolean);
+        assert_eq!(ReplyHint::for_command("INCRBYFLOAT"), ReplyHint::Double);
+        assert_eq!(ReplyHint::for_command("GET"), ReplyHint::Plain);
+    }
+
+    #[test]
+    fn booleans() {
+        let reply: Vec<Dynamic> = deserialize(b"*3\r\n:1\r\n:0\r\n:1\r\n", "SMISMEMBER");
+        assert_eq!(
+            reply,
+            [
+                Dynamic::Bool(true),
+                Dynamic::Bool(false),
+                Dynamic::Bool(true)
+            ]
+        );
+
+        // Without the hint, they're integers
+        let reply: Vec<Dynamic> = deserialize(b"*2\r\n:1\r\n:0\r\n", "LPUSH");
+        assert_eq!(reply, [Dynamic::Integer(1), Dynamic::Integer(0)]);
+    }
+
+    #[test]
+    fn doubles() {
+        let reply: f64 = deserialize(b"$3\r\n1.5\r\n", "HINCRBYFLOAT");
+        assert_eq!(reply, 1.5);
+
+        let reply: Dynamic = deserialize(b"$3\r\n1.5\r\n", "GET");
+        assert_eq!(reply, Dynamic::Text("1.5".to_owned()));
+    }
+
+    #[test]
+    fn plain_is_unchanged() {
+        assert_eq!(ReplyHint::Plain.apply(Config::new()), Config::new());
+    }
+}
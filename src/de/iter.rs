@@ -0,0 +1,218 @@
+//! Lazy iteration over the elements of a RESP array.
+
+use core::{iter::FusedIterator, marker::PhantomData};
+
+use serde::de;
+
+use super::{ArrayCursor, Error};
+
+/**
+An iterator that lazily deserializes the elements of a top-level RESP array.
+
+This is useful for replies like `KEYS` or `SMEMBERS` that are forwarded
+element by element into some other system, since it never collects the
+elements into a `Vec`. Each call to [`next`][Iterator::next] deserializes
+one element and advances the input past it.
+
+When the iterator is dropped, any elements that weren't consumed are
+skipped, so that the input is left positioned after the end of the array.
+Use [`finish`][ArrayIter::finish] to find out if skipping them failed. If
+an element fails to deserialize, the iterator yields the error and then
+stops, and the input is left positioned at the start of that element.
+
+# Example
+
+```
+use seredies::de::ArrayIter;
+
+let mut input: &[u8] = b"*3\r\n$1\r\na\r\n$1\r\nb\r\n$1\r\nc\r\n:10\r\n";
+let mut keys = ArrayIter::<&str>::new(&mut input).unwrap();
+
+assert_eq!(keys.len(), 3);
+assert_eq!(keys.next().unwrap().unwrap(), "a");
+drop(keys);
+
+// The rest of the array was skipped
+assert_eq!(input, b":10\r\n");
+```
+*/
+#[derive(Debug)]
+pub struct ArrayIter<'a, 'de, T> {
+    input: &'a mut &'de [u8],
+    cursor: ArrayCursor,
+    failed: bool,
+    element: PhantomData<fn() -> T>,
+}
+
+impl<'a, 'de, T> ArrayIter<'a, 'de, T> {
+    /// Read an array header from the input and create an iterator over its
+    /// elements. It's an error if the next value in the input is anything
+    /// other than an array, in which case the input isn't modified.
+    pub fn new(input: &'a mut &'de [u8]) -> Result<Self, Error> {
+        let cursor = ArrayCursor::new(input)?;
+
+        Ok(Self {
+            input,
+            cursor,
+            failed: false,
+            element: PhantomData,
+        })
+    }
+
+    /// The number of elements that haven't been deserialized yet.
+    #[inline]
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.cursor.remaining()
+    }
+
+    /// Skip any elements that haven't been consumed, leaving the input
+    /// positioned after the end of the array. This is what happens when the
+    /// iterator is dropped, but `finish` also reports any error that occurs
+    /// while skipping.
+    pub fn finish(mut self) -> Result<(), Error> {
+        self.skip_remaining()
+    }
+
+    fn skip_remaining(&mut self) -> Result<(), Error> {
+        if self.failed {
+            return Ok(());
+        }
+
+        while self
+            .cursor
+            .next::<de::IgnoredAny>(self.input)
+            .inspect_err(|_| self.failed = true)?
+            .is_some()
+        {}
+
+        Ok(())
+    }
+}
+
+impl<'de, T> Iterator for ArrayIter<'_, 'de, T>
+where
+    T: de::Deserialize<'de>,
+{
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
+        self.cursor
+            .next(self.input)
+            .inspect_err(|_| self.failed = true)
+            .transpose()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<'de, T> ExactSizeIterator for ArrayIter<'_, 'de, T>
+where
+    T: de::Deserialize<'de>,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        match self.failed {
+            true => 0,
+            false => self.cursor.remaining(),
+        }
+    }
+}
+
+impl<'de, T> FusedIterator for ArrayIter<'_, 'de, T> where T: de::Deserialize<'de> {}
+
+impl<T> Drop for ArrayIter<'_, '_, T> {
+    fn drop(&mut self) {
+        let _ = self.skip_remaining();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cool_asserts::assert_matches;
+
+    use super::*;
+    use crate::de::parse;
+
+    #[test]
+    fn iterate() {
+        let mut input: &[u8] = b"*3\r\n:1\r\n:2\r\n:3\r\n+OK\r\n";
+        let items: Result<Vec<i64>, Error> = ArrayIter::new(&mut input).unwrap().collect();
+
+        assert_eq!(items.unwrap(), [1, 2, 3]);
+        assert_eq!(input, b"+OK\r\n");
+    }
+
+    #[test]
+    fn borrowed() {
+        let mut input: &[u8] = b"*2\r\n$5\r\nhello\r\n$5\r\nworld\r\n";
+        let mut iter = ArrayIter::<&str>::new(&mut input).unwrap();
+
+        assert_eq!(iter.next().unwrap().unwrap(), "hello");
+        assert_eq!(iter.remaining(), 1);
+        assert_eq!(iter.next().unwrap().unwrap(), "world");
+        assert_matches!(iter.next(), None);
+        drop(iter);
+
+        assert!(input.is_empty());
+    }
+
+    #[test]
+    fn empty() {
+        let mut input: &[u8] = b"*0\r\n";
+        let mut iter = ArrayIter::<i64>::new(&mut input).unwrap();
+
+        assert_eq!(iter.len(), 0);
+        assert_matches!(iter.next(), None);
+    }
+
+    #[test]
+    fn finish_skips_rest() {
+        let mut input: &[u8] = b"*3\r\n*2\r\n:1\r\n:2\r\n$1\r\na\r\n:3\r\n:4\r\n";
+        let iter = ArrayIter::<Vec<i64>>::new(&mut input).unwrap();
+
+        iter.finish().unwrap();
+        assert_eq!(input, b":4\r\n");
+    }
+
+    #[test]
+    fn finish_reports_eof() {
+        let mut input: &[u8] = b"*3\r\n:1\r\n:2";
+        let iter = ArrayIter::<i64>::new(&mut input).unwrap();
+
+        assert_matches!(
+            iter.finish(),
+            Err(Error::Parse(parse::Error::UnexpectedEof(_)))
+        );
+        assert_eq!(input, b":2");
+    }
+
+    #[test]
+    fn stops_after_error() {
+        let mut input: &[u8] = b"*3\r\n:1\r\n$1\r\na\r\n:3\r\n";
+        let mut iter = ArrayIter::<i64>::new(&mut input).unwrap();
+
+        assert_eq!(iter.next().unwrap().unwrap(), 1);
+        assert_matches!(iter.next(), Some(Err(_)));
+        assert_matches!(iter.next(), None);
+        assert_eq!(iter.len(), 0);
+        drop(iter);
+
+        assert_eq!(input, b"$1\r\na\r\n:3\r\n");
+    }
+
+    #[test]
+    fn not_an_array() {
+        let mut input: &[u8] = b":1\r\n";
+        assert_matches!(ArrayIter::<i64>::new(&mut input), Err(Error::Custom(_)));
+        assert_eq!(input, b":1\r\n");
+    }
+}
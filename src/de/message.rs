@@ -0,0 +1,214 @@
+use alloc::{borrow::Cow, boxed::Box, string::String, vec::Vec};
+use core::{fmt, ops::Deref, str::Utf8Error};
+
+/// The number of bytes that a [`RedisMessage`] can store without allocating.
+/// This is enough to hold most of the common Redis error messages, like
+/// `ERR value is not an integer or out of range`.
+const INLINE_CAPACITY: usize = 46;
+
+/**
+The message from a Redis error, as carried by
+[`Error::Redis`][super::Error::Redis].
+
+Redis error messages are usually short, so messages up to a modest length
+are stored inline, without allocating; longer ones fall back to a heap
+allocation. This keeps error-heavy workloads (such as speculative commands
+that are expected to fail) from allocating for every reply. The message is
+stored as raw bytes; the `to_str` family of methods decode it on demand.
+
+By convention, Redis error messages begin with an uppercase error code, like
+`ERR` or `WRONGTYPE`; this can be retrieved with [`code`][RedisMessage::code].
+
+# Example
+
+```
+use seredies::de::{from_bytes, Error};
+
+let err = from_bytes::<i64>(b"-WRONGTYPE Operation against a key\r\n").unwrap_err();
+let Error::Redis(message) = err else { panic!() };
+
+assert_eq!(message, b"WRONGTYPE Operation against a key");
+assert_eq!(message.code(), b"WRONGTYPE");
+assert_eq!(message.description(), b"Operation against a key");
+assert_eq!(message.to_str().unwrap(), "WRONGTYPE Operation against a key");
+```
+*/
+#[derive(Clone)]
+pub struct RedisMessage {
+    repr: Repr,
+}
+
+#[derive(Clone)]
+enum Repr {
+    Inline { len: u8, buf: [u8; INLINE_CAPACITY] },
+    Heap(Box<[u8]>),
+}
+
+impl RedisMessage {
+    /// Create a new message, copying the bytes.
+    #[must_use]
+    pub fn new(message: &[u8]) -> Self {
+        let repr = match u8::try_from(message.len()) {
+            Ok(len) if message.len() <= INLINE_CAPACITY => {
+                let mut buf = [0; INLINE_CAPACITY];
+                buf[..message.len()].copy_from_slice(message);
+                Repr::Inline { len, buf }
+            }
+            _ => Repr::Heap(message.into()),
+        };
+
+        Self { repr }
+    }
+
+    /// Get the message as bytes.
+    #[inline]
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        match self.repr {
+            Repr::Inline { len, ref buf } => &buf[..len as usize],
+            Repr::Heap(ref buf) => buf,
+        }
+    }
+
+    /// Decode the message as UTF-8.
+    #[inline]
+    pub fn to_str(&self) -> Result<&str, Utf8Error> {
+        core::str::from_utf8(self.as_bytes())
+    }
+
+    /// Decode the message as UTF-8, replacing any invalid sequences with
+    /// `U+FFFD REPLACEMENT CHARACTER`.
+    #[inline]
+    #[must_use]
+    pub fn to_str_lossy(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(self.as_bytes())
+    }
+
+    /// Get the error code at the start of the message, such as `ERR` or
+    /// `WRONGTYPE`. This is everything up to the first space.
+    #[must_use]
+    pub fn code(&self) -> &[u8] {
+        self.split().0
+    }
+
+    /// Get the human-readable part of the message, after the error code.
+    #[must_use]
+    pub fn description(&self) -> &[u8] {
+        self.split().1
+    }
+
+    fn split(&self) -> (&[u8], &[u8]) {
+        let bytes = self.as_bytes();
+
+        match memchr::memchr(b' ', bytes) {
+            Some(idx) => (&bytes[..idx], &bytes[idx + 1..]),
+            None => (bytes, &[]),
+        }
+    }
+
+    /// Convert the message into a `Vec<u8>`.
+    #[inline]
+    #[must_use]
+    pub fn into_vec(self) -> Vec<u8> {
+        match self.repr {
+            Repr::Inline { .. } => self.as_bytes().to_vec(),
+            Repr::Heap(buf) => buf.into_vec(),
+        }
+    }
+}
+
+impl Deref for RedisMessage {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl AsRef<[u8]> for RedisMessage {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> PartialEq<T> for RedisMessage {
+    #[inline]
+    fn eq(&self, other: &T) -> bool {
+        self.as_bytes() == other.as_ref()
+    }
+}
+
+impl Eq for RedisMessage {}
+
+impl From<&[u8]> for RedisMessage {
+    #[inline]
+    fn from(message: &[u8]) -> Self {
+        Self::new(message)
+    }
+}
+
+impl From<RedisMessage> for Vec<u8> {
+    #[inline]
+    fn from(message: RedisMessage) -> Self {
+        message.into_vec()
+    }
+}
+
+impl fmt::Debug for RedisMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RedisMessage")
+            .field(&self.to_str_lossy())
+            .finish()
+    }
+}
+
+impl fmt::Display for RedisMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.to_str_lossy().fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inline_and_heap() {
+        let short = RedisMessage::new(b"ERR no such key");
+        assert!(matches!(short.repr, Repr::Inline { .. }));
+        assert_eq!(short, b"ERR no such key");
+
+        let exact = RedisMessage::new(&[b'x'; INLINE_CAPACITY]);
+        assert!(matches!(exact.repr, Repr::Inline { .. }));
+        assert_eq!(exact.len(), INLINE_CAPACITY);
+
+        let long =
+            RedisMessage::new(b"WRONGTYPE Operation against a key holding the wrong kind of value");
+        assert!(matches!(long.repr, Repr::Heap(_)));
+        assert_eq!(long.code(), b"WRONGTYPE");
+        assert_eq!(
+            long.into_vec(),
+            b"WRONGTYPE Operation against a key holding the wrong kind of value"
+        );
+    }
+
+    #[test]
+    fn no_description() {
+        let message = RedisMessage::new(b"LOADING");
+        assert_eq!(message.code(), b"LOADING");
+        assert_eq!(message.description(), b"");
+
+        let empty = RedisMessage::new(b"");
+        assert_eq!(empty.code(), b"");
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn lossy() {
+        let message = RedisMessage::new(b"ERR \xFF");
+        assert!(message.to_str().is_err());
+        assert_eq!(message.to_str_lossy(), "ERR \u{FFFD}");
+    }
+}
@@ -0,0 +1,159 @@
+// Helpers for describing the RESP value in type mismatch errors. Visitors
+// report mismatches with a serde `Unexpected`, which only knows the Rust
+// type that the deserializer passed to the visitor (like "byte array"), so
+// the deserializer passes each value to its visitor with `VisitError`, which
+// holds the mismatch back until it can be described in RESP terms.
+
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+use core::fmt::{self, Display};
+
+use serde::de;
+
+use super::Error;
+use crate::fmt::{Item, Preview};
+
+/// The error type passed to visitors while they visit a single RESP value.
+#[derive(Debug)]
+pub(super) enum VisitError {
+    /// Any error other than a mismatch, including errors from nested values,
+    /// which were already described when they were visited.
+    Error(Error),
+
+    /// The visitor didn't accept the type or value that it was given.
+    Mismatch {
+        kind: MismatchKind,
+        expected: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(super) enum MismatchKind {
+    Type,
+    Value,
+}
+
+impl VisitError {
+    /// Convert this into a deserialize error, describing `item` as the value
+    /// that caused the mismatch, if this is one.
+    #[must_use]
+    fn describe(self, item: Item<'_>) -> Error {
+        match self {
+            VisitError::Error(err) => err,
+            VisitError::Mismatch { kind, expected } => {
+                let kind = match kind {
+                    MismatchKind::Type => "type",
+                    MismatchKind::Value => "value",
+                };
+
+                Error::Custom(format!(
+                    "invalid {kind}: {}, expected {expected}",
+                    Found(item)
+                ))
+            }
+        }
+    }
+}
+
+impl Display for VisitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            VisitError::Error(ref err) => err.fmt(f),
+            VisitError::Mismatch {
+                kind: MismatchKind::Type,
+                ref expected,
+            } => write!(f, "invalid type, expected {expected}"),
+            VisitError::Mismatch {
+                kind: MismatchKind::Value,
+                ref expected,
+            } => write!(f, "invalid value, expected {expected}"),
+        }
+    }
+}
+
+impl de::StdError for VisitError {}
+
+impl de::Error for VisitError {
+    #[inline]
+    fn custom<T>(msg: T) -> Self
+    where
+        T: Display,
+    {
+        VisitError::Error(de::Error::custom(msg))
+    }
+
+    #[inline]
+    fn invalid_type(_unexp: de::Unexpected<'_>, exp: &dyn de::Expected) -> Self {
+        VisitError::Mismatch {
+            kind: MismatchKind::Type,
+            expected: exp.to_string(),
+        }
+    }
+
+    #[inline]
+    fn invalid_value(_unexp: de::Unexpected<'_>, exp: &dyn de::Expected) -> Self {
+        VisitError::Mismatch {
+            kind: MismatchKind::Value,
+            expected: exp.to_string(),
+        }
+    }
+}
+
+/// Call `visit`, which passes `item` to a visitor, and describe `item` in
+/// any mismatch error that the visitor reports.
+#[inline]
+pub(super) fn visit_item<T>(
+    item: Item<'_>,
+    visit: impl FnOnce() -> Result<T, VisitError>,
+) -> Result<T, Error> {
+    visit().map_err(|err| err.describe(item))
+}
+
+/// Adapter for passing the elements of an array to a visitor with
+/// [`VisitError`]. Each element reports its own mismatches, so errors from
+/// the elements are passed through as they are.
+pub(super) struct VisitSeq<A>(pub A);
+
+impl<'de, A> de::SeqAccess<'de> for VisitSeq<A>
+where
+    A: de::SeqAccess<'de, Error = Error>,
+{
+    type Error = VisitError;
+
+    #[inline]
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        self.0.next_element_seed(seed).map_err(VisitError::Error)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        self.0.size_hint()
+    }
+}
+
+/// Describes a value that a visitor didn't accept, including the length of
+/// strings alongside their preview.
+struct Found<'a>(Item<'a>);
+
+impl Display for Found<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Item::SimpleString(payload)
+            | Item::Error(payload)
+            | Item::BulkString(payload)
+            | Item::Verbatim(payload) => write!(
+                f,
+                "{} of length {} ({})",
+                self.0.kind(),
+                payload.len(),
+                Preview::new(payload, 0)
+            ),
+            item => item.fmt(f),
+        }
+    }
+}
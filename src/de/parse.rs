@@ -6,9 +6,25 @@
 //! [`Deserializer`][crate::de::Deserializer].
 //!
 //! The parsers here are modeled after [nom](https://docs.rs/nom).
+//!
+//! Each parser takes the input, and returns the parsed value along with the
+//! unparsed tail of the input. [`read_header`] reads the header of any
+//! value, while [`read_integer`], [`read_bulk`], and [`read_array_header`]
+//! read a value of a particular type, and [`skip_value`] skips over a
+//! complete value of any type. Together with [`read_exact`], these are
+//! enough to read any RESP data without the serde layer.
+//!
+//! None of the parsers keep any state: when the input is incomplete, they
+//! fail with [`Error::UnexpectedEof`], and the parse should be retried from
+//! the same place once at least that many more bytes are available. The
+//! [`Parser`] type does this bookkeeping for a stream of incoming data.
+
+use alloc::{borrow::Cow, vec::Vec};
+use core::{fmt, task::Poll};
 
 use memchr::memchr2;
-use thiserror::Error;
+
+use crate::protocol::{Tag, MAX_BULK_LEN, NULL, OK};
 
 /// Parse errors that can occur while attempting to deserialize RESP data.
 ///
@@ -16,29 +32,88 @@ use thiserror::Error;
 /// [`UnexpectedEof`][Error::UnexpectedEof] variant; it includes a minimum
 /// amount of additional bytes that must be read, after which the parse can
 /// be retried.
-#[derive(Debug, Clone, Copy, Error)]
+#[derive(Debug, Clone, Copy)]
 #[non_exhaustive]
 pub enum Error {
     /// The data wasn't malformed, but it ended before the parse could complete.
     /// The value in the error is the minimum number of additional bytes
     /// required for a successful parse.
-    #[error("unexpected end of input; read at least {0} more bytes and try again")]
     UnexpectedEof(usize),
 
     /// A newline was expected and was malformed somehow. It might have been
     /// entirely missing, or was only a `\n`.
-    #[error("malformed newline during parsing (all redis newlines are \\r\\n")]
     MalformedNewline,
 
     /// A header tag byte wasn't one of the recognized RESP tag bytes.
-    #[error("unrecognized tag byte {0:#x}")]
     BadTag(u8),
 
     /// A decimal number failed to parse.
-    #[error("failed to parse a decimal integer")]
     Number,
+
+    /// The data was well-formed RESP, but it wasn't a client command (a
+    /// non-empty array of bulk strings). Only returned by
+    /// [`validate_command_frame`].
+    NotACommand,
+
+    /// The length of an array or bulk string was negative or out of bounds.
+    /// Not returned by [`read_header`], which doesn't check lengths.
+    Length,
+
+    /// A well-formed value had a different type than the one being read.
+    /// The value in the error is the tag byte of the value that was found.
+    /// Only returned by [`read_integer`], [`read_bulk`], and
+    /// [`read_array_header`].
+    UnexpectedTag(u8),
+
+    /// An inline command had a quoted argument that was never closed, or a
+    /// closing quote that wasn't followed by a space. Only returned by
+    /// [`read_inline_command`].
+    UnbalancedQuotes,
+
+    /// The payload of a RESP3 Verbatim String didn't begin with a 3 byte
+    /// format and a `:`. Only returned by [`split_verbatim`].
+    Verbatim,
 }
 
+impl Error {
+    /// If the input was incomplete, get the minimum number of additional
+    /// bytes needed before the parse should be retried; see
+    /// [`UnexpectedEof`][Error::UnexpectedEof].
+    #[inline]
+    #[must_use]
+    pub const fn bytes_needed(&self) -> Option<usize> {
+        match *self {
+            Error::UnexpectedEof(count) => Some(count),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Error::UnexpectedEof(count) => write!(
+                f,
+                "unexpected end of input; read at least {count} more bytes and try again"
+            ),
+            Error::MalformedNewline => {
+                f.write_str("malformed newline during parsing (all redis newlines are \\r\\n")
+            }
+            Error::BadTag(tag) => write!(f, "unrecognized tag byte {tag:#x}"),
+            Error::Number => f.write_str("failed to parse a decimal integer"),
+            Error::NotACommand => {
+                f.write_str("expected a command: a non-empty array of bulk strings")
+            }
+            Error::Length => f.write_str("an array or bulk string length was out of bounds"),
+            Error::UnexpectedTag(tag) => write!(f, "unexpected value with tag byte {tag:#x}"),
+            Error::UnbalancedQuotes => f.write_str("unbalanced quotes in inline command"),
+            Error::Verbatim => f.write_str("verbatim string is missing its format prefix"),
+        }
+    }
+}
+
+impl serde::de::StdError for Error {}
+
 /// A parsed RESP "header".
 ///
 /// In RESP, all data includes a header, which consists of some tag byte,
@@ -50,6 +125,7 @@ pub enum Error {
 /// See the [protocol specification](https://redis.io/docs/reference/protocol-spec/)
 /// for details.
 #[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
 pub enum TaggedHeader<'a> {
     /// A RESP [Simple String](https://redis.io/docs/reference/protocol-spec/#resp-simple-strings).
     /// These are often used to communicate trivial response information.
@@ -81,6 +157,26 @@ pub enum TaggedHeader<'a> {
     /// Null is a special case of a Bulk String, and is used to indicate the
     /// absence of a value (such as a `GET` for a key that doesn't exist)
     Null,
+
+    /// A RESP3 [Verbatim String](https://redis.io/docs/reference/protocol-spec/#verbatim-strings)
+    /// header. Verbatim Strings are like Bulk Strings, but the payload begins
+    /// with a 3 byte format, like `txt` or `mkd`, and a `:`; see
+    /// [`split_verbatim`]. The value in the header is the number of bytes in
+    /// the payload, including the format.
+    Verbatim(i64),
+
+    /// A RESP3 [Push](https://redis.io/docs/reference/protocol-spec/#pushes)
+    /// header. Pushes are out-of-band messages, like pub/sub messages and
+    /// client-side caching invalidations, which the server can send in
+    /// between replies. They're structured like arrays; the value in the
+    /// header is the number of items in the push.
+    Push(i64),
+
+    /// A RESP3 [Big Number](https://redis.io/docs/reference/protocol-spec/#big-numbers),
+    /// an integer that might not fit in 64 bits. The payload is its decimal
+    /// digits, with an optional leading sign; [`read_header`] checks that
+    /// it's well-formed, but not that it fits in any particular type.
+    BigNumber(&'a [u8]),
 }
 
 /// The result of a parse, which can either be a parse error, or a successful
@@ -115,8 +211,10 @@ assert_matches!(
 pub fn read_header(input: &[u8]) -> ParseResult<'_, TaggedHeader<'_>> {
     // Fast path for these common cases
     match try_split_at(input, 5) {
-        Some((b"+OK\r\n", tail)) => return Ok((TaggedHeader::SimpleString(b"OK"), tail)),
-        Some((b"$-1\r\n", tail)) => return Ok((TaggedHeader::Null, tail)),
+        Some((head, tail)) if head == OK.as_bytes() => {
+            return Ok((TaggedHeader::SimpleString(b"OK"), tail))
+        }
+        Some((head, tail)) if head == NULL.as_bytes() => return Ok((TaggedHeader::Null, tail)),
         _ => {}
     };
 
@@ -127,19 +225,22 @@ pub fn read_header(input: &[u8]) -> ParseResult<'_, TaggedHeader<'_>> {
     };
     let ((), input) = read_endline(input)?;
 
-    match tag {
-        b'+' => Ok(TaggedHeader::SimpleString(payload)),
-        b'-' => Ok(TaggedHeader::Error(payload)),
-        b':' => parse_number(payload).map(TaggedHeader::Integer),
-        b'$' => parse_number(payload).map(|len| match len {
+    match Tag::from_byte(tag).ok_or(Error::BadTag(tag))? {
+        Tag::SimpleString => Ok(TaggedHeader::SimpleString(payload)),
+        Tag::Error => Ok(TaggedHeader::Error(payload)),
+        Tag::Integer => parse_number(payload).map(TaggedHeader::Integer),
+        Tag::BulkString => parse_number(payload).map(|len| match len {
             -1 => TaggedHeader::Null,
             len => TaggedHeader::BulkString(len),
         }),
-        b'*' => parse_number(payload).map(|len| match len {
+        Tag::Array => parse_number(payload).map(|len| match len {
             -1 => TaggedHeader::Null,
             len => TaggedHeader::Array(len),
         }),
-        tag => Err(Error::BadTag(tag)),
+        Tag::Verbatim => parse_number(payload).map(TaggedHeader::Verbatim),
+        Tag::Push => parse_number(payload).map(TaggedHeader::Push),
+        Tag::BigNumber => check_big_number(payload).map(TaggedHeader::BigNumber),
+        Tag::Null | Tag::Boolean | Tag::Double | Tag::Map | Tag::Set => Err(Error::BadTag(tag)),
     }
     .map(|header| (header, input))
 }
@@ -169,40 +270,756 @@ assert_matches!(
 ```
 */
 pub fn read_exact(length: usize, input: &[u8]) -> ParseResult<'_, &[u8]> {
-    let (payload, input) = try_split_at(input, length)
-        .ok_or_else(|| Error::UnexpectedEof((length - input.len()).saturating_add(2)))?;
+    let (payload, input) = try_split_at(input, length).ok_or_else(|| {
+        Error::UnexpectedEof(length.saturating_sub(input.len()).saturating_add(2))
+    })?;
 
     let ((), input) = read_endline(input)?;
 
     Ok((payload, input))
 }
 
-#[inline]
+/**
+Read a RESP integer.
+
+If the input begins with a complete value of some other type, this fails
+with [`Error::UnexpectedTag`]. If the integer is incomplete, it fails with
+[`Error::UnexpectedEof`], like [`read_header`].
+
+# Example
+
+```
+use seredies::de::parse::{read_integer, Error};
+use cool_asserts::assert_matches;
+
+assert_matches!(read_integer(b":-15\r\n+OK\r\n"), Ok((-15, b"+OK\r\n")));
+assert_matches!(read_integer(b"+OK\r\n"), Err(Error::UnexpectedTag(b'+')));
+assert_matches!(read_integer(b":12\r"), Err(Error::UnexpectedEof(1)));
+```
+*/
+pub fn read_integer(input: &[u8]) -> ParseResult<'_, i64> {
+    match read_header(input)? {
+        (TaggedHeader::Integer(value), tail) => Ok((value, tail)),
+        _ => Err(Error::UnexpectedTag(input[0])),
+    }
+}
+
+/**
+Read a complete RESP bulk string, including its payload, or a null. Nulls are
+returned as `None`; like [`read_header`], this accepts both of the RESP2
+nulls, `$-1` and `*-1`.
+
+If the input begins with a complete value of some other type, this fails
+with [`Error::UnexpectedTag`], and if the length is negative or longer than
+[`MAX_BULK_LEN`], it fails with [`Error::Length`]. If the header is
+complete but the payload isn't, the count in [`Error::UnexpectedEof`] is
+exactly the number of bytes needed to complete the bulk string; if the
+header itself is incomplete, the count is only a lower bound, since the
+length of the payload isn't known yet.
+
+# Example
+
+```
+use seredies::de::parse::{read_bulk, Error};
+use cool_asserts::assert_matches;
+
+assert_matches!(read_bulk(b"$5\r\nhello\r\n:1\r\n"), Ok((Some(b"hello"), b":1\r\n")));
+assert_matches!(read_bulk(b"$-1\r\n"), Ok((None, b"")));
+assert_matches!(read_bulk(b":1\r\n"), Err(Error::UnexpectedTag(b':')));
+
+// 2 more bytes of payload, then the \r\n
+assert_matches!(read_bulk(b"$5\r\nhel"), Err(Error::UnexpectedEof(4)));
+```
+*/
+pub fn read_bulk(input: &[u8]) -> ParseResult<'_, Option<&[u8]>> {
+    match read_header(input)? {
+        (TaggedHeader::Null, tail) => Ok((None, tail)),
+        (TaggedHeader::BulkString(len), tail) => match len {
+            0..=MAX_BULK_LEN => {
+                read_exact(len as usize, tail).map(|(payload, tail)| (Some(payload), tail))
+            }
+            _ => Err(Error::Length),
+        },
+        _ => Err(Error::UnexpectedTag(input[0])),
+    }
+}
+
+/**
+Read the header of a RESP array, or a null, and return the number of
+elements in the array. Nulls, either `*-1` or `$-1`, are returned as
+`None`. The elements follow
+the header in the tail, and can be read with the other functions here.
+
+If the input begins with a complete value of some other type, this fails
+with [`Error::UnexpectedTag`], and if the length is negative, it fails with
+[`Error::Length`]. Like [`read_header`], it doesn't read any of the
+elements, so it only fails with [`Error::UnexpectedEof`] if the header is
+incomplete.
+
+# Example
+
+```
+use seredies::de::parse::{read_array_header, read_bulk, read_integer, Error};
+use cool_asserts::assert_matches;
+
+let (len, tail) = read_array_header(b"*2\r\n$3\r\nabc\r\n:7\r\n").unwrap();
+assert_eq!(len, Some(2));
+
+let (value, tail) = read_bulk(tail).unwrap();
+assert_eq!(value, Some(&b"abc"[..]));
+
+let (value, tail) = read_integer(tail).unwrap();
+assert_eq!(value, 7);
+assert!(tail.is_empty());
+
+assert_matches!(read_array_header(b"*-1\r\n"), Ok((None, b"")));
+assert_matches!(read_array_header(b"*-2\r\n"), Err(Error::Length));
+assert_matches!(read_array_header(b"*2"), Err(Error::UnexpectedEof(2)));
+```
+*/
+pub fn read_array_header(input: &[u8]) -> ParseResult<'_, Option<usize>> {
+    match read_header(input)? {
+        (TaggedHeader::Null, tail) => Ok((None, tail)),
+        (TaggedHeader::Array(len), tail) => match len.try_into() {
+            Ok(len) => Ok((Some(len), tail)),
+            Err(_) => Err(Error::Length),
+        },
+        _ => Err(Error::UnexpectedTag(input[0])),
+    }
+}
+
+/**
+Split the payload of a RESP3 Verbatim String into its 3 byte format and its
+content.
+
+# Example
+
+```
+use seredies::de::parse::{read_header, read_exact, split_verbatim, TaggedHeader};
+
+let (header, tail) = read_header(b"=15\r\ntxt:Some string\r\n").unwrap();
+let TaggedHeader::Verbatim(len) = header else { panic!() };
+let (payload, _) = read_exact(len as usize, tail).unwrap();
+
+assert_eq!(split_verbatim(payload).unwrap(), (b"txt", &b"Some string"[..]));
+```
+*/
+pub fn split_verbatim(payload: &[u8]) -> Result<(&[u8; 3], &[u8]), Error> {
+    match payload.split_first_chunk() {
+        Some((format, [b':', content @ ..])) => Ok((format, content)),
+        _ => Err(Error::Verbatim),
+    }
+}
+
+/**
+Skip any number of keepalive frames at the start of the input. Each
+keepalive is a complete RESP value, like [`PING`][crate::protocol::PING],
+which is skipped if the input starts with exactly those bytes. Incomplete
+keepalives at the end of the input aren't skipped.
+
+# Example
+
+```
+use seredies::de::parse::skip_keepalives;
+use seredies::protocol::{PING, PONG};
+
+let keepalives = [PING.as_bytes(), PONG.as_bytes()];
+let input = b"+PONG\r\n*1\r\n$4\r\nPING\r\n+PONG\r\n:1\r\n+PONG\r\n";
+
+assert_eq!(skip_keepalives(input, &keepalives), b":1\r\n+PONG\r\n");
+```
+*/
 #[must_use]
-const fn ascii_to_digit(b: u8) -> Option<i64> {
-    match b {
-        b'0'..=b'9' => Some((b - b'0') as i64),
-        _ => None,
+pub fn skip_keepalives<'a>(mut input: &'a [u8], keepalives: &[&[u8]]) -> &'a [u8] {
+    while let Some(tail) = keepalives
+        .iter()
+        .filter(|keepalive| !keepalive.is_empty())
+        .find_map(|keepalive| input.strip_prefix(*keepalive))
+    {
+        input = tail;
     }
+
+    input
 }
 
-fn parse_number(payload: &[u8]) -> Result<i64, Error> {
-    let (payload, positive) = match payload.split_first().ok_or(Error::Number)? {
-        (&b'-', tail) => (tail, false),
-        (&b'+', tail) => (tail, true),
-        _ => (payload, true),
+/**
+Find a plausible frame boundary after a parse error, for recovering from a
+desynchronized stream. Returns the offset of the first byte in `input` that
+follows a `\r\n` and is a RESP [tag byte][Tag], or `None` if there isn't
+one. The offset is never 0, since the frame at the start of the input is
+the one that failed to parse.
+
+This is only a heuristic. The payload of a bulk string can contain `\r\n`
+followed by a tag byte, so the boundary might be in the middle of a value,
+and it might be an element of an array rather than a top-level value. The
+parse should be retried from the offset, and if it fails again, `resync`
+can be called again on the rest of the input. If this returns `None`,
+everything except a trailing `\r` can be discarded, and the scan retried
+when more data arrives.
+
+Note that resynchronizing can't tell which replies were lost, so a client
+that pairs replies with its requests usually needs to fail every pending
+request, rather than trying to carry on.
+
+# Example
+
+```
+use seredies::de::parse::{read_header, resync, TaggedHeader};
+use cool_asserts::assert_matches;
+
+let input = b":12x\r\n+OK\r\n";
+assert!(read_header(input).is_err());
+
+let offset = resync(input).unwrap();
+assert_eq!(offset, 6);
+assert_matches!(read_header(&input[offset..]), Ok((TaggedHeader::SimpleString(b"OK"), b"")));
+
+assert_eq!(resync(b"garbage\r\nmore garbage"), None);
+```
+*/
+#[must_use]
+pub fn resync(input: &[u8]) -> Option<usize> {
+    memchr::memchr_iter(b'\n', input)
+        .filter(|&idx| idx > 0 && input[idx - 1] == b'\r')
+        .map(|idx| idx + 1)
+        .find(|&offset| {
+            input
+                .get(offset)
+                .is_some_and(|&byte| Tag::from_byte(byte).is_some())
+        })
+}
+
+/// The shape of a client command frame, as returned by
+/// [`validate_command_frame`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CommandShape {
+    /// The number of arguments in the command, including the command name.
+    pub args: usize,
+
+    /// The total length of the frame, in bytes. The frame is always at the
+    /// beginning of the input, so this is also the offset of whatever data
+    /// follows it.
+    pub len: usize,
+}
+
+/**
+Check that the input begins with a complete, well-formed client command
+frame: a non-empty RESP array of bulk strings. This is done in a single pass
+without allocating or materializing any of the arguments, which makes it
+suitable for servers and proxies that want to check frames before queueing
+them.
+
+If the frame is incomplete, this returns [`Error::UnexpectedEof`]; any
+trailing data after the frame is ignored.
+
+# Example
+
+```
+use seredies::de::parse::{validate_command_frame, CommandShape, Error};
+use cool_asserts::assert_matches;
+
+assert_eq!(
+    validate_command_frame(b"*2\r\n$3\r\nGET\r\n$3\r\nkey\r\n*1\r\n").unwrap(),
+    CommandShape { args: 2, len: 22 },
+);
+
+assert_matches!(
+    validate_command_frame(b"*2\r\n$3\r\nGET\r\n:1\r\n"),
+    Err(Error::NotACommand)
+);
+
+assert_matches!(
+    validate_command_frame(b"*2\r\n$3\r\nGET\r\n$3\r\nke"),
+    Err(Error::UnexpectedEof(_))
+);
+```
+*/
+pub fn validate_command_frame(input: &[u8]) -> Result<CommandShape, Error> {
+    let total = input.len();
+
+    let (args, mut input) = match read_header(input)? {
+        (TaggedHeader::Array(len), tail) if len > 0 => (len, tail),
+        _ => return Err(Error::NotACommand),
     };
 
-    payload
+    let args: usize = args.try_into().map_err(|_| Error::NotACommand)?;
+
+    for _ in 0..args {
+        let (length, tail) = match read_header(input)? {
+            (TaggedHeader::BulkString(len), tail) => (len, tail),
+            _ => return Err(Error::NotACommand),
+        };
+
+        let length: usize = length.try_into().map_err(|_| Error::NotACommand)?;
+        let (_, tail) = read_exact(length, tail)?;
+        input = tail;
+    }
+
+    Ok(CommandShape {
+        args,
+        len: total.saturating_sub(input.len()),
+    })
+}
+
+/**
+Read a client command in the [inline format]: a single line of arguments
+separated by spaces, followed by `\r\n`. Arguments are split the same way
+that `redis-server` splits them:
+
+- An argument in double quotes can contain spaces and the escapes `\n`,
+  `\r`, `\t`, `\b`, `\a`, and `\xHH`; any other character after a `\` is
+  taken literally.
+- An argument in single quotes can contain spaces, and `\'` for a single
+  quote; everything else is taken literally.
+- A closing quote must be followed by a space or the end of the line.
+
+Arguments that don't contain any quotes are borrowed from the input. A blank
+line is parsed as an empty list of arguments; servers usually ignore these.
+
+# Example
+
+```
+use seredies::de::parse::{read_inline_command, Error};
+use cool_asserts::assert_matches;
+
+let (args, tail) = read_inline_command(b"SET key \"hello world\\n\"\r\nPING").unwrap();
+
+assert_eq!(args, [&b"SET"[..], b"key", b"hello world\n"]);
+assert_eq!(tail, b"PING");
+
+assert_matches!(read_inline_command(b"PING"), Err(Error::UnexpectedEof(2)));
+assert_matches!(
+    read_inline_command(b"ECHO \"abc\r\n"),
+    Err(Error::UnbalancedQuotes)
+);
+```
+
+[inline format]: https://redis.io/docs/reference/protocol-spec/#inline-commands
+*/
+pub fn read_inline_command(input: &[u8]) -> ParseResult<'_, Vec<Cow<'_, [u8]>>> {
+    let (mut line, input) = {
+        let idx = memchr2(b'\r', b'\n', input).ok_or(Error::UnexpectedEof(2))?;
+        input.split_at(idx)
+    };
+    let ((), input) = read_endline(input)?;
+
+    let mut args = Vec::new();
+
+    loop {
+        line = line.trim_ascii_start();
+
+        if line.is_empty() {
+            break Ok((args, input));
+        }
+
+        let (arg, tail) = read_inline_argument(line)?;
+        args.push(arg);
+        line = tail;
+    }
+}
+
+/// Read a single (possibly quoted) argument from an inline command line,
+/// which must not start with whitespace.
+fn read_inline_argument(line: &[u8]) -> ParseResult<'_, Cow<'_, [u8]>> {
+    #[derive(Clone, Copy)]
+    enum Quote {
+        None,
+        Double,
+        Single,
+    }
+
+    // A closing quote must be followed by whitespace or the end of the line
+    fn close_quote(tail: &[u8]) -> Result<&[u8], Error> {
+        match tail.first() {
+            None => Ok(tail),
+            Some(b) if b.is_ascii_whitespace() || *b == 0x0b => Ok(tail),
+            Some(_) => Err(Error::UnbalancedQuotes),
+        }
+    }
+
+    let end = line
         .iter()
-        .copied()
-        .try_fold(0i64, move |accum, b| {
-            let digit = ascii_to_digit(b)?;
-            let digit = if positive { digit } else { -digit };
-            let accum = accum.checked_mul(10)?;
-            accum.checked_add(digit)
-        })
-        .ok_or(Error::Number)
+        .position(|&b| matches!(b, b' ' | b'\t' | b'"' | b'\''))
+        .unwrap_or(line.len());
+
+    // Fast path: an argument with no quotes is borrowed as-is
+    if !matches!(line.get(end), Some(b'"' | b'\'')) {
+        let (arg, tail) = line.split_at(end);
+        return Ok((Cow::Borrowed(arg), tail));
+    }
+
+    let mut arg = Vec::from(&line[..end]);
+    let mut quote = Quote::None;
+    let mut rest = &line[end..];
+
+    loop {
+        rest = match (quote, rest) {
+            (Quote::Double | Quote::Single, []) => return Err(Error::UnbalancedQuotes),
+            (Quote::None, []) => return Ok((Cow::Owned(arg), rest)),
+
+            (Quote::None, [b' ' | b'\t', ..]) => return Ok((Cow::Owned(arg), rest)),
+            (Quote::None, [b'"', tail @ ..]) => {
+                quote = Quote::Double;
+                tail
+            }
+            (Quote::None, [b'\'', tail @ ..]) => {
+                quote = Quote::Single;
+                tail
+            }
+
+            (Quote::Double, [b'\\', b'x', high, low, tail @ ..])
+                if high.is_ascii_hexdigit() && low.is_ascii_hexdigit() =>
+            {
+                let digit = |b: u8| (b as char).to_digit(16).unwrap_or(0) as u8;
+                arg.push((digit(*high) << 4) | digit(*low));
+                tail
+            }
+            (Quote::Double, [b'\\', escaped, tail @ ..]) => {
+                arg.push(match escaped {
+                    b'n' => b'\n',
+                    b'r' => b'\r',
+                    b't' => b'\t',
+                    b'b' => 0x08,
+                    b'a' => 0x07,
+                    &b => b,
+                });
+                tail
+            }
+            (Quote::Double, [b'"', tail @ ..]) | (Quote::Single, [b'\'', tail @ ..]) => {
+                return Ok((Cow::Owned(arg), close_quote(tail)?));
+            }
+            (Quote::Single, [b'\\', b'\'', tail @ ..]) => {
+                arg.push(b'\'');
+                tail
+            }
+
+            (_, [b, tail @ ..]) => {
+                arg.push(*b);
+                tail
+            }
+        };
+    }
+}
+
+/**
+A resumable parser that finds the boundaries of complete RESP values in a
+stream of incoming data.
+
+[`read_header`] and the other parsers here are stateless: when they fail with
+[`Error::UnexpectedEof`], the caller has to read more data and parse again
+from the beginning. For large or deeply nested replies arriving in many small
+chunks, that means re-parsing the same prefix over and over. A `Parser`
+instead buffers incoming data and remembers how much of the current value it
+has already parsed, so each byte is parsed (approximately) once, no matter
+how the data is chunked.
+
+Each call to [`feed`][Parser::feed] adds a chunk of data and returns
+[`Poll::Ready`] with the bytes of the next complete RESP value, which can
+then be passed to [`from_bytes`][crate::de::from_bytes] or a
+[`Deserializer`][crate::de::Deserializer]. If there's not yet enough data,
+it returns [`Poll::Pending`]. The returned value is discarded from the buffer
+at the start of the next call. If a chunk contains more than one value (for
+instance, because of pipelining), call `feed` with an empty chunk to get the
+next one.
+
+If the data is malformed, `feed` returns an error. After an error, the
+stream is unrecoverable, and the parser should be discarded.
+
+# Example
+
+```
+use std::task::Poll;
+use seredies::de::parse::Parser;
+
+let mut parser = Parser::new();
+
+assert!(parser.feed(b"*2\r\n$5\r\nhel").is_pending());
+assert!(parser.feed(b"lo\r\n:1").is_pending());
+
+let frame = match parser.feed(b"0\r\n+OK\r\n") {
+    Poll::Ready(frame) => frame.unwrap(),
+    Poll::Pending => panic!("frame should be complete"),
+};
+assert_eq!(frame, b"*2\r\n$5\r\nhello\r\n:10\r\n");
+
+let data: (String, i64) = seredies::de::from_bytes(frame).unwrap();
+assert_eq!(data, ("hello".to_owned(), 10));
+
+// The next value was already buffered
+assert!(matches!(parser.feed(b""), Poll::Ready(Ok(b"+OK\r\n"))));
+assert!(parser.feed(b"").is_pending());
+```
+*/
+#[derive(Debug, Clone)]
+pub struct Parser {
+    buffer: Vec<u8>,
+
+    /// The length of the value returned by the previous call to `feed`,
+    /// which is at the front of the buffer and will be discarded.
+    returned: usize,
+
+    /// The progress through the current value.
+    frame: FrameScanner,
+}
+
+impl Parser {
+    /// Create a new, empty parser.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            buffer: Vec::new(),
+            returned: 0,
+            frame: FrameScanner::new(),
+        }
+    }
+
+    /// The number of bytes that have been fed to the parser and not yet
+    /// returned as part of a complete value.
+    #[inline]
+    #[must_use]
+    pub fn buffered(&self) -> usize {
+        self.buffer.len() - self.returned
+    }
+
+    /// Add a chunk of data to the parser, and return the next complete RESP
+    /// value, if one is available. See the [type docs][Parser] for details.
+    pub fn feed(&mut self, chunk: &[u8]) -> Poll<Result<&[u8], Error>> {
+        if self.returned > 0 {
+            self.buffer.drain(..self.returned);
+            self.returned = 0;
+        }
+
+        self.buffer.extend_from_slice(chunk);
+
+        match self.frame.scan(&self.buffer) {
+            Ok(len) => {
+                self.returned = len;
+                Poll::Ready(Ok(&self.buffer[..len]))
+            }
+            Err(Error::UnexpectedEof(_)) => Poll::Pending,
+            Err(err) => Poll::Ready(Err(err)),
+        }
+    }
+}
+
+impl Default for Parser {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Tracks the progress through a single RESP value at the front of a buffer
+/// that grows as more data arrives, so that each part of the value is only
+/// parsed once. This is the framing shared by [`Parser`] and the readers
+/// that must stop reading at the end of a value.
+#[derive(Debug, Clone)]
+pub(crate) struct FrameScanner {
+    /// The number of bytes at the front of the buffer that have been parsed
+    /// as part of the current value.
+    position: usize,
+
+    /// The number of values that still need to be parsed to complete the
+    /// current value. Each array header adds its length to this count.
+    pending: usize,
+}
+
+impl FrameScanner {
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            position: 0,
+            pending: 1,
+        }
+    }
+
+    /// Continue parsing the value at the front of `buffer`, which must start
+    /// with the same bytes as in the previous call. Returns the length of the
+    /// value once it's complete, after which the scanner starts over with the
+    /// next value. Otherwise, returns [`Error::UnexpectedEof`] with the
+    /// minimum number of bytes to add to the buffer before trying again.
+    pub fn scan(&mut self, buffer: &[u8]) -> Result<usize, Error> {
+        while self.pending > 0 {
+            let (children, tail) = read_value_shape(&buffer[self.position..])?;
+
+            self.position = buffer.len() - tail.len();
+            self.pending = (self.pending - 1)
+                .checked_add(children)
+                .ok_or(Error::Length)?;
+        }
+
+        let len = self.position;
+        *self = Self::new();
+        Ok(len)
+    }
+}
+
+/// Read a header, and the body of a bulk string, and return the number of
+/// nested values that follow it (the length of an array, or 0).
+pub(crate) fn read_value_shape(input: &[u8]) -> ParseResult<'_, usize> {
+    let (header, tail) = read_header(input)?;
+
+    match header {
+        TaggedHeader::BulkString(len) | TaggedHeader::Verbatim(len)
+            if (0..=MAX_BULK_LEN).contains(&len) =>
+        {
+            read_exact(len as usize, tail).map(|(_, tail)| (0, tail))
+        }
+        TaggedHeader::Array(len) | TaggedHeader::Push(len) => match len.try_into() {
+            Ok(len) => Ok((len, tail)),
+            Err(_) => Err(Error::Length),
+        },
+        TaggedHeader::BulkString(_) | TaggedHeader::Verbatim(_) => Err(Error::Length),
+        _ => Ok((0, tail)),
+    }
+}
+
+/**
+Skip a complete RESP value of any type, including all of its nested values,
+and return its raw bytes.
+
+Lengths are checked like in [`read_bulk`] and [`read_array_header`]. When
+the value is incomplete, the count in [`Error::UnexpectedEof`] is the
+number of bytes needed to complete the part of the value that was
+incomplete, which is only a lower bound for the whole value, since the
+nested values after it haven't been seen yet.
+
+# Example
+
+```
+use seredies::de::parse::{skip_value, Error};
+use cool_asserts::assert_matches;
+
+let (raw, tail) = skip_value(b"*2\r\n*1\r\n:1\r\n$2\r\nab\r\n+OK\r\n").unwrap();
+assert_eq!(raw, b"*2\r\n*1\r\n:1\r\n$2\r\nab\r\n");
+assert_eq!(tail, b"+OK\r\n");
+
+// The second element hasn't arrived yet, so it needs at least 3 more bytes
+assert_matches!(skip_value(b"*2\r\n:1\r\n"), Err(Error::UnexpectedEof(3)));
+```
+*/
+pub fn skip_value(input: &[u8]) -> ParseResult<'_, &[u8]> {
+    let mut pending: usize = 1;
+    let mut tail = input;
+
+    while pending > 0 {
+        let (children, next) = read_value_shape(tail)?;
+        tail = next;
+        pending = (pending - 1).checked_add(children).ok_or(Error::Length)?;
+    }
+
+    Ok((&input[..input.len() - tail.len()], tail))
+}
+
+fn parse_number(payload: &[u8]) -> Result<i64, Error> {
+    parse_integer(payload).ok_or(Error::Number)
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// The integer types that [`parse_integer`] can parse. This trait is sealed,
+/// and implemented for all of the primitive integer types.
+pub trait ParseInteger: Copy + sealed::Sealed {
+    #[doc(hidden)]
+    const ZERO: Self;
+
+    #[doc(hidden)]
+    const SIGNED: bool;
+
+    #[doc(hidden)]
+    fn push_digit(self, digit: u8, positive: bool) -> Option<Self>;
+}
+
+macro_rules! impl_parse_integer {
+    ($($signed:literal: $($type:ty)*;)*) => {$($(
+        impl sealed::Sealed for $type {}
+
+        impl ParseInteger for $type {
+            const ZERO: Self = 0;
+            const SIGNED: bool = $signed;
+
+            #[inline]
+            fn push_digit(self, digit: u8, positive: bool) -> Option<Self> {
+                let accum = self.checked_mul(10)?;
+                let digit = Self::try_from(digit).ok()?;
+
+                match positive {
+                    true => accum.checked_add(digit),
+                    false => accum.checked_sub(digit),
+                }
+            }
+        }
+    )*)*};
+}
+
+impl_parse_integer! {
+    true: i8 i16 i32 i64 i128;
+    false: u8 u16 u32 u64 u128;
+}
+
+/**
+Parse the payload of a RESP integer, or the digits of a
+[`BigNumber`][TaggedHeader::BigNumber], into an integer type, or return
+`None` if it's malformed or doesn't fit.
+
+The payload is an optional `+` or `-` sign, followed by at least one ASCII
+digit; there's no whitespace, and no other characters, allowed. This is the
+same format that [`str::parse`] accepts for the integer types, so a `-` sign
+is rejected for unsigned types, even for `-0`. All of the integers in RESP
+headers, including lengths, are parsed with this function.
+
+# Example
+
+```
+use seredies::de::parse::parse_integer;
+
+assert_eq!(parse_integer::<i64>(b"-12"), Some(-12));
+assert_eq!(parse_integer::<i64>(b"+007"), Some(7));
+assert_eq!(parse_integer::<i64>(b"3492890328409238509324850943850943825024385"), None);
+assert_eq!(parse_integer::<u128>(b"18446744073709551616"), Some(1 << 64));
+assert_eq!(parse_integer::<u64>(b"-0"), None);
+assert_eq!(parse_integer::<i64>(b" 1"), None);
+```
+*/
+#[must_use]
+pub fn parse_integer<T: ParseInteger>(payload: &[u8]) -> Option<T> {
+    let (digits, positive) = match payload {
+        [b'-', digits @ ..] => (digits, false),
+        [b'+', digits @ ..] => (digits, true),
+        digits => (digits, true),
+    };
+
+    // A sign with no digits after it isn't a number
+    if digits.is_empty() || (!positive && !T::SIGNED) {
+        return None;
+    }
+
+    digits.iter().try_fold(T::ZERO, |accum, &b| {
+        let digit = match b {
+            b'0'..=b'9' => b - b'0',
+            _ => return None,
+        };
+
+        accum.push_digit(digit, positive)
+    })
+}
+
+/// Check that a big number payload is an optional sign followed by at least
+/// one digit.
+fn check_big_number(payload: &[u8]) -> Result<&[u8], Error> {
+    let digits = match payload {
+        [b'-' | b'+', digits @ ..] => digits,
+        digits => digits,
+    };
+
+    match !digits.is_empty() && digits.iter().all(u8::is_ascii_digit) {
+        true => Ok(payload),
+        false => Err(Error::Number),
+    }
 }
 
 #[cfg(test)]
@@ -262,6 +1079,10 @@ mod tests {
             null_array: b"*-1\r\nabc\r\n" == Ok((TaggedHeader::Null, b"abc\r\n")),
             bad_tag: b"xABC\r\n" == Err(Error::BadTag(b'x')),
             incomplete: b"+OK\r" == Err(Error::UnexpectedEof(1)),
+            sign_only: b":-\r\n" == Err(Error::Number),
+            empty_length: b"$\r\n" == Err(Error::Number),
+            overflow: b":9223372036854775808\r\n" == Err(Error::Number),
+            min_int: b":-9223372036854775808\r\n" == Ok((TaggedHeader::Integer(i64::MIN), b"")),
         }
     }
 
@@ -282,5 +1103,358 @@ mod tests {
             incomplete_newline: 4 @ b"abcd" == Err(Error::UnexpectedEof(2)),
             malformed: 4 @ b"abcdef\r\n" == Err(Error::MalformedNewline),
         }
+
+        #[test]
+        fn huge_length() {
+            assert_matches!(
+                read_exact(usize::MAX, b"abc"),
+                Err(Error::UnexpectedEof(n)) => assert_eq!(n, usize::MAX - 1)
+            );
+        }
+    }
+
+    mod typed {
+        use super::*;
+
+        test_cases! {
+            integer_from_ok: read_integer(b"+OK\r\n"), Err(Error::UnexpectedTag(b'+')),
+            integer_malformed: read_integer(b":1x\r\n"), Err(Error::Number),
+            bulk_from_null_array: read_bulk(b"*-1\r\n"), Ok((None, b"")),
+            bulk_empty: read_bulk(b"$0\r\n\r\n"), Ok((Some(b""), b"")),
+            bulk_negative: read_bulk(b"$-2\r\n"), Err(Error::Length),
+            bulk_too_long: read_bulk(b"$600000000\r\n"), Err(Error::Length),
+            bulk_incomplete_header: read_bulk(b"$5"), Err(Error::UnexpectedEof(2)),
+            array_from_push: read_array_header(b">1\r\n"), Err(Error::UnexpectedTag(b'>')),
+            array_from_null: read_array_header(b"$-1\r\n"), Ok((None, b"")),
+            skip_error: skip_value(b"-ERR\r\n:1\r\n"), Ok((b"-ERR\r\n", b":1\r\n")),
+            skip_bad_length: skip_value(b"*1\r\n$-5\r\n"), Err(Error::Length),
+            skip_incomplete_bulk: skip_value(b"*1\r\n$3\r\na"), Err(Error::UnexpectedEof(4)),
+        }
+    }
+
+    mod validate_command_frame {
+        use super::*;
+
+        macro_rules! command_test_cases {
+            ($($name:ident: $input:literal == $expected:pat,)*) => {
+                test_cases!{$(
+                    $name: validate_command_frame($input), $expected,
+                )*}
+            };
+        }
+
+        command_test_cases! {
+            basic: b"*1\r\n$4\r\nPING\r\n" == Ok(CommandShape { args: 1, len: 14 }),
+            trailing: b"*1\r\n$4\r\nPING\r\n+OK\r\n" == Ok(CommandShape { args: 1, len: 14 }),
+            empty_arg: b"*2\r\n$4\r\nECHO\r\n$0\r\n\r\n" == Ok(CommandShape { args: 2, len: 20 }),
+            empty_array: b"*0\r\n" == Err(Error::NotACommand),
+            null_array: b"*-1\r\n" == Err(Error::NotACommand),
+            not_array: b"$4\r\nPING\r\n" == Err(Error::NotACommand),
+            null_arg: b"*2\r\n$4\r\nECHO\r\n$-1\r\n" == Err(Error::NotACommand),
+            nested: b"*2\r\n$4\r\nECHO\r\n*0\r\n" == Err(Error::NotACommand),
+            negative_length: b"*1\r\n$-2\r\n" == Err(Error::NotACommand),
+            short_payload: b"*1\r\n$4\r\nPIN\r\n" == Err(Error::MalformedNewline),
+            incomplete_header: b"*2\r\n$4\r\nECHO\r\n" == Err(Error::UnexpectedEof(3)),
+            incomplete_payload: b"*1\r\n$4\r\nPI" == Err(Error::UnexpectedEof(4)),
+        }
+    }
+
+    mod read_inline_command {
+        use super::*;
+
+        fn args(input: &[u8]) -> Vec<Vec<u8>> {
+            let (args, tail) = read_inline_command(input).expect("failed to parse");
+            assert!(tail.is_empty());
+            args.into_iter().map(Cow::into_owned).collect()
+        }
+
+        #[test]
+        fn plain() {
+            assert_eq!(args(b"SET key value\r\n"), [&b"SET"[..], b"key", b"value"]);
+        }
+
+        #[test]
+        fn borrowed() {
+            let (args, _) = read_inline_command(b"GET key\r\n").unwrap();
+            assert!(args.iter().all(|arg| matches!(arg, Cow::Borrowed(_))));
+        }
+
+        #[test]
+        fn extra_whitespace() {
+            assert_eq!(args(b"  GET \t key  \r\n"), [&b"GET"[..], b"key"]);
+        }
+
+        #[test]
+        fn blank_line() {
+            assert!(args(b"\r\n").is_empty());
+            assert!(args(b"   \r\n").is_empty());
+        }
+
+        #[test]
+        fn double_quotes() {
+            assert_eq!(
+                args(b"ECHO \"a b\\\"c\\\\\\n\\r\\t\\a\\b\\q\" \"\"\r\n"),
+                [&b"ECHO"[..], b"a b\"c\\\n\r\t\x07\x08q", b""]
+            );
+        }
+
+        #[test]
+        fn hex_escapes() {
+            assert_eq!(
+                args(b"ECHO \"\\x00\\xfF\\xzz\"\r\n"),
+                [&b"ECHO"[..], b"\x00\xffxzz"]
+            );
+        }
+
+        #[test]
+        fn single_quotes() {
+            assert_eq!(
+                args(b"ECHO 'it\\'s \\n \"raw\"'\r\n"),
+                [&b"ECHO"[..], b"it's \\n \"raw\""]
+            );
+        }
+
+        #[test]
+        fn quote_inside_argument() {
+            assert_eq!(args(b"ECHO ab\"c d\"\r\n"), [&b"ECHO"[..], b"abc d"]);
+        }
+
+        #[test]
+        fn trailing_data() {
+            assert_matches!(
+                read_inline_command(b"PING\r\n*1\r\n"),
+                Ok((args, b"*1\r\n")) => assert_eq!(args, [&b"PING"[..]])
+            );
+        }
+
+        #[test]
+        fn inline_string_roundtrip() {
+            let command = ("SET", "a key", "it's \"quoted\"\r\n", "", "\u{e9}");
+            let inline = crate::ser::to_inline_string(&command).unwrap();
+
+            assert_eq!(
+                args(inline.as_bytes()),
+                [
+                    &b"SET"[..],
+                    b"a key",
+                    b"it's \"quoted\"\r\n",
+                    b"",
+                    "\u{e9}".as_bytes()
+                ]
+            );
+        }
+
+        test_cases! {
+            incomplete: read_inline_command(b"GET ke"), Err(Error::UnexpectedEof(2)),
+            partial_newline: read_inline_command(b"GET key\r"), Err(Error::UnexpectedEof(1)),
+            bare_newline: read_inline_command(b"GET key\n"), Err(Error::MalformedNewline),
+            unclosed_double: read_inline_command(b"ECHO \"abc\r\n"), Err(Error::UnbalancedQuotes),
+            unclosed_single: read_inline_command(b"ECHO 'abc\r\n"), Err(Error::UnbalancedQuotes),
+            text_after_quote: read_inline_command(b"ECHO \"a\"b\r\n"), Err(Error::UnbalancedQuotes),
+            escaped_close: read_inline_command(b"ECHO \"a\\\"\r\n"), Err(Error::UnbalancedQuotes),
+        }
+    }
+
+    mod parser {
+        use super::*;
+
+        /// Feed the input to a parser in chunks of `size` bytes, collecting
+        /// every complete value.
+        fn parse_chunked(input: &[u8], size: usize) -> Vec<Vec<u8>> {
+            let mut parser = Parser::new();
+            let mut frames = Vec::new();
+
+            for chunk in input.chunks(size) {
+                let mut chunk = chunk;
+
+                while let Poll::Ready(frame) = parser.feed(chunk) {
+                    frames.push(frame.expect("parse error").to_vec());
+                    chunk = b"";
+                }
+            }
+
+            assert_eq!(parser.buffered(), 0);
+            frames
+        }
+
+        #[test]
+        fn every_chunk_size() {
+            let input: &[u8] = b"\
+                *3\r\n\
+                    $5\r\nab\r\nc\r\n\
+                    *2\r\n:1\r\n$-1\r\n\
+                    *0\r\n\
+                +OK\r\n\
+                -ERR bad\r\n\
+                $0\r\n\r\n\
+                *-1\r\n\
+            ";
+
+            let expected = Vec::from([
+                b"*3\r\n$5\r\nab\r\nc\r\n*2\r\n:1\r\n$-1\r\n*0\r\n".to_vec(),
+                b"+OK\r\n".to_vec(),
+                b"-ERR bad\r\n".to_vec(),
+                b"$0\r\n\r\n".to_vec(),
+                b"*-1\r\n".to_vec(),
+            ]);
+
+            for size in 1..=input.len() {
+                assert_eq!(parse_chunked(input, size), expected, "chunk size {size}");
+            }
+        }
+
+        #[test]
+        fn pending_until_complete() {
+            let mut parser = Parser::new();
+
+            assert!(parser.feed(b"").is_pending());
+            assert!(parser.feed(b"*1\r\n").is_pending());
+            assert!(parser.feed(b"$3\r\nabc\r").is_pending());
+            assert_eq!(parser.buffered(), 12);
+            assert_matches!(parser.feed(b"\n"), Poll::Ready(Ok(b"*1\r\n$3\r\nabc\r\n")));
+            assert_eq!(parser.buffered(), 0);
+        }
+
+        #[test]
+        fn malformed() {
+            let mut parser = Parser::new();
+            assert_matches!(
+                parser.feed(b"*1\r\nx\r\n"),
+                Poll::Ready(Err(Error::BadTag(b'x')))
+            );
+
+            let mut parser = Parser::new();
+            assert_matches!(
+                parser.feed(b"$3\r\nabcd\r\n"),
+                Poll::Ready(Err(Error::MalformedNewline))
+            );
+        }
+
+        #[test]
+        fn bad_lengths() {
+            let mut parser = Parser::new();
+            assert_matches!(parser.feed(b"*-2\r\n"), Poll::Ready(Err(Error::Length)));
+
+            let mut parser = Parser::new();
+            assert_matches!(parser.feed(b"$-2\r\n"), Poll::Ready(Err(Error::Length)));
+
+            let mut parser = Parser::new();
+            assert_matches!(
+                parser.feed(b"$1000000000\r\n"),
+                Poll::Ready(Err(Error::Length))
+            );
+        }
+    }
+
+    #[test]
+    fn skip_keepalives_empty() {
+        assert_eq!(skip_keepalives(b"+PONG\r\n", &[b""]), b"+PONG\r\n");
+        assert_eq!(skip_keepalives(b"+PONG\r\n", &[b"+PONG\r\n"]), b"");
+        assert_eq!(skip_keepalives(b"+PON", &[b"+PONG\r\n"]), b"+PON");
+    }
+
+    test_cases! {
+        resync_basic: resync(b"$3\r\nabcdef\r\n:1\r\n"), Some(12),
+        resync_skips_start: resync(b"+OK\r\n"), None,
+        resync_bare_newline: resync(b"abc\n:1\r\n"), None,
+        resync_not_a_tag: resync(b"abc\r\n123\r\n_\r\n"), Some(10),
+        resync_trailing_crlf: resync(b"abc\r\n"), None,
+        resync_empty: resync(b""), None,
+    }
+
+    mod parse_integer {
+        use core::str::FromStr;
+
+        use proptest::prelude::*;
+
+        use super::*;
+
+        /// Check that `parse_integer` agrees with `str::parse`.
+        fn check<T>(input: &str)
+        where
+            T: ParseInteger + FromStr + PartialEq + fmt::Debug,
+        {
+            let expected = input.parse::<T>().ok();
+
+            assert_eq!(parse_integer::<T>(input.as_bytes()), expected, "{input:?}");
+        }
+
+        fn check_all(input: &str) {
+            check::<i8>(input);
+            check::<i16>(input);
+            check::<i32>(input);
+            check::<i64>(input);
+            check::<i128>(input);
+            check::<u8>(input);
+            check::<u16>(input);
+            check::<u32>(input);
+            check::<u64>(input);
+            check::<u128>(input);
+        }
+
+        #[test]
+        fn edge_cases() {
+            for input in [
+                "",
+                "+",
+                "-",
+                "0",
+                "-0",
+                "+0",
+                "00",
+                "007",
+                "--1",
+                "+-1",
+                "-+1",
+                " 1",
+                "1 ",
+                "1_000",
+                "0x10",
+                "1e3",
+                "1.0",
+                "\u{663}",
+                "127",
+                "128",
+                "-128",
+                "-129",
+                "255",
+                "256",
+                "9223372036854775807",
+                "9223372036854775808",
+                "-9223372036854775808",
+                "-9223372036854775809",
+                "18446744073709551615",
+                "18446744073709551616",
+                "170141183460469231731687303715884105727",
+                "-170141183460469231731687303715884105728",
+                "340282366920938463463374607431768211455",
+                "340282366920938463463374607431768211456",
+            ] {
+                check_all(input);
+            }
+        }
+
+        proptest! {
+            #[test]
+            fn round_trip_i64(value: i64) {
+                prop_assert_eq!(parse_integer::<i64>(value.to_string().as_bytes()), Some(value));
+            }
+
+            #[test]
+            fn round_trip_u128(value: u128) {
+                prop_assert_eq!(parse_integer::<u128>(value.to_string().as_bytes()), Some(value));
+            }
+
+            #[test]
+            fn matches_str_parse(input in "[-+]{0,2}[0-9]{0,45}[ a+-]?[0-9]{0,3}") {
+                check_all(&input);
+            }
+
+            #[test]
+            fn never_panics(input: Vec<u8>) {
+                let _ = parse_integer::<i64>(&input);
+                let _ = parse_integer::<u64>(&input);
+            }
+        }
     }
 }
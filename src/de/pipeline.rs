@@ -0,0 +1,452 @@
+//! Deserialization of many pipelined replies from a single buffer.
+
+use core::{fmt, iter::FusedIterator, marker::PhantomData};
+
+use serde::{de, forward_to_deserialize_any};
+
+use super::{parse, Deserializer, Error};
+use crate::Config;
+
+/**
+Deserialize a tuple of consecutive pipelined RESP values from a byte slice,
+returning the tuple along with the rest of the input.
+
+When several different commands are pipelined, their replies arrive
+back-to-back as separate values, rather than in an array. Each element of
+`T` is deserialized from the next value in the input, in order, so the
+elements can all have different types. `T` can be a tuple, a tuple struct,
+or a fixed-size array. Any input after the last value is returned as-is, so
+it can be kept for the next call.
+
+If the input ends before the last value, this returns an
+[`UnexpectedEof`][parse::Error::UnexpectedEof] error, like any other
+incomplete input.
+
+# Example
+
+```
+use seredies::de::from_bytes_pipeline;
+
+// Replies to SET, INCR, and LRANGE, followed by the start of another reply
+let input = b"+OK\r\n:5\r\n*2\r\n$1\r\na\r\n$1\r\nb\r\n:1";
+
+let ((set, count, items), rest): (((), i64, Vec<&str>), _) =
+    from_bytes_pipeline(input).unwrap();
+
+assert_eq!(set, ());
+assert_eq!(count, 5);
+assert_eq!(items, ["a", "b"]);
+assert_eq!(rest, b":1");
+```
+*/
+pub fn from_bytes_pipeline<'a, T>(input: &'a [u8]) -> Result<(T, &'a [u8]), Error>
+where
+    T: de::Deserialize<'a>,
+{
+    from_bytes_pipeline_with_config(input, Config::new())
+}
+
+/// Deserialize a tuple of consecutive pipelined RESP values from a byte
+/// slice, using the given [`Config`] for each of them. See
+/// [`from_bytes_pipeline`] for details.
+pub fn from_bytes_pipeline_with_config<'a, T>(
+    mut input: &'a [u8],
+    config: Config,
+) -> Result<(T, &'a [u8]), Error>
+where
+    T: de::Deserialize<'a>,
+{
+    let value = T::deserialize(TupleDeserializer {
+        input: &mut input,
+        config,
+    })?;
+
+    Ok((value, input))
+}
+
+/// Deserializer that presents consecutive top-level values as the elements
+/// of a tuple.
+struct TupleDeserializer<'a, 'de> {
+    input: &'a mut &'de [u8],
+    config: Config,
+}
+
+impl<'de> de::Deserializer<'de> for TupleDeserializer<'_, 'de> {
+    type Error = Error;
+
+    #[inline]
+    fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        Err(de::Error::custom(
+            "pipelined replies can only be deserialized as a tuple",
+        ))
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct seq map struct enum identifier
+        ignored_any
+    }
+
+    #[inline]
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    #[inline]
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_seq(TupleAccess {
+            input: self.input,
+            config: self.config,
+            remaining: len,
+        })
+    }
+
+    #[inline]
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_tuple(len, visitor)
+    }
+}
+
+struct TupleAccess<'a, 'de> {
+    input: &'a mut &'de [u8],
+    config: Config,
+    remaining: usize,
+}
+
+impl<'de> de::SeqAccess<'de> for TupleAccess<'_, 'de> {
+    type Error = Error;
+
+    #[inline]
+    fn next_element_seed<S>(&mut self, seed: S) -> Result<Option<S::Value>, Self::Error>
+    where
+        S: de::DeserializeSeed<'de>,
+    {
+        match self.remaining.checked_sub(1) {
+            None => Ok(None),
+            Some(remaining) => {
+                self.remaining = remaining;
+                seed.deserialize(Deserializer::with_config(self.input, self.config))
+                    .map(Some)
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+/**
+An iterator that deserializes a sequence of pipelined RESP values from a
+single buffer, one `T` at a time.
+
+When commands are pipelined, their replies arrive back-to-back, and a single
+read from the connection might contain several of them (and the start of
+another). A `Pipeline` yields each complete reply in the buffer, and stops
+cleanly at the end of the input or at an incomplete reply, which is left in
+[`remaining`][Pipeline::remaining] so that it can be retried once more data
+has been read.
+
+If a reply fails to deserialize, the error is yielded as a [`PipelineError`]
+that includes the position of the reply in the buffer, and the reply is
+skipped, so the replies after it can still be deserialized. If the data is
+malformed and the reply can't be skipped, the iterator stops after the
+error.
+
+# Example
+
+```
+use seredies::de::Pipeline;
+
+let input = b":1\r\n:2\r\n-ERR wrong type\r\n:4\r\n:5";
+let mut pipeline: Pipeline<Result<i64, String>> = Pipeline::new(input);
+
+assert_eq!(pipeline.next().unwrap().unwrap(), Ok(1));
+assert_eq!(pipeline.next().unwrap().unwrap(), Ok(2));
+assert_eq!(
+    pipeline.next().unwrap().unwrap(),
+    Err("ERR wrong type".to_owned())
+);
+assert_eq!(pipeline.next().unwrap().unwrap(), Ok(4));
+
+// The last reply is incomplete
+assert!(pipeline.next().is_none());
+assert_eq!(pipeline.remaining(), b":5");
+assert_eq!(pipeline.offset(), 29);
+```
+*/
+#[derive(Debug, Clone)]
+pub struct Pipeline<'de, T> {
+    input: &'de [u8],
+    offset: usize,
+    index: usize,
+    failed: bool,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<'de, T> Pipeline<'de, T> {
+    /// Create a new pipeline that deserializes values from the `input`.
+    #[inline]
+    #[must_use]
+    pub fn new(input: &'de [u8]) -> Self {
+        Self {
+            input,
+            offset: 0,
+            index: 0,
+            failed: false,
+            phantom: PhantomData,
+        }
+    }
+
+    /// The part of the input that hasn't been deserialized yet. Once the
+    /// iterator is exhausted, this is either empty or an incomplete reply.
+    #[inline]
+    #[must_use]
+    pub fn remaining(&self) -> &'de [u8] {
+        self.input
+    }
+
+    /// The number of bytes of the input that have been consumed, including
+    /// replies that failed to deserialize and were skipped.
+    #[inline]
+    #[must_use]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    #[inline]
+    fn advance(&mut self, tail: &'de [u8]) {
+        self.offset += self.input.len() - tail.len();
+        self.index += 1;
+        self.input = tail;
+    }
+}
+
+impl<'de, T> Iterator for Pipeline<'de, T>
+where
+    T: de::Deserialize<'de>,
+{
+    type Item = Result<T, PipelineError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || self.input.is_empty() {
+            return None;
+        }
+
+        let mut tail = self.input;
+
+        let error = match T::deserialize(Deserializer::new(&mut tail)) {
+            Ok(value) => {
+                self.advance(tail);
+                return Some(Ok(value));
+            }
+            Err(Error::Parse(parse::Error::UnexpectedEof(_))) => return None,
+            Err(error) => PipelineError {
+                index: self.index,
+                offset: self.offset,
+                error,
+            },
+        };
+
+        // Skip over the reply that failed, so that the next one can be
+        // deserialized.
+        match parse::skip_value(self.input) {
+            Ok((_, tail)) => self.advance(tail),
+
+            // `T` rejected the start of a reply that hasn't fully arrived;
+            // wait until it has, so that it can be skipped.
+            Err(parse::Error::UnexpectedEof(_)) => return None,
+
+            // The reply is malformed, so there's no way to find the start of
+            // the next one.
+            Err(_) => self.failed = true,
+        }
+
+        Some(Err(error))
+    }
+}
+
+impl<'de, T> FusedIterator for Pipeline<'de, T> where T: de::Deserialize<'de> {}
+
+/// An error from a [`Pipeline`], including the position of the reply that
+/// failed to deserialize.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct PipelineError {
+    /// The index of the reply in the pipeline, counting from 0.
+    pub index: usize,
+
+    /// The offset of the start of the reply in the pipeline's input.
+    pub offset: usize,
+
+    /// The deserialization error.
+    pub error: Error,
+}
+
+impl fmt::Display for PipelineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "failed to deserialize pipelined reply {} at byte {}",
+            self.index, self.offset
+        )
+    }
+}
+
+impl de::StdError for PipelineError {
+    fn source(&self) -> Option<&(dyn de::StdError + 'static)> {
+        Some(&self.error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cool_asserts::assert_matches;
+
+    use super::*;
+
+    type Replies<'a> = ((), Option<&'a str>, Result<i64, String>, [i64; 1]);
+
+    #[test]
+    fn tuple() {
+        let input = b"+OK\r\n$-1\r\n-ERR x\r\n*1\r\n:1\r\n";
+        let (value, rest): (Replies, _) = from_bytes_pipeline(input).unwrap();
+
+        assert_eq!(value, ((), None, Err("ERR x".to_owned()), [1]));
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn tuple_leftover() {
+        let (value, rest): ([i64; 2], _) = from_bytes_pipeline(b":1\r\n:2\r\n:3\r\n").unwrap();
+
+        assert_eq!(value, [1, 2]);
+        assert_eq!(rest, b":3\r\n");
+    }
+
+    #[test]
+    fn tuple_incomplete() {
+        assert_matches!(
+            from_bytes_pipeline::<(i64, i64)>(b":1\r\n:2"),
+            Err(Error::Parse(parse::Error::UnexpectedEof(_)))
+        );
+    }
+
+    #[test]
+    fn tuple_error() {
+        assert_matches!(
+            from_bytes_pipeline::<(i64, i64)>(b":1\r\n-ERR x\r\n"),
+            Err(Error::Redis(_))
+        );
+    }
+
+    #[test]
+    fn not_a_tuple() {
+        assert_matches!(
+            from_bytes_pipeline::<Vec<i64>>(b":1\r\n"),
+            Err(Error::Custom(_))
+        );
+    }
+
+    #[test]
+    fn every_reply() {
+        let pipeline: Pipeline<&str> = Pipeline::new(b"$3\r\nabc\r\n+OK\r\n$0\r\n\r\n");
+        let replies: Vec<&str> = pipeline.map(Result::unwrap).collect();
+
+        assert_eq!(replies, ["abc", "OK", ""]);
+    }
+
+    #[test]
+    fn empty_input() {
+        let mut pipeline: Pipeline<i64> = Pipeline::new(b"");
+
+        assert!(pipeline.next().is_none());
+        assert_eq!(pipeline.offset(), 0);
+    }
+
+    #[test]
+    fn incomplete_reply() {
+        let mut pipeline: Pipeline<Vec<i64>> = Pipeline::new(b"*1\r\n:1\r\n*2\r\n:1\r\n");
+
+        assert_eq!(pipeline.next().unwrap().unwrap(), [1]);
+        assert!(pipeline.next().is_none());
+        assert!(pipeline.next().is_none());
+        assert_eq!(pipeline.remaining(), b"*2\r\n:1\r\n");
+        assert_eq!(pipeline.offset(), 8);
+    }
+
+    #[test]
+    fn skip_failed_reply() {
+        let mut pipeline: Pipeline<i64> =
+            Pipeline::new(b":1\r\n*2\r\n:2\r\n:3\r\n-ERR x\r\n:4\r\n");
+
+        assert_matches!(pipeline.next(), Some(Ok(1)));
+        assert_matches!(
+            pipeline.next(),
+            Some(Err(PipelineError {
+                index: 1,
+                offset: 4,
+                error: Error::Custom(_)
+            }))
+        );
+        assert_matches!(
+            pipeline.next(),
+            Some(Err(PipelineError {
+                index: 2,
+                offset: 16,
+                error: Error::Redis(_)
+            }))
+        );
+        assert_matches!(pipeline.next(), Some(Ok(4)));
+        assert!(pipeline.next().is_none());
+        assert!(pipeline.remaining().is_empty());
+    }
+
+    #[test]
+    fn failed_incomplete_reply() {
+        let mut pipeline: Pipeline<i64> = Pipeline::new(b":1\r\n*2\r\n:2\r\n");
+
+        assert_matches!(pipeline.next(), Some(Ok(1)));
+        assert!(pipeline.next().is_none());
+        assert_eq!(pipeline.remaining(), b"*2\r\n:2\r\n");
+    }
+
+    #[test]
+    fn malformed_reply() {
+        let mut pipeline: Pipeline<i64> = Pipeline::new(b":1\r\n:abc\r\n:2\r\n");
+
+        assert_matches!(pipeline.next(), Some(Ok(1)));
+        assert_matches!(
+            pipeline.next(),
+            Some(Err(PipelineError {
+                index: 1,
+                offset: 4,
+                error: Error::Parse(parse::Error::Number)
+            }))
+        );
+        assert!(pipeline.next().is_none());
+        assert_eq!(pipeline.offset(), 4);
+    }
+}
@@ -0,0 +1,300 @@
+//! Telling RESP3 pushes apart from ordinary replies.
+
+use core::{fmt, marker::PhantomData};
+
+use serde::de;
+
+use super::{parse::TaggedHeader, Error, PreParsedDeserializer};
+
+/// The enum name used by [`Incoming`] to request that the deserializer
+/// report whether the next value is a push.
+pub(crate) const INCOMING_TOKEN: &str = "$seredies::Incoming";
+
+const VARIANTS: &[&str] = &["Reply", "Push"];
+
+/**
+A value received from a RESP3 server, which is either an ordinary reply or
+an out-of-band [push](https://redis.io/docs/reference/protocol-spec/#pushes).
+
+With RESP3, a server can send pushes, like pub/sub messages and client-side
+caching invalidations, in between the replies to a client's commands.
+Deserializing each value as an `Incoming` lets a client dispatch the pushes
+separately, while matching the replies up with their commands as usual.
+
+A push is structured like an array, so `P` is typically a sequence or a
+tuple; the first element is the kind of push, like `message` or
+`invalidate`. Errors in replies are handled the same way as they would be
+for `R` itself, so use a [`Result`] for `R` to receive them as values.
+
+# Example
+
+```
+use seredies::de::{from_bytes, Incoming};
+
+type Message<'a> = Incoming<i64, (&'a str, &'a str, &'a str)>;
+
+let message: Message = from_bytes(b">3\r\n$7\r\nmessage\r\n$4\r\nnews\r\n$5\r\nhello\r\n").unwrap();
+assert_eq!(message, Incoming::Push(("message", "news", "hello")));
+
+let message: Message = from_bytes(b":10\r\n").unwrap();
+assert_eq!(message, Incoming::Reply(10));
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Incoming<R, P> {
+    /// An ordinary reply to a command
+    Reply(R),
+
+    /// An out-of-band push
+    Push(P),
+}
+
+impl<R, P> Incoming<R, P> {
+    /// True if this is a push.
+    #[inline]
+    #[must_use]
+    pub fn is_push(&self) -> bool {
+        matches!(*self, Incoming::Push(_))
+    }
+
+    /// Get the reply, or `None` if this is a push.
+    #[inline]
+    #[must_use]
+    pub fn reply(self) -> Option<R> {
+        match self {
+            Incoming::Reply(reply) => Some(reply),
+            Incoming::Push(_) => None,
+        }
+    }
+
+    /// Get the push, or `None` if this is a reply.
+    #[inline]
+    #[must_use]
+    pub fn push(self) -> Option<P> {
+        match self {
+            Incoming::Reply(_) => None,
+            Incoming::Push(push) => Some(push),
+        }
+    }
+}
+
+impl<'de, R, P> de::Deserialize<'de> for Incoming<R, P>
+where
+    R: de::Deserialize<'de>,
+    P: de::Deserialize<'de>,
+{
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_enum(
+            INCOMING_TOKEN,
+            VARIANTS,
+            IncomingVisitor { kind: PhantomData },
+        )
+    }
+}
+
+struct IncomingVisitor<R, P> {
+    kind: PhantomData<fn() -> (R, P)>,
+}
+
+impl<'de, R, P> de::Visitor<'de> for IncomingVisitor<R, P>
+where
+    R: de::Deserialize<'de>,
+    P: de::Deserialize<'de>,
+{
+    type Value = Incoming<R, P>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a reply or a push")
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::EnumAccess<'de>,
+    {
+        use de::VariantAccess as _;
+
+        let (kind, variant) = data.variant()?;
+
+        match kind {
+            Kind::Reply => variant.newtype_variant().map(Incoming::Reply),
+            Kind::Push => variant.newtype_variant().map(Incoming::Push),
+        }
+    }
+}
+
+enum Kind {
+    Reply,
+    Push,
+}
+
+impl<'de> de::Deserialize<'de> for Kind {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_identifier(KindVisitor)
+    }
+}
+
+struct KindVisitor;
+
+impl de::Visitor<'_> for KindVisitor {
+    type Value = Kind;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("Reply or Push")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        match v {
+            "Reply" => Ok(Kind::Reply),
+            "Push" => Ok(Kind::Push),
+            _ => Err(E::unknown_variant(v, VARIANTS)),
+        }
+    }
+}
+
+/// Enum access for [`Incoming`]. The variant is `Push` if the header is a
+/// push and `Reply` otherwise, and in both cases the value is deserialized
+/// normally.
+pub(super) struct IncomingAccess<'a, 'de> {
+    parsed: PreParsedDeserializer<'a, 'de>,
+}
+
+impl<'a, 'de> IncomingAccess<'a, 'de> {
+    #[inline]
+    #[must_use]
+    pub fn new(parsed: PreParsedDeserializer<'a, 'de>) -> Self {
+        Self { parsed }
+    }
+}
+
+impl<'de> de::EnumAccess<'de> for IncomingAccess<'_, 'de> {
+    type Error = Error;
+    type Variant = Self;
+
+    #[inline]
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        let variant = match self.parsed.header {
+            TaggedHeader::Push(_) => "Push",
+            _ => "Reply",
+        };
+
+        seed.deserialize(de::value::BorrowedStrDeserializer::new(variant))
+            .map(|value| (value, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for IncomingAccess<'_, 'de> {
+    type Error = Error;
+
+    #[inline]
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Err(de::Error::invalid_type(
+            de::Unexpected::NewtypeVariant,
+            &"unit variant",
+        ))
+    }
+
+    #[inline]
+    fn newtype_variant_seed<S>(self, seed: S) -> Result<S::Value, Self::Error>
+    where
+        S: de::DeserializeSeed<'de>,
+    {
+        seed.deserialize(self.parsed)
+    }
+
+    #[inline]
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        Err(de::Error::invalid_type(
+            de::Unexpected::NewtypeVariant,
+            &visitor,
+        ))
+    }
+
+    #[inline]
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        Err(de::Error::invalid_type(
+            de::Unexpected::NewtypeVariant,
+            &visitor,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cool_asserts::assert_matches;
+
+    use super::*;
+    use crate::{de::from_bytes, value::Value};
+
+    #[test]
+    fn push() {
+        let message: Incoming<i64, Vec<&str>> =
+            from_bytes(b">3\r\n$7\r\nmessage\r\n$4\r\nnews\r\n$5\r\nhello\r\n").unwrap();
+
+        assert_eq!(
+            message,
+            Incoming::Push(Vec::from(["message", "news", "hello"]))
+        );
+        assert!(message.is_push());
+    }
+
+    #[test]
+    fn nested_push() {
+        let message: Incoming<i64, (&str, Vec<&str>)> =
+            from_bytes(b">2\r\n$10\r\ninvalidate\r\n*1\r\n$3\r\nkey\r\n").unwrap();
+
+        assert_eq!(message.push(), Some(("invalidate", Vec::from(["key"]))));
+    }
+
+    #[test]
+    fn array_reply() {
+        let message: Incoming<Vec<&str>, Vec<&str>> =
+            from_bytes(b"*2\r\n$1\r\na\r\n$1\r\nb\r\n").unwrap();
+
+        assert_eq!(message.reply(), Some(Vec::from(["a", "b"])));
+    }
+
+    #[test]
+    fn error_reply() {
+        let message: Incoming<Result<i64, &str>, Value> = from_bytes(b"-ERR bad\r\n").unwrap();
+        assert_eq!(message, Incoming::Reply(Err("ERR bad")));
+
+        assert_matches!(
+            from_bytes::<Incoming<i64, Value>>(b"-ERR bad\r\n"),
+            Err(Error::Redis(message)) => assert_eq!(message, b"ERR bad")
+        );
+    }
+
+    #[test]
+    fn push_as_value() {
+        let message: Incoming<Value, Value> = from_bytes(b">1\r\n:1\r\n").unwrap();
+        assert_eq!(
+            message,
+            Incoming::Push(Value::Array(Vec::from([Value::Integer(1)])))
+        );
+
+        // Without `Incoming`, a push looks just like an array
+        let value: Value = from_bytes(b">1\r\n:1\r\n").unwrap();
+        assert_eq!(value, Value::Array(Vec::from([Value::Integer(1)])));
+    }
+}
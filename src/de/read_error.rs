@@ -0,0 +1,51 @@
+use std::{fmt, io};
+
+use serde::de;
+
+use super::Error;
+
+/// Errors that can occur while deserializing from an async reader, with
+/// `de::from_async_reader` or `aio::read_reply`.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ReadError {
+    /// There was an i/o error while reading. If the reader reached EOF
+    /// before a complete RESP value was read, this will be an error with
+    /// [`io::ErrorKind::UnexpectedEof`].
+    Io(io::Error),
+
+    /// The data was read successfully, but failed to deserialize.
+    Deserialize(Error),
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            ReadError::Io(_) => f.write_str("i/o error during deserialization"),
+            ReadError::Deserialize(_) => f.write_str("failed to deserialize"),
+        }
+    }
+}
+
+impl de::StdError for ReadError {
+    fn source(&self) -> Option<&(dyn de::StdError + 'static)> {
+        match *self {
+            ReadError::Io(ref err) => Some(err),
+            ReadError::Deserialize(ref err) => Some(err),
+        }
+    }
+}
+
+impl From<io::Error> for ReadError {
+    #[inline]
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<Error> for ReadError {
+    #[inline]
+    fn from(err: Error) -> Self {
+        Self::Deserialize(err)
+    }
+}
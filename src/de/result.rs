@@ -129,8 +129,8 @@ impl<'de> de::Deserializer<'de> for ResultPlainOkPattern {
 
     forward_to_deserialize_any! {
         bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
-        option unit_struct newtype_struct seq tuple
-        tuple_struct map struct identifier ignored_any enum
+        unit_struct newtype_struct seq tuple
+        tuple_struct map struct identifier ignored_any
     }
 
     #[inline]
@@ -141,6 +141,34 @@ impl<'de> de::Deserializer<'de> for ResultPlainOkPattern {
         visitor.visit_borrowed_str("OK")
     }
 
+    /// `+OK\r\n` is never null, so `Result<Option<T>, E>` is `Ok(Some(..))`
+    #[inline]
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    /// Nested results, like `Result<Result<(), E1>, E2>`, are `Ok(Ok(..))`
+    #[inline]
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match (name, variants) {
+            ("Result", ["Ok", "Err"] | ["Err", "Ok"]) => {
+                visitor.visit_enum(ResultAccess::new(self))
+            }
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
     #[inline]
     fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
@@ -194,6 +222,193 @@ impl<'de> ResultAccessPattern<'de> for ResultErrPattern<'de> {
     where
         T: de::DeserializeSeed<'de>,
     {
-        seed.deserialize(de::value::BorrowedBytesDeserializer::new(self.message))
+        seed.deserialize(MessageDeserializer {
+            message: self.message,
+        })
+    }
+}
+
+/// Deserializer for the message in a Redis error. This is a plain byte
+/// string, except that it can also be deserialized as `Some`, so that types
+/// like `Result<T, Option<String>>` work. It can also be deserialized as a
+/// unit variant, for error codes modeled as enums, or as a 2-tuple or
+/// 2-field struct, in which case it's split into a code and a message at
+/// the first space.
+#[derive(Clone, Copy)]
+struct MessageDeserializer<'de> {
+    message: &'de [u8],
+}
+
+impl<'de> MessageDeserializer<'de> {
+    #[inline]
+    fn parts(self) -> MessageParts<'de> {
+        let (code, message) = match memchr::memchr(b' ', self.message) {
+            Some(idx) => (&self.message[..idx], &self.message[idx + 1..]),
+            None => (self.message, &b""[..]),
+        };
+
+        MessageParts {
+            parts: [code, message],
+            index: 0,
+        }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for MessageDeserializer<'de> {
+    type Error = Error;
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct newtype_struct seq
+        map identifier ignored_any
+    }
+
+    #[inline]
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_borrowed_bytes(self.message)
+    }
+
+    #[inline]
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    #[inline]
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_enum(self)
+    }
+
+    #[inline]
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_seq(self.parts())
+    }
+
+    #[inline]
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_seq(self.parts())
+    }
+
+    #[inline]
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_seq(self.parts())
+    }
+}
+
+impl<'de> de::EnumAccess<'de> for MessageDeserializer<'de> {
+    type Error = Error;
+    type Variant = Self;
+
+    #[inline]
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        seed.deserialize(self).map(|value| (value, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for MessageDeserializer<'de> {
+    type Error = Error;
+
+    #[inline]
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    #[inline]
+    fn newtype_variant_seed<S>(self, _seed: S) -> Result<S::Value, Self::Error>
+    where
+        S: de::DeserializeSeed<'de>,
+    {
+        Err(de::Error::invalid_type(
+            de::Unexpected::UnitVariant,
+            &"newtype variant",
+        ))
+    }
+
+    #[inline]
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        Err(de::Error::invalid_type(
+            de::Unexpected::UnitVariant,
+            &visitor,
+        ))
+    }
+
+    #[inline]
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        Err(de::Error::invalid_type(
+            de::Unexpected::UnitVariant,
+            &visitor,
+        ))
+    }
+}
+
+/// The code and message of a Redis error, as a sequence
+struct MessageParts<'de> {
+    parts: [&'de [u8]; 2],
+    index: usize,
+}
+
+impl<'de> de::SeqAccess<'de> for MessageParts<'de> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        let Some(&message) = self.parts.get(self.index) else {
+            return Ok(None);
+        };
+
+        self.index += 1;
+        seed.deserialize(MessageDeserializer { message }).map(Some)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.parts.len() - self.index)
     }
 }
@@ -0,0 +1,169 @@
+//! Checking that deserialized values serialize back to the same bytes.
+
+use alloc::string::String;
+use core::fmt;
+
+use serde::{de, ser};
+
+use super::{from_bytes_with_config, Error};
+use crate::{fmt::diff, ser::to_vec_with_config, Config};
+
+/**
+Deserialize a `T` from a byte slice containing RESP data, and then check that
+serializing it again reproduces exactly the same bytes.
+
+The serializer and deserializer are each designed to accept anything the
+other produces, but a value usually has more than one RESP representation,
+and only one of them is what the serializer writes. This function catches
+the cases where they disagree, like a Simple String that's written back as a
+Bulk String, or a RESP2 null that's written back as a RESP3 null. This makes it
+useful for conformance checking in proxies, which need to forward replies
+unchanged, and for fuzzing, where any divergence may be a canonicalization
+bug.
+
+The same `config` is used for deserialization and serialization, so it
+should use [`Version::Resp3`][crate::protocol::Version::Resp3] when the
+input is RESP3. Like [`from_bytes`][super::from_bytes], the input must
+contain exactly one value.
+
+# Example
+
+```
+use seredies::de::{from_bytes_round_trip, RoundTripError};
+use seredies::value::Value;
+use seredies::Config;
+
+// `Value` preserves the exact RESP type of everything in the input
+let input = b"*2\r\n+OK\r\n$5\r\nhello\r\n";
+let value: Value = from_bytes_round_trip(input, Config::new()).unwrap();
+
+// A `String` is always serialized as a Bulk String
+let result = from_bytes_round_trip::<Vec<String>>(input, Config::new());
+let Err(RoundTripError::Mismatch { offset, description }) = result else { panic!() };
+assert_eq!(offset, 4);
+assert_eq!(description, r#"#0[0]: type differs: left is simple string "OK", right is bulk string "OK""#);
+```
+*/
+pub fn from_bytes_round_trip<'a, T>(input: &'a [u8], config: Config) -> Result<T, RoundTripError>
+where
+    T: de::Deserialize<'a> + ser::Serialize,
+{
+    let value: T = from_bytes_with_config(input, config).map_err(RoundTripError::Deserialize)?;
+    let output = to_vec_with_config(&value, config).map_err(RoundTripError::Serialize)?;
+
+    match input
+        .iter()
+        .zip(&output)
+        .position(|(a, b)| a != b)
+        .or_else(|| (input.len() != output.len()).then(|| input.len().min(output.len())))
+    {
+        None => Ok(value),
+        Some(offset) => Err(RoundTripError::Mismatch {
+            offset,
+            description: diff(input, &output),
+        }),
+    }
+}
+
+/// An error from [`from_bytes_round_trip`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum RoundTripError {
+    /// The input couldn't be deserialized.
+    Deserialize(Error),
+
+    /// The deserialized value couldn't be serialized.
+    Serialize(crate::ser::Error),
+
+    /// The value was serialized to different bytes than the input.
+    Mismatch {
+        /// The offset of the first byte that differs. If one of them is a
+        /// prefix of the other, this is the length of the shorter one.
+        offset: usize,
+
+        /// A description of the first structural difference, from
+        /// [`fmt::diff`][crate::fmt::diff], where the input is on the left
+        /// and the serialized value is on the right.
+        description: String,
+    },
+}
+
+impl fmt::Display for RoundTripError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            RoundTripError::Deserialize(_) => f.write_str("failed to deserialize the input"),
+            RoundTripError::Serialize(_) => f.write_str("failed to serialize the value"),
+            RoundTripError::Mismatch {
+                offset,
+                ref description,
+            } => write!(
+                f,
+                "the value serialized differently from the input, starting at \
+                byte {offset} ({description})"
+            ),
+        }
+    }
+}
+
+impl de::StdError for RoundTripError {
+    fn source(&self) -> Option<&(dyn de::StdError + 'static)> {
+        match *self {
+            RoundTripError::Deserialize(ref error) => Some(error),
+            RoundTripError::Serialize(ref error) => Some(error),
+            RoundTripError::Mismatch { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cool_asserts::assert_matches;
+
+    use super::*;
+    use crate::{protocol::Version, value::Value};
+
+    #[test]
+    fn exact() {
+        let input = b"*3\r\n:1\r\n$3\r\nabc\r\n$-1\r\n";
+        let value: (i64, &str, Option<&str>) = from_bytes_round_trip(input, Config::new()).unwrap();
+
+        assert_eq!(value, (1, "abc", None));
+    }
+
+    #[test]
+    fn value_preserves_types() {
+        let input = b"*4\r\n+OK\r\n-ERR x\r\n:-5\r\n*0\r\n";
+        let _: Value = from_bytes_round_trip(input, Config::new()).unwrap();
+    }
+
+    #[test]
+    fn simple_string() {
+        assert_matches!(
+            from_bytes_round_trip::<&str>(b"+OK\r\n", Config::new()),
+            Err(RoundTripError::Mismatch { offset: 0, .. })
+        );
+    }
+
+    #[test]
+    fn resp3() {
+        let input = b"(18446744073709551616\r\n";
+
+        let value: u128 =
+            from_bytes_round_trip(input, Config::new().with_version(Version::Resp3)).unwrap();
+        assert_eq!(value, 1 << 64);
+
+        // RESP2 doesn't have big numbers
+        assert_matches!(
+            from_bytes_round_trip::<u128>(input, Config::new()),
+            Err(RoundTripError::Serialize(_))
+        );
+    }
+
+    #[test]
+    fn deserialize_error() {
+        assert_matches!(
+            from_bytes_round_trip::<i64>(b":1\r\n:2\r\n", Config::new()),
+            Err(RoundTripError::Deserialize(Error::TrailingData))
+        );
+    }
+}
@@ -0,0 +1,115 @@
+// Helpers for deserializing a `Value`, which needs to know the exact RESP
+// type of each element (for instance, to tell a Simple String apart from a
+// Bulk String). The deserializer presents the parsed value as an enum whose
+// variant is the RESP type.
+
+use serde::de;
+
+use super::{
+    parse::{parse_integer, TaggedHeader},
+    Error, PreParsedDeserializer,
+};
+
+pub(super) struct ValueAccess<'a, 'de> {
+    parsed: PreParsedDeserializer<'a, 'de>,
+}
+
+impl<'a, 'de> ValueAccess<'a, 'de> {
+    #[inline]
+    #[must_use]
+    pub fn new(parsed: PreParsedDeserializer<'a, 'de>) -> Self {
+        Self { parsed }
+    }
+}
+
+impl<'de> de::EnumAccess<'de> for ValueAccess<'_, 'de> {
+    type Error = Error;
+    type Variant = Self;
+
+    #[inline]
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        let variant = match self.parsed.header {
+            TaggedHeader::SimpleString(_) => "SimpleString",
+            TaggedHeader::Error(_) => "Error",
+            TaggedHeader::Integer(_) => "Integer",
+            // `Value` doesn't have a variant for Verbatim Strings, so they're
+            // presented as Bulk Strings of their content
+            TaggedHeader::BulkString(_) | TaggedHeader::Verbatim(_) => "BulkString",
+            // Likewise, pushes are presented as arrays
+            TaggedHeader::Array(_) | TaggedHeader::Push(_) => "Array",
+            TaggedHeader::Null => "Null",
+            // Big numbers are presented as integers if they fit, or as Bulk
+            // Strings of their digits otherwise
+            TaggedHeader::BigNumber(digits) => match parse_integer::<i64>(digits) {
+                Some(_) => "Integer",
+                None => "BulkString",
+            },
+        };
+
+        seed.deserialize(de::value::BorrowedStrDeserializer::new(variant))
+            .map(|value| (value, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for ValueAccess<'_, 'de> {
+    type Error = Error;
+
+    #[inline]
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        match self.parsed.header {
+            TaggedHeader::Null => Ok(()),
+            _ => Err(de::Error::invalid_type(
+                de::Unexpected::NewtypeVariant,
+                &"null",
+            )),
+        }
+    }
+
+    #[inline]
+    fn newtype_variant_seed<S>(self, seed: S) -> Result<S::Value, Self::Error>
+    where
+        S: de::DeserializeSeed<'de>,
+    {
+        match self.parsed.header {
+            // Errors would be rejected by the deserializer, so hand over
+            // the message directly
+            TaggedHeader::Error(message) => {
+                seed.deserialize(de::value::BorrowedBytesDeserializer::new(message))
+            }
+            TaggedHeader::Null => Err(de::Error::invalid_type(
+                de::Unexpected::UnitVariant,
+                &"non-null value",
+            )),
+            _ => seed.deserialize(self.parsed),
+        }
+    }
+
+    #[inline]
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        Err(de::Error::invalid_type(
+            de::Unexpected::NewtypeVariant,
+            &visitor,
+        ))
+    }
+
+    #[inline]
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        Err(de::Error::invalid_type(
+            de::Unexpected::NewtypeVariant,
+            &visitor,
+        ))
+    }
+}
@@ -0,0 +1,79 @@
+// Helpers for deserializing unit enum variants, such as the values of a hash
+// field that is effectively an enumeration ("type" => "zset"). The variant
+// name is the payload of a Simple String or Bulk String.
+
+use serde::de;
+
+use super::Error;
+
+pub(super) struct UnitVariantAccess<'de> {
+    variant: &'de [u8],
+}
+
+impl<'de> UnitVariantAccess<'de> {
+    #[inline]
+    #[must_use]
+    pub fn new(variant: &'de [u8]) -> Self {
+        Self { variant }
+    }
+}
+
+impl<'de> de::EnumAccess<'de> for UnitVariantAccess<'de> {
+    type Error = Error;
+    type Variant = Self;
+
+    #[inline]
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        seed.deserialize(de::value::BorrowedBytesDeserializer::new(self.variant))
+            .map(|value| (value, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for UnitVariantAccess<'de> {
+    type Error = Error;
+
+    #[inline]
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    #[inline]
+    fn newtype_variant_seed<S>(self, _seed: S) -> Result<S::Value, Self::Error>
+    where
+        S: de::DeserializeSeed<'de>,
+    {
+        Err(de::Error::invalid_type(
+            de::Unexpected::UnitVariant,
+            &"newtype variant",
+        ))
+    }
+
+    #[inline]
+    fn tuple_variant<V>(self, _len: usize, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        Err(de::Error::invalid_type(
+            de::Unexpected::UnitVariant,
+            &"tuple variant",
+        ))
+    }
+
+    #[inline]
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        Err(de::Error::invalid_type(
+            de::Unexpected::UnitVariant,
+            &"struct variant",
+        ))
+    }
+}
@@ -0,0 +1,240 @@
+//! Deserialization from a sub-slice of a larger buffer, with offsets that
+//! refer to the whole buffer.
+
+use core::{fmt, ops::Range};
+
+use serde::de;
+
+use super::{parse, Deserializer, Error};
+use crate::Config;
+
+/**
+A window onto part of a larger buffer, which deserializes the RESP values in
+that part one at a time.
+
+Proxies and other programs that manage their own receive buffers usually
+deserialize values from somewhere in the middle of a buffer. A `Window`
+remembers where its part of the buffer starts, so that every offset it
+reports, in errors and in the [ranges of raw values][Window::read_raw], is
+an index into the whole buffer rather than into the window. Values are
+never read past the end of the window.
+
+Each value is deserialized atomically: if it fails to deserialize, the
+window isn't advanced, and the error includes the offset of the start of
+the value. To skip over a value that failed, use [`read_raw`][Window::read_raw].
+
+# Example
+
+```
+use seredies::de::Window;
+
+let buffer = b"garbage:1\r\n$3\r\nabc\r\n+OK\r\nmore garbage";
+let mut window = Window::new(buffer, 7..25);
+
+let number: i64 = window.read().unwrap();
+assert_eq!(number, 1);
+assert_eq!(window.offset(), 11);
+
+// The raw bytes of a value can be captured by their range in the buffer
+let range = window.read_raw().unwrap();
+assert_eq!(range, 11..20);
+assert_eq!(&buffer[range], b"$3\r\nabc\r\n");
+
+// Errors are reported at their position in the buffer
+let err = window.read::<i64>().unwrap_err();
+assert_eq!(err.offset, 20);
+
+let reply: &str = window.read().unwrap();
+assert_eq!(reply, "OK");
+assert!(window.is_empty());
+```
+*/
+#[derive(Debug, Clone, Copy)]
+pub struct Window<'de> {
+    input: &'de [u8],
+    offset: usize,
+    config: Config,
+}
+
+impl<'de> Window<'de> {
+    /// Create a window onto the given `range` of the `buffer`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds for the buffer, like slice
+    /// indexing.
+    #[inline]
+    #[must_use]
+    pub fn new(buffer: &'de [u8], range: Range<usize>) -> Self {
+        Self::with_offset(&buffer[range.clone()], range.start)
+    }
+
+    /// Create a window from a slice that's already been taken from a larger
+    /// buffer, starting at `offset` in that buffer.
+    #[inline]
+    #[must_use]
+    pub const fn with_offset(input: &'de [u8], offset: usize) -> Self {
+        Self {
+            input,
+            offset,
+            config: Config::new(),
+        }
+    }
+
+    /// Use the given [`Config`] to deserialize each value.
+    #[inline]
+    #[must_use]
+    pub const fn with_config(self, config: Config) -> Self {
+        Self { config, ..self }
+    }
+
+    /// The offset in the buffer of the first byte that hasn't been read yet.
+    #[inline]
+    #[must_use]
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The part of the window that hasn't been read yet.
+    #[inline]
+    #[must_use]
+    pub const fn remaining(&self) -> &'de [u8] {
+        self.input
+    }
+
+    /// True if the whole window has been read.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.input.is_empty()
+    }
+
+    /// Deserialize the next value in the window. On error, the window isn't
+    /// advanced.
+    pub fn read<T>(&mut self) -> Result<T, WindowError>
+    where
+        T: de::Deserialize<'de>,
+    {
+        let mut tail = self.input;
+
+        match T::deserialize(Deserializer::with_config(&mut tail, self.config)) {
+            Ok(value) => {
+                self.advance(tail);
+                Ok(value)
+            }
+            Err(error) => Err(self.error(error)),
+        }
+    }
+
+    /// Skip the next value in the window, without deserializing it, and
+    /// return the range of its raw bytes in the buffer. On error, the window
+    /// isn't advanced.
+    pub fn read_raw(&mut self) -> Result<Range<usize>, WindowError> {
+        let input = parse::skip_keepalives(self.input, self.config.keepalives);
+        let start = self.offset + (self.input.len() - input.len());
+
+        let (raw, tail) = parse::skip_value(input).map_err(|error| self.error(error.into()))?;
+        self.advance(tail);
+
+        Ok(start..start + raw.len())
+    }
+
+    #[inline]
+    fn advance(&mut self, tail: &'de [u8]) {
+        self.offset += self.input.len() - tail.len();
+        self.input = tail;
+    }
+
+    #[inline]
+    fn error(&self, error: Error) -> WindowError {
+        WindowError {
+            offset: self.offset,
+            error,
+        }
+    }
+}
+
+/// An error from a [`Window`], including the position in the buffer of the
+/// value that failed to deserialize.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct WindowError {
+    /// The offset in the buffer of the start of the value.
+    pub offset: usize,
+
+    /// The deserialization error.
+    pub error: Error,
+}
+
+impl fmt::Display for WindowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to deserialize value at byte {}", self.offset)
+    }
+}
+
+impl de::StdError for WindowError {
+    fn source(&self) -> Option<&(dyn de::StdError + 'static)> {
+        Some(&self.error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cool_asserts::assert_matches;
+
+    use super::*;
+
+    #[test]
+    fn stays_in_window() {
+        let buffer = b":1\r\n:2\r\n:3\r\n";
+        let mut window = Window::new(buffer, 4..10);
+
+        assert_eq!(window.read::<i64>().unwrap(), 2);
+        assert_eq!(window.offset(), 8);
+
+        // The third value is cut off by the end of the window
+        assert_matches!(
+            window.read::<i64>(),
+            Err(WindowError {
+                offset: 8,
+                error: Error::Parse(parse::Error::UnexpectedEof(_)),
+            })
+        );
+        assert_eq!(window.remaining(), b":3");
+    }
+
+    #[test]
+    fn raw_nested() {
+        let buffer = b"xx*2\r\n:1\r\n*1\r\n+a\r\n:5\r\n";
+        let mut window = Window::with_offset(&buffer[2..], 2);
+
+        assert_eq!(window.read_raw().unwrap(), 2..18);
+        assert_eq!(window.read_raw().unwrap(), 18..22);
+        assert!(window.is_empty());
+    }
+
+    #[test]
+    fn malformed() {
+        let buffer = b":1\r\n?\r\n";
+        let mut window = Window::new(buffer, 0..buffer.len());
+
+        window.read_raw().unwrap();
+        assert_matches!(
+            window.read_raw(),
+            Err(WindowError {
+                offset: 4,
+                error: Error::Parse(parse::Error::BadTag(b'?')),
+            })
+        );
+        assert_eq!(window.offset(), 4);
+    }
+
+    #[test]
+    fn keepalives() {
+        let buffer = b"...\r\n\r\n:1\r\n";
+        let config = Config::new().with_keepalives(&[b"\r\n"]);
+        let mut window = Window::new(buffer, 3..buffer.len()).with_config(config);
+
+        assert_eq!(window.read_raw().unwrap(), 7..11);
+    }
+}
@@ -0,0 +1,432 @@
+/*!
+Formatting utilities for inspecting RESP data.
+
+When a test that compares serialized RESP against a known-good frame fails,
+the raw byte strings are usually too long to compare by eye, and the first
+differing byte offset says little about *where* in the structure the two
+frames diverge. [`diff`] parses both frames and describes the first
+structural difference between them instead.
+
+# Example
+
+```
+use seredies::fmt::diff;
+
+let expected = b"*3\r\n$3\r\nSET\r\n$3\r\nkey\r\n$5\r\nvalue\r\n";
+let actual = b"*3\r\n$3\r\nSET\r\n$3\r\nkey\r\n$5\r\nvaLue\r\n";
+
+assert_eq!(diff(expected, expected), "");
+assert_eq!(
+    diff(actual, expected),
+    r#"#0[2]: bulk string payload differs at byte 2: left "vaLue", right "value""#,
+);
+```
+*/
+
+use alloc::{format, string::String, vec::Vec};
+
+use crate::de::parse::{self, read_exact, read_header, TaggedHeader};
+use crate::protocol::MAX_BULK_LEN;
+
+/// The number of bytes of a payload to include in a preview.
+const PREVIEW_LEN: usize = 24;
+
+/// The number of bytes to include in a preview before the first differing
+/// byte, so that the difference is shown in context.
+const PREVIEW_CONTEXT: usize = 8;
+
+/**
+Parse two buffers of RESP data and describe the first structural difference
+between them, or return an empty string if they're identical.
+
+Both buffers may contain several consecutive values (such as a pipeline of
+commands). The description includes the path to the differing value, where
+`#1[0][2]` is the third element of the first element of the second top-level
+value, and describes how the two values differ: their types, their lengths,
+or their contents, with a short preview of each. If either buffer is
+malformed or truncated, the path to the problem is reported along with the
+parse error.
+
+Because identical frames produce an empty string, `diff` works well with
+`assert_eq!`, which then prints only the difference when a test fails.
+
+# Example
+
+```
+use seredies::fmt::diff;
+
+assert_eq!(
+    diff(b"*2\r\n:1\r\n:2\r\n", b"*2\r\n:1\r\n$1\r\n2\r\n"),
+    "#0[1]: type differs: left is integer 2, right is bulk string \"2\"",
+);
+
+assert_eq!(
+    diff(b"*2\r\n:1\r\n:2\r\n", b"*3\r\n:1\r\n:2\r\n:3\r\n"),
+    "#0: array length differs: left 2, right 3",
+);
+
+assert_eq!(
+    diff(b"+OK\r\n", b"+OK\r\n+OK\r\n"),
+    "#1: left ended, but right has another value: simple string \"OK\"",
+);
+```
+*/
+#[must_use]
+pub fn diff(left: &[u8], right: &[u8]) -> String {
+    let mut path = Path::default();
+    let mut left_input = left;
+    let mut right_input = right;
+
+    loop {
+        if path.is_top_level() {
+            match (left_input.is_empty(), right_input.is_empty()) {
+                (true, true) => return String::new(),
+                (true, false) => {
+                    return match read_item(right_input) {
+                        Ok((item, _)) => {
+                            format!("{path}: left ended, but right has another value: {item}")
+                        }
+                        Err(err) => format!(
+                            "{path}: left ended, but right has another value, \
+                            which is malformed: {err}"
+                        ),
+                    }
+                }
+                (false, true) => {
+                    return match read_item(left_input) {
+                        Ok((item, _)) => {
+                            format!("{path}: right ended, but left has another value: {item}")
+                        }
+                        Err(err) => format!(
+                            "{path}: right ended, but left has another value, \
+                            which is malformed: {err}"
+                        ),
+                    }
+                }
+                (false, false) => {}
+            }
+        }
+
+        let (left_item, right_item) = match (read_item(left_input), read_item(right_input)) {
+            (Ok((left_item, left_tail)), Ok((right_item, right_tail))) => {
+                left_input = left_tail;
+                right_input = right_tail;
+                (left_item, right_item)
+            }
+            (Err(err), _) => return format!("{path}: left is malformed: {err}"),
+            (_, Err(err)) => return format!("{path}: right is malformed: {err}"),
+        };
+
+        if let Some(difference) = compare(&left_item, &right_item) {
+            return format!("{path}: {difference}");
+        }
+
+        match left_item {
+            Item::Array(len) | Item::Push(len) if len > 0 => path.enter(len),
+            _ => path.advance(),
+        }
+    }
+}
+
+/// A single RESP value; for arrays, only the header is included, and the
+/// elements follow it. This is also used by the deserializer to describe the
+/// value in type mismatch errors.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Item<'a> {
+    SimpleString(&'a [u8]),
+    Error(&'a [u8]),
+    Integer(i64),
+    BulkString(&'a [u8]),
+    Array(usize),
+    Null,
+    Verbatim(&'a [u8]),
+    Push(usize),
+    BigNumber(&'a [u8]),
+}
+
+impl Item<'_> {
+    #[must_use]
+    pub(crate) fn kind(&self) -> &'static str {
+        match *self {
+            Item::SimpleString(_) => "simple string",
+            Item::Error(_) => "error",
+            Item::Integer(_) => "integer",
+            Item::BulkString(_) => "bulk string",
+            Item::Array(_) => "array",
+            Item::Null => "null",
+            Item::Verbatim(_) => "verbatim string",
+            Item::Push(_) => "push",
+            Item::BigNumber(_) => "big number",
+        }
+    }
+}
+
+impl core::fmt::Display for Item<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match *self {
+            Item::SimpleString(payload)
+            | Item::Error(payload)
+            | Item::BulkString(payload)
+            | Item::Verbatim(payload) => {
+                write!(f, "{} {}", self.kind(), Preview::new(payload, 0))
+            }
+            Item::Integer(value) => write!(f, "integer {value}"),
+            Item::BigNumber(digits) => write!(f, "big number {}", Preview::new(digits, 0)),
+            Item::Array(len) => write!(f, "array of length {len}"),
+            Item::Push(len) => write!(f, "push of length {len}"),
+            Item::Null => f.write_str("null"),
+        }
+    }
+}
+
+fn read_item(input: &[u8]) -> parse::ParseResult<'_, Item<'_>> {
+    let (header, tail) = read_header(input)?;
+
+    match header {
+        TaggedHeader::SimpleString(payload) => Ok((Item::SimpleString(payload), tail)),
+        TaggedHeader::Error(payload) => Ok((Item::Error(payload), tail)),
+        TaggedHeader::Integer(value) => Ok((Item::Integer(value), tail)),
+        TaggedHeader::BulkString(len) if (0..=MAX_BULK_LEN).contains(&len) => {
+            read_exact(len as usize, tail).map(|(payload, tail)| (Item::BulkString(payload), tail))
+        }
+        TaggedHeader::BulkString(_) => Err(parse::Error::Length),
+        TaggedHeader::Array(len) => match len.try_into() {
+            Ok(len) => Ok((Item::Array(len), tail)),
+            Err(_) => Err(parse::Error::Length),
+        },
+        TaggedHeader::Null => Ok((Item::Null, tail)),
+        TaggedHeader::Verbatim(len) if (0..=MAX_BULK_LEN).contains(&len) => {
+            read_exact(len as usize, tail).map(|(payload, tail)| (Item::Verbatim(payload), tail))
+        }
+        TaggedHeader::Verbatim(_) => Err(parse::Error::Length),
+        TaggedHeader::Push(len) => match len.try_into() {
+            Ok(len) => Ok((Item::Push(len), tail)),
+            Err(_) => Err(parse::Error::Length),
+        },
+        TaggedHeader::BigNumber(digits) => Ok((Item::BigNumber(digits), tail)),
+    }
+}
+
+/// Describe the difference between two values, not including their
+/// elements, if there is one.
+fn compare(left: &Item<'_>, right: &Item<'_>) -> Option<String> {
+    match (*left, *right) {
+        (Item::SimpleString(left_payload), Item::SimpleString(right_payload))
+        | (Item::Error(left_payload), Item::Error(right_payload))
+        | (Item::BulkString(left_payload), Item::BulkString(right_payload))
+        | (Item::Verbatim(left_payload), Item::Verbatim(right_payload)) => {
+            compare_payloads(left.kind(), left_payload, right_payload)
+        }
+        (Item::Integer(left), Item::Integer(right)) if left != right => {
+            Some(format!("integer differs: left {left}, right {right}"))
+        }
+        (Item::Array(left), Item::Array(right)) if left != right => {
+            Some(format!("array length differs: left {left}, right {right}"))
+        }
+        (Item::Push(left), Item::Push(right)) if left != right => {
+            Some(format!("push length differs: left {left}, right {right}"))
+        }
+        (Item::BigNumber(left_digits), Item::BigNumber(right_digits)) => {
+            compare_payloads(left.kind(), left_digits, right_digits)
+        }
+        (Item::Integer(_), Item::Integer(_))
+        | (Item::Array(_), Item::Array(_))
+        | (Item::Push(_), Item::Push(_))
+        | (Item::Null, Item::Null) => None,
+        _ => Some(format!("type differs: left is {left}, right is {right}")),
+    }
+}
+
+fn compare_payloads(kind: &str, left: &[u8], right: &[u8]) -> Option<String> {
+    let index = left
+        .iter()
+        .zip(right)
+        .position(|(l, r)| l != r)
+        .or_else(|| (left.len() != right.len()).then(|| left.len().min(right.len())))?;
+
+    let start = index.saturating_sub(PREVIEW_CONTEXT);
+    let left_preview = Preview::new(left, start);
+    let right_preview = Preview::new(right, start);
+
+    Some(if left.len() != right.len() {
+        format!(
+            "{kind} length differs: left {} {left_preview}, right {} {right_preview}",
+            left.len(),
+            right.len(),
+        )
+    } else {
+        format!(
+            "{kind} payload differs at byte {index}: left {left_preview}, right {right_preview}"
+        )
+    })
+}
+
+/// A short, escaped excerpt of a payload, starting at `start`.
+pub(crate) struct Preview<'a> {
+    payload: &'a [u8],
+    start: usize,
+}
+
+impl<'a> Preview<'a> {
+    #[inline]
+    #[must_use]
+    pub(crate) fn new(payload: &'a [u8], start: usize) -> Self {
+        Self { payload, start }
+    }
+}
+
+impl core::fmt::Display for Preview<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let start = self.start.min(self.payload.len());
+        let end = start.saturating_add(PREVIEW_LEN).min(self.payload.len());
+
+        if start > 0 {
+            f.write_str("...")?;
+        }
+
+        write!(f, "\"{}\"", self.payload[start..end].escape_ascii())?;
+
+        if end < self.payload.len() {
+            f.write_str("...")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The path to the value currently being compared. `value` is the index of
+/// the top-level value, and `elements` are the `(index, length)` of each
+/// array that contains the current value.
+#[derive(Debug, Default)]
+struct Path {
+    value: usize,
+    elements: Vec<(usize, usize)>,
+}
+
+impl Path {
+    #[inline]
+    #[must_use]
+    fn is_top_level(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Move to the first element of an array of length `len`.
+    #[inline]
+    fn enter(&mut self, len: usize) {
+        self.elements.push((0, len));
+    }
+
+    /// Move to the next value, leaving any arrays that have been completed.
+    fn advance(&mut self) {
+        loop {
+            match self.elements.last_mut() {
+                None => {
+                    self.value += 1;
+                    return;
+                }
+                Some((index, len)) => {
+                    *index += 1;
+
+                    if *index < *len {
+                        return;
+                    }
+
+                    self.elements.pop();
+                }
+            }
+        }
+    }
+}
+
+impl core::fmt::Display for Path {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "#{}", self.value)?;
+
+        self.elements
+            .iter()
+            .try_for_each(|(index, _)| write!(f, "[{index}]"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical() {
+        let frame = b"*3\r\n$3\r\nGET\r\n*1\r\n:-4\r\n$-1\r\n+OK\r\n";
+        assert_eq!(diff(frame, frame), "");
+        assert_eq!(diff(b"", b""), "");
+    }
+
+    #[test]
+    fn nested_integer() {
+        assert_eq!(
+            diff(
+                b"*2\r\n*2\r\n:1\r\n:2\r\n*1\r\n:3\r\n",
+                b"*2\r\n*2\r\n:1\r\n:2\r\n*1\r\n:4\r\n"
+            ),
+            "#0[1][0]: integer differs: left 3, right 4"
+        );
+    }
+
+    #[test]
+    fn null() {
+        assert_eq!(
+            diff(b"$-1\r\n", b"$0\r\n\r\n"),
+            r#"#0: type differs: left is null, right is bulk string """#
+        );
+    }
+
+    #[test]
+    fn bulk_string_length() {
+        assert_eq!(
+            diff(b"$5\r\nhello\r\n", b"$6\r\nhello!\r\n"),
+            r#"#0: bulk string length differs: left 5 "hello", right 6 "hello!""#
+        );
+    }
+
+    #[test]
+    fn long_payload_preview() {
+        let left = b"$40\r\naaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\r\n";
+        let right = b"$40\r\naaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaab\r\n";
+
+        assert_eq!(
+            diff(left, right),
+            r#"#0: bulk string payload differs at byte 39: left ..."aaaaaaaaa", right ..."aaaaaaaab""#
+        );
+    }
+
+    #[test]
+    fn escaped_preview() {
+        assert_eq!(
+            diff(b"+a\"b\r\n", b"-a\tb\r\n"),
+            r#"#0: type differs: left is simple string "a\"b", right is error "a\tb""#
+        );
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(
+            diff(b"*2\r\n:1\r\n:x\r\n", b"*2\r\n:1\r\n:2\r\n"),
+            "#0[1]: left is malformed: failed to parse a decimal integer"
+        );
+    }
+
+    #[test]
+    fn truncated() {
+        assert_eq!(
+            diff(b"*2\r\n:1\r\n:2\r\n", b"*2\r\n:1\r\n"),
+            "#0[1]: right is malformed: unexpected end of input; \
+            read at least 3 more bytes and try again"
+        );
+    }
+
+    #[test]
+    fn extra_value() {
+        assert_eq!(
+            diff(b":1\r\n*0\r\n:2\r\n", b":1\r\n*0\r\n"),
+            "#2: right ended, but left has another value: integer 2"
+        );
+    }
+}
@@ -7,6 +7,8 @@ a [`Serializer`][crate::ser::Serializer] and
 
 See the [de] and [ser] modules for examples on how to serialize and deserialize
 RESP data.
+The [value] module provides [`Value`][value::Value], an owned type that can
+hold any RESP data, for when the shape of the data isn't known ahead of time.
 
 # Faithful
 
@@ -25,9 +27,11 @@ These are the types supported by the [serializer][ser::Serializer] and
 [deserializer][de::Deserializer].
 
 - `bool` (treated as an integer 0 or 1).
-- All integers (though note that RESP only supports integers in the signed
-  64 bit range).
-- Unit (treated as null).
+- All integers (though note that RESP2 only supports integers in the signed
+  64 bit range; RESP3 big numbers can be deserialized into `i128` or `u128`,
+  or into a string of their digits).
+- Unit (treated as null). The `+OK` simple string can also be deserialized as
+  a unit; see [`UnitSimpleString`][de::UnitSimpleString].
 - Sequences, tuples, and tuple structs.
 - Bytes and string types.
     - See the [RedisString][crate::components::RedisString] component for a
@@ -37,18 +41,27 @@ These are the types supported by the [serializer][ser::Serializer] and
 - [`Result`] (see below).
 - [`Option`]: similar to JSON, an [`Option`] is handled as either a null or as
   an untagged value
-- Unit variants: these are encoded as strings.
+- Unit variants: these are encoded as strings, and can be deserialized from
+  either Bulk Strings or Simple Strings.
 
 ## Unsupported types
 
 - Floats.
     - Consider [RedisString][crate::components::RedisString] for the common
-      case that Redis is treating your float data as a string.
+      case that Redis is treating your float data as a string, or enable
+      [`Serializer::with_string_floats`][ser::Serializer::with_string_floats]
+      and [`Deserializer::with_string_floats`][de::Deserializer::with_string_floats]
+      to handle all floats as strings.
 - Maps, structs, complex enums.
     - Consider [KeyValuePairs][crate::components::KeyValuePairs] for the common
       case that your key-value data is being treated by Redis as a flattened
       array of key-value pairs.
 
+When talking to a server using RESP3, the
+[RESP3 serializer][ser::Serializer::new_resp3] additionally supports floats,
+maps, and structs, serializes `bool` as a native boolean, and serializes
+integers outside of the `i64` range as big numbers.
+
 If you're trying to serialize a Redis command, consider additionally using the
 [Command][crate::components::Command] component; it handles converting all
 of the command data into a list of strings, using conventions that follow the
@@ -68,11 +81,81 @@ Additionally, seredies ubiquitously uses the simple string "OK" to signal an
 uninteresting success. This pattern is so common that `seredies` supports
 (de)serializing it directly to an `Ok(())` [`Result`] value.
 
+`Result` and [`Option`] can be nested in either order. An
+`Option<Result<T, E>>` is `None` for a null and otherwise behaves like a
+`Result`, while a `Result<Option<T>, E>` (as you might use for `GET`) is
+`Ok(None)` for a null, `Err` for an error, and `Ok(Some(..))` for anything
+else, including "OK".
+
+# Panics
+
+The [deserializer][de::Deserializer] and the [parsers][de::parse] are designed
+to never panic, regardless of their input. All of the length and numeric
+arithmetic that depends on untrusted input is checked or saturating, and
+malformed data is always reported as an [`Error`][de::Error]. Any panics
+during a deserialize come from the `Deserialize` type itself. This is
+exercised by a fuzz-style test that feeds truncated, mutated, and random
+inputs through the deserializer.
+
+# `no_std`
+
+seredies supports `no_std` environments, as long as an allocator is
+available. Disable the default `std` feature to build without the standard
+library; this removes [`ser::to_writer`], [`ser::IoWrite`], and
+[`ser::TransactionalWrite`], along with the [`Io`][ser::Error::Io] variant of
+the serialization error, since they depend on `std::io`. Everything else,
+including the [`Deserializer`][de::Deserializer] and the
+[`Serializer`][ser::Serializer] with `Vec<u8>` or `String` output, works the
+same way in either case.
+
 [error type]: https://redis.io/docs/reference/protocol-spec/#resp-errors
 */
 
 #![deny(missing_docs)]
+#![allow(clippy::multiple_bound_locations)]
+#![cfg_attr(not(feature = "std"), no_std)]
+// Some docs link to items, like `IoWrite`, that only exist with `std`
+#![cfg_attr(not(feature = "std"), allow(rustdoc::broken_intra_doc_links))]
 
+extern crate alloc;
+
+// Lets the code generated by the derive macros, which refers to `::seredies`,
+// work inside of this crate's own tests.
+#[cfg(all(test, feature = "derive"))]
+extern crate self as seredies;
+
+#[cfg(feature = "tokio")]
+pub mod aio;
+pub mod checksum;
+#[cfg(feature = "client")]
+pub mod client;
+pub mod cluster;
+#[cfg(feature = "commands")]
+pub mod commands;
 pub mod components;
+mod config;
 pub mod de;
+pub mod fmt;
+pub mod protocol;
 pub mod ser;
+#[cfg(test)]
+mod test_util;
+pub mod util;
+pub mod value;
+
+pub use config::Config;
+
+/// Items used by the code generated by the derive macros. Not public API.
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+pub mod __private {
+    pub use serde::ser::{Serialize, SerializeSeq, Serializer};
+
+    /// The number of arguments a flag is sent as. This only accepts a
+    /// `bool`, so that other types can't be used as flags.
+    #[inline]
+    #[must_use]
+    pub fn flag_count(flag: &bool) -> usize {
+        usize::from(*flag)
+    }
+}
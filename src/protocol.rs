@@ -0,0 +1,197 @@
+/*!
+Constants and definitions from the
+[RESP specification](https://redis.io/docs/reference/protocol-spec/).
+
+These are used internally by both the serializer and the deserializer, and
+are exposed for authors of lower level protocol tooling.
+
+# Example
+
+```
+use seredies::protocol::{Tag, NULL, OK};
+
+assert_eq!(Tag::from_byte(b'*'), Some(Tag::Array));
+assert_eq!(Tag::BulkString.byte(), b'$');
+
+assert_eq!(seredies::ser::to_string(&()).unwrap(), NULL);
+assert_eq!(seredies::ser::to_string(&Ok::<(), String>(())).unwrap(), OK);
+```
+*/
+
+/// The maximum length of a bulk string, in bytes (512 MB). Longer bulk
+/// strings are rejected as malformed, so every valid length fits in a
+/// `usize`, even on 32-bit targets.
+pub const MAX_BULK_LEN: i64 = 512 * 1024 * 1024;
+
+/// The line terminator used by every RESP value.
+pub const CRLF: &str = "\r\n";
+
+/// The simple string `OK`, which is the typical successful reply to commands
+/// that don't return any other data.
+pub const OK: &str = "+OK\r\n";
+
+/// The `PING` command, as sent by a client. Some proxies and servers also
+/// send it in the middle of a stream of replies, as a keepalive.
+pub const PING: &str = "*1\r\n$4\r\nPING\r\n";
+
+/// The simple string `PONG`, which is the reply to `PING`.
+pub const PONG: &str = "+PONG\r\n";
+
+/// The null bulk string, which is how RESP represents the absence of a value.
+pub const NULL: &str = "$-1\r\n";
+
+/// The null array, which RESP2 uses for some replies that would otherwise be
+/// arrays, like `EXEC` for an aborted transaction.
+pub const NULL_ARRAY: &str = "*-1\r\n";
+
+/// The RESP3 [null](https://redis.io/docs/reference/protocol-spec/#nulls),
+/// which replaces the null bulk string and null array.
+pub const RESP3_NULL: &str = "_\r\n";
+
+/// A version of the RESP protocol. RESP3 is a superset of RESP2 that adds
+/// native types like maps, sets, doubles, and booleans. Clients opt in to
+/// RESP3 with the `HELLO 3` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum Version {
+    /// RESP2, which is supported by every Redis server.
+    #[default]
+    Resp2,
+
+    /// RESP3, introduced in Redis 6.
+    Resp3,
+}
+
+/// The tag byte at the start of every RESP value, which identifies its type.
+///
+/// Tags from RESP3 are included; more may be added in the future.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+#[non_exhaustive]
+pub enum Tag {
+    /// `+`, a [Simple String](https://redis.io/docs/reference/protocol-spec/#resp-simple-strings)
+    SimpleString = b'+',
+
+    /// `-`, an [Error](https://redis.io/docs/reference/protocol-spec/#resp-errors)
+    Error = b'-',
+
+    /// `:`, an [Integer](https://redis.io/docs/reference/protocol-spec/#resp-integers)
+    Integer = b':',
+
+    /// `$`, a [Bulk String](https://redis.io/docs/reference/protocol-spec/#resp-bulk-strings)
+    BulkString = b'$',
+
+    /// `*`, an [Array](https://redis.io/docs/reference/protocol-spec/#resp-arrays)
+    Array = b'*',
+
+    /// `_`, a RESP3 [Null](https://redis.io/docs/reference/protocol-spec/#nulls)
+    Null = b'_',
+
+    /// `#`, a RESP3 [Boolean](https://redis.io/docs/reference/protocol-spec/#booleans)
+    Boolean = b'#',
+
+    /// `,`, a RESP3 [Double](https://redis.io/docs/reference/protocol-spec/#doubles)
+    Double = b',',
+
+    /// `%`, a RESP3 [Map](https://redis.io/docs/reference/protocol-spec/#maps)
+    Map = b'%',
+
+    /// `~`, a RESP3 [Set](https://redis.io/docs/reference/protocol-spec/#sets)
+    Set = b'~',
+
+    /// `=`, a RESP3 [Verbatim String](https://redis.io/docs/reference/protocol-spec/#verbatim-strings)
+    Verbatim = b'=',
+
+    /// `>`, a RESP3 [Push](https://redis.io/docs/reference/protocol-spec/#pushes)
+    Push = b'>',
+
+    /// `(`, a RESP3 [Big Number](https://redis.io/docs/reference/protocol-spec/#big-numbers)
+    BigNumber = b'(',
+}
+
+impl Tag {
+    /// Get the tag for a tag byte, or `None` if it isn't a RESP tag byte.
+    #[inline]
+    #[must_use]
+    pub const fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            b'+' => Some(Tag::SimpleString),
+            b'-' => Some(Tag::Error),
+            b':' => Some(Tag::Integer),
+            b'$' => Some(Tag::BulkString),
+            b'*' => Some(Tag::Array),
+            b'_' => Some(Tag::Null),
+            b'#' => Some(Tag::Boolean),
+            b',' => Some(Tag::Double),
+            b'%' => Some(Tag::Map),
+            b'~' => Some(Tag::Set),
+            b'=' => Some(Tag::Verbatim),
+            b'>' => Some(Tag::Push),
+            b'(' => Some(Tag::BigNumber),
+            _ => None,
+        }
+    }
+
+    /// Get the byte for this tag.
+    #[inline]
+    #[must_use]
+    pub const fn byte(self) -> u8 {
+        self as u8
+    }
+
+    /// Get the byte for this tag, as a string.
+    #[inline]
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Tag::SimpleString => "+",
+            Tag::Error => "-",
+            Tag::Integer => ":",
+            Tag::BulkString => "$",
+            Tag::Array => "*",
+            Tag::Null => "_",
+            Tag::Boolean => "#",
+            Tag::Double => ",",
+            Tag::Map => "%",
+            Tag::Set => "~",
+            Tag::Verbatim => "=",
+            Tag::Push => ">",
+            Tag::BigNumber => "(",
+        }
+    }
+}
+
+impl From<Tag> for u8 {
+    #[inline]
+    fn from(tag: Tag) -> Self {
+        tag.byte()
+    }
+}
+
+impl TryFrom<u8> for Tag {
+    type Error = u8;
+
+    /// Convert a byte to a tag. The error contains the unrecognized byte.
+    #[inline]
+    fn try_from(byte: u8) -> Result<Self, u8> {
+        Tag::from_byte(byte).ok_or(byte)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tag_round_trip() {
+        for byte in 0..=u8::MAX {
+            match Tag::from_byte(byte) {
+                Some(tag) => {
+                    assert_eq!(tag.byte(), byte);
+                    assert_eq!(tag.as_str().as_bytes(), [byte]);
+                }
+                None => assert!(!b"+-:$*_#,%~=>(".contains(&byte)),
+            }
+        }
+    }
+}
@@ -78,73 +78,274 @@ assert_eq!(
 ```
 */
 
+#[cfg(feature = "futures-io")]
+mod async_io;
+mod inline;
 mod output;
 mod primitives;
+mod raw;
 pub mod util;
 
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+#[cfg(feature = "std")]
 use std::io;
 
 use arrayvec::ArrayString;
 use paste::paste;
 use serde::ser;
-use thiserror::Error;
 
-pub use self::output::{IoWrite, Output};
+#[cfg(feature = "futures-io")]
+pub use self::async_io::to_async_writer;
+pub use self::inline::{to_inline_string, InlineSerializer, SerializeInline};
+use self::output::SizeCounter;
+#[cfg(feature = "diagnostics")]
+pub use self::output::{CountingVec, OutputStats};
+pub use self::output::{FmtWrite, Output, Truncate};
+#[cfg(feature = "std")]
+pub use self::output::{IoWrite, TransactionalWrite};
+use self::primitives::{Prereserved, Writable};
+use self::raw::RawValueSerializer;
+pub(crate) use self::raw::RAW_VALUE_TOKEN;
 use self::util::TupleSeqAdapter;
+use crate::{
+    components::{CommandLike, VERBATIM_TOKEN},
+    protocol::{self, Version},
+    Config,
+};
 
 /// Serialize an object as a RESP byte buffer.
 pub fn to_vec<T>(data: &T) -> Result<Vec<u8>, Error>
+where
+    T: ser::Serialize + ?Sized,
+{
+    to_vec_with_config(data, Config::new())
+}
+
+/// Serialize an object as a RESP byte buffer, using the given [`Config`].
+pub fn to_vec_with_config<T>(data: &T, config: Config) -> Result<Vec<u8>, Error>
 where
     T: ser::Serialize + ?Sized,
 {
     let mut buffer = Vec::new();
-    let serializer = Serializer::new(&mut buffer);
+    let serializer = Serializer::with_config(&mut buffer, config);
     data.serialize(serializer)?;
     Ok(buffer)
 }
 
 /// Serialize an object as a RESP byte buffer in a [`String`].
 ///
-/// Note that RESP is a binary protocol, so if there is any non-UTF-8
-/// data in `data`, the serialization will fail with
-/// [`Error::Utf8Encode`]. Most data should be fine, though.
+/// For data that is entirely UTF-8, this produces exactly the same bytes as
+/// [`to_vec`]. Note that RESP is a binary protocol, so if there is any
+/// non-UTF-8 data in `data`, the serialization will fail with
+/// [`Error::Utf8Encode`]; it never replaces or escapes invalid data. If you
+/// need a lossy rendering of binary data (for instance, for logging), use
+/// [`to_vec`] followed by [`String::from_utf8_lossy`].
 pub fn to_string<T>(data: &T) -> Result<String, Error>
+where
+    T: ser::Serialize + ?Sized,
+{
+    to_string_with_config(data, Config::new())
+}
+
+/// Serialize an object as a RESP byte buffer in a [`String`], using the given
+/// [`Config`]. See [`to_string`] for details.
+pub fn to_string_with_config<T>(data: &T, config: Config) -> Result<String, Error>
 where
     T: ser::Serialize + ?Sized,
 {
     let mut buffer = String::new();
-    let serializer = Serializer::new(&mut buffer);
+    let serializer = Serializer::with_config(&mut buffer, config);
     data.serialize(serializer)?;
     Ok(buffer)
 }
 
+/**
+Compute the number of bytes that an object would be serialized to, without
+writing them anywhere. This can be used to allocate a buffer of exactly the
+right size, or to enforce a limit on the size of outgoing messages before
+building them.
+
+This runs the whole serialization, so it fails in the same cases as
+[`to_vec`].
+
+# Example
+
+```
+use seredies::ser::{size_of, to_vec};
+
+let command = ("SET", "key", "value");
+assert_eq!(size_of(&command).unwrap(), to_vec(&command).unwrap().len());
+assert_eq!(size_of(&command).unwrap(), 33);
+```
+*/
+pub fn size_of<T>(data: &T) -> Result<usize, Error>
+where
+    T: ser::Serialize + ?Sized,
+{
+    size_of_with_config(data, Config::new())
+}
+
+/// Compute the number of bytes that an object would be serialized to, using
+/// the given [`Config`]. See [`size_of`] for details.
+pub fn size_of_with_config<T>(data: &T, config: Config) -> Result<usize, Error>
+where
+    T: ser::Serialize + ?Sized,
+{
+    let mut counter = SizeCounter::default();
+    let serializer = Serializer::with_config(&mut counter, config);
+    data.serialize(serializer)?;
+    Ok(counter.size)
+}
+
+/**
+Serialize an object, appending it to the end of an existing buffer, such as
+a `Vec<u8>` that's reused for many values. If serialization fails, the
+buffer is [truncated][Truncate::truncate] back to its original length, so
+it contains either the complete value or nothing at all from it. See
+[`Serializer`] for why this isn't the case in general.
+
+# Example
+
+```
+use seredies::ser::to_buffer;
+
+let mut buffer = b"*1\r\n$4\r\nPING\r\n".to_vec();
+
+// Serializing a float fails on the third element, after the array header
+// and the first two elements were written
+assert!(to_buffer(&("ZADD", "key", 1.5, "member"), &mut buffer).is_err());
+assert_eq!(buffer, b"*1\r\n$4\r\nPING\r\n");
+
+to_buffer(&("GET", "key"), &mut buffer).unwrap();
+assert_eq!(buffer, b"*1\r\n$4\r\nPING\r\n*2\r\n$3\r\nGET\r\n$3\r\nkey\r\n");
+```
+*/
+pub fn to_buffer<T, O>(data: &T, buffer: &mut O) -> Result<(), Error>
+where
+    T: ser::Serialize + ?Sized,
+    O: Truncate,
+{
+    to_buffer_with_config(data, buffer, Config::new())
+}
+
+/// Serialize an object, appending it to the end of an existing buffer, using
+/// the given [`Config`]. See [`to_buffer`] for details.
+pub fn to_buffer_with_config<T, O>(data: &T, buffer: &mut O, config: Config) -> Result<(), Error>
+where
+    T: ser::Serialize + ?Sized,
+    O: Truncate,
+{
+    let len = buffer.len();
+
+    data.serialize(Serializer::with_config(buffer, config))
+        .inspect_err(|_| buffer.truncate(len))
+}
+
 /// Serialize an object as RESP data to an [`io::Write`] destination, such as a
 /// [`File`][std::fs::File].
+///
+/// Data is written as it's serialized, so if an error occurs, part of the
+/// value may already have been written; see [`IoWrite`] for details.
+#[cfg(feature = "std")]
 pub fn to_writer<T>(data: &T, dest: impl io::Write) -> Result<(), Error>
+where
+    T: ser::Serialize + ?Sized,
+{
+    to_writer_with_config(data, dest, Config::new())
+}
+
+/// Serialize an object as RESP data to an [`io::Write`] destination, using
+/// the given [`Config`].
+#[cfg(feature = "std")]
+pub fn to_writer_with_config<T>(data: &T, dest: impl io::Write, config: Config) -> Result<(), Error>
 where
     T: ser::Serialize + ?Sized,
 {
     let mut dest = IoWrite(dest);
-    let serializer = Serializer::new(&mut dest);
+    let serializer = Serializer::with_config(&mut dest, config);
     data.serialize(serializer)
 }
 
+/**
+Serialize each of the `values`, such as a pipeline of commands, as
+consecutive RESP values in a single byte buffer.
+
+The values are serialized twice: first with [`size_of`], to find the exact
+size of the whole pipeline, and then into a buffer allocated with exactly
+that capacity. For a large pipeline, this avoids both the repeated
+reallocations of a growing buffer and the wasted space of an estimate.
+
+# Example
+
+```
+use seredies::ser::to_vec_all;
+
+let pipeline = [("GET", "key"), ("DEL", "key")];
+
+assert_eq!(
+    to_vec_all(&pipeline).unwrap(),
+    b"\
+        *2\r\n$3\r\nGET\r\n$3\r\nkey\r\n\
+        *2\r\n$3\r\nDEL\r\n$3\r\nkey\r\n\
+    ",
+);
+```
+*/
+pub fn to_vec_all<T>(values: &[T]) -> Result<Vec<u8>, Error>
+where
+    T: ser::Serialize,
+{
+    let size = values.iter().try_fold(0usize, |size, value| {
+        size_of(value).map(|value_size| size.saturating_add(value_size))
+    })?;
+
+    let mut buffer = Vec::with_capacity(size);
+
+    values
+        .iter()
+        .try_for_each(|value| value.serialize(Serializer::new(&mut buffer)))?;
+
+    Ok(buffer)
+}
+
+/// Serialize a batch of commands, such as a pipeline, as consecutive RESP
+/// arrays in a byte buffer. See [`CommandLike`] for an example.
+pub fn batch_to_vec<I>(commands: I) -> Result<Vec<u8>, Error>
+where
+    I: IntoIterator,
+    I::Item: CommandLike,
+{
+    let mut buffer = Vec::new();
+
+    commands
+        .into_iter()
+        .try_for_each(|command| command.write_command(&mut buffer))?;
+
+    Ok(buffer)
+}
+
 /// When serializing `Ok(())`, we prefer to serialize it as `"+OK\r\n"`
 /// instead of as a null. This trait switches the behavior for serializing a
 /// unit, allowing for this behavior
 trait UnitBehavior: Sized {
     #[must_use]
-    fn unit_payload(self) -> &'static str;
+    fn unit_payload(self, version: Version) -> &'static str;
 }
 
-/// Serialize a unit as `"$-1\r\n"`
+/// Serialize a unit as `"$-1\r\n"` (or `"_\r\n"` in RESP3)
 struct NullUnit;
 
 impl UnitBehavior for NullUnit {
     #[inline(always)]
-    #[must_use]
-    fn unit_payload(self) -> &'static str {
-        "$-1\r\n"
+    fn unit_payload(self, version: Version) -> &'static str {
+        match version {
+            Version::Resp2 => protocol::NULL,
+            Version::Resp3 => protocol::RESP3_NULL,
+        }
     }
 }
 
@@ -153,9 +354,8 @@ struct ResultOkUnit;
 
 impl UnitBehavior for ResultOkUnit {
     #[inline(always)]
-    #[must_use]
-    fn unit_payload(self) -> &'static str {
-        "+OK\r\n"
+    fn unit_payload(self, _version: Version) -> &'static str {
+        protocol::OK
     }
 }
 
@@ -203,6 +403,29 @@ macro_rules! forward {
 /// A single `Serializer` can be used to serialize at most one RESP value. They
 /// are trivially cheap to create, though, so a new `Serializer` can be used
 /// for each additional value.
+///
+/// By default, arrays may be nested arbitrarily deeply. Some servers reject
+/// deeply nested data, so a limit can be set with
+/// [`with_max_depth`][Serializer::with_max_depth].
+///
+/// RESP2 has no floating point type, so floats are rejected unless
+/// [`with_string_floats`][Serializer::with_string_floats] is used to
+/// serialize them as bulk strings.
+///
+/// All of these options can also be set at once with a [`Config`], using
+/// [`with_config`][Serializer::with_config].
+///
+/// A serializer created with [`new_resp3`][Serializer::new_resp3] uses the
+/// additional types from RESP3.
+///
+/// The `Serializer` writes each piece of a value to the output as soon as
+/// it's serialized, and never takes anything back. If serialization fails
+/// partway through a value, such as on an unsupported element in the middle
+/// of an array, whatever was written before the error (including the array
+/// header) stays in the output, which makes it unusable for further RESP
+/// data. To either write a complete value or leave the output untouched,
+/// use [`to_buffer`] with a [`Truncate`] output like a `Vec<u8>`, or
+/// [`TransactionalWrite`] for an [`io::Write`] destination.
 pub struct Serializer<'a, O> {
     inner: BaseSerializer<'a, O, NullUnit>,
 }
@@ -216,8 +439,197 @@ where
     #[inline]
     #[must_use]
     pub fn new(writer: &'a mut O) -> Self {
+        Self::with_config(writer, Config::new())
+    }
+
+    /// Create a new RESP serializer with the given [`Config`], which will
+    /// write the serialized data to the given writer.
+    #[inline]
+    #[must_use]
+    pub fn with_config(writer: &'a mut O, config: Config) -> Self {
+        let depth = match config.max_depth {
+            Some(limit) => Depth::new(limit),
+            None => Depth::UNLIMITED,
+        };
+
+        Self {
+            inner: BaseSerializer::new(writer, depth, config.version)
+                .with_string_floats(config.string_floats)
+                .with_ok_as_null(config.ok_as_null)
+                .with_reserve(!config.no_reserve),
+        }
+    }
+
+    /**
+    Create a new RESP3 serializer that will write the serialized data to the
+    given writer. In addition to everything supported by the RESP2
+    serializer, it supports:
+
+    - Floats, which are serialized as [doubles].
+    - `bool`, which is serialized as a [boolean] instead of as an integer.
+    - Maps and structs, which are serialized as [maps]. Struct field names
+      are serialized as bulk strings.
+    - [`RedisSet`][crate::components::RedisSet], which is serialized as a
+      [set].
+
+    Nulls are serialized as the RESP3 [null] `_\r\n`.
+
+    # Example
+
+    ```
+    use std::collections::BTreeMap;
+
+    use serde::Serialize;
+    use seredies::ser::Serializer;
+
+    let mut buffer = Vec::new();
+    let data = BTreeMap::from([("pi", 3.5), ("e", 2.75)]);
+    data.serialize(Serializer::new_resp3(&mut buffer)).unwrap();
+
+    assert_eq!(buffer, b"%2\r\n$1\r\ne\r\n,2.75\r\n$2\r\npi\r\n,3.5\r\n");
+    ```
+
+    [doubles]: https://redis.io/docs/reference/protocol-spec/#doubles
+    [boolean]: https://redis.io/docs/reference/protocol-spec/#booleans
+    [maps]: https://redis.io/docs/reference/protocol-spec/#maps
+    [set]: https://redis.io/docs/reference/protocol-spec/#sets
+    [null]: https://redis.io/docs/reference/protocol-spec/#nulls
+    */
+    #[inline]
+    #[must_use]
+    pub fn new_resp3(writer: &'a mut O) -> Self {
+        Self::with_config(writer, Config::new().with_version(Version::Resp3))
+    }
+
+    /**
+    Limit the nesting depth of arrays produced by this serializer. A
+    top-level array has a depth of 1, an array inside of it has a depth of
+    2, and so on; attempting to exceed the limit is an
+    [`Error::DepthLimit`].
+
+    # Example
+
+    ```
+    use serde::Serialize;
+    use seredies::ser::{Error, Serializer};
+
+    let mut buffer = Vec::new();
+    let data = [[1, 2], [3, 4]];
+
+    let result = data.serialize(Serializer::new(&mut buffer).with_max_depth(1));
+    assert!(matches!(result, Err(Error::DepthLimit(1))));
+
+    buffer.clear();
+    data.serialize(Serializer::new(&mut buffer).with_max_depth(2)).unwrap();
+    assert_eq!(buffer, b"*2\r\n*2\r\n:1\r\n:2\r\n*2\r\n:3\r\n:4\r\n");
+    ```
+    */
+    #[inline]
+    #[must_use]
+    pub fn with_max_depth(self, limit: usize) -> Self {
+        Self {
+            inner: BaseSerializer {
+                depth: Depth::new(limit),
+                ..self.inner
+            },
+        }
+    }
+
+    /**
+    Serialize `f32` and `f64` as bulk strings containing their decimal
+    representation, rather than returning [`Error::UnsupportedType`]. Redis
+    commands that take floating point arguments, like `ZADD` and
+    `INCRBYFLOAT`, accept them in this form. Infinities are serialized as
+    `+inf` and `-inf`, and NaN, which Redis rejects, is an
+    [`Error::UnsupportedType`]. This has no effect on a RESP3 serializer,
+    which always serializes floats as doubles.
+
+    # Example
+
+    ```
+    use serde::Serialize;
+    use seredies::ser::{Error, Serializer};
+
+    let mut buffer = Vec::new();
+    let data = ("ZADD", "scores", 1.5, "alice");
+
+    let result = data.serialize(Serializer::new(&mut buffer));
+    assert!(matches!(result, Err(Error::UnsupportedType("f64"))));
+
+    buffer.clear();
+    data.serialize(Serializer::new(&mut buffer).with_string_floats(true))
+        .unwrap();
+    assert_eq!(
+        buffer,
+        b"*4\r\n$4\r\nZADD\r\n$6\r\nscores\r\n$3\r\n1.5\r\n$5\r\nalice\r\n"
+    );
+    ```
+    */
+    #[inline]
+    #[must_use]
+    pub fn with_string_floats(self, enabled: bool) -> Self {
+        Self {
+            inner: self.inner.with_string_floats(enabled),
+        }
+    }
+
+    /**
+    Serialize `Ok(())` as a null, like any other unit, rather than as the
+    Simple String `+OK`. By default, `Ok(())` is serialized as `+OK`, which
+    is how Redis replies to commands like `SET`; disable that for data
+    where the `Result` doesn't represent a reply.
+
+    # Example
+
+    ```
+    use serde::Serialize;
+    use seredies::ser::Serializer;
+
+    let mut buffer = Vec::new();
+    Ok::<(), String>(()).serialize(Serializer::new(&mut buffer)).unwrap();
+    assert_eq!(buffer, b"+OK\r\n");
+
+    buffer.clear();
+    Ok::<(), String>(())
+        .serialize(Serializer::new(&mut buffer).with_ok_as_null(true))
+        .unwrap();
+    assert_eq!(buffer, b"$-1\r\n");
+    ```
+    */
+    #[inline]
+    #[must_use]
+    pub fn with_ok_as_null(self, enabled: bool) -> Self {
+        Self {
+            inner: self.inner.with_ok_as_null(enabled),
+        }
+    }
+
+    /**
+    Enable or disable space reservation. By default, the serializer
+    estimates the size of each value before writing it, and passes the
+    estimate to [`Output::reserve`], so that a growable output like a
+    `Vec<u8>` reallocates less often. The estimates for arrays assume a
+    small, fixed size for each element; disable reservation when they're a
+    poor fit for the data, or when the output already has enough capacity.
+
+    # Example
+
+    ```
+    use serde::Serialize;
+    use seredies::ser::Serializer;
+
+    let mut buffer = Vec::new();
+    ["a", "b"]
+        .serialize(Serializer::new(&mut buffer).with_reserve(false))
+        .unwrap();
+    assert_eq!(buffer, b"*2\r\n$1\r\na\r\n$1\r\nb\r\n");
+    ```
+    */
+    #[inline]
+    #[must_use]
+    pub fn with_reserve(self, enabled: bool) -> Self {
         Self {
-            inner: BaseSerializer::new(writer),
+            inner: self.inner.with_reserve(enabled),
         }
     }
 }
@@ -233,8 +645,8 @@ where
     type SerializeTuple = TupleSeqAdapter<SerializeSeq<'a, O>>;
     type SerializeTupleStruct = TupleSeqAdapter<SerializeSeq<'a, O>>;
 
-    type SerializeMap = ser::Impossible<(), Error>;
-    type SerializeStruct = ser::Impossible<(), Error>;
+    type SerializeMap = SerializeMap<'a, O>;
+    type SerializeStruct = SerializeMap<'a, O>;
 
     type SerializeStructVariant = ser::Impossible<(), Error>;
     type SerializeTupleVariant = ser::Impossible<(), Error>;
@@ -304,15 +716,62 @@ where
     #[inline]
     fn collect_str<T: ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error>
     where
-        T: std::fmt::Display,
+        T: fmt::Display,
     {
         self.inner.collect_str(value)
     }
 }
 
+/// Tracks how many more levels of array nesting are permitted, along with
+/// the configured limit (for error reporting).
+#[derive(Debug, Clone, Copy)]
+struct Depth {
+    remaining: usize,
+    limit: usize,
+}
+
+impl Depth {
+    const UNLIMITED: Self = Self::new(usize::MAX);
+
+    #[inline]
+    #[must_use]
+    const fn new(limit: usize) -> Self {
+        Self {
+            remaining: limit,
+            limit,
+        }
+    }
+
+    /// Descend into a nested array, or return an error if that would exceed
+    /// the limit.
+    #[inline]
+    fn enter(self) -> Result<Self, Error> {
+        match self.remaining.checked_sub(1) {
+            Some(remaining) => Ok(Self { remaining, ..self }),
+            None => Err(Error::DepthLimit(self.limit)),
+        }
+    }
+}
+
 struct BaseSerializer<'a, O, U> {
     output: &'a mut O,
     unit: U,
+    depth: Depth,
+    version: Version,
+
+    /// If true, RESP2 floats are serialized as bulk strings rather than
+    /// rejected.
+    string_floats: bool,
+
+    /// If true, `Ok(())` is serialized as a null rather than as `+OK`.
+    ok_as_null: bool,
+
+    /// If false, nothing is reserved in the output before writing.
+    reserve: bool,
+
+    /// The number of bytes that the parent of this value already reserved
+    /// for it, which is subtracted from this value's own reservation.
+    reserved: usize,
 }
 
 impl<'a, O> BaseSerializer<'a, O, NullUnit>
@@ -321,10 +780,16 @@ where
 {
     #[inline]
     #[must_use]
-    pub fn new(writer: &'a mut O) -> Self {
+    fn new(writer: &'a mut O, depth: Depth, version: Version) -> Self {
         Self {
             output: writer,
             unit: NullUnit,
+            depth,
+            version,
+            string_floats: false,
+            ok_as_null: false,
+            reserve: true,
+            reserved: 0,
         }
     }
 }
@@ -335,31 +800,123 @@ where
 {
     #[inline]
     #[must_use]
-    pub fn new_ok(writer: &'a mut O) -> Self {
+    fn new_ok(writer: &'a mut O, depth: Depth, version: Version) -> Self {
         Self {
             output: writer,
             unit: ResultOkUnit,
+            depth,
+            version,
+            string_floats: false,
+            ok_as_null: false,
+            reserve: true,
+            reserved: 0,
+        }
+    }
+}
+
+impl<'a, O, U> BaseSerializer<'a, O, U>
+where
+    O: Output,
+{
+    /// Note that the parent of this value already reserved `reserved` bytes
+    /// for it.
+    #[inline]
+    #[must_use]
+    fn prereserved(self, reserved: usize) -> Self {
+        Self { reserved, ..self }
+    }
+
+    #[inline]
+    #[must_use]
+    fn with_string_floats(self, string_floats: bool) -> Self {
+        Self {
+            string_floats,
+            ..self
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    fn with_ok_as_null(self, ok_as_null: bool) -> Self {
+        Self { ok_as_null, ..self }
+    }
+
+    #[inline]
+    #[must_use]
+    fn with_reserve(self, reserve: bool) -> Self {
+        Self { reserve, ..self }
+    }
+
+    /// Get the output, for writing this value in its entirety.
+    #[inline]
+    #[must_use]
+    fn into_output(self) -> Prereserved<&'a mut O> {
+        Prereserved::new(self.output, self.reserved, self.reserve)
+    }
+
+    fn serialize_resp3_map(self, len: usize) -> Result<SerializeMap<'a, O>, Error> {
+        let depth = self.depth.enter()?;
+        primitives::serialize_map_header(
+            Prereserved::new(&mut *self.output, self.reserved, self.reserve),
+            len,
+        )?;
+        Ok(SerializeMap::new(
+            self.output,
+            len,
+            depth,
+            self.ok_as_null,
+            self.reserve,
+        ))
+    }
+
+    /// Serialize an integer that might not fit in an `i64`. If it doesn't,
+    /// it's serialized as a RESP3 big number, which RESP2 doesn't have.
+    fn serialize_wide_number<T>(self, value: T) -> Result<(), Error>
+    where
+        T: TryInto<i64> + fmt::Display + Copy,
+    {
+        match (value.try_into(), self.version) {
+            (Ok(value), _) => primitives::serialize_number(self.into_output(), value),
+            (Err(_), Version::Resp3) => primitives::serialize_big_number(self.into_output(), value),
+            (Err(_), Version::Resp2) => Err(Error::NumberOutOfRange),
         }
     }
 }
 
+/// The newtype struct name used by [`RedisSet`][crate::components::RedisSet]
+/// to request that its contents be serialized as a RESP3 set.
+pub(crate) const SET_TOKEN: &str = "$seredies::RedisSet";
+
+/// The newtype struct name used by [`Value`][crate::value::Value] and
+/// [`SimpleString`][crate::components::SimpleString] to request that a
+/// string be serialized as a RESP Simple String, rather than a Bulk String.
+pub(crate) const SIMPLE_STRING_TOKEN: &str = "$seredies::SimpleString";
+
+/// The newtype struct name used by
+/// [`ExecResponse`][crate::components::ExecResponse] to request that a null
+/// be serialized as a RESP2 null array, rather than a null Bulk String.
+pub(crate) const NULL_ARRAY_TOKEN: &str = "$seredies::NullArray";
+
+/// The result of serializing to RESP. Like [`de::Result`][crate::de::Result],
+/// this keeps signatures stable if the error type changes shape.
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+
 /// Errors that can occur during serialization.
-#[derive(Debug, Error)]
+#[derive(Debug)]
 #[non_exhaustive]
 pub enum Error {
     /// Certain types can't be serialized. The argument contains the kind of
     /// type that failed to serialize.
-    #[error("can't serialize {0}")]
     UnsupportedType(&'static str),
 
     /// Attempted to serialize a number that was outside the range of a signed
-    /// 64 bit integer. Redis integers always fit in this range.
+    /// 64 bit integer. Redis integers always fit in this range. (RESP3
+    /// serializers write larger values as big numbers instead.)
     ///
     /// Don't forget that Redis commands are always a list of strings, even when
     /// they contain numeric data. Consider using
     /// [`RedisString`][crate::components::RedisString] or
     /// [`Command`][crate::components::Command] in this case.
-    #[error("can't serialize numbers outside the range of a signed 64 bit integer")]
     NumberOutOfRange,
 
     /// Redis arrays are length-prefixed; they must know the length ahead of
@@ -367,13 +924,11 @@ pub enum Error {
     /// length. Consider using [`Command`][crate::components::Command] if you're
     /// trying to serialize a Redis command, as it automatically handles
     /// efficiently computing the length of the array (without allocating).
-    #[error("can't serialize sequences of unknown length")]
     UnknownSeqLength,
 
     /// Attempted to serialize too many or too few sequence elements. This error
     /// occurs when the number of serialized array elements differed from the
     /// prefix-reported length of the array.
-    #[error("attempted to serialize too many or too few sequence elements")]
     BadSeqLength,
 
     /// Attempted to serialize a RESP [Simple String] or [Error] that contained
@@ -382,38 +937,112 @@ pub enum Error {
     /// [Simple String]:
     ///     https://redis.io/docs/reference/protocol-spec/#resp-simple-strings
     /// [Error]: https://redis.io/docs/reference/protocol-spec/#resp-errors
-    #[error("attempted to serialize a Simple String that contained a \\r or \\n")]
     BadSimpleString,
 
     /// There was an i/o error during serialization. Generally this can only
     /// happen when serializing to a "real" i/o device, like a file.
-    #[error("i/o error during serialization")]
-    Io(#[from] io::Error),
+    #[cfg(feature = "std")]
+    Io(io::Error),
 
     /// The data being serialized encountered some kind of error, separate from
     /// the RESP protocol.
-    #[error("error from Serialize type: {0}")]
     Custom(String),
 
     /// Attempted to serialize something other than a string, bytes, or unit
-    /// enum as a RESP [Error].
+    /// enum (or a pair of them, like an error code and message) as a RESP
+    /// [Error].
     ///
     /// [Error]: https://redis.io/docs/reference/protocol-spec/#resp-errors
-    #[error("invalid payload for a Result::Err. Must be a string or simple enum")]
     InvalidErrorPayload,
 
     /// Attempted to encode non-UTF-8 data. This error can only occur when the
     /// [`Output`] type must be UTF-8 data (such as a [`String`]); most output
     /// types can accept arbitrary bytes.
-    #[error("attempted to encode non-UTF-8 data to a string-like destination")]
     Utf8Encode,
+
+    /// Attempted to serialize arrays nested more deeply than the limit set
+    /// with [`Serializer::with_max_depth`]. The argument contains the limit.
+    DepthLimit(usize),
+
+    /// Attempted to serialize a [`Verbatim`][crate::components::Verbatim]
+    /// string whose format wasn't exactly 3 bytes, or whose format or content
+    /// wasn't a string or bytes.
+    InvalidVerbatimPayload,
+
+    /// The [`Output`] ran out of space, such as when a fixed-size buffer
+    /// like an [`ArrayVec`][arrayvec::ArrayVec] is full, or when the
+    /// destination of a [`FmtWrite`] returned an error.
+    BufferFull,
+
+    /// A [`Display`][fmt::Display] implementation returned an error while it
+    /// was being serialized as a string, such as with
+    /// [`collect_str`][ser::Serializer::collect_str].
+    Format,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Error::UnsupportedType(kind) => write!(f, "can't serialize {kind}"),
+            Error::NumberOutOfRange => {
+                f.write_str("can't serialize numbers outside the range of a signed 64 bit integer")
+            }
+            Error::UnknownSeqLength => f.write_str("can't serialize sequences of unknown length"),
+            Error::BadSeqLength => {
+                f.write_str("attempted to serialize too many or too few sequence elements")
+            }
+            Error::BadSimpleString => {
+                f.write_str("attempted to serialize a Simple String that contained a \\r or \\n")
+            }
+            #[cfg(feature = "std")]
+            Error::Io(_) => f.write_str("i/o error during serialization"),
+            Error::Custom(ref msg) => write!(f, "error from Serialize type: {msg}"),
+            Error::InvalidErrorPayload => f.write_str(
+                "invalid payload for a Result::Err. \
+                Must be a string, simple enum, or a pair of them",
+            ),
+            Error::Utf8Encode => {
+                f.write_str("attempted to encode non-UTF-8 data to a string-like destination")
+            }
+            Error::DepthLimit(limit) => {
+                write!(
+                    f,
+                    "arrays were nested more deeply than the limit of {limit}"
+                )
+            }
+            Error::InvalidVerbatimPayload => f.write_str(
+                "invalid payload for a Verbatim String. \
+                Must be a 3 byte format and a string",
+            ),
+            Error::BufferFull => f.write_str("the output buffer is full"),
+            Error::Format => f.write_str("a Display implementation returned an error"),
+        }
+    }
+}
+
+impl ser::StdError for Error {
+    fn source(&self) -> Option<&(dyn ser::StdError + 'static)> {
+        match *self {
+            #[cfg(feature = "std")]
+            Error::Io(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for Error {
+    #[inline]
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
 }
 
 impl ser::Error for Error {
     #[inline]
     fn custom<T>(msg: T) -> Self
     where
-        T: std::fmt::Display,
+        T: fmt::Display,
     {
         Self::Custom(msg.to_string())
     }
@@ -431,75 +1060,98 @@ where
     type SerializeTuple = TupleSeqAdapter<SerializeSeq<'a, O>>;
     type SerializeTupleStruct = TupleSeqAdapter<SerializeSeq<'a, O>>;
 
-    type SerializeMap = ser::Impossible<(), Error>;
-    type SerializeStruct = ser::Impossible<(), Error>;
+    type SerializeMap = SerializeMap<'a, O>;
+    type SerializeStruct = SerializeMap<'a, O>;
 
     type SerializeStructVariant = ser::Impossible<(), Error>;
     type SerializeTupleVariant = ser::Impossible<(), Error>;
 
     #[inline]
     fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
-        primitives::serialize_number(self.output, if v { 1 } else { 0 })
+        match self.version {
+            Version::Resp2 => {
+                primitives::serialize_number(self.into_output(), if v { 1 } else { 0 })
+            }
+            Version::Resp3 => primitives::serialize_boolean(self.into_output(), v),
+        }
     }
 
     #[inline]
     fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
-        primitives::serialize_number(self.output, v)
+        primitives::serialize_number(self.into_output(), v)
     }
 
     #[inline]
     fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
-        primitives::serialize_number(self.output, v)
+        primitives::serialize_number(self.into_output(), v)
     }
 
     #[inline]
     fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
-        primitives::serialize_number(self.output, v)
+        primitives::serialize_number(self.into_output(), v)
     }
 
     #[inline]
     fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
-        primitives::serialize_number(self.output, v)
+        primitives::serialize_number(self.into_output(), v)
     }
 
     #[inline]
     fn serialize_i128(self, v: i128) -> Result<Self::Ok, Self::Error> {
-        primitives::serialize_number(self.output, v)
+        self.serialize_wide_number(v)
     }
 
     #[inline]
     fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
-        primitives::serialize_number(self.output, v)
+        primitives::serialize_number(self.into_output(), v)
     }
 
     #[inline]
     fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
-        primitives::serialize_number(self.output, v)
+        primitives::serialize_number(self.into_output(), v)
     }
 
     #[inline]
     fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
-        primitives::serialize_number(self.output, v)
+        primitives::serialize_number(self.into_output(), v)
     }
 
     #[inline]
     fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
-        primitives::serialize_number(self.output, v)
+        self.serialize_wide_number(v)
     }
 
     #[inline]
     fn serialize_u128(self, v: u128) -> Result<Self::Ok, Self::Error> {
-        primitives::serialize_number(self.output, v)
+        self.serialize_wide_number(v)
     }
 
     #[inline]
-    fn serialize_f32(self, _v: f32) -> Result<Self::Ok, Self::Error> {
-        Err(Error::UnsupportedType("f32"))
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
+        match (self.version, self.string_floats) {
+            (Version::Resp2, false) => Err(Error::UnsupportedType("f32")),
+            (Version::Resp2, true) => match v {
+                f32::INFINITY => self.serialize_str("+inf"),
+                f32::NEG_INFINITY => self.serialize_str("-inf"),
+                v if v.is_nan() => Err(Error::UnsupportedType("NaN")),
+                v => self.collect_str(&v),
+            },
+            (Version::Resp3, _) => primitives::serialize_double(self.into_output(), v),
+        }
     }
 
     #[inline]
-    fn serialize_f64(self, _v: f64) -> Result<Self::Ok, Self::Error> {
-        Err(Error::UnsupportedType("f64"))
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> {
+        match (self.version, self.string_floats) {
+            (Version::Resp2, false) => Err(Error::UnsupportedType("f64")),
+            (Version::Resp2, true) => match v {
+                f64::INFINITY => self.serialize_str("+inf"),
+                f64::NEG_INFINITY => self.serialize_str("-inf"),
+                v if v.is_nan() => Err(Error::UnsupportedType("NaN")),
+                v => self.collect_str(&v),
+            },
+            (Version::Resp3, _) => primitives::serialize_double(self.into_output(), v),
+        }
     }
 
     #[inline]
@@ -510,35 +1162,41 @@ where
 
     #[inline]
     fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
-        primitives::serialize_bulk_string(self.output, v)
+        primitives::serialize_bulk_string(self.into_output(), v)
     }
 
     fn collect_str<T: ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error>
     where
-        T: std::fmt::Display,
+        T: fmt::Display,
     {
-        use std::fmt::Write as _;
-
         // We assume that things that need to be collected as strings are
         // usually pretty short, so we try first to serialize to a local buffer.
-        // In the future we'll also have a #[no_std] switch here that fails
-        // outright if Strings can't be created.
+        // `ArrayString` is also an `Output`, so `fmt::Write` is named
+        // explicitly.
         let mut buffer: ArrayString<256> = ArrayString::new();
 
-        match write!(&mut buffer, "{value}") {
-            Ok(_) => self.serialize_str(&buffer),
-            Err(_) => self.serialize_str(&value.to_string()),
+        if fmt::Write::write_fmt(&mut buffer, format_args!("{value}")).is_ok() {
+            return self.serialize_str(&buffer);
+        }
+
+        // Either the value didn't fit, or its `Display` returned an error;
+        // retry with a growable buffer to find out which.
+        let mut buffer = String::new();
+
+        match fmt::Write::write_fmt(&mut buffer, format_args!("{value}")) {
+            Ok(()) => self.serialize_str(&buffer),
+            Err(fmt::Error) => Err(Error::Format),
         }
     }
 
     #[inline]
     fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
-        primitives::serialize_bulk_string(self.output, v)
+        primitives::serialize_bulk_string(self.into_output(), v)
     }
 
     #[inline]
     fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
-        self.output.write_str("$-1\r\n")
+        self.output.write_str(NullUnit.unit_payload(self.version))
     }
 
     #[inline]
@@ -551,7 +1209,7 @@ where
 
     #[inline]
     fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
-        self.output.write_str(self.unit.unit_payload())
+        self.output.write_str(self.unit.unit_payload(self.version))
     }
 
     #[inline]
@@ -572,14 +1230,30 @@ where
     #[inline]
     fn serialize_newtype_struct<T: ?Sized>(
         self,
-        _name: &'static str,
+        name: &'static str,
         value: &T,
     ) -> Result<Self::Ok, Self::Error>
     where
         T: serde::Serialize,
     {
-        // TODO: use special newtype struct to handle simple strings
-        value.serialize(self)
+        match (name, self.version) {
+            (SET_TOKEN, Version::Resp3) => value.serialize(SetSerializer { inner: self }),
+            (RAW_VALUE_TOKEN, version) => value.serialize(RawValueSerializer {
+                output: self.into_output(),
+                version,
+            }),
+            (SIMPLE_STRING_TOKEN, _) => value.serialize(SimpleStringSerializer {
+                output: self.into_output(),
+            }),
+            (NULL_ARRAY_TOKEN, Version::Resp2) => self.output.write_str(protocol::NULL_ARRAY),
+            (NULL_ARRAY_TOKEN, Version::Resp3) => self.serialize_none(),
+            (VERBATIM_TOKEN, version) => value.serialize(VerbatimSerializer {
+                output: self.into_output(),
+                version,
+                part: VerbatimPart::Whole,
+            }),
+            _ => value.serialize(self),
+        }
     }
 
     fn serialize_newtype_variant<T: ?Sized>(
@@ -593,8 +1267,20 @@ where
         T: serde::Serialize,
     {
         match (name, variant) {
-            ("Result", "Ok") => value.serialize(BaseSerializer::new_ok(self.output)),
-            ("Result", "Err") => value.serialize(SerializeResultError::new(self.output)),
+            ("Result", "Ok") if self.ok_as_null => value.serialize(
+                BaseSerializer::new(self.output, self.depth, self.version)
+                    .with_string_floats(self.string_floats)
+                    .with_ok_as_null(self.ok_as_null)
+                    .with_reserve(self.reserve)
+                    .prereserved(self.reserved),
+            ),
+            ("Result", "Ok") => value.serialize(
+                BaseSerializer::new_ok(self.output, self.depth, self.version)
+                    .with_string_floats(self.string_floats)
+                    .with_reserve(self.reserve)
+                    .prereserved(self.reserved),
+            ),
+            ("Result", "Err") => value.serialize(SerializeResultError::new(self.into_output())),
             _ => Err(Error::UnsupportedType("data enum")),
         }
     }
@@ -606,8 +1292,20 @@ where
     }
 
     fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
-        primitives::serialize_array_header(&mut *self.output, len)?;
-        Ok(TupleSeqAdapter::new(SerializeSeq::new(self.output, len)))
+        let depth = self.depth.enter()?;
+        primitives::serialize_array_header(
+            Prereserved::new(&mut *self.output, self.reserved, self.reserve),
+            len,
+        )?;
+        Ok(TupleSeqAdapter::new(SerializeSeq::new(
+            self.output,
+            len,
+            depth,
+            self.version,
+            self.string_floats,
+            self.ok_as_null,
+            self.reserve,
+        )))
     }
 
     #[inline]
@@ -631,17 +1329,23 @@ where
     }
 
     #[inline]
-    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
-        Err(Error::UnsupportedType("map"))
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        match self.version {
+            Version::Resp2 => Err(Error::UnsupportedType("map")),
+            Version::Resp3 => self.serialize_resp3_map(len.ok_or(Error::UnknownSeqLength)?),
+        }
     }
 
     #[inline]
     fn serialize_struct(
         self,
         _name: &'static str,
-        _len: usize,
+        len: usize,
     ) -> Result<Self::SerializeStruct, Self::Error> {
-        Err(Error::UnsupportedType("struct"))
+        match self.version {
+            Version::Resp2 => Err(Error::UnsupportedType("struct")),
+            Version::Resp3 => self.serialize_resp3_map(len),
+        }
     }
 
     #[inline]
@@ -662,6 +1366,11 @@ where
 pub struct SerializeSeq<'a, O> {
     remaining: usize,
     output: &'a mut O,
+    depth: Depth,
+    version: Version,
+    string_floats: bool,
+    ok_as_null: bool,
+    reserve: bool,
 }
 
 impl<'a, O> SerializeSeq<'a, O>
@@ -670,14 +1379,78 @@ where
 {
     #[inline]
     #[must_use]
-    fn new(output: &'a mut O, length: usize) -> Self {
+    fn new(
+        output: &'a mut O,
+        length: usize,
+        depth: Depth,
+        version: Version,
+        string_floats: bool,
+        ok_as_null: bool,
+        reserve: bool,
+    ) -> Self {
         Self {
             output,
             remaining: length,
+            depth,
+            version,
+            string_floats,
+            ok_as_null,
+            reserve,
         }
     }
 }
 
+impl<O> SerializeSeq<'_, O> {
+    /**
+    The number of elements that still need to be serialized to complete the
+    array.
+
+    When a large array, like a reply with thousands of elements, is
+    serialized by calling
+    [`serialize_element`][ser::SerializeSeq::serialize_element] directly,
+    this can be used to report progress. Each element is written to the
+    output as soon as it's serialized, so with an output that writes through
+    to a destination, like an [`io::Write`], the caller can also pause
+    between elements without holding the whole array in memory.
+
+    This is for the elements of a single RESP array. A pipeline of commands
+    is a sequence of separate arrays, not one array containing them; use
+    [`to_vec_all`] to serialize one.
+
+    # Example
+
+    ```
+    use serde::ser::{SerializeSeq as _, Serializer as _};
+    use seredies::ser::Serializer;
+
+    let members = ["a", "b", "c", "d"];
+    let mut buffer = Vec::new();
+    let mut progress = Vec::new();
+
+    let mut seq = Serializer::new(&mut buffer)
+        .serialize_seq(Some(members.len()))
+        .unwrap();
+
+    for member in members {
+        seq.serialize_element(member).unwrap();
+
+        if seq.remaining() % 2 == 0 {
+            progress.push(members.len() - seq.remaining());
+        }
+    }
+
+    seq.end().unwrap();
+    assert_eq!(progress, [2, 4]);
+    assert_eq!(buffer, b"*4\r\n$1\r\na\r\n$1\r\nb\r\n$1\r\nc\r\n$1\r\nd\r\n");
+    ```
+    */
+    #[inline]
+    #[must_use]
+    pub const fn remaining(&self) -> usize {
+        self.remaining
+    }
+}
+
 impl<O> ser::SerializeSeq for SerializeSeq<'_, O>
 where
     O: Output,
@@ -690,15 +1463,19 @@ where
     where
         T: serde::Serialize,
     {
-        let reserve = primitives::estimate_array_reservation(self.remaining);
-
         match self.remaining.checked_sub(1) {
             Some(remain) => self.remaining = remain,
             None => return Err(Error::BadSeqLength),
         }
 
-        self.output.reserve(reserve);
-        value.serialize(BaseSerializer::new(self.output))
+        // The array header already reserved space for each element
+        value.serialize(
+            BaseSerializer::new(self.output, self.depth, self.version)
+                .with_string_floats(self.string_floats)
+                .with_ok_as_null(self.ok_as_null)
+                .with_reserve(self.reserve)
+                .prereserved(primitives::ELEMENT_RESERVATION),
+        )
     }
 
     #[inline]
@@ -710,15 +1487,299 @@ where
     }
 }
 
+/// The RESP3 map serializer. This is used by the [`Serializer`] to create
+/// RESP3 maps from maps and structs. You should rarely need to interact with
+/// this type directly.
+#[derive(Debug)]
+pub struct SerializeMap<'a, O> {
+    remaining: usize,
+    output: &'a mut O,
+    depth: Depth,
+    ok_as_null: bool,
+    reserve: bool,
+}
+
+impl<'a, O> SerializeMap<'a, O>
+where
+    O: Output,
+{
+    #[inline]
+    #[must_use]
+    fn new(
+        output: &'a mut O,
+        length: usize,
+        depth: Depth,
+        ok_as_null: bool,
+        reserve: bool,
+    ) -> Self {
+        Self {
+            output,
+            remaining: length,
+            depth,
+            ok_as_null,
+            reserve,
+        }
+    }
+
+    #[inline]
+    fn start_entry(&mut self) -> Result<(), Error> {
+        match self.remaining.checked_sub(1) {
+            Some(remain) => self.remaining = remain,
+            None => return Err(Error::BadSeqLength),
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_item<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: serde::Serialize + ?Sized,
+    {
+        // The map header already reserved space for each key and value
+        value.serialize(
+            BaseSerializer::new(self.output, self.depth, Version::Resp3)
+                .with_ok_as_null(self.ok_as_null)
+                .with_reserve(self.reserve)
+                .prereserved(primitives::ELEMENT_RESERVATION),
+        )
+    }
+
+    #[inline]
+    fn finish(self) -> Result<(), Error> {
+        match self.remaining {
+            0 => Ok(()),
+            _ => Err(Error::BadSeqLength),
+        }
+    }
+}
+
+impl<O> ser::SerializeMap for SerializeMap<'_, O>
+where
+    O: Output,
+{
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_key<T: ?Sized>(&mut self, key: &T) -> Result<(), Self::Error>
+    where
+        T: serde::Serialize,
+    {
+        self.start_entry()?;
+        self.serialize_item(key)
+    }
+
+    #[inline]
+    fn serialize_value<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: serde::Serialize,
+    {
+        self.serialize_item(value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl<O> ser::SerializeStruct for SerializeMap<'_, O>
+where
+    O: Output,
+{
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_field<T: ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error>
+    where
+        T: serde::Serialize,
+    {
+        self.start_entry()?;
+        self.serialize_item(key)?;
+        self.serialize_item(value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+/// A serializer that serializes sequences as RESP3 sets, and everything else
+/// normally. This is used for the contents of a
+/// [`RedisSet`][crate::components::RedisSet].
+struct SetSerializer<'a, O, U> {
+    inner: BaseSerializer<'a, O, U>,
+}
+
+impl<'a, O, U> ser::Serializer for SetSerializer<'a, O, U>
+where
+    O: Output,
+    U: UnitBehavior,
+{
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = SerializeSeq<'a, O>;
+    type SerializeTuple = TupleSeqAdapter<SerializeSeq<'a, O>>;
+    type SerializeTupleStruct = TupleSeqAdapter<SerializeSeq<'a, O>>;
+
+    type SerializeMap = SerializeMap<'a, O>;
+    type SerializeStruct = SerializeMap<'a, O>;
+
+    type SerializeStructVariant = ser::Impossible<(), Error>;
+    type SerializeTupleVariant = ser::Impossible<(), Error>;
+
+    forward! {
+        bool
+        i8 i16 i32 i64 i128
+        u8 u16 u32 u64 u128
+        f32 f64
+        char
+        str(v: &str)
+        bytes(v: &[u8])
+        none()
+        some<T>(value: &T)
+        unit()
+        unit_struct(name: &'static str)
+        unit_variant(
+            name: &'static str,
+            variant_index: u32,
+            variant: &'static str
+        )
+        newtype_struct<T>(name: &'static str, value: &T)
+        newtype_variant<T>(
+            name: &'static str,
+            variant_index: u32,
+            variant: &'static str,
+            value: &T
+        )
+        tuple_variant(
+            name: &'static str,
+            variant_index: u32,
+            variant: &'static str,
+            len: usize,
+        ) -> SerializeTupleVariant
+        map(len: Option<usize>) -> SerializeMap
+        struct(name: &'static str, len: usize) -> SerializeStruct
+        struct_variant(
+            name: &'static str,
+            variant_index: u32,
+            variant: &'static str,
+            len: usize
+        ) -> SerializeStructVariant
+    }
+
+    #[inline]
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        self.serialize_tuple(len.ok_or(Error::UnknownSeqLength)?)
+            .map(|adapter| adapter.inner)
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        let BaseSerializer {
+            output,
+            depth,
+            version,
+            string_floats,
+            ok_as_null,
+            reserve,
+            reserved,
+            ..
+        } = self.inner;
+
+        let depth = depth.enter()?;
+        primitives::serialize_set_header(Prereserved::new(&mut *output, reserved, reserve), len)?;
+        Ok(TupleSeqAdapter::new(SerializeSeq::new(
+            output,
+            len,
+            depth,
+            version,
+            string_floats,
+            ok_as_null,
+            reserve,
+        )))
+    }
+
+    #[inline]
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        self.serialize_tuple(len)
+    }
+}
+
 /// An error serializer only accepts strings / bytes or similar payloads and
-/// serializes them as Redis error values.
+/// serializes them as Redis error values. It also accepts a pair of such
+/// values (as a 2-tuple or a 2-field struct), which are joined with a space,
+/// for errors modeled as a code and a message.
 struct SerializeResultError<O> {
     output: O,
+    part: ErrorPart,
+}
+
+/// Which part of an error a `SerializeResultError` is serializing. For the
+/// parts of a structured error, the error prefix and trailing CRLF are
+/// handled by `SerializeErrorParts`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ErrorPart {
+    /// The whole error
+    Whole,
+
+    /// The first part of a structured error, like the code
+    First,
+
+    /// A later part of a structured error, like the message. It's separated
+    /// from the previous part by a space, unless it's empty, so that a code
+    /// with an empty message is written as just the code.
+    Rest,
 }
 
 impl<O: Output> SerializeResultError<O> {
+    #[inline]
     pub fn new(output: O) -> Self {
-        Self { output }
+        Self {
+            output,
+            part: ErrorPart::Whole,
+        }
+    }
+
+    #[inline]
+    fn new_part(output: O, part: ErrorPart) -> Self {
+        Self { output, part }
+    }
+
+    fn serialize_payload(mut self, v: &(impl Writable + ?Sized)) -> Result<(), Error> {
+        match self.part {
+            ErrorPart::Whole => primitives::serialize_error(self.output, v),
+            ErrorPart::First => primitives::serialize_error_part(self.output, v),
+            ErrorPart::Rest if v.len() == 0 => Ok(()),
+            ErrorPart::Rest => {
+                self.output.write_str(" ")?;
+                primitives::serialize_error_part(self.output, v)
+            }
+        }
+    }
+
+    fn serialize_parts(mut self, len: usize) -> Result<SerializeErrorParts<O>, Error> {
+        match (self.part, len) {
+            (ErrorPart::Whole, 2) => {
+                primitives::serialize_error_prefix(&mut self.output)?;
+                Ok(SerializeErrorParts {
+                    output: self.output,
+                    index: 0,
+                })
+            }
+            _ => Err(Error::InvalidErrorPayload),
+        }
     }
 }
 
@@ -727,11 +1788,11 @@ impl<O: Output> ser::Serializer for SerializeResultError<O> {
     type Error = Error;
 
     type SerializeSeq = ser::Impossible<(), Error>;
-    type SerializeTuple = ser::Impossible<(), Error>;
-    type SerializeTupleStruct = ser::Impossible<(), Error>;
+    type SerializeTuple = SerializeErrorParts<O>;
+    type SerializeTupleStruct = SerializeErrorParts<O>;
     type SerializeTupleVariant = ser::Impossible<(), Error>;
     type SerializeMap = ser::Impossible<(), Error>;
-    type SerializeStruct = ser::Impossible<(), Error>;
+    type SerializeStruct = SerializeErrorParts<O>;
     type SerializeStructVariant = ser::Impossible<(), Error>;
 
     #[inline]
@@ -807,11 +1868,12 @@ impl<O: Output> ser::Serializer for SerializeResultError<O> {
 
     #[inline]
     fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
-        primitives::serialize_error(self.output, v)
+        self.serialize_payload(v)
     }
 
+    #[inline]
     fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
-        primitives::serialize_error(self.output, v)
+        self.serialize_payload(v)
     }
 
     #[inline]
@@ -879,17 +1941,17 @@ impl<O: Output> ser::Serializer for SerializeResultError<O> {
     }
 
     #[inline]
-    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
-        Err(Error::InvalidErrorPayload)
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        self.serialize_parts(len)
     }
 
     #[inline]
     fn serialize_tuple_struct(
         self,
         _name: &'static str,
-        _len: usize,
+        len: usize,
     ) -> Result<Self::SerializeTupleStruct, Self::Error> {
-        Err(Error::InvalidErrorPayload)
+        self.serialize_parts(len)
     }
 
     #[inline]
@@ -912,9 +1974,9 @@ impl<O: Output> ser::Serializer for SerializeResultError<O> {
     fn serialize_struct(
         self,
         _name: &'static str,
-        _len: usize,
+        len: usize,
     ) -> Result<Self::SerializeStruct, Self::Error> {
-        Err(Error::InvalidErrorPayload)
+        self.serialize_parts(len)
     }
 
     #[inline]
@@ -929,52 +1991,656 @@ impl<O: Output> ser::Serializer for SerializeResultError<O> {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use std::io::Write;
-
-    use super::*;
+/// Serializer for the parts of a structured error, like `-WRONGTYPE message`.
+/// Each part is written with a `SerializeResultError` in part mode, which
+/// writes the space between them. The error prefix has already been written.
+struct SerializeErrorParts<O> {
+    output: O,
+    index: usize,
+}
 
-    use serde::Serialize;
-    use serde_bytes::Bytes;
-    use tempfile::tempfile;
+impl<O: Output> SerializeErrorParts<O> {
+    fn serialize_part<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ser::Serialize + ?Sized,
+    {
+        let part = match self.index {
+            0 => ErrorPart::First,
+            _ => ErrorPart::Rest,
+        };
 
-    #[derive(Debug, Serialize)]
-    #[serde(untagged)]
-    enum Data<'a> {
-        Null,
-        String(&'a Bytes),
-        Integer(i64),
-        Array(Vec<Data<'a>>),
+        self.index += 1;
+        value.serialize(SerializeResultError::new_part(&mut self.output, part))
     }
 
-    use Data::Null;
-
-    impl<'a> From<&'a [u8]> for Data<'a> {
-        fn from(value: &'a [u8]) -> Self {
-            Self::String(Bytes::new(value))
+    fn finish(mut self) -> Result<(), Error> {
+        match self.index {
+            2 => self.output.write_str(protocol::CRLF),
+            _ => Err(Error::InvalidErrorPayload),
         }
     }
+}
 
-    impl<'a> From<&'a str> for Data<'a> {
-        fn from(value: &'a str) -> Self {
-            value.as_bytes().into()
-        }
-    }
+impl<O: Output> ser::SerializeTuple for SerializeErrorParts<O> {
+    type Ok = ();
+    type Error = Error;
 
-    impl From<i64> for Data<'_> {
-        fn from(value: i64) -> Self {
-            Self::Integer(value)
-        }
+    #[inline]
+    fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ser::Serialize,
+    {
+        self.serialize_part(value)
     }
 
-    impl<'a, T: Into<Data<'a>>, const N: usize> From<[T; N]> for Data<'a> {
-        fn from(value: [T; N]) -> Self {
-            Self::Array(value.into_iter().map(Into::into).collect())
-        }
+    #[inline]
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
     }
+}
 
-    fn test_basic_serialize<'a>(
+impl<O: Output> ser::SerializeTupleStruct for SerializeErrorParts<O> {
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_field<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ser::Serialize,
+    {
+        self.serialize_part(value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl<O: Output> ser::SerializeStruct for SerializeErrorParts<O> {
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_field<T: ?Sized>(
+        &mut self,
+        _key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error>
+    where
+        T: ser::Serialize,
+    {
+        self.serialize_part(value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+/// A serializer that writes strings and bytes as RESP Simple Strings. This is
+/// used for [`Value::SimpleString`][crate::value::Value::SimpleString]; any
+/// non-string payload is rejected.
+struct SimpleStringSerializer<O> {
+    output: O,
+}
+
+impl<O: Output> ser::Serializer for SimpleStringSerializer<O> {
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = ser::Impossible<(), Error>;
+    type SerializeTuple = ser::Impossible<(), Error>;
+    type SerializeTupleStruct = ser::Impossible<(), Error>;
+    type SerializeTupleVariant = ser::Impossible<(), Error>;
+    type SerializeMap = ser::Impossible<(), Error>;
+    type SerializeStruct = ser::Impossible<(), Error>;
+    type SerializeStructVariant = ser::Impossible<(), Error>;
+
+    #[inline]
+    fn serialize_bool(self, _v: bool) -> Result<Self::Ok, Self::Error> {
+        Err(Error::UnsupportedType("non-string Simple String"))
+    }
+
+    #[inline]
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
+        self.collect_str(&v)
+    }
+
+    #[inline]
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
+        self.collect_str(&v)
+    }
+
+    #[inline]
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
+        self.collect_str(&v)
+    }
+
+    #[inline]
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
+        self.collect_str(&v)
+    }
+
+    #[inline]
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
+        self.collect_str(&v)
+    }
+
+    #[inline]
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
+        self.collect_str(&v)
+    }
+
+    #[inline]
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
+        self.collect_str(&v)
+    }
+
+    #[inline]
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
+        self.collect_str(&v)
+    }
+
+    #[inline]
+    fn serialize_i128(self, v: i128) -> Result<Self::Ok, Self::Error> {
+        self.collect_str(&v)
+    }
+
+    #[inline]
+    fn serialize_u128(self, v: u128) -> Result<Self::Ok, Self::Error> {
+        self.collect_str(&v)
+    }
+
+    #[inline]
+    fn serialize_f32(self, _v: f32) -> Result<Self::Ok, Self::Error> {
+        Err(Error::UnsupportedType("non-string Simple String"))
+    }
+
+    #[inline]
+    fn serialize_f64(self, _v: f64) -> Result<Self::Ok, Self::Error> {
+        Err(Error::UnsupportedType("non-string Simple String"))
+    }
+
+    #[inline]
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
+        let mut buf = [0; 4];
+        self.serialize_str(v.encode_utf8(&mut buf))
+    }
+
+    #[inline]
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        primitives::serialize_simple_string(self.output, v)
+    }
+
+    #[inline]
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        primitives::serialize_simple_string(self.output, v)
+    }
+
+    #[inline]
+    fn collect_str<T: ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: fmt::Display,
+    {
+        primitives::serialize_simple_string_display(self.output, value)
+    }
+
+    #[inline]
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Err(Error::UnsupportedType("non-string Simple String"))
+    }
+
+    #[inline]
+    fn serialize_some<T: ?Sized>(self, _value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: serde::Serialize,
+    {
+        Err(Error::UnsupportedType("non-string Simple String"))
+    }
+
+    #[inline]
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Err(Error::UnsupportedType("non-string Simple String"))
+    }
+
+    #[inline]
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        Err(Error::UnsupportedType("non-string Simple String"))
+    }
+
+    #[inline]
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.serialize_str(variant)
+    }
+
+    #[inline]
+    fn serialize_newtype_struct<T: ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: serde::Serialize,
+    {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_newtype_variant<T: ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: serde::Serialize,
+    {
+        Err(Error::UnsupportedType("non-string Simple String"))
+    }
+
+    #[inline]
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Err(Error::UnsupportedType("non-string Simple String"))
+    }
+
+    #[inline]
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Err(Error::UnsupportedType("non-string Simple String"))
+    }
+
+    #[inline]
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Err(Error::UnsupportedType("non-string Simple String"))
+    }
+
+    #[inline]
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(Error::UnsupportedType("non-string Simple String"))
+    }
+
+    #[inline]
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Err(Error::UnsupportedType("non-string Simple String"))
+    }
+
+    #[inline]
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Err(Error::UnsupportedType("non-string Simple String"))
+    }
+
+    #[inline]
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(Error::UnsupportedType("non-string Simple String"))
+    }
+}
+
+/// Which part of a [`Verbatim`][crate::components::Verbatim] string a
+/// `VerbatimSerializer` is serializing.
+enum VerbatimPart<'a> {
+    /// The whole `(format, content)` pair
+    Whole,
+
+    /// The format, which is stored in the slot
+    Format(&'a mut Option<[u8; 3]>),
+
+    /// The content, which is written with the format that preceded it
+    Content([u8; 3]),
+}
+
+/// A serializer for [`Verbatim`][crate::components::Verbatim] strings. The
+/// string is passed as a `(format, content)` pair; each of them must be a
+/// string or bytes. RESP2 doesn't have Verbatim Strings, so only the content
+/// is written, as a Bulk String.
+struct VerbatimSerializer<'a, O> {
+    output: O,
+    version: Version,
+    part: VerbatimPart<'a>,
+}
+
+impl<O: Output> VerbatimSerializer<'_, O> {
+    fn serialize_payload(self, payload: &[u8]) -> Result<(), Error> {
+        match self.part {
+            VerbatimPart::Whole => Err(Error::InvalidVerbatimPayload),
+            VerbatimPart::Format(slot) => {
+                let format = payload.try_into();
+                *slot = Some(format.map_err(|_| Error::InvalidVerbatimPayload)?);
+                Ok(())
+            }
+            VerbatimPart::Content(format) => match self.version {
+                Version::Resp2 => primitives::serialize_bulk_string(self.output, payload),
+                Version::Resp3 => primitives::serialize_verbatim(self.output, &format, payload),
+            },
+        }
+    }
+}
+
+impl<O: Output> ser::Serializer for VerbatimSerializer<'_, O> {
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = ser::Impossible<(), Error>;
+    type SerializeTuple = SerializeVerbatimParts<O>;
+    type SerializeTupleStruct = ser::Impossible<(), Error>;
+    type SerializeTupleVariant = ser::Impossible<(), Error>;
+    type SerializeMap = ser::Impossible<(), Error>;
+    type SerializeStruct = ser::Impossible<(), Error>;
+    type SerializeStructVariant = ser::Impossible<(), Error>;
+
+    #[inline]
+    fn serialize_bool(self, _v: bool) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidVerbatimPayload)
+    }
+
+    #[inline]
+    fn serialize_i8(self, _v: i8) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidVerbatimPayload)
+    }
+
+    #[inline]
+    fn serialize_i16(self, _v: i16) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidVerbatimPayload)
+    }
+
+    #[inline]
+    fn serialize_i32(self, _v: i32) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidVerbatimPayload)
+    }
+
+    #[inline]
+    fn serialize_i64(self, _v: i64) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidVerbatimPayload)
+    }
+
+    #[inline]
+    fn serialize_u8(self, _v: u8) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidVerbatimPayload)
+    }
+
+    #[inline]
+    fn serialize_u16(self, _v: u16) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidVerbatimPayload)
+    }
+
+    #[inline]
+    fn serialize_u32(self, _v: u32) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidVerbatimPayload)
+    }
+
+    #[inline]
+    fn serialize_u64(self, _v: u64) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidVerbatimPayload)
+    }
+
+    #[inline]
+    fn serialize_f32(self, _v: f32) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidVerbatimPayload)
+    }
+
+    #[inline]
+    fn serialize_f64(self, _v: f64) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidVerbatimPayload)
+    }
+
+    #[inline]
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
+        let mut buf = [0; 4];
+        self.serialize_str(v.encode_utf8(&mut buf))
+    }
+
+    #[inline]
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        self.serialize_payload(v.as_bytes())
+    }
+
+    #[inline]
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        self.serialize_payload(v)
+    }
+
+    #[inline]
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidVerbatimPayload)
+    }
+
+    #[inline]
+    fn serialize_some<T: ?Sized>(self, _value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: serde::Serialize,
+    {
+        Err(Error::InvalidVerbatimPayload)
+    }
+
+    #[inline]
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidVerbatimPayload)
+    }
+
+    #[inline]
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidVerbatimPayload)
+    }
+
+    #[inline]
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.serialize_str(variant)
+    }
+
+    #[inline]
+    fn serialize_newtype_struct<T: ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: serde::Serialize,
+    {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_newtype_variant<T: ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: serde::Serialize,
+    {
+        Err(Error::InvalidVerbatimPayload)
+    }
+
+    #[inline]
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Err(Error::InvalidVerbatimPayload)
+    }
+
+    #[inline]
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        match (self.part, len) {
+            (VerbatimPart::Whole, 2) => Ok(SerializeVerbatimParts {
+                output: self.output,
+                version: self.version,
+                format: None,
+                done: false,
+            }),
+            _ => Err(Error::InvalidVerbatimPayload),
+        }
+    }
+
+    #[inline]
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Err(Error::InvalidVerbatimPayload)
+    }
+
+    #[inline]
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(Error::InvalidVerbatimPayload)
+    }
+
+    #[inline]
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Err(Error::InvalidVerbatimPayload)
+    }
+
+    #[inline]
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Err(Error::InvalidVerbatimPayload)
+    }
+
+    #[inline]
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(Error::InvalidVerbatimPayload)
+    }
+}
+
+/// Serializer for the `(format, content)` pair of a Verbatim String. The
+/// format is stored until the content arrives, since the header can't be
+/// written until the content's length is known.
+struct SerializeVerbatimParts<O> {
+    output: O,
+    version: Version,
+    format: Option<[u8; 3]>,
+    done: bool,
+}
+
+impl<O: Output> ser::SerializeTuple for SerializeVerbatimParts<O> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ser::Serialize,
+    {
+        let part = match (self.format, self.done) {
+            (None, _) => VerbatimPart::Format(&mut self.format),
+            (Some(format), false) => VerbatimPart::Content(format),
+            (Some(_), true) => return Err(Error::InvalidVerbatimPayload),
+        };
+
+        let content = matches!(part, VerbatimPart::Content(_));
+
+        value.serialize(VerbatimSerializer {
+            output: &mut self.output,
+            version: self.version,
+            part,
+        })?;
+
+        self.done = content;
+        Ok(())
+    }
+
+    #[inline]
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        match self.done {
+            true => Ok(()),
+            false => Err(Error::InvalidVerbatimPayload),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    use arrayvec::ArrayVec;
+    use cool_asserts::assert_matches;
+    use serde::Serialize;
+    use serde_bytes::Bytes;
+    use tempfile::tempfile;
+
+    #[derive(Debug, Serialize)]
+    #[serde(untagged)]
+    enum Data<'a> {
+        Null,
+        String(&'a Bytes),
+        Integer(i64),
+        Array(Vec<Data<'a>>),
+    }
+
+    use Data::Null;
+
+    impl<'a> From<&'a [u8]> for Data<'a> {
+        fn from(value: &'a [u8]) -> Self {
+            Self::String(Bytes::new(value))
+        }
+    }
+
+    impl<'a> From<&'a str> for Data<'a> {
+        fn from(value: &'a str) -> Self {
+            value.as_bytes().into()
+        }
+    }
+
+    impl From<i64> for Data<'_> {
+        fn from(value: i64) -> Self {
+            Self::Integer(value)
+        }
+    }
+
+    impl<'a, T: Into<Data<'a>>, const N: usize> From<[T; N]> for Data<'a> {
+        fn from(value: [T; N]) -> Self {
+            Self::Array(value.into_iter().map(Into::into).collect())
+        }
+    }
+
+    fn test_basic_serialize<'a>(
         input: impl Into<Data<'a>>,
         expected: &'a (impl AsRef<[u8]> + ?Sized),
     ) {
@@ -1002,6 +2668,9 @@ mod tests {
         bulk_string: "Hello, World!" => "$13\r\nHello, World!\r\n";
         empty_bulk_string: "" => "$0\r\n\r\n";
         null: Null => "$-1\r\n";
+        empty_array: Data::Array(Vec::new()) => "*0\r\n";
+        array_of_nulls: [Null, Null, Null] => "*3\r\n$-1\r\n$-1\r\n$-1\r\n";
+        nested_empty: [Data::Array(Vec::new()), Data::from([""])] => "*2\r\n*0\r\n*1\r\n$0\r\n\r\n";
         array: ["hello", "world"] => "\
             *2\r\n\
             $5\r\nhello\r\n\
@@ -1100,6 +2769,55 @@ mod tests {
         test_result_serializer::<(), &str>(Err("ERROR bad data"), b"-ERROR bad data\r\n")
     }
 
+    #[derive(Serialize)]
+    #[allow(clippy::upper_case_acronyms)]
+    enum Code {
+        WRONGTYPE,
+    }
+
+    #[test]
+    fn test_result_error_tuple() {
+        test_result_serializer::<(), _>(
+            Err((Code::WRONGTYPE, "wrong kind of value")),
+            b"-WRONGTYPE wrong kind of value\r\n",
+        )
+    }
+
+    #[test]
+    fn test_result_error_struct() {
+        #[derive(Serialize)]
+        struct Structured {
+            code: char,
+            message: &'static Bytes,
+        }
+
+        test_result_serializer::<(), _>(
+            Err(Structured {
+                code: 'E',
+                message: Bytes::new(b"oops"),
+            }),
+            b"-E oops\r\n",
+        )
+    }
+
+    #[test]
+    fn test_result_error_bad_parts() {
+        fn serialize<E: Serialize>(error: E) -> Error {
+            Err::<(), E>(error)
+                .serialize(Serializer::new(&mut Vec::new()))
+                .unwrap_err()
+        }
+
+        assert_matches!(serialize(("ERR", "bad\r\nmessage")), Error::BadSimpleString);
+        assert_matches!(serialize(("ERR", 10)), Error::InvalidErrorPayload);
+        assert_matches!(serialize(("ERR",)), Error::InvalidErrorPayload);
+        assert_matches!(serialize(("ERR", "a", "b")), Error::InvalidErrorPayload);
+        assert_matches!(
+            serialize(("ERR", ("nested", "parts"))),
+            Error::InvalidErrorPayload
+        );
+    }
+
     #[test]
     fn test_result_array() {
         test_result_serializer::<((), i32), &str>(Ok(((), 10)), b"*2\r\n$-1\r\n:10\r\n")
@@ -1135,4 +2853,820 @@ mod tests {
             "
         )
     }
+
+    /// A writer that fails every other write with `kind`, and otherwise
+    /// writes at most 4 bytes at a time.
+    struct FlakyWriter {
+        data: Vec<u8>,
+        kind: std::io::ErrorKind,
+        fail: bool,
+    }
+
+    impl FlakyWriter {
+        fn new(kind: std::io::ErrorKind) -> Self {
+            Self {
+                data: Vec::new(),
+                kind,
+                fail: true,
+            }
+        }
+    }
+
+    impl std::io::Write for FlakyWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.fail = !self.fail;
+
+            match self.fail {
+                false => Err(self.kind.into()),
+                true => {
+                    let count = buf.len().min(4);
+                    self.data.extend_from_slice(&buf[..count]);
+                    Ok(count)
+                }
+            }
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_to_writer_retries_interrupted() {
+        let mut writer = FlakyWriter::new(std::io::ErrorKind::Interrupted);
+
+        to_writer(&["GET", "key"], &mut writer).expect("failed to serialize");
+        assert_eq!(writer.data, b"*2\r\n$3\r\nGET\r\n$3\r\nkey\r\n");
+    }
+
+    #[test]
+    fn test_transactional_write_would_block() {
+        let mut output = TransactionalWrite::new(FlakyWriter::new(std::io::ErrorKind::WouldBlock));
+
+        ["GET", "key"]
+            .serialize(Serializer::new(&mut output))
+            .expect("failed to serialize");
+
+        let mut attempts = 0;
+
+        while let Err(err) = output.commit() {
+            assert_eq!(err.kind(), std::io::ErrorKind::WouldBlock);
+            attempts += 1;
+        }
+
+        assert!(attempts > 1);
+        assert!(output.pending().is_empty());
+        assert_eq!(
+            output.into_inner().data,
+            b"*2\r\n$3\r\nGET\r\n$3\r\nkey\r\n"
+        );
+    }
+
+    #[test]
+    fn test_transactional_write_discard() {
+        let mut output = TransactionalWrite::new(Vec::new());
+
+        let result = ("GET", 1.5).serialize(Serializer::new(&mut output));
+        assert_matches!(result, Err(Error::UnsupportedType("f64")));
+        assert!(!output.pending().is_empty());
+
+        output.discard();
+        output.commit().expect("failed to commit");
+        assert!(output.get_ref().is_empty());
+    }
+
+    #[test]
+    fn test_transactional_write_discard_to_mark() {
+        let mut output = TransactionalWrite::new(Vec::<u8>::new());
+
+        ["PING"]
+            .serialize(Serializer::new(&mut output))
+            .expect("failed to serialize");
+        output.mark();
+
+        let result = ("GET", 1.5).serialize(Serializer::new(&mut output));
+        assert_matches!(result, Err(Error::UnsupportedType("f64")));
+
+        output.discard();
+        assert_eq!(output.pending(), b"*1\r\n$4\r\nPING\r\n");
+    }
+
+    #[test]
+    fn test_transactional_write_to_buffer() {
+        let mut output = TransactionalWrite::new(Vec::new());
+
+        to_buffer(&["PING"], &mut output).expect("failed to serialize");
+        let result = to_buffer(&("GET", 1.5), &mut output);
+        assert_matches!(result, Err(Error::UnsupportedType("f64")));
+
+        output.commit().expect("failed to commit");
+        assert_eq!(output.get_ref(), b"*1\r\n$4\r\nPING\r\n");
+    }
+
+    #[test]
+    fn test_transactional_write_truncate_keeps_written() {
+        let mut output = TransactionalWrite::new(FlakyWriter::new(std::io::ErrorKind::WouldBlock));
+
+        ["GET", "key"]
+            .serialize(Serializer::new(&mut output))
+            .expect("failed to serialize");
+
+        // Every other write fails, and the others only write 4 bytes
+        for _ in 0..2 {
+            let err = output.commit().expect_err("commit should fail");
+            assert_eq!(err.kind(), std::io::ErrorKind::WouldBlock);
+        }
+
+        Truncate::truncate(&mut output, 0);
+        assert_eq!(Truncate::len(&output), 4);
+        assert!(output.pending().is_empty());
+
+        output.discard();
+        output.commit().expect("failed to commit");
+        assert_eq!(output.into_inner().data, b"*2\r\n");
+    }
+
+    #[test]
+    fn test_to_vec_all() {
+        let pipeline = [
+            Vec::from([Data::String(Bytes::new(b"GET")), Data::Integer(1)]),
+            Vec::from([Data::Null]),
+            Vec::new(),
+        ];
+
+        let expected: Vec<u8> = pipeline
+            .iter()
+            .flat_map(|command| to_vec(command).unwrap())
+            .collect();
+
+        let buffer = to_vec_all(&pipeline).unwrap();
+        assert_eq!(buffer, expected);
+        assert_eq!(buffer.capacity(), expected.len());
+    }
+
+    #[test]
+    fn test_to_vec_all_empty() {
+        let pipeline: [i64; 0] = [];
+        assert_eq!(to_vec_all(&pipeline).unwrap(), b"");
+    }
+
+    #[test]
+    fn test_size_of() {
+        let value = (
+            "SET",
+            Some(-15),
+            ["a", "bc"],
+            Bytes::new(b"\xff\x00"),
+            Ok::<_, ()>(None::<i64>),
+        );
+
+        assert_eq!(size_of(&value).unwrap(), to_vec(&value).unwrap().len());
+
+        let config = Config::new().with_version(Version::Resp3);
+        assert_eq!(
+            size_of_with_config(&value, config).unwrap(),
+            to_vec_with_config(&value, config).unwrap().len()
+        );
+    }
+
+    /// A sequence whose `Serialize` implementation fails after serializing
+    /// some of its elements.
+    struct FailsAfter(usize);
+
+    impl Serialize for FailsAfter {
+        fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            use ser::SerializeSeq as _;
+
+            let mut seq = serializer.serialize_seq(Some(self.0 + 1))?;
+            for i in 0..self.0 {
+                seq.serialize_element(&[i as i64])?;
+            }
+            Err(ser::Error::custom("failed"))
+        }
+    }
+
+    #[test]
+    fn test_to_buffer_rollback() {
+        let mut buffer = b"+OK\r\n".to_vec();
+
+        for count in 0..4 {
+            assert_matches!(
+                to_buffer(&[FailsAfter(count)], &mut buffer),
+                Err(Error::Custom(_))
+            );
+            assert_eq!(buffer, b"+OK\r\n");
+        }
+
+        let mut buffer = String::from("+OK\r\n");
+        assert_matches!(
+            to_buffer(&(1, Bytes::new(b"\xff")), &mut buffer),
+            Err(Error::Utf8Encode)
+        );
+        assert_eq!(buffer, "+OK\r\n");
+    }
+
+    #[test]
+    fn test_to_buffer_full() {
+        let mut buffer: ArrayVec<u8, 32> = ArrayVec::new();
+        to_buffer(&["GET", "key"], &mut buffer).unwrap();
+        let len = buffer.len();
+
+        assert_matches!(
+            to_buffer(&["GET", "key"], &mut buffer),
+            Err(Error::BufferFull)
+        );
+        assert_eq!(buffer.len(), len);
+        assert_eq!(buffer.as_slice(), b"*2\r\n$3\r\nGET\r\n$3\r\nkey\r\n");
+    }
+
+    #[test]
+    fn test_partial_frame_without_rollback() {
+        let mut buffer = Vec::new();
+        assert!(FailsAfter(1)
+            .serialize(Serializer::new(&mut buffer))
+            .is_err());
+        assert_eq!(buffer, b"*2\r\n*1\r\n:0\r\n");
+    }
+
+    #[test]
+    fn test_size_of_error() {
+        assert_matches!(size_of(&Err::<(), _>(5)), Err(Error::InvalidErrorPayload));
+    }
+
+    fn serialize_with_depth<T: Serialize>(data: &T, limit: usize) -> Result<Vec<u8>, Error> {
+        let mut buffer = Vec::new();
+        data.serialize(Serializer::new(&mut buffer).with_max_depth(limit))?;
+        Ok(buffer)
+    }
+
+    #[test]
+    fn test_depth_limit() {
+        let data = Data::from([Data::from([Data::from([1])]), Data::from(2)]);
+
+        assert_eq!(
+            serialize_with_depth(&data, 3).unwrap(),
+            b"*2\r\n*1\r\n*1\r\n:1\r\n:2\r\n"
+        );
+        assert!(matches!(
+            serialize_with_depth(&data, 2),
+            Err(Error::DepthLimit(2))
+        ));
+    }
+
+    #[test]
+    fn test_depth_limit_zero() {
+        assert_eq!(serialize_with_depth(&"abc", 0).unwrap(), b"$3\r\nabc\r\n");
+        assert!(matches!(
+            serialize_with_depth(&Data::Array(Vec::new()), 0),
+            Err(Error::DepthLimit(0))
+        ));
+    }
+
+    #[test]
+    fn test_depth_limit_through_result() {
+        let data: Result<Vec<Vec<i32>>, &str> = Ok(Vec::from([Vec::from([1])]));
+
+        assert!(serialize_with_depth(&data, 2).is_ok());
+        assert!(matches!(
+            serialize_with_depth(&data, 1),
+            Err(Error::DepthLimit(1))
+        ));
+    }
+
+    mod resp3 {
+        use std::collections::BTreeMap;
+
+        use super::*;
+        use crate::components::RedisSet;
+
+        fn serialize_resp3<T: Serialize + ?Sized>(data: &T) -> Result<Vec<u8>, Error> {
+            let mut buffer = Vec::new();
+            data.serialize(Serializer::new_resp3(&mut buffer))?;
+            Ok(buffer)
+        }
+
+        macro_rules! resp3_tests {
+            ($($name:ident: $data:expr => $expected:literal,)*) => {$(
+                #[test]
+                fn $name() {
+                    assert_eq!(
+                        serialize_resp3(&$data).expect("failed to serialize"),
+                        $expected,
+                    );
+                }
+            )*};
+        }
+
+        #[derive(Serialize)]
+        struct Point {
+            x: i32,
+            y: Option<f64>,
+        }
+
+        resp3_tests! {
+            bool_true: true => b"#t\r\n",
+            bool_false: false => b"#f\r\n",
+            double: 1.5 => b",1.5\r\n",
+            double_integral: 10.0 => b",10\r\n",
+            double_negative: -0.25 => b",-0.25\r\n",
+            double_f32: 0.1f32 => b",0.1\r\n",
+            double_inf: f64::INFINITY => b",inf\r\n",
+            double_neg_inf: f64::NEG_INFINITY => b",-inf\r\n",
+            double_nan: f64::NAN => b",nan\r\n",
+            null: None::<i32> => b"_\r\n",
+            unit: () => b"_\r\n",
+            ok: Ok::<(), &str>(()) => b"+OK\r\n",
+            string: "abc" => b"$3\r\nabc\r\n",
+            array: [1, 2] => b"*2\r\n:1\r\n:2\r\n",
+            map: BTreeMap::from([("a", 1), ("b", 2)])
+                => b"%2\r\n$1\r\na\r\n:1\r\n$1\r\nb\r\n:2\r\n",
+            empty_map: BTreeMap::<i32, i32>::new() => b"%0\r\n",
+            structure: Point { x: 1, y: None }
+                => b"%2\r\n$1\r\nx\r\n:1\r\n$1\r\ny\r\n_\r\n",
+            set: RedisSet(["a", "b"]) => b"~2\r\n$1\r\na\r\n$1\r\nb\r\n",
+            nested_set: [RedisSet([true])] => b"*1\r\n~1\r\n#t\r\n",
+            set_of_arrays: RedisSet([[1]]) => b"~1\r\n*1\r\n:1\r\n",
+            small_i128: -5i128 => b":-5\r\n",
+            big_i128: i128::MIN => b"(-170141183460469231731687303715884105728\r\n",
+            big_u128: u128::MAX => b"(340282366920938463463374607431768211455\r\n",
+            big_u64: u64::MAX => b"(18446744073709551615\r\n",
+        }
+
+        #[test]
+        fn resp2_rejects_big_numbers() {
+            assert_eq!(to_vec(&10u128).unwrap(), b":10\r\n");
+            assert_matches!(to_vec(&u128::MAX), Err(Error::NumberOutOfRange));
+        }
+
+        #[test]
+        fn resp2_rejects_resp3_types() {
+            assert_matches!(to_vec(&1.5), Err(Error::UnsupportedType("f64")));
+            assert_matches!(
+                to_vec(&BTreeMap::from([(1, 2)])),
+                Err(Error::UnsupportedType("map"))
+            );
+            assert_matches!(
+                to_vec(&Point { x: 1, y: None }),
+                Err(Error::UnsupportedType("struct"))
+            );
+            assert_eq!(to_vec(&true).unwrap(), b":1\r\n");
+            assert_eq!(to_vec(&RedisSet([1])).unwrap(), b"*1\r\n:1\r\n");
+        }
+
+        #[test]
+        fn map_depth_limit() {
+            let data = BTreeMap::from([(1, [2])]);
+            let mut buffer = Vec::new();
+
+            let result = data.serialize(Serializer::new_resp3(&mut buffer).with_max_depth(1));
+            assert_matches!(result, Err(Error::DepthLimit(1)));
+        }
+
+        #[test]
+        fn unknown_map_length() {
+            struct Unsized;
+
+            impl Serialize for Unsized {
+                fn serialize<S: serde::Serializer>(
+                    &self,
+                    serializer: S,
+                ) -> Result<S::Ok, S::Error> {
+                    serializer.collect_map([(1, 2)].into_iter().filter(|_| true))
+                }
+            }
+
+            assert_matches!(serialize_resp3(&Unsized), Err(Error::UnknownSeqLength));
+        }
+    }
+
+    mod display_errors {
+        use super::*;
+
+        /// A `Display` that writes part of its output and then fails
+        struct Failing;
+
+        impl fmt::Display for Failing {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("partial")?;
+                Err(fmt::Error)
+            }
+        }
+
+        /// A `Display` that fails after writing more than fits in the
+        /// `collect_str` stack buffer
+        struct FailingLong;
+
+        impl fmt::Display for FailingLong {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&"a".repeat(300))?;
+                Err(fmt::Error)
+            }
+        }
+
+        struct Collected<T>(T);
+
+        impl<T: fmt::Display> Serialize for Collected<T> {
+            fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(&self.0)
+            }
+        }
+
+        #[test]
+        fn collect_str() {
+            let mut buffer = Vec::new();
+            let result = Collected(Failing).serialize(Serializer::new(&mut buffer));
+            assert_matches!(result, Err(Error::Format));
+        }
+
+        #[test]
+        fn collect_str_long() {
+            let mut buffer = Vec::new();
+            let result = Collected(FailingLong).serialize(Serializer::new(&mut buffer));
+            assert_matches!(result, Err(Error::Format));
+        }
+
+        #[test]
+        fn write_fmt() {
+            let mut buffer = Vec::new();
+            assert_matches!(
+                Output::write_fmt(&mut buffer, format_args!("{Failing}")),
+                Err(Error::Format)
+            );
+        }
+
+        #[test]
+        fn write_fmt_output_error() {
+            let mut buffer: ArrayVec<u8, 4> = ArrayVec::new();
+            assert_matches!(
+                Output::write_fmt(&mut buffer, format_args!("{}", "too long")),
+                Err(Error::BufferFull)
+            );
+        }
+    }
+
+    mod string_floats {
+        use super::*;
+
+        fn serialize_floats<T: Serialize + ?Sized>(data: &T) -> Result<Vec<u8>, Error> {
+            let mut buffer = Vec::new();
+            data.serialize(Serializer::new(&mut buffer).with_string_floats(true))?;
+            Ok(buffer)
+        }
+
+        #[test]
+        fn f64() {
+            assert_eq!(serialize_floats(&2.75).unwrap(), b"$4\r\n2.75\r\n");
+        }
+
+        #[test]
+        fn f32() {
+            assert_eq!(serialize_floats(&0.1f32).unwrap(), b"$3\r\n0.1\r\n");
+        }
+
+        #[test]
+        fn integral() {
+            assert_eq!(serialize_floats(&10.0).unwrap(), b"$2\r\n10\r\n");
+        }
+
+        #[test]
+        fn infinity() {
+            assert_eq!(serialize_floats(&f64::INFINITY).unwrap(), b"$4\r\n+inf\r\n");
+            assert_eq!(
+                serialize_floats(&f64::NEG_INFINITY).unwrap(),
+                b"$4\r\n-inf\r\n"
+            );
+            assert_eq!(serialize_floats(&f32::INFINITY).unwrap(), b"$4\r\n+inf\r\n");
+            assert_eq!(
+                serialize_floats(&f32::NEG_INFINITY).unwrap(),
+                b"$4\r\n-inf\r\n"
+            );
+        }
+
+        #[test]
+        fn nan() {
+            assert_matches!(
+                serialize_floats(&f64::NAN),
+                Err(Error::UnsupportedType("NaN"))
+            );
+            assert_matches!(
+                serialize_floats(&f32::NAN),
+                Err(Error::UnsupportedType("NaN"))
+            );
+        }
+
+        #[test]
+        fn nested() {
+            assert_eq!(
+                serialize_floats(&("ZADD", "key", [(1.5, "a")], Some(Ok::<_, ()>(-2.0)))).unwrap(),
+                b"*4\r\n$4\r\nZADD\r\n$3\r\nkey\r\n*1\r\n*2\r\n$3\r\n1.5\r\n$1\r\na\r\n$2\r\n-2\r\n"
+            );
+        }
+
+        #[test]
+        fn resp3_doubles() {
+            let mut buffer = Vec::new();
+            1.5.serialize(Serializer::new_resp3(&mut buffer).with_string_floats(true))
+                .unwrap();
+            assert_eq!(buffer, b",1.5\r\n");
+        }
+    }
+
+    mod ok_as_null {
+        use std::collections::BTreeMap;
+
+        use super::*;
+        use crate::Config;
+
+        fn serialize_config<T: Serialize + ?Sized>(
+            data: &T,
+            config: Config,
+        ) -> Result<Vec<u8>, Error> {
+            let mut buffer = Vec::new();
+            data.serialize(Serializer::with_config(&mut buffer, config))?;
+            Ok(buffer)
+        }
+
+        #[test]
+        fn default_is_plain_ok() {
+            assert_eq!(
+                serialize_config(&Ok::<(), ()>(()), Config::new()).unwrap(),
+                b"+OK\r\n"
+            );
+        }
+
+        #[test]
+        fn top_level() {
+            let config = Config::new().with_ok_as_null(true);
+
+            assert_eq!(
+                serialize_config(&Ok::<(), ()>(()), config).unwrap(),
+                b"$-1\r\n"
+            );
+            assert_eq!(
+                serialize_config(&Ok::<i64, ()>(5), config).unwrap(),
+                b":5\r\n"
+            );
+        }
+
+        #[test]
+        fn resp3() {
+            let config = Config::new()
+                .with_version(Version::Resp3)
+                .with_ok_as_null(true);
+
+            assert_eq!(
+                serialize_config(&Ok::<(), ()>(()), config).unwrap(),
+                b"_\r\n"
+            );
+        }
+
+        #[test]
+        fn nested() {
+            let config = Config::new().with_ok_as_null(true);
+
+            assert_eq!(
+                serialize_config(&[Ok::<(), ()>(()), Ok(())], config).unwrap(),
+                b"*2\r\n$-1\r\n$-1\r\n"
+            );
+            assert_eq!(
+                serialize_config(&(Some(Ok::<(), ()>(())), 1), config).unwrap(),
+                b"*2\r\n$-1\r\n:1\r\n"
+            );
+
+            let map = BTreeMap::from([("a", Ok::<(), ()>(()))]);
+            assert_eq!(
+                serialize_config(&map, config.with_version(Version::Resp3)).unwrap(),
+                b"%1\r\n$1\r\na\r\n_\r\n"
+            );
+        }
+    }
+
+    /// `to_string` and `to_vec` must produce identical bytes for any data
+    /// that can be represented as UTF-8.
+    mod wire_compat {
+        use std::collections::BTreeMap;
+
+        use super::*;
+        use crate::test_util::xorshift;
+
+        #[derive(Debug, Serialize)]
+        #[serde(untagged)]
+        enum Value {
+            Null,
+            Bool(bool),
+            Integer(i64),
+            Double(f64),
+            Char(char),
+            String(String),
+            Result(Result<(), String>),
+            Array(Vec<Value>),
+            Map(BTreeMap<String, Value>),
+        }
+
+        fn random_string(state: &mut u64) -> String {
+            let chars = ['a', 'Z', '0', ' ', '\r', '\n', 'é', 'ß', '日', '🦀', '\0'];
+            let len = xorshift(state) % 12;
+
+            (0..len)
+                .map(|_| chars[(xorshift(state) as usize) % chars.len()])
+                .collect()
+        }
+
+        fn random_value(state: &mut u64, depth: usize) -> Value {
+            let kinds = if depth == 0 { 7 } else { 9 };
+
+            match xorshift(state) % kinds {
+                0 => Value::Null,
+                1 => Value::Bool(xorshift(state) & 1 == 0),
+                2 => Value::Integer(xorshift(state) as i64),
+                3 => Value::Double(f64::from_bits(xorshift(state))),
+                4 => Value::Char(random_string(state).chars().next().unwrap_or('x')),
+                5 => Value::String(random_string(state)),
+                6 => Value::Result(match xorshift(state) % 2 {
+                    0 => Ok(()),
+                    _ => Err(random_string(state)),
+                }),
+                7 => Value::Array(
+                    (0..xorshift(state) % 4)
+                        .map(|_| random_value(state, depth - 1))
+                        .collect(),
+                ),
+                _ => Value::Map(
+                    (0..xorshift(state) % 4)
+                        .map(|_| (random_string(state), random_value(state, depth - 1)))
+                        .collect(),
+                ),
+            }
+        }
+
+        fn serialize_both(
+            value: &Value,
+            resp3: bool,
+        ) -> (Result<Vec<u8>, Error>, Result<String, Error>) {
+            let mut vec = Vec::new();
+            let mut string = String::new();
+
+            let (vec_serializer, string_serializer) = match resp3 {
+                false => (Serializer::new(&mut vec), Serializer::new(&mut string)),
+                true => (
+                    Serializer::new_resp3(&mut vec),
+                    Serializer::new_resp3(&mut string),
+                ),
+            };
+
+            (
+                value.serialize(vec_serializer).map(|()| vec),
+                value.serialize(string_serializer).map(|()| string),
+            )
+        }
+
+        #[test]
+        fn identical_output() {
+            let mut state = 0x9E3779B97F4A7C15;
+
+            for _ in 0..5_000 {
+                let value = random_value(&mut state, 3);
+
+                for resp3 in [false, true] {
+                    match serialize_both(&value, resp3) {
+                        (Ok(vec), Ok(string)) => assert_eq!(vec, string.as_bytes(), "{value:?}"),
+                        (Err(vec), Err(string)) => {
+                            assert_eq!(vec.to_string(), string.to_string(), "{value:?}")
+                        }
+                        (vec, string) => {
+                            panic!("{value:?}: to_vec gave {vec:?}, to_string gave {string:?}")
+                        }
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn binary_data() {
+            let data = Bytes::new(b"\xFF\xFE");
+
+            assert_eq!(to_vec(data).unwrap(), b"$2\r\n\xFF\xFE\r\n");
+            assert_matches!(to_string(data), Err(Error::Utf8Encode));
+        }
+
+        #[test]
+        fn utf8_bytes() {
+            let data = Bytes::new("日本".as_bytes());
+
+            assert_eq!(to_string(data).unwrap().as_bytes(), to_vec(data).unwrap());
+        }
+    }
+
+    /// Compare the total space requested with `Output::reserve` to the size
+    /// of the data that was actually written. Nested values subtract their
+    /// parent's estimate, so these shouldn't compound.
+    mod reservation {
+        use std::collections::BTreeMap;
+
+        use super::*;
+
+        #[derive(Debug, Default)]
+        struct Tracker {
+            reserved: usize,
+            written: usize,
+        }
+
+        impl Output for Tracker {
+            fn reserve(&mut self, count: usize) {
+                self.reserved += count;
+            }
+
+            fn write_str(&mut self, s: &str) -> Result<(), Error> {
+                self.write_bytes(s.as_bytes())
+            }
+
+            fn write_bytes(&mut self, b: &[u8]) -> Result<(), Error> {
+                self.written += b.len();
+                Ok(())
+            }
+        }
+
+        fn track(value: &impl Serialize, resp3: bool) -> Tracker {
+            let mut tracker = Tracker::default();
+
+            match resp3 {
+                false => value.serialize(Serializer::new(&mut tracker)),
+                true => value.serialize(Serializer::new_resp3(&mut tracker)),
+            }
+            .unwrap();
+
+            tracker
+        }
+
+        #[test]
+        fn command() {
+            let command: Vec<String> = (0..1000).map(|i| format!("key:{i}")).collect();
+            let tracker = track(&command, false);
+
+            assert_eq!(tracker.reserved, tracker.written);
+        }
+
+        #[test]
+        fn nested_arrays() {
+            let nested: Vec<Vec<String>> = (0..100)
+                .map(|i| (0..100).map(|j| format!("{i}:{j}")).collect())
+                .collect();
+            let tracker = track(&nested, false);
+
+            assert_eq!(tracker.reserved, tracker.written);
+        }
+
+        #[test]
+        fn small_elements() {
+            // Integers can be smaller than the per-element estimate, but the
+            // excess is bounded by that estimate
+            let numbers: Vec<i64> = (0..1000).collect();
+            let tracker = track(&numbers, false);
+
+            assert!(tracker.reserved >= tracker.written);
+            assert!(
+                tracker.reserved
+                    <= tracker.written + numbers.len() * primitives::ELEMENT_RESERVATION
+            );
+        }
+
+        #[test]
+        fn resp3_map() {
+            let map: BTreeMap<String, Vec<String>> = (0..100)
+                .map(|i| (format!("key:{i}"), Vec::from([format!("value:{i}")])))
+                .collect();
+            let tracker = track(&map, true);
+
+            assert_eq!(tracker.reserved, tracker.written);
+        }
+
+        #[test]
+        fn disabled() {
+            let data = (
+                "key",
+                Vec::from([Ok::<_, String>(()), Err("ERR oops".to_owned())]),
+                Some(BTreeMap::from([("a", 1.5)])),
+                1.5,
+            );
+
+            let mut tracker = Tracker::default();
+            data.serialize(Serializer::new(&mut tracker).with_reserve(false))
+                .unwrap_err();
+            assert_eq!(tracker.reserved, 0);
+
+            let mut tracker = Tracker::default();
+            data.serialize(Serializer::new_resp3(&mut tracker).with_reserve(false))
+                .unwrap();
+            assert_eq!(tracker.reserved, 0);
+            assert_ne!(tracker.written, 0);
+
+            let mut tracker = Tracker::default();
+            let config = Config::new().with_string_floats(true).with_reserve(false);
+            ("key", [1.5, 2.5])
+                .serialize(Serializer::with_config(&mut tracker, config))
+                .unwrap();
+            assert_eq!(tracker.reserved, 0);
+            assert_ne!(tracker.written, 0);
+        }
+    }
 }
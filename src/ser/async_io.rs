@@ -0,0 +1,49 @@
+use std::{future::poll_fn, io, pin::Pin};
+
+use futures_io::AsyncWrite;
+use serde::ser;
+
+use super::{to_vec, Error};
+
+/**
+Serialize an object as RESP data to a [`futures_io::AsyncWrite`] destination.
+This works with any runtime that uses the `futures-io` traits, such as
+`smol` or `async-std`.
+
+The data is first serialized into an in-memory buffer, which is then written
+in its entirety, so a serialization error never results in a partial write.
+The destination is flushed after the write.
+
+# Example
+
+```
+use seredies::ser::to_async_writer;
+
+# futures_executor::block_on(async {
+let mut buffer: Vec<u8> = Vec::new();
+to_async_writer(&["GET", "key"], &mut buffer).await.unwrap();
+assert_eq!(buffer, b"*2\r\n$3\r\nGET\r\n$3\r\nkey\r\n");
+# })
+```
+*/
+pub async fn to_async_writer<T, W>(data: &T, mut dest: W) -> Result<(), Error>
+where
+    T: ser::Serialize + ?Sized,
+    W: AsyncWrite + Unpin,
+{
+    let buffer = to_vec(data)?;
+    let mut buffer = buffer.as_slice();
+
+    while !buffer.is_empty() {
+        let written = poll_fn(|cx| Pin::new(&mut dest).poll_write(cx, buffer)).await?;
+
+        if written == 0 {
+            return Err(Error::Io(io::ErrorKind::WriteZero.into()));
+        }
+
+        buffer = &buffer[written..];
+    }
+
+    poll_fn(|cx| Pin::new(&mut dest).poll_flush(cx)).await?;
+    Ok(())
+}
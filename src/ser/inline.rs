@@ -0,0 +1,624 @@
+//! Serialization of commands in the Redis [inline command] format.
+//!
+//! [inline command]: https://redis.io/docs/reference/protocol-spec/#inline-commands
+
+use alloc::string::String;
+
+use serde::ser;
+
+use super::{Error, Output};
+
+/**
+Serialize a command in the Redis [inline command] format: a single line of
+space-separated arguments, like `SET key value\r\n`. This is the format that
+a person typing into `telnet` or `nc` would use, which makes it handy for
+debugging and logging.
+
+The command should be a sequence of strings, bytes, or integers, such as a
+tuple or a [`Command`][crate::components::Command]. Arguments that are
+empty, or that contain spaces, quotes, or non-printable bytes, are quoted
+and escaped so that Redis reads them back unchanged.
+
+# Example
+
+```
+use serde::Serialize;
+use seredies::components::Command;
+use seredies::ser::to_inline_string;
+
+#[derive(Serialize)]
+#[serde(rename = "SET")]
+struct Set<'a> {
+    key: &'a str,
+    value: &'a str,
+}
+
+let command = Command(Set {
+    key: "greeting",
+    value: "hello \"world\"",
+});
+
+assert_eq!(
+    to_inline_string(&command).unwrap(),
+    "SET greeting \"hello \\\"world\\\"\"\r\n",
+);
+
+assert_eq!(to_inline_string(&["PING"]).unwrap(), "PING\r\n");
+```
+
+[inline command]: https://redis.io/docs/reference/protocol-spec/#inline-commands
+*/
+pub fn to_inline_string<T>(command: &T) -> Result<String, Error>
+where
+    T: ser::Serialize + ?Sized,
+{
+    let mut buffer = String::new();
+    command.serialize(InlineSerializer::new(&mut buffer))?;
+    Ok(buffer)
+}
+
+/// Write a single argument, quoting and escaping it if necessary.
+fn write_argument(output: &mut impl Output, arg: &[u8]) -> Result<(), Error> {
+    let plain = |b: &u8| b.is_ascii_graphic() && *b != b'"' && *b != b'\'';
+
+    if !arg.is_empty() && arg.iter().all(plain) {
+        return output.write_bytes(arg);
+    }
+
+    output.write_str("\"")?;
+
+    // Write runs of bytes that don't need escaping all at once
+    let mut rest = arg;
+
+    while let Some(idx) = rest
+        .iter()
+        .position(|&b| !(b.is_ascii_graphic() || b == b' ') || b == b'"' || b == b'\\')
+    {
+        let (run, tail) = rest.split_at(idx);
+        output.write_bytes(run)?;
+
+        match tail[0] {
+            b'"' => output.write_str("\\\"")?,
+            b'\\' => output.write_str("\\\\")?,
+            b'\n' => output.write_str("\\n")?,
+            b'\r' => output.write_str("\\r")?,
+            b'\t' => output.write_str("\\t")?,
+            0x07 => output.write_str("\\a")?,
+            0x08 => output.write_str("\\b")?,
+            b => write!(output, "\\x{b:02x}")?,
+        }
+
+        rest = &tail[1..];
+    }
+
+    output.write_bytes(rest)?;
+    output.write_str("\"")
+}
+
+/**
+A serializer for commands in the Redis inline command format. See
+[`to_inline_string`] for details.
+
+The command must be a sequence; anything else is rejected with
+[`Error::UnsupportedType`].
+*/
+#[derive(Debug)]
+pub struct InlineSerializer<'a, O> {
+    output: &'a mut O,
+}
+
+impl<'a, O> InlineSerializer<'a, O>
+where
+    O: Output,
+{
+    /// Create a new inline serializer that will write the serialized command
+    /// to the given writer.
+    #[inline]
+    #[must_use]
+    pub fn new(writer: &'a mut O) -> Self {
+        Self { output: writer }
+    }
+}
+
+macro_rules! reject {
+    ($kind:literal: $($method:ident($($type:ty)?))*) => {$(
+        #[inline]
+        fn $method(self $(, _v: $type)?) -> Result<Self::Ok, Self::Error> {
+            Err(Error::UnsupportedType($kind))
+        }
+    )*};
+}
+
+impl<'a, O> ser::Serializer for InlineSerializer<'a, O>
+where
+    O: Output,
+{
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = SerializeInline<'a, O>;
+    type SerializeTuple = SerializeInline<'a, O>;
+    type SerializeTupleStruct = SerializeInline<'a, O>;
+    type SerializeTupleVariant = ser::Impossible<(), Error>;
+    type SerializeMap = ser::Impossible<(), Error>;
+    type SerializeStruct = ser::Impossible<(), Error>;
+    type SerializeStructVariant = ser::Impossible<(), Error>;
+
+    reject! {
+        "non-array inline command":
+        serialize_bool(bool)
+        serialize_i8(i8)
+        serialize_i16(i16)
+        serialize_i32(i32)
+        serialize_i64(i64)
+        serialize_u8(u8)
+        serialize_u16(u16)
+        serialize_u32(u32)
+        serialize_u64(u64)
+        serialize_f32(f32)
+        serialize_f64(f64)
+        serialize_char(char)
+        serialize_str(&str)
+        serialize_bytes(&[u8])
+        serialize_none()
+        serialize_unit()
+    }
+
+    #[inline]
+    fn serialize_some<T: ?Sized>(self, _value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: ser::Serialize,
+    {
+        Err(Error::UnsupportedType("non-array inline command"))
+    }
+
+    #[inline]
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        Err(Error::UnsupportedType("non-array inline command"))
+    }
+
+    #[inline]
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        Err(Error::UnsupportedType("non-array inline command"))
+    }
+
+    #[inline]
+    fn serialize_newtype_struct<T: ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ser::Serialize,
+    {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_newtype_variant<T: ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ser::Serialize,
+    {
+        Err(Error::UnsupportedType("non-array inline command"))
+    }
+
+    #[inline]
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Ok(SerializeInline {
+            output: self.output,
+            empty: true,
+        })
+    }
+
+    #[inline]
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    #[inline]
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    #[inline]
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(Error::UnsupportedType("non-array inline command"))
+    }
+
+    #[inline]
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Err(Error::UnsupportedType("non-array inline command"))
+    }
+
+    #[inline]
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Err(Error::UnsupportedType("non-array inline command"))
+    }
+
+    #[inline]
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(Error::UnsupportedType("non-array inline command"))
+    }
+}
+
+/// The inline command sequence serializer. This is used by the
+/// [`InlineSerializer`] to write each argument of the command. You should
+/// rarely need to interact with this type directly.
+#[derive(Debug)]
+pub struct SerializeInline<'a, O> {
+    output: &'a mut O,
+    empty: bool,
+}
+
+impl<O> ser::SerializeSeq for SerializeInline<'_, O>
+where
+    O: Output,
+{
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ser::Serialize,
+    {
+        if !self.empty {
+            self.output.write_str(" ")?;
+        }
+
+        self.empty = false;
+
+        value.serialize(ArgumentSerializer {
+            output: &mut *self.output,
+        })
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        match self.empty {
+            true => Err(Error::UnsupportedType("empty inline command")),
+            false => self.output.write_str("\r\n"),
+        }
+    }
+}
+
+impl<O> ser::SerializeTuple for SerializeInline<'_, O>
+where
+    O: Output,
+{
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ser::Serialize,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl<O> ser::SerializeTupleStruct for SerializeInline<'_, O>
+where
+    O: Output,
+{
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_field<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ser::Serialize,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+/// Serializer for a single argument of an inline command. Strings, bytes,
+/// and integers are allowed; everything else is rejected.
+struct ArgumentSerializer<'a, O> {
+    output: &'a mut O,
+}
+
+macro_rules! integer {
+    ($($method:ident($type:ty))*) => {$(
+        #[inline]
+        fn $method(self, v: $type) -> Result<Self::Ok, Self::Error> {
+            write!(self.output, "{v}")
+        }
+    )*};
+}
+
+impl<O> ser::Serializer for ArgumentSerializer<'_, O>
+where
+    O: Output,
+{
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = ser::Impossible<(), Error>;
+    type SerializeTuple = ser::Impossible<(), Error>;
+    type SerializeTupleStruct = ser::Impossible<(), Error>;
+    type SerializeTupleVariant = ser::Impossible<(), Error>;
+    type SerializeMap = ser::Impossible<(), Error>;
+    type SerializeStruct = ser::Impossible<(), Error>;
+    type SerializeStructVariant = ser::Impossible<(), Error>;
+
+    integer! {
+        serialize_i8(i8)
+        serialize_i16(i16)
+        serialize_i32(i32)
+        serialize_i64(i64)
+        serialize_i128(i128)
+        serialize_u8(u8)
+        serialize_u16(u16)
+        serialize_u32(u32)
+        serialize_u64(u64)
+        serialize_u128(u128)
+    }
+
+    reject! {
+        "non-string inline argument":
+        serialize_bool(bool)
+        serialize_f32(f32)
+        serialize_f64(f64)
+        serialize_none()
+        serialize_unit()
+    }
+
+    #[inline]
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
+        let mut buf = [0; 4];
+        self.serialize_str(v.encode_utf8(&mut buf))
+    }
+
+    #[inline]
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        write_argument(self.output, v.as_bytes())
+    }
+
+    #[inline]
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        write_argument(self.output, v)
+    }
+
+    #[inline]
+    fn serialize_some<T: ?Sized>(self, _value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: ser::Serialize,
+    {
+        Err(Error::UnsupportedType("non-string inline argument"))
+    }
+
+    #[inline]
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        Err(Error::UnsupportedType("non-string inline argument"))
+    }
+
+    #[inline]
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.serialize_str(variant)
+    }
+
+    #[inline]
+    fn serialize_newtype_struct<T: ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ser::Serialize,
+    {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_newtype_variant<T: ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ser::Serialize,
+    {
+        Err(Error::UnsupportedType("non-string inline argument"))
+    }
+
+    #[inline]
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Err(Error::UnsupportedType("non-string inline argument"))
+    }
+
+    #[inline]
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Err(Error::UnsupportedType("non-string inline argument"))
+    }
+
+    #[inline]
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Err(Error::UnsupportedType("non-string inline argument"))
+    }
+
+    #[inline]
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(Error::UnsupportedType("non-string inline argument"))
+    }
+
+    #[inline]
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Err(Error::UnsupportedType("non-string inline argument"))
+    }
+
+    #[inline]
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Err(Error::UnsupportedType("non-string inline argument"))
+    }
+
+    #[inline]
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(Error::UnsupportedType("non-string inline argument"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cool_asserts::assert_matches;
+    use serde::Serialize;
+    use serde_bytes::Bytes;
+
+    use super::*;
+    use crate::components::Command;
+
+    #[test]
+    fn plain_arguments() {
+        assert_eq!(
+            to_inline_string(&("SET", "key", 10)).unwrap(),
+            "SET key 10\r\n"
+        );
+    }
+
+    #[test]
+    fn empty_argument() {
+        assert_eq!(
+            to_inline_string(&("SET", "key", "")).unwrap(),
+            "SET key \"\"\r\n"
+        );
+    }
+
+    #[test]
+    fn quoted_arguments() {
+        assert_eq!(
+            to_inline_string(&("ECHO", "it's a \\ test")).unwrap(),
+            "ECHO \"it's a \\\\ test\"\r\n"
+        );
+    }
+
+    #[test]
+    fn escaped_bytes() {
+        assert_eq!(
+            to_inline_string(&("ECHO", Bytes::new(b"\r\n\t\x07\x08\x00\xff"))).unwrap(),
+            "ECHO \"\\r\\n\\t\\a\\b\\x00\\xff\"\r\n"
+        );
+    }
+
+    #[test]
+    fn command() {
+        #[derive(Serialize)]
+        #[serde(rename = "EXPIRE")]
+        struct Expire<'a> {
+            key: &'a str,
+            seconds: u64,
+            #[serde(rename = "NX")]
+            nx: bool,
+        }
+
+        let command = Command(Expire {
+            key: "my key",
+            seconds: 60,
+            nx: true,
+        });
+
+        assert_eq!(
+            to_inline_string(&command).unwrap(),
+            "EXPIRE \"my key\" 60 NX\r\n"
+        );
+    }
+
+    #[test]
+    fn rejects_non_array() {
+        assert_matches!(
+            to_inline_string("PING"),
+            Err(Error::UnsupportedType("non-array inline command"))
+        );
+    }
+
+    #[test]
+    fn rejects_empty_command() {
+        assert_matches!(
+            to_inline_string::<[&str]>(&[]),
+            Err(Error::UnsupportedType("empty inline command"))
+        );
+    }
+
+    #[test]
+    fn rejects_nested_array() {
+        assert_matches!(
+            to_inline_string(&("DEL", ["a", "b"])),
+            Err(Error::UnsupportedType("non-string inline argument"))
+        );
+    }
+
+    #[test]
+    fn rejects_null() {
+        assert_matches!(
+            to_inline_string(&("GET", None::<&str>)),
+            Err(Error::UnsupportedType("non-string inline argument"))
+        );
+    }
+}
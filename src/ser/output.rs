@@ -1,10 +1,18 @@
-use std::{fmt, io};
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io;
+
+use arrayvec::{ArrayString, ArrayVec};
 
 use super::Error;
 
 /// The [`Output`] trait is used as a destination for writing bytes by the
 /// [`Serializer`]. It serves a similar role as [`io::Write`] or [`fmt::Write`],
 /// but allows for the serializer to work in `#[no_std]` contexts.
+///
+/// `Output` is meant to be implemented for your own buffers and writers, so
+/// any methods added to it in the future will have default implementations.
 pub trait Output {
     /// Hint that there are upcoming writes totalling this number of
     /// bytes.
@@ -18,6 +26,8 @@ pub trait Output {
 
     /// Append formatted data to the output. This method allows
     /// [`Output`] objects to be used as the destination of a [`write!`] call.
+    /// If a [`Display`][fmt::Display] implementation returns an error, this
+    /// returns [`Error::Format`].
     fn write_fmt(&mut self, fmt: fmt::Arguments<'_>) -> Result<(), Error> {
         if let Some(s) = fmt.as_str() {
             return self.write_str(s);
@@ -43,14 +53,13 @@ pub trait Output {
             result: Ok(()),
         };
 
-        let res = fmt::write(&mut adapter, fmt);
-
-        debug_assert!(match adapter.result.as_ref() {
-            Ok(()) => res.is_ok(),
-            Err(_) => res.is_err(),
-        });
-
-        adapter.result
+        match (fmt::write(&mut adapter, fmt), adapter.result) {
+            (_, Err(err)) => Err(err),
+            (Ok(()), Ok(())) => Ok(()),
+            // The output didn't fail, so the error came from a `Display`
+            // implementation.
+            (Err(fmt::Error), Ok(())) => Err(Error::Format),
+        }
     }
 
     // TODO: vectored write support
@@ -105,7 +114,7 @@ impl Output for String {
 
     #[inline]
     fn write_bytes(&mut self, b: &[u8]) -> Result<(), Error> {
-        self.write_str(std::str::from_utf8(b).map_err(|_| Error::Utf8Encode)?)
+        self.write_str(core::str::from_utf8(b).map_err(|_| Error::Utf8Encode)?)
     }
 
     #[inline]
@@ -114,11 +123,210 @@ impl Output for String {
     }
 }
 
-/// [`Output`] adapter type for serializing to an [`io::Write`] object, such as a file
-/// or pipeline.
+/**
+An [`Output`] buffer that can be truncated back to an earlier length. This
+is used by [`to_buffer`][super::to_buffer] to remove a partially serialized
+value when serialization fails.
+
+It's implemented for [`Vec<u8>`], [`String`], and their fixed-size
+equivalents from [`arrayvec`].
+*/
+pub trait Truncate: Output {
+    /// The number of bytes in the buffer.
+    #[must_use]
+    fn len(&self) -> usize;
+
+    /// True if the buffer is empty.
+    #[inline]
+    #[must_use]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Shorten the buffer to `len` bytes, which is never more than its
+    /// current length, and is always the length of the buffer at some
+    /// point in the past.
+    fn truncate(&mut self, len: usize);
+}
+
+impl<T: Truncate + ?Sized> Truncate for &mut T {
+    #[inline]
+    fn len(&self) -> usize {
+        T::len(self)
+    }
+
+    #[inline]
+    fn truncate(&mut self, len: usize) {
+        T::truncate(self, len)
+    }
+}
+
+impl Truncate for Vec<u8> {
+    #[inline]
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    #[inline]
+    fn truncate(&mut self, len: usize) {
+        Vec::truncate(self, len)
+    }
+}
+
+impl Truncate for String {
+    #[inline]
+    fn len(&self) -> usize {
+        String::len(self)
+    }
+
+    #[inline]
+    fn truncate(&mut self, len: usize) {
+        String::truncate(self, len)
+    }
+}
+
+impl<const N: usize> Truncate for ArrayVec<u8, N> {
+    #[inline]
+    fn len(&self) -> usize {
+        ArrayVec::len(self)
+    }
+
+    #[inline]
+    fn truncate(&mut self, len: usize) {
+        ArrayVec::truncate(self, len)
+    }
+}
+
+impl<const N: usize> Truncate for ArrayString<N> {
+    #[inline]
+    fn len(&self) -> usize {
+        ArrayString::len(self)
+    }
+
+    #[inline]
+    fn truncate(&mut self, len: usize) {
+        ArrayString::truncate(self, len)
+    }
+}
+
+/// [`Output`] type that discards everything written to it, and only counts the
+/// number of bytes. Used by [`size_of`][super::size_of].
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct SizeCounter {
+    pub size: usize,
+}
+
+impl Output for SizeCounter {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> Result<(), Error> {
+        self.write_bytes(s.as_bytes())
+    }
+
+    #[inline]
+    fn write_bytes(&mut self, b: &[u8]) -> Result<(), Error> {
+        self.size += b.len();
+        Ok(())
+    }
+
+    #[inline]
+    fn reserve(&mut self, _count: usize) {}
+}
+
+/// Writes to a fixed-size buffer fail with [`Error::BufferFull`] once it's
+/// full, leaving whatever was written before that point in the buffer.
+impl<const N: usize> Output for ArrayVec<u8, N> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> Result<(), Error> {
+        self.write_bytes(s.as_bytes())
+    }
+
+    #[inline]
+    fn write_bytes(&mut self, b: &[u8]) -> Result<(), Error> {
+        self.try_extend_from_slice(b).map_err(|_| Error::BufferFull)
+    }
+
+    #[inline]
+    fn reserve(&mut self, _count: usize) {}
+}
+
+/// Writes to a fixed-size buffer fail with [`Error::BufferFull`] once it's
+/// full. Like [`String`], non-UTF-8 data fails with [`Error::Utf8Encode`].
+impl<const N: usize> Output for ArrayString<N> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> Result<(), Error> {
+        self.try_push_str(s).map_err(|_| Error::BufferFull)
+    }
+
+    #[inline]
+    fn write_bytes(&mut self, b: &[u8]) -> Result<(), Error> {
+        self.write_str(core::str::from_utf8(b).map_err(|_| Error::Utf8Encode)?)
+    }
+
+    #[inline]
+    fn reserve(&mut self, _count: usize) {}
+}
+
+/**
+[`Output`] adapter type for serializing to a [`fmt::Write`] object, such as a
+[`fmt::Formatter`] or a fixed-size string buffer from another crate.
+
+`fmt::Write` can only accept strings, so serializing non-UTF-8 data fails
+with [`Error::Utf8Encode`]. Because [`fmt::Error`] doesn't carry any
+information, any error from the destination is reported as
+[`Error::BufferFull`].
+
+# Example
+
+```
+use serde::Serialize;
+use seredies::ser::{FmtWrite, Serializer};
+
+let mut dest = FmtWrite(String::new());
+("SET", "key", 10).serialize(Serializer::new(&mut dest)).unwrap();
+assert_eq!(dest.0, "*3\r\n$3\r\nSET\r\n$3\r\nkey\r\n:10\r\n");
+```
+*/
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FmtWrite<T>(pub T);
+
+impl<T: fmt::Write> Output for FmtWrite<T> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> Result<(), Error> {
+        self.0.write_str(s).map_err(|_| Error::BufferFull)
+    }
+
+    #[inline]
+    fn write_bytes(&mut self, b: &[u8]) -> Result<(), Error> {
+        self.write_str(core::str::from_utf8(b).map_err(|_| Error::Utf8Encode)?)
+    }
+
+    #[inline]
+    fn write_fmt(&mut self, fmt: fmt::Arguments<'_>) -> Result<(), Error> {
+        self.0.write_fmt(fmt).map_err(|_| Error::BufferFull)
+    }
+
+    #[inline]
+    fn reserve(&mut self, _count: usize) {}
+}
+
+/**
+[`Output`] adapter type for serializing to an [`io::Write`] object, such as a file
+or pipeline.
+
+Each piece of a RESP value is passed to the writer with
+[`write_all`][io::Write::write_all] as soon as it's serialized, which retries
+writes that fail with [`ErrorKind::Interrupted`][io::ErrorKind::Interrupted].
+Any other error, including [`ErrorKind::WouldBlock`][io::ErrorKind::WouldBlock]
+from a non-blocking writer, is returned as [`Error::Io`]. When that happens,
+or when serialization fails for some other reason, part of the value may
+already have been written, so the stream can't be used for further RESP
+data. Use [`TransactionalWrite`] to avoid writing partial values.
+*/
+#[cfg(feature = "std")]
 #[derive(Debug, Clone, Copy, Default)]
 pub struct IoWrite<T>(pub T);
 
+#[cfg(feature = "std")]
 impl<T: io::Write> Output for IoWrite<T> {
     #[inline]
     fn reserve(&mut self, _count: usize) {}
@@ -138,3 +346,433 @@ impl<T: io::Write> Output for IoWrite<T> {
         self.0.write_fmt(fmt).map_err(Error::Io)
     }
 }
+
+/**
+[`Output`] adapter type that buffers serialized data in memory, and only
+hands it to an [`io::Write`] object when it's [committed][Self::commit].
+Unlike [`IoWrite`], a serialization error never leaves a partial value in
+the writer; the buffered data after the last [mark][Self::mark] can simply
+be [discarded][Self::discard], while the complete values before it stay
+buffered. `TransactionalWrite` also implements [`Truncate`], so
+[`to_buffer`][super::to_buffer] removes a failed value from it by itself.
+
+[`commit`][Self::commit] keeps track of how much of the buffer has been
+written, so if it fails partway through, such as with
+[`ErrorKind::WouldBlock`][io::ErrorKind::WouldBlock] from a non-blocking
+writer, it can be called again to write the rest. Data that has been
+written is never truncated or discarded.
+
+# Example
+
+```
+use serde::Serialize;
+use seredies::ser::{Serializer, TransactionalWrite};
+
+let mut output = TransactionalWrite::new(Vec::new());
+
+["PING"].serialize(Serializer::new(&mut output)).unwrap();
+output.mark();
+
+// Serializing a float fails partway through, so it's discarded, but the
+// `PING` before the mark is kept
+let result = ("ZADD", "key", 1.5, "member").serialize(Serializer::new(&mut output));
+assert!(result.is_err());
+output.discard();
+
+["GET", "key"].serialize(Serializer::new(&mut output)).unwrap();
+assert_eq!(output.get_ref(), b"");
+
+output.commit().unwrap();
+assert_eq!(
+    output.get_ref(),
+    b"*1\r\n$4\r\nPING\r\n*2\r\n$3\r\nGET\r\n$3\r\nkey\r\n",
+);
+```
+*/
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Default)]
+pub struct TransactionalWrite<T> {
+    writer: T,
+    buffer: Vec<u8>,
+    written: usize,
+    mark: usize,
+}
+
+#[cfg(feature = "std")]
+impl<T> TransactionalWrite<T> {
+    /// Create a new `TransactionalWrite` that commits data to the `writer`.
+    #[inline]
+    #[must_use]
+    pub fn new(writer: T) -> Self {
+        Self {
+            writer,
+            buffer: Vec::new(),
+            written: 0,
+            mark: 0,
+        }
+    }
+
+    /// The buffered data that hasn't been written yet.
+    #[inline]
+    #[must_use]
+    pub fn pending(&self) -> &[u8] {
+        &self.buffer[self.written..]
+    }
+
+    /// Mark the end of the buffered data, usually after a complete value
+    /// has been serialized. A later [`discard`][Self::discard] only drops
+    /// the data after this point.
+    #[inline]
+    pub fn mark(&mut self) {
+        self.mark = self.buffer.len();
+    }
+
+    /// Discard the buffered data after the last [mark][Self::mark], or all
+    /// of it if there's no mark since the last commit. Data that a
+    /// previously failed commit already passed to the writer is kept.
+    #[inline]
+    pub fn discard(&mut self) {
+        self.buffer.truncate(self.mark.max(self.written));
+    }
+
+    /// Get a reference to the underlying writer.
+    #[inline]
+    #[must_use]
+    pub fn get_ref(&self) -> &T {
+        &self.writer
+    }
+
+    /// Get a mutable reference to the underlying writer.
+    #[inline]
+    #[must_use]
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.writer
+    }
+
+    /// Unwrap this `TransactionalWrite`, returning the underlying writer.
+    /// Any data that hasn't been written is lost.
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.writer
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: io::Write> TransactionalWrite<T> {
+    /// Write all of the buffered data to the writer. Writes that fail with
+    /// [`ErrorKind::Interrupted`][io::ErrorKind::Interrupted] are retried;
+    /// if any other error occurs, the data that wasn't written stays
+    /// buffered, and `commit` can be called again to finish writing it.
+    /// The writer isn't flushed. A successful commit clears the mark.
+    pub fn commit(&mut self) -> io::Result<()> {
+        while self.written < self.buffer.len() {
+            match self.writer.write(&self.buffer[self.written..]) {
+                Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+                Ok(count) => self.written += count,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+
+        self.buffer.clear();
+        self.written = 0;
+        self.mark = 0;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> Output for TransactionalWrite<T> {
+    #[inline]
+    fn reserve(&mut self, count: usize) {
+        self.buffer.reserve(count)
+    }
+
+    #[inline]
+    fn write_str(&mut self, s: &str) -> Result<(), Error> {
+        self.buffer.write_str(s)
+    }
+
+    #[inline]
+    fn write_bytes(&mut self, b: &[u8]) -> Result<(), Error> {
+        self.buffer.write_bytes(b)
+    }
+}
+
+/// The length is the amount of data buffered since the last successful
+/// commit, including any that a failed commit already wrote. Truncating
+/// never removes data that was written.
+#[cfg(feature = "std")]
+impl<T> Truncate for TransactionalWrite<T> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    #[inline]
+    fn truncate(&mut self, len: usize) {
+        self.buffer.truncate(len.max(self.written));
+        self.mark = self.mark.min(self.buffer.len());
+    }
+}
+
+/**
+[`Output`] that writes to a [`Vec<u8>`], like the `Vec` itself, while
+keeping track of how the buffer grows.
+
+Services that reuse one buffer across many requests usually want to give it
+enough capacity up front that serializing never reallocates. A `CountingVec`
+records how many times the buffer reallocated, the largest capacity it
+reached, and the [`reserve`][Output::reserve] hints it received from the
+serializer, which is useful for choosing that capacity and for benchmarks.
+[`clear`][Self::clear] empties the buffer without resetting the counts, so a
+single `CountingVec` can collect statistics over a whole workload.
+
+This is only available with the `diagnostics` feature.
+
+# Example
+
+```
+use serde::Serialize;
+use seredies::ser::{CountingVec, Serializer};
+
+let mut output = CountingVec::with_capacity(8);
+
+["SET", "key", "a much longer value"]
+    .serialize(Serializer::new(&mut output))
+    .unwrap();
+
+// The buffer was too small, so it had to grow
+let stats = output.stats();
+assert!(stats.reallocations > 0);
+assert!(stats.peak_capacity >= output.get_ref().len());
+assert!(stats.reserve_requests > 0);
+
+// Reusing the buffer doesn't reset the statistics, and now it's big enough
+output.clear();
+["GET", "key"].serialize(Serializer::new(&mut output)).unwrap();
+assert_eq!(output.stats().reallocations, stats.reallocations);
+```
+*/
+#[cfg(feature = "diagnostics")]
+#[derive(Debug, Clone, Default)]
+pub struct CountingVec {
+    buffer: Vec<u8>,
+    stats: OutputStats,
+}
+
+/// Statistics about the growth of a [`CountingVec`].
+#[cfg(feature = "diagnostics")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct OutputStats {
+    /// The number of times the buffer's capacity changed, which includes its
+    /// first allocation if it started out empty.
+    pub reallocations: usize,
+
+    /// The largest capacity that the buffer had.
+    pub peak_capacity: usize,
+
+    /// The number of calls to [`reserve`][Output::reserve].
+    pub reserve_requests: usize,
+
+    /// The total number of bytes requested by calls to
+    /// [`reserve`][Output::reserve].
+    pub reserved_bytes: usize,
+}
+
+#[cfg(feature = "diagnostics")]
+impl CountingVec {
+    /// Create a new, empty `CountingVec`.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new, empty `CountingVec` with at least the given capacity.
+    /// This initial allocation isn't counted as a reallocation.
+    #[inline]
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::from_vec(Vec::with_capacity(capacity))
+    }
+
+    /// Create a `CountingVec` that appends to an existing buffer.
+    #[inline]
+    #[must_use]
+    pub fn from_vec(buffer: Vec<u8>) -> Self {
+        Self {
+            stats: OutputStats {
+                peak_capacity: buffer.capacity(),
+                ..OutputStats::default()
+            },
+            buffer,
+        }
+    }
+
+    /// The statistics collected so far.
+    #[inline]
+    #[must_use]
+    pub fn stats(&self) -> OutputStats {
+        self.stats
+    }
+
+    /// Reset the statistics, as though the buffer had just been created
+    /// with its current capacity.
+    #[inline]
+    pub fn reset_stats(&mut self) {
+        self.stats = OutputStats {
+            peak_capacity: self.buffer.capacity(),
+            ..OutputStats::default()
+        };
+    }
+
+    /// Empty the buffer, keeping its capacity and the statistics.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.buffer.clear()
+    }
+
+    /// Get a reference to the buffer.
+    #[inline]
+    #[must_use]
+    pub fn get_ref(&self) -> &Vec<u8> {
+        &self.buffer
+    }
+
+    /// Unwrap this `CountingVec`, returning the buffer.
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> Vec<u8> {
+        self.buffer
+    }
+
+    /// Run an operation on the buffer, and count it as a reallocation if it
+    /// changed the capacity.
+    #[inline]
+    fn track<R>(&mut self, op: impl FnOnce(&mut Vec<u8>) -> R) -> R {
+        let capacity = self.buffer.capacity();
+        let result = op(&mut self.buffer);
+
+        if self.buffer.capacity() != capacity {
+            self.stats.reallocations += 1;
+            self.stats.peak_capacity = self.stats.peak_capacity.max(self.buffer.capacity());
+        }
+
+        result
+    }
+}
+
+#[cfg(feature = "diagnostics")]
+impl Output for CountingVec {
+    #[inline]
+    fn reserve(&mut self, count: usize) {
+        self.stats.reserve_requests += 1;
+        self.stats.reserved_bytes = self.stats.reserved_bytes.saturating_add(count);
+        self.track(|buffer| buffer.reserve(count))
+    }
+
+    #[inline]
+    fn write_str(&mut self, s: &str) -> Result<(), Error> {
+        self.write_bytes(s.as_bytes())
+    }
+
+    #[inline]
+    fn write_bytes(&mut self, b: &[u8]) -> Result<(), Error> {
+        self.track(|buffer| buffer.write_bytes(b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cool_asserts::assert_matches;
+    use serde::Serialize;
+
+    use super::*;
+    use crate::ser::Serializer;
+
+    fn serialize_into<O: Output>(output: &mut O) -> Result<(), Error> {
+        ("SET", "key", 10).serialize(Serializer::new(output))
+    }
+
+    const EXPECTED: &str = "*3\r\n$3\r\nSET\r\n$3\r\nkey\r\n:10\r\n";
+
+    #[test]
+    fn array_vec() {
+        let mut output: ArrayVec<u8, 64> = ArrayVec::new();
+        serialize_into(&mut output).unwrap();
+        assert_eq!(output.as_slice(), EXPECTED.as_bytes());
+
+        let mut output: ArrayVec<u8, 16> = ArrayVec::new();
+        assert_matches!(serialize_into(&mut output), Err(Error::BufferFull));
+    }
+
+    #[test]
+    fn array_string() {
+        let mut output: ArrayString<64> = ArrayString::new();
+        serialize_into(&mut output).unwrap();
+        assert_eq!(output.as_str(), EXPECTED);
+
+        let mut output: ArrayString<16> = ArrayString::new();
+        assert_matches!(serialize_into(&mut output), Err(Error::BufferFull));
+
+        let mut output: ArrayString<64> = ArrayString::new();
+        assert_matches!(output.write_bytes(b"\xff"), Err(Error::Utf8Encode));
+    }
+
+    #[test]
+    fn fmt_write() {
+        let mut output = FmtWrite(ArrayString::<64>::new());
+        serialize_into(&mut output).unwrap();
+        assert_eq!(output.0.as_str(), EXPECTED);
+
+        let mut output = FmtWrite(ArrayString::<16>::new());
+        assert_matches!(serialize_into(&mut output), Err(Error::BufferFull));
+    }
+
+    #[test]
+    #[cfg(feature = "diagnostics")]
+    fn counts_growth() {
+        let mut output = CountingVec::new();
+        assert_eq!(output.stats(), OutputStats::default());
+
+        output.write_bytes(b"abc").unwrap();
+        let stats = output.stats();
+        assert_eq!(stats.reallocations, 1);
+        assert_eq!(stats.peak_capacity, output.get_ref().capacity());
+        assert_eq!(stats.reserve_requests, 0);
+
+        output.reserve(1000);
+        let stats = output.stats();
+        assert_eq!(stats.reallocations, 2);
+        assert!(stats.peak_capacity >= 1003);
+        assert_eq!(stats.reserve_requests, 1);
+        assert_eq!(stats.reserved_bytes, 1000);
+
+        // There's already enough room for these
+        output.reserve(10);
+        output.write_str("defg").unwrap();
+        let stats = output.stats();
+        assert_eq!(stats.reallocations, 2);
+        assert_eq!(stats.reserve_requests, 2);
+        assert_eq!(stats.reserved_bytes, 1010);
+
+        assert_eq!(output.into_inner(), b"abcdefg");
+    }
+
+    #[test]
+    #[cfg(feature = "diagnostics")]
+    fn reset() {
+        let mut output = CountingVec::new();
+        output.write_bytes(&[0; 100]).unwrap();
+        output.clear();
+        output.reset_stats();
+
+        let stats = output.stats();
+        assert_eq!(stats.reallocations, 0);
+        assert!(stats.peak_capacity >= 100);
+        assert!(output.get_ref().is_empty());
+    }
+}
@@ -2,7 +2,10 @@
 Basic implementations of serialize primitives for RESP
 */
 
+use core::fmt;
+
 use super::{Error, Output};
+use crate::protocol::{Tag, CRLF};
 
 /// Helper trait for writing things to `Output`, using the best available
 /// method. Abstracts over `str` and `[u8]`.
@@ -20,7 +23,6 @@ impl Writable for [u8] {
     }
 
     #[inline]
-    #[must_use]
     fn len(&self) -> usize {
         self.len()
     }
@@ -33,7 +35,6 @@ impl Writable for str {
     }
 
     #[inline]
-    #[must_use]
     fn len(&self) -> usize {
         self.len()
     }
@@ -54,21 +55,20 @@ pub const fn estimate_number_reservation(value: i64) -> usize {
 
 /**
 Write a redis header containing a numeric `value` to the `output`, using the
-`prefix`. This method will reserve space in the `output` sufficient to contain
+`tag`. This method will reserve space in the `output` sufficient to contain
 the header, plus additional space equal to `suffix_reserve`.
 */
 fn serialize_header(
     mut output: impl Output,
-    prefix: u8,
+    tag: Tag,
     value: impl TryInto<i64>,
     suffix_reserve: usize,
 ) -> Result<(), Error> {
-    let prefix = prefix as char;
-    debug_assert!("*:$".contains(prefix));
+    let prefix = tag.as_str();
 
     let value: i64 = value.try_into().map_err(|_| Error::NumberOutOfRange)?;
 
-    let width = (estimate_number_reservation(value) as usize)
+    let width = estimate_number_reservation(value)
         .saturating_add(3) // the width of the prefix byte and the CRLF
         .saturating_add(suffix_reserve);
 
@@ -81,9 +81,30 @@ Serialize a plain Redis number
 */
 #[inline]
 pub fn serialize_number(output: impl Output, value: impl TryInto<i64>) -> Result<(), Error> {
-    serialize_header(output, b':', value, 0)
+    serialize_header(output, Tag::Integer, value, 0)
 }
 
+/**
+Serialize a RESP3 big number. `value` is written with its `Display`
+implementation, so it must format as an optionally signed decimal integer.
+*/
+#[inline]
+pub fn serialize_big_number(
+    mut output: impl Output,
+    value: impl fmt::Display,
+) -> Result<(), Error> {
+    let prefix = Tag::BigNumber.as_str();
+    write!(output, "{prefix}{value}\r\n")
+}
+
+/**
+The number of bytes reserved for each element of an array, as part of the
+reservation for the array itself. By far the most common thing we serialize
+is a bulk string (for a command), and the smallest bulk string (an empty one)
+is 6 bytes, so that's the estimate we use.
+*/
+pub const ELEMENT_RESERVATION: usize = 6;
+
 /**
 Given an array of length `len`, estimate how many bytes are reasonable
 to reserve in an output buffer that will contain that array. This should
@@ -93,10 +114,7 @@ the data that is *likely* to be contained.
 #[inline]
 #[must_use]
 pub const fn estimate_array_reservation(len: usize) -> usize {
-    // By far the most common thing we serialize is a bulk string (for a
-    // command), and the smallest bulk string (an empty one) is 6 bytes, so
-    // that's the factor we use.
-    len.saturating_mul(6)
+    len.saturating_mul(ELEMENT_RESERVATION)
 }
 
 /**
@@ -105,7 +123,58 @@ elements to be serialized after this header.
 */
 #[inline]
 pub fn serialize_array_header(output: impl Output, len: usize) -> Result<(), Error> {
-    serialize_header(output, b'*', len, estimate_array_reservation(len))
+    serialize_header(output, Tag::Array, len, estimate_array_reservation(len))
+}
+
+/**
+Serialize the header for a RESP3 map of `len` key-value pairs. RESP will
+expect `len * 2` elements to be serialized after this header.
+*/
+#[inline]
+pub fn serialize_map_header(output: impl Output, len: usize) -> Result<(), Error> {
+    serialize_header(
+        output,
+        Tag::Map,
+        len,
+        estimate_array_reservation(len.saturating_mul(2)),
+    )
+}
+
+/**
+Serialize the header for a RESP3 set of `len` elements.
+*/
+#[inline]
+pub fn serialize_set_header(output: impl Output, len: usize) -> Result<(), Error> {
+    serialize_header(output, Tag::Set, len, estimate_array_reservation(len))
+}
+
+/**
+Serialize a RESP3 boolean
+*/
+#[inline]
+pub fn serialize_boolean(mut output: impl Output, value: bool) -> Result<(), Error> {
+    output.write_str(match value {
+        true => "#t\r\n",
+        false => "#f\r\n",
+    })
+}
+
+/**
+Serialize a RESP3 double. Infinities are written as `inf` and `-inf`, and
+NaN is written as `nan`. `value` is formatted with its own `Display`
+implementation, so that an `f32` is written with the shortest representation
+that round-trips as an `f32`.
+*/
+pub fn serialize_double<T>(mut output: impl Output, value: T) -> Result<(), Error>
+where
+    T: fmt::Display + Into<f64> + Copy,
+{
+    let prefix = Tag::Double.as_str();
+
+    match value.into().is_nan() {
+        true => output.write_str(",nan\r\n"),
+        false => write!(output, "{prefix}{value}\r\n"),
+    }
 }
 
 /**
@@ -117,9 +186,27 @@ pub fn serialize_bulk_string(
 ) -> Result<(), Error> {
     let len = value.len();
 
-    serialize_header(&mut output, b'$', len, len.saturating_add(2))?;
+    serialize_header(&mut output, Tag::BulkString, len, len.saturating_add(2))?;
     value.write_to_output(&mut output)?;
-    output.write_str("\r\n")
+    output.write_str(CRLF)
+}
+
+/**
+Serialize something writable as a RESP3 Verbatim String, with the given
+format
+*/
+pub fn serialize_verbatim(
+    mut output: impl Output,
+    format: &[u8; 3],
+    value: &(impl Writable + ?Sized),
+) -> Result<(), Error> {
+    let len = value.len().saturating_add(4);
+
+    serialize_header(&mut output, Tag::Verbatim, len, len.saturating_add(2))?;
+    output.write_bytes(format)?;
+    output.write_str(":")?;
+    value.write_to_output(&mut output)?;
+    output.write_str(CRLF)
 }
 
 /**
@@ -152,12 +239,90 @@ impl<O: Output> Output for NewlineRejector<O> {
     }
 }
 
+/**
+An `Output` adapter for a value that its parent already reserved some space
+for, such as an array element (which is covered by the array's
+[`ELEMENT_RESERVATION`]). The first reservation made through this adapter is
+reduced by that amount, so that the estimates of nested values are
+subtracted from, rather than added on top of, their parent's estimate.
+If reservations are disabled, none of them are passed along.
+*/
+pub struct Prereserved<O: Output> {
+    output: O,
+    reserved: usize,
+    enabled: bool,
+}
+
+impl<O: Output> Prereserved<O> {
+    #[inline]
+    #[must_use]
+    pub fn new(output: O, reserved: usize, enabled: bool) -> Self {
+        Self {
+            output,
+            reserved,
+            enabled,
+        }
+    }
+}
+
+impl<O: Output> Output for Prereserved<O> {
+    #[inline]
+    fn reserve(&mut self, count: usize) {
+        if self.enabled {
+            self.output.reserve(count.saturating_sub(self.reserved));
+        }
+
+        self.reserved = 0;
+    }
+
+    #[inline]
+    fn write_str(&mut self, s: &str) -> Result<(), Error> {
+        self.output.write_str(s)
+    }
+
+    #[inline]
+    fn write_bytes(&mut self, b: &[u8]) -> Result<(), Error> {
+        self.output.write_bytes(b)
+    }
+
+    #[inline]
+    fn write_fmt(&mut self, fmt: fmt::Arguments<'_>) -> Result<(), Error> {
+        self.output.write_fmt(fmt)
+    }
+}
+
 #[inline]
 #[must_use]
 fn has_newline(data: &[u8]) -> bool {
     memchr::memchr2(b'\n', b'\r', data).is_some()
 }
 
+/**
+Serialize a RESP Simple String
+*/
+pub fn serialize_simple_string(
+    mut dest: impl Output,
+    value: &(impl Writable + ?Sized),
+) -> Result<(), Error> {
+    dest.reserve(value.len().saturating_add(3));
+    dest.write_str(Tag::SimpleString.as_str())?;
+    value.write_to_output(NewlineRejector(&mut dest))?;
+    dest.write_str(CRLF)
+}
+
+/**
+Serialize a RESP simple string from a `Display` value, which is formatted
+directly into the output.
+*/
+pub fn serialize_simple_string_display(
+    mut dest: impl Output,
+    value: &(impl fmt::Display + ?Sized),
+) -> Result<(), Error> {
+    dest.write_str(Tag::SimpleString.as_str())?;
+    write!(NewlineRejector(&mut dest), "{value}")?;
+    dest.write_str(CRLF)
+}
+
 /**
 Serialize a RESP error
 */
@@ -166,7 +331,29 @@ pub fn serialize_error(
     value: &(impl Writable + ?Sized),
 ) -> Result<(), Error> {
     dest.reserve(value.len().saturating_add(3));
-    dest.write_str("-")?;
+    dest.write_str(Tag::Error.as_str())?;
     value.write_to_output(NewlineRejector(&mut dest))?;
-    dest.write_str("\r\n")
+    dest.write_str(CRLF)
+}
+
+/**
+Serialize one space-separated part of a structured RESP error, such as the
+error code. The caller is responsible for writing the error prefix, the
+separators, and the trailing CRLF.
+*/
+#[inline]
+pub fn serialize_error_part(
+    mut dest: impl Output,
+    value: &(impl Writable + ?Sized),
+) -> Result<(), Error> {
+    value.write_to_output(NewlineRejector(&mut dest))
+}
+
+/**
+Begin a structured RESP error, which is written in parts with
+`serialize_error_part`. Finish it with `CRLF`.
+*/
+#[inline]
+pub fn serialize_error_prefix(mut dest: impl Output) -> Result<(), Error> {
+    dest.write_str(Tag::Error.as_str())
 }
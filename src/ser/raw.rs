@@ -0,0 +1,256 @@
+//! Serialization of [`RawValue`][crate::value::RawValue], which writes
+//! already-encoded RESP data to the output verbatim.
+
+use serde::ser;
+
+use super::{Error, Output};
+use crate::{
+    de::parse::read_value_shape,
+    protocol::{Tag, Version},
+};
+
+/// The newtype struct name used by [`RawValue`][crate::value::RawValue] to
+/// request that its bytes be written to the output as-is, rather than as a
+/// Bulk String.
+pub(crate) const RAW_VALUE_TOKEN: &str = "$seredies::RawValue";
+
+/// Serializer for the contents of a [`RawValue`][crate::value::RawValue].
+/// The only thing it accepts is bytes, which are assumed to already be a
+/// single, complete RESP value; `RawValue` checks this when it's created.
+/// When the output is RESP2, the value must not contain any RESP3 types.
+#[derive(Debug)]
+pub(super) struct RawValueSerializer<O> {
+    pub output: O,
+    pub version: Version,
+}
+
+#[inline]
+fn not_raw<T>() -> Result<T, Error> {
+    Err(Error::UnsupportedType("non-bytes raw value"))
+}
+
+/// Check that every frame in a complete RESP value has a type that exists in
+/// RESP2.
+fn check_resp2(mut input: &[u8]) -> Result<(), Error> {
+    let mut pending: usize = 1;
+
+    while pending > 0 {
+        if let Some(Tag::Verbatim | Tag::Push | Tag::BigNumber) =
+            input.first().copied().and_then(Tag::from_byte)
+        {
+            return Err(Error::UnsupportedType("RESP3 raw values in RESP2"));
+        }
+
+        let (children, tail) =
+            read_value_shape(input).map_err(|_| Error::UnsupportedType("malformed raw value"))?;
+
+        input = tail;
+        pending = (pending - 1)
+            .checked_add(children)
+            .ok_or(Error::UnsupportedType("malformed raw value"))?;
+    }
+
+    Ok(())
+}
+
+impl<O: Output> ser::Serializer for RawValueSerializer<O> {
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = ser::Impossible<(), Error>;
+    type SerializeTuple = ser::Impossible<(), Error>;
+    type SerializeTupleStruct = ser::Impossible<(), Error>;
+    type SerializeTupleVariant = ser::Impossible<(), Error>;
+    type SerializeMap = ser::Impossible<(), Error>;
+    type SerializeStruct = ser::Impossible<(), Error>;
+    type SerializeStructVariant = ser::Impossible<(), Error>;
+
+    #[inline]
+    fn serialize_bytes(mut self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        if self.version == Version::Resp2 {
+            check_resp2(v)?;
+        }
+
+        self.output.reserve(v.len());
+        self.output.write_bytes(v)
+    }
+
+    #[inline]
+    fn serialize_bool(self, _v: bool) -> Result<Self::Ok, Self::Error> {
+        not_raw()
+    }
+
+    #[inline]
+    fn serialize_i8(self, _v: i8) -> Result<Self::Ok, Self::Error> {
+        not_raw()
+    }
+
+    #[inline]
+    fn serialize_i16(self, _v: i16) -> Result<Self::Ok, Self::Error> {
+        not_raw()
+    }
+
+    #[inline]
+    fn serialize_i32(self, _v: i32) -> Result<Self::Ok, Self::Error> {
+        not_raw()
+    }
+
+    #[inline]
+    fn serialize_i64(self, _v: i64) -> Result<Self::Ok, Self::Error> {
+        not_raw()
+    }
+
+    #[inline]
+    fn serialize_u8(self, _v: u8) -> Result<Self::Ok, Self::Error> {
+        not_raw()
+    }
+
+    #[inline]
+    fn serialize_u16(self, _v: u16) -> Result<Self::Ok, Self::Error> {
+        not_raw()
+    }
+
+    #[inline]
+    fn serialize_u32(self, _v: u32) -> Result<Self::Ok, Self::Error> {
+        not_raw()
+    }
+
+    #[inline]
+    fn serialize_u64(self, _v: u64) -> Result<Self::Ok, Self::Error> {
+        not_raw()
+    }
+
+    #[inline]
+    fn serialize_f32(self, _v: f32) -> Result<Self::Ok, Self::Error> {
+        not_raw()
+    }
+
+    #[inline]
+    fn serialize_f64(self, _v: f64) -> Result<Self::Ok, Self::Error> {
+        not_raw()
+    }
+
+    #[inline]
+    fn serialize_char(self, _v: char) -> Result<Self::Ok, Self::Error> {
+        not_raw()
+    }
+
+    #[inline]
+    fn serialize_str(self, _v: &str) -> Result<Self::Ok, Self::Error> {
+        not_raw()
+    }
+
+    #[inline]
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        not_raw()
+    }
+
+    #[inline]
+    fn serialize_some<T: ?Sized>(self, _value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: ser::Serialize,
+    {
+        not_raw()
+    }
+
+    #[inline]
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        not_raw()
+    }
+
+    #[inline]
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        not_raw()
+    }
+
+    #[inline]
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        not_raw()
+    }
+
+    #[inline]
+    fn serialize_newtype_struct<T: ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ser::Serialize,
+    {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_newtype_variant<T: ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ser::Serialize,
+    {
+        not_raw()
+    }
+
+    #[inline]
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        not_raw()
+    }
+
+    #[inline]
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        not_raw()
+    }
+
+    #[inline]
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        not_raw()
+    }
+
+    #[inline]
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        not_raw()
+    }
+
+    #[inline]
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        not_raw()
+    }
+
+    #[inline]
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        not_raw()
+    }
+
+    #[inline]
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        not_raw()
+    }
+}
@@ -0,0 +1,11 @@
+//! Helpers shared by the tests in several modules.
+
+/// A tiny deterministic xorshift generator, so that randomized tests are
+/// reproducible and don't need any extra dependencies. `state` must not be
+/// zero.
+pub(crate) fn xorshift(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
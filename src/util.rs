@@ -0,0 +1,6 @@
+/*!
+Protocol-adjacent helpers that don't involve serialization, for embedders
+that need them without depending on a full client.
+*/
+
+pub mod uri;
@@ -0,0 +1,1023 @@
+/*!
+An owned representation of arbitrary RESP data.
+
+[`Value`] can hold any RESP value, without knowing its shape ahead of time.
+This is useful for proxies, debugging tools, and tests, which need to
+inspect or forward data without a dedicated Rust type for it.
+
+# Example
+
+```
+use seredies::{de::from_bytes, ser::to_vec, value::Value};
+
+let data = b"*4\r\n+OK\r\n:10\r\n$5\r\nhello\r\n$-1\r\n";
+let value: Value = from_bytes(data).unwrap();
+
+assert_eq!(
+    value,
+    Value::Array(Vec::from([
+        Value::ok(),
+        Value::Integer(10),
+        Value::from("hello"),
+        Value::Null,
+    ])),
+);
+
+assert_eq!(to_vec(&value).unwrap(), data);
+```
+
+When data only needs to be forwarded, [`RawValue`] skips the work of
+building a `Value` at all: it borrows the bytes of a single RESP value
+straight from the input, and writes them back out unchanged.
+*/
+
+use alloc::{borrow::ToOwned, collections::BTreeMap, string::String, vec::Vec};
+use core::{fmt, marker::PhantomData};
+
+use serde::{de, forward_to_deserialize_any, ser};
+
+use crate::{
+    de::{parse, VALUE_TOKEN},
+    ser::{RAW_VALUE_TOKEN, SIMPLE_STRING_TOKEN},
+};
+
+/**
+Any RESP value.
+
+When deserialized with the seredies [`Deserializer`][crate::de::Deserializer],
+each variant corresponds exactly to a RESP type, so a `Value` can be
+serialized back into the same bytes it was deserialized from. In particular,
+RESP [Errors] are deserialized into [`Value::Error`] instead of failing
+deserialization.
+
+With other deserializers, `Value` falls back to a best-effort conversion:
+strings and bytes become [`Value::BulkString`], integers and booleans become
+[`Value::Integer`], nulls become [`Value::Null`], and sequences become
+[`Value::Array`].
+
+[Errors]: https://redis.io/docs/reference/protocol-spec/#resp-errors
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum Value {
+    /// A RESP null.
+    #[default]
+    Null,
+
+    /// A RESP [Integer](https://redis.io/docs/reference/protocol-spec/#resp-integers).
+    Integer(i64),
+
+    /// A RESP [Simple String](https://redis.io/docs/reference/protocol-spec/#resp-simple-strings).
+    /// This may not contain `\r` or `\n`.
+    SimpleString(String),
+
+    /// A RESP [Bulk String](https://redis.io/docs/reference/protocol-spec/#resp-bulk-strings).
+    BulkString(Vec<u8>),
+
+    /// A RESP [Array](https://redis.io/docs/reference/protocol-spec/#resp-arrays).
+    Array(Vec<Value>),
+
+    /// A RESP [Error](https://redis.io/docs/reference/protocol-spec/#resp-errors).
+    /// This may not contain `\r` or `\n`.
+    Error(String),
+}
+
+impl Value {
+    /// The `+OK` simple string, which Redis uses as the response to many
+    /// commands.
+    #[inline]
+    #[must_use]
+    pub fn ok() -> Self {
+        Self::SimpleString("OK".to_owned())
+    }
+
+    /// Check if this value is [`Value::Null`].
+    #[inline]
+    #[must_use]
+    pub fn is_null(&self) -> bool {
+        matches!(*self, Self::Null)
+    }
+
+    /// Get the number in this value, if it's an [`Integer`][Value::Integer].
+    #[inline]
+    #[must_use]
+    pub fn as_integer(&self) -> Option<i64> {
+        match *self {
+            Self::Integer(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Get the content of this value, if it's a [Bulk String][Value::BulkString]
+    /// or a [Simple String][Value::SimpleString].
+    #[inline]
+    #[must_use]
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match *self {
+            Self::SimpleString(ref value) => Some(value.as_bytes()),
+            Self::BulkString(ref value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Get the content of this value as a `str`, if it's a
+    /// [Bulk String][Value::BulkString] containing UTF-8 or a
+    /// [Simple String][Value::SimpleString].
+    #[inline]
+    #[must_use]
+    pub fn as_str(&self) -> Option<&str> {
+        self.as_bytes()
+            .and_then(|bytes| core::str::from_utf8(bytes).ok())
+    }
+
+    /// Get the elements of this value, if it's an [`Array`][Value::Array].
+    #[inline]
+    #[must_use]
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match *self {
+            Self::Array(ref elements) => Some(elements),
+            _ => None,
+        }
+    }
+
+    /// Get the message in this value, if it's an [`Error`][Value::Error].
+    #[inline]
+    #[must_use]
+    pub fn as_error(&self) -> Option<&str> {
+        match *self {
+            Self::Error(ref message) => Some(message),
+            _ => None,
+        }
+    }
+
+    /**
+    Convert an [`Array`][Value::Array] of flattened key-value pairs, like the
+    ones in a [`LooseMap`][crate::components::LooseMap], into a map. Every
+    key must be a string (as in [`as_str`][Value::as_str]); if a key appears
+    more than once, the last value is kept. If this value isn't an array of
+    pairs, it's returned unchanged as the error.
+
+    # Example
+
+    ```
+    use seredies::value::Value;
+
+    let value = Value::from_iter([Value::from("keys"), Value::Integer(3)]);
+    let map = value.into_map().unwrap();
+
+    assert_eq!(map["keys"], Value::Integer(3));
+    assert!(Value::Integer(3).into_map().is_err());
+    ```
+    */
+    pub fn into_map(self) -> Result<BTreeMap<String, Value>, Self> {
+        let elements = match self {
+            Self::Array(elements)
+                if elements.len() % 2 == 0
+                    && elements.iter().step_by(2).all(|key| key.as_str().is_some()) =>
+            {
+                elements
+            }
+            value => return Err(value),
+        };
+
+        let mut elements = elements.into_iter();
+        let mut map = BTreeMap::new();
+
+        while let (Some(key), Some(value)) = (elements.next(), elements.next()) {
+            let key = match key {
+                Self::SimpleString(key) => key,
+                Self::BulkString(key) => {
+                    String::from_utf8(key).expect("keys were already checked for UTF-8")
+                }
+                _ => unreachable!("keys were already checked to be strings"),
+            };
+
+            map.insert(key, value);
+        }
+
+        Ok(map)
+    }
+}
+
+impl From<i64> for Value {
+    #[inline]
+    fn from(value: i64) -> Self {
+        Self::Integer(value)
+    }
+}
+
+impl From<&str> for Value {
+    #[inline]
+    fn from(value: &str) -> Self {
+        Self::BulkString(value.as_bytes().to_vec())
+    }
+}
+
+impl From<String> for Value {
+    #[inline]
+    fn from(value: String) -> Self {
+        Self::BulkString(value.into_bytes())
+    }
+}
+
+impl From<&[u8]> for Value {
+    #[inline]
+    fn from(value: &[u8]) -> Self {
+        Self::BulkString(value.to_vec())
+    }
+}
+
+impl From<Vec<u8>> for Value {
+    #[inline]
+    fn from(value: Vec<u8>) -> Self {
+        Self::BulkString(value)
+    }
+}
+
+impl From<Vec<Value>> for Value {
+    #[inline]
+    fn from(value: Vec<Value>) -> Self {
+        Self::Array(value)
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    #[inline]
+    fn from(value: Option<T>) -> Self {
+        value.map(Into::into).unwrap_or(Self::Null)
+    }
+}
+
+impl FromIterator<Value> for Value {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = Value>>(iter: I) -> Self {
+        Self::Array(iter.into_iter().collect())
+    }
+}
+
+impl ser::Serialize for Value {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        match *self {
+            Self::Null => serializer.serialize_none(),
+            Self::Integer(value) => serializer.serialize_i64(value),
+            Self::SimpleString(ref value) => {
+                serializer.serialize_newtype_struct(SIMPLE_STRING_TOKEN, value.as_str())
+            }
+            Self::BulkString(ref value) => serializer.serialize_bytes(value),
+            Self::Array(ref elements) => elements.serialize(serializer),
+            Self::Error(ref message) => {
+                serializer.serialize_newtype_variant("Result", 1, "Err", message.as_str())
+            }
+        }
+    }
+}
+
+impl<'de> de::Deserialize<'de> for Value {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_newtype_struct(VALUE_TOKEN, ValueVisitor)
+    }
+}
+
+/// The RESP type of a value, as reported by the seredies deserializer when
+/// it sees the `VALUE_TOKEN`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Kind {
+    Null,
+    Integer,
+    SimpleString,
+    BulkString,
+    Array,
+    Error,
+}
+
+impl<'de> de::Deserialize<'de> for Kind {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_identifier(KindVisitor)
+    }
+}
+
+struct KindVisitor;
+
+impl de::Visitor<'_> for KindVisitor {
+    type Value = Kind;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a RESP type")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        match v {
+            "Null" => Ok(Kind::Null),
+            "Integer" => Ok(Kind::Integer),
+            "SimpleString" => Ok(Kind::SimpleString),
+            "BulkString" => Ok(Kind::BulkString),
+            "Array" => Ok(Kind::Array),
+            "Error" => Ok(Kind::Error),
+            _ => Err(E::unknown_variant(v, VARIANTS)),
+        }
+    }
+}
+
+impl Kind {
+    /// A description of this type, for error messages.
+    #[inline]
+    #[must_use]
+    pub(crate) const fn description(self) -> &'static str {
+        match self {
+            Kind::Null => "null",
+            Kind::Integer => "integer",
+            Kind::SimpleString => "simple string",
+            Kind::BulkString => "bulk string",
+            Kind::Array => "array",
+            Kind::Error => "error",
+        }
+    }
+}
+
+const VARIANTS: &[&str] = &[
+    "Null",
+    "Integer",
+    "SimpleString",
+    "BulkString",
+    "Array",
+    "Error",
+];
+
+/// The payload of a Bulk String, which (unlike a `Vec<u8>`) is deserialized
+/// from bytes rather than from a sequence.
+struct BulkString(Vec<u8>);
+
+impl<'de> de::Deserialize<'de> for BulkString {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_byte_buf(BulkStringVisitor)
+    }
+}
+
+struct BulkStringVisitor;
+
+impl de::Visitor<'_> for BulkStringVisitor {
+    type Value = BulkString;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a bulk string")
+    }
+
+    #[inline]
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(BulkString(v.to_vec()))
+    }
+
+    #[inline]
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(BulkString(v))
+    }
+}
+
+struct ValueVisitor;
+
+impl<'de> de::Visitor<'de> for ValueVisitor {
+    type Value = Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("any RESP value")
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::EnumAccess<'de>,
+    {
+        use de::VariantAccess as _;
+
+        let (kind, variant) = data.variant()?;
+
+        match kind {
+            Kind::Null => variant.unit_variant().map(|()| Value::Null),
+            Kind::Integer => variant.newtype_variant().map(Value::Integer),
+            Kind::SimpleString => variant.newtype_variant().map(Value::SimpleString),
+            Kind::BulkString => variant
+                .newtype_variant()
+                .map(|BulkString(value)| Value::BulkString(value)),
+            Kind::Array => variant.newtype_variant().map(Value::Array),
+            Kind::Error => variant.newtype_variant().map(Value::Error),
+        }
+    }
+
+    #[inline]
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(Value::Integer(v.into()))
+    }
+
+    #[inline]
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(Value::Integer(v))
+    }
+
+    #[inline]
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        v.try_into()
+            .map(Value::Integer)
+            .map_err(|_| E::invalid_value(de::Unexpected::Unsigned(v), &self))
+    }
+
+    #[inline]
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(Value::from(v))
+    }
+
+    #[inline]
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(Value::from(v))
+    }
+
+    #[inline]
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(Value::from(v))
+    }
+
+    #[inline]
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(Value::from(v))
+    }
+
+    #[inline]
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(Value::Null)
+    }
+
+    #[inline]
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(Value::Null)
+    }
+
+    #[inline]
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        de::Deserialize::deserialize(deserializer)
+    }
+
+    #[inline]
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let mut elements = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+
+        while let Some(element) = seq.next_element()? {
+            elements.push(element);
+        }
+
+        Ok(Value::Array(elements))
+    }
+}
+
+/// Deserializer for a `Value` that has already been deserialized, used when
+/// values have to be buffered before they're handed to their final type.
+/// When that type is itself a `Value`, the RESP type of each value is
+/// preserved, including errors.
+pub(crate) struct ValueDeserializer<E> {
+    value: Value,
+    error: PhantomData<E>,
+}
+
+impl<E> ValueDeserializer<E> {
+    #[inline]
+    #[must_use]
+    pub(crate) fn new(value: Value) -> Self {
+        Self {
+            value,
+            error: PhantomData,
+        }
+    }
+}
+
+impl<'de, E: de::Error> de::IntoDeserializer<'de, E> for ValueDeserializer<E> {
+    type Deserializer = Self;
+
+    #[inline]
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de, E: de::Error> de::Deserializer<'de> for ValueDeserializer<E> {
+    type Error = E;
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        enum identifier ignored_any
+    }
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, E>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.value {
+            Value::Null => visitor.visit_unit(),
+            Value::Integer(value) => visitor.visit_i64(value),
+            Value::SimpleString(value) => visitor.visit_string(value),
+            Value::BulkString(value) => visitor.visit_byte_buf(value),
+            Value::Array(elements) => {
+                let mut seq = de::value::SeqDeserializer::new(elements.into_iter().map(Self::new));
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+                Ok(value)
+            }
+            Value::Error(message) => Err(E::custom(message)),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, E>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.value {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value, E>
+    where
+        V: de::Visitor<'de>,
+    {
+        match name {
+            VALUE_TOKEN => visitor.visit_enum(self),
+            _ => visitor.visit_newtype_struct(self),
+        }
+    }
+}
+
+/// A buffered `Value` is presented to [`ValueVisitor`] the same way the
+/// seredies deserializer presents one: as an enum whose variant is the RESP
+/// type.
+impl<'de, E: de::Error> de::EnumAccess<'de> for ValueDeserializer<E> {
+    type Error = E;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self), E>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        let kind = match self.value {
+            Value::Null => "Null",
+            Value::Integer(_) => "Integer",
+            Value::SimpleString(_) => "SimpleString",
+            Value::BulkString(_) => "BulkString",
+            Value::Array(_) => "Array",
+            Value::Error(_) => "Error",
+        };
+
+        seed.deserialize(de::IntoDeserializer::<E>::into_deserializer(kind))
+            .map(|kind| (kind, self))
+    }
+}
+
+impl<'de, E: de::Error> de::VariantAccess<'de> for ValueDeserializer<E> {
+    type Error = E;
+
+    #[inline]
+    fn unit_variant(self) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, E>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        use de::IntoDeserializer as _;
+
+        match self.value {
+            Value::Integer(value) => seed.deserialize(value.into_deserializer()),
+            Value::SimpleString(value) | Value::Error(value) => {
+                seed.deserialize(value.into_deserializer())
+            }
+            Value::BulkString(ref value) => {
+                seed.deserialize(de::value::BytesDeserializer::new(value))
+            }
+            Value::Array(elements) => seed.deserialize(de::value::SeqDeserializer::new(
+                elements.into_iter().map(Self::new),
+            )),
+            Value::Null => Err(E::invalid_type(
+                de::Unexpected::UnitVariant,
+                &"newtype variant",
+            )),
+        }
+    }
+
+    fn tuple_variant<V>(self, _len: usize, _visitor: V) -> Result<V::Value, E>
+    where
+        V: de::Visitor<'de>,
+    {
+        Err(E::invalid_type(
+            de::Unexpected::NewtypeVariant,
+            &"tuple variant",
+        ))
+    }
+
+    fn struct_variant<V>(self, _fields: &'static [&'static str], _visitor: V) -> Result<V::Value, E>
+    where
+        V: de::Visitor<'de>,
+    {
+        Err(E::invalid_type(
+            de::Unexpected::NewtypeVariant,
+            &"struct variant",
+        ))
+    }
+}
+
+/**
+A single, complete RESP value, kept in its original encoded form.
+
+When deserialized with the seredies [`Deserializer`][crate::de::Deserializer],
+a `RawValue` checks that the input contains a well-formed RESP value and
+borrows its bytes, without decoding them into anything. When serialized with
+the seredies [`Serializer`][crate::ser::Serializer], those bytes are written
+to the output verbatim. This makes it much cheaper than [`Value`] for
+proxies and other code that forwards most of the data it reads, and only
+needs to look inside some of it.
+
+Because it borrows from the input, a `RawValue` can only be deserialized
+from a byte slice; it also isn't subject to the depth and length limits of
+the `Deserializer`, since it never builds anything from the data. It can't
+be nested in an `Option` or `Result`, which need to parse the value's header
+before the `RawValue` sees it; a `RawValue` can hold nulls and errors
+itself.
+
+A `RawValue` accepts the same types as the `Deserializer`: all of the RESP2
+types (`+`, `-`, `:`, `$`, and `*`), and the RESP3 Verbatim Strings (`=`),
+Pushes (`>`), and Big Numbers (`(`). The other RESP3 types, such as maps
+(`%`), sets (`~`), doubles (`,`), and booleans (`#`), are rejected with
+[`BadTag`][crate::de::parse::Error::BadTag]. A `RawValue` containing RESP3
+types can only be serialized with a RESP3 `Serializer`; a RESP2 `Serializer`
+fails with [`UnsupportedType`][crate::ser::Error::UnsupportedType] rather than
+writing a frame the reader won't understand.
+
+Other serializers and deserializers see a `RawValue` as a byte string
+containing RESP data.
+
+# Example
+
+```
+use seredies::{de::from_bytes, ser::to_vec, value::RawValue};
+
+let data = b"*3\r\n$3\r\nSET\r\n$3\r\nkey\r\n*2\r\n:1\r\n:2\r\n";
+let mut command: Vec<RawValue> = from_bytes(data).unwrap();
+
+assert_eq!(command[2].as_bytes(), b"*2\r\n:1\r\n:2\r\n");
+assert_eq!(command[2].parse::<(i64, i64)>().unwrap(), (1, 2));
+
+// Replace the key, and forward everything else as-is
+command[1] = RawValue::new(b"$5\r\nother\r\n").unwrap();
+
+assert_eq!(
+    to_vec(&command).unwrap(),
+    b"*3\r\n$3\r\nSET\r\n$5\r\nother\r\n*2\r\n:1\r\n:2\r\n",
+);
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RawValue<'a>(&'a [u8]);
+
+impl<'a> RawValue<'a> {
+    /// Create a `RawValue` from bytes that contain exactly one complete RESP
+    /// value, and nothing else.
+    pub fn new(data: &'a [u8]) -> Result<Self, crate::de::Error> {
+        match parse::skip_value(data)? {
+            (raw, []) => Ok(Self(raw)),
+            _ => Err(crate::de::Error::TrailingData),
+        }
+    }
+
+    /// Get the encoded bytes of this value.
+    #[inline]
+    #[must_use]
+    pub const fn as_bytes(&self) -> &'a [u8] {
+        self.0
+    }
+
+    /// Deserialize the contents of this value.
+    #[inline]
+    pub fn parse<T>(&self) -> Result<T, crate::de::Error>
+    where
+        T: de::Deserialize<'a>,
+    {
+        crate::de::from_bytes(self.0)
+    }
+}
+
+impl ser::Serialize for RawValue<'_> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.serialize_newtype_struct(RAW_VALUE_TOKEN, serde_bytes::Bytes::new(self.0))
+    }
+}
+
+impl<'de> de::Deserialize<'de> for RawValue<'de> {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_newtype_struct(RAW_VALUE_TOKEN, RawValueVisitor)
+    }
+}
+
+struct RawValueVisitor;
+
+impl<'de> de::Visitor<'de> for RawValueVisitor {
+    type Value = RawValue<'de>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("borrowed bytes containing a RESP value")
+    }
+
+    fn visit_borrowed_bytes<E: de::Error>(self, v: &'de [u8]) -> Result<Self::Value, E> {
+        RawValue::new(v).map_err(de::Error::custom)
+    }
+
+    fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+        self.visit_borrowed_bytes(v.as_bytes())
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_bytes(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cool_asserts::assert_matches;
+
+    use super::*;
+    use crate::{
+        de::{self, from_bytes},
+        ser::{self, to_vec},
+    };
+
+    fn round_trip(data: &[u8]) -> Value {
+        let value: Value = from_bytes(data).unwrap();
+        assert_eq!(to_vec(&value).unwrap(), data);
+        value
+    }
+
+    #[test]
+    fn simple_string() {
+        assert_eq!(round_trip(b"+OK\r\n"), Value::ok());
+    }
+
+    #[test]
+    fn bulk_string() {
+        assert_eq!(
+            round_trip(b"$5\r\nhello\r\n"),
+            Value::BulkString(b"hello".to_vec())
+        );
+    }
+
+    #[test]
+    fn binary_bulk_string() {
+        assert_eq!(
+            round_trip(b"$4\r\n\xff\r\n\x00\r\n"),
+            Value::BulkString(b"\xff\r\n\x00".to_vec())
+        );
+    }
+
+    #[test]
+    fn integer() {
+        assert_eq!(round_trip(b":-12\r\n"), Value::Integer(-12));
+    }
+
+    #[test]
+    fn null() {
+        assert_eq!(round_trip(b"$-1\r\n"), Value::Null);
+    }
+
+    #[test]
+    fn error() {
+        assert_eq!(
+            round_trip(b"-ERR unknown command\r\n"),
+            Value::Error("ERR unknown command".to_owned())
+        );
+    }
+
+    #[test]
+    fn nested_array() {
+        assert_eq!(
+            round_trip(b"*3\r\n*0\r\n-WRONGTYPE bad\r\n*2\r\n:1\r\n$-1\r\n"),
+            Value::Array(Vec::from([
+                Value::Array(Vec::new()),
+                Value::Error("WRONGTYPE bad".to_owned()),
+                Value::Array(Vec::from([Value::Integer(1), Value::Null])),
+            ]))
+        );
+    }
+
+    #[test]
+    fn inside_other_types() {
+        let (key, value): (&str, Value) = from_bytes(b"*2\r\n$3\r\nkey\r\n-ERR oops\r\n").unwrap();
+
+        assert_eq!(key, "key");
+        assert_eq!(value, Value::Error("ERR oops".to_owned()));
+    }
+
+    #[test]
+    fn unfinished_array() {
+        assert_matches!(
+            from_bytes::<Value>(b"*2\r\n:1\r\n"),
+            Err(de::Error::Parse(_))
+        );
+    }
+
+    #[test]
+    fn simple_string_with_newline() {
+        assert_matches!(
+            to_vec(&Value::SimpleString("a\r\nb".to_owned())),
+            Err(ser::Error::BadSimpleString)
+        );
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Value::from("abc"), Value::BulkString(b"abc".to_vec()));
+        assert_eq!(Value::from(None::<i64>), Value::Null);
+        assert_eq!(Value::from(Some(5_i64)), Value::Integer(5));
+        assert_eq!(
+            [Value::from(1_i64), Value::from("a")]
+                .into_iter()
+                .collect::<Value>(),
+            Value::Array(Vec::from([Value::Integer(1), Value::from("a")]))
+        );
+    }
+
+    #[test]
+    fn accessors() {
+        assert_eq!(Value::ok().as_str(), Some("OK"));
+        assert_eq!(Value::from(&b"\xff"[..]).as_bytes(), Some(&b"\xff"[..]));
+        assert_eq!(Value::from(&b"\xff"[..]).as_str(), None);
+        assert_eq!(Value::Integer(3).as_integer(), Some(3));
+        assert_eq!(Value::Integer(3).as_bytes(), None);
+        assert_eq!(Value::Error("ERR".to_owned()).as_error(), Some("ERR"));
+        assert!(Value::Null.is_null());
+    }
+
+    #[test]
+    fn into_map() {
+        let value = round_trip(b"*4\r\n+a\r\n:1\r\n$1\r\nb\r\n*1\r\n$-1\r\n");
+        let map = value.into_map().expect("value should be a map");
+
+        assert_eq!(
+            map,
+            BTreeMap::from([
+                ("a".to_owned(), Value::Integer(1)),
+                ("b".to_owned(), Value::Array(Vec::from([Value::Null]))),
+            ])
+        );
+    }
+
+    #[test]
+    fn into_map_duplicate_keys() {
+        let value = Value::from_iter([
+            Value::from("a"),
+            Value::Integer(1),
+            Value::from("a"),
+            Value::Integer(2),
+        ]);
+
+        assert_eq!(value.into_map().unwrap()["a"], Value::Integer(2));
+    }
+
+    #[test]
+    fn into_map_rejects() {
+        let odd = Value::from_iter([Value::from("a")]);
+        assert_eq!(odd.clone().into_map(), Err(odd));
+
+        let integer_key = Value::from_iter([Value::Integer(1), Value::Integer(2)]);
+        assert_eq!(integer_key.clone().into_map(), Err(integer_key));
+
+        let binary_key = Value::from_iter([Value::from(&b"\xff"[..]), Value::Integer(2)]);
+        assert_eq!(binary_key.clone().into_map(), Err(binary_key));
+
+        assert_eq!(Value::Null.into_map(), Err(Value::Null));
+    }
+
+    #[test]
+    fn raw_pass_through() {
+        let data = b"*3\r\n+OK\r\n-ERR oops\r\n*2\r\n$-1\r\n*-1\r\n";
+        let raw: Vec<RawValue<'_>> = from_bytes(data).unwrap();
+
+        assert_eq!(raw.len(), 3);
+        assert_eq!(raw[1].as_bytes(), b"-ERR oops\r\n");
+        assert_eq!(to_vec(&raw).unwrap(), data);
+
+        let whole: RawValue<'_> = from_bytes(data).unwrap();
+        assert_eq!(whole.as_bytes(), data);
+        assert_eq!(to_vec(&whole).unwrap(), data);
+    }
+
+    #[test]
+    fn raw_inside_other_types() {
+        let (key, value): (&str, RawValue<'_>) = from_bytes(b"*2\r\n$3\r\nkey\r\n:12\r\n").unwrap();
+
+        assert_eq!(key, "key");
+        assert_eq!(value.parse::<i64>().unwrap(), 12);
+        assert_eq!(
+            to_vec(&("other", value)).unwrap(),
+            b"*2\r\n$5\r\nother\r\n:12\r\n"
+        );
+    }
+
+    #[test]
+    fn raw_new() {
+        assert!(RawValue::new(b"*1\r\n:1\r\n").is_ok());
+        assert_matches!(RawValue::new(b"*2\r\n:1\r\n"), Err(de::Error::Parse(_)));
+        assert_matches!(RawValue::new(b":1\r\n:2\r\n"), Err(de::Error::TrailingData));
+    }
+
+    #[test]
+    fn raw_unsupported_tags() {
+        assert_matches!(
+            RawValue::new(b"%1\r\n+a\r\n:1\r\n"),
+            Err(de::Error::Parse(de::parse::Error::BadTag(b'%')))
+        );
+        assert_matches!(
+            RawValue::new(b"#t\r\n"),
+            Err(de::Error::Parse(de::parse::Error::BadTag(b'#')))
+        );
+    }
+
+    #[test]
+    fn raw_resp3_version() {
+        let raw = RawValue::new(b"*2\r\n:1\r\n=7\r\ntxt:abc\r\n").unwrap();
+
+        assert_matches!(
+            ser::to_vec(&raw),
+            Err(ser::Error::UnsupportedType("RESP3 raw values in RESP2"))
+        );
+
+        let mut buffer = Vec::new();
+        serde::Serialize::serialize(&raw, ser::Serializer::new_resp3(&mut buffer)).unwrap();
+        assert_eq!(buffer, raw.as_bytes());
+    }
+
+    #[test]
+    fn raw_incomplete() {
+        assert_matches!(
+            from_bytes::<Vec<RawValue<'_>>>(b"*2\r\n:1\r\n$5\r\nab"),
+            Err(de::Error::Parse(_))
+        );
+    }
+
+    #[test]
+    fn raw_in_option() {
+        assert_matches!(
+            from_bytes::<Option<RawValue<'_>>>(b":1\r\n"),
+            Err(de::Error::Custom(_))
+        );
+    }
+
+    #[test]
+    fn raw_rejects_other_data() {
+        struct NotBytes;
+
+        impl serde::Serialize for NotBytes {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                serializer.serialize_newtype_struct(RAW_VALUE_TOKEN, "+OK\r\n")
+            }
+        }
+
+        assert_matches!(ser::to_vec(&NotBytes), Err(ser::Error::UnsupportedType(_)));
+    }
+}